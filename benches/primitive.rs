@@ -0,0 +1,136 @@
+//! Primitive construction benchmarks: `cargo bench`.
+//!
+//! Each iteration builds a primitive from scratch, as the plot does when its
+//! data or view changes.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use iced::Color;
+use iced_plotter::plotter::{
+    ColorMode, LineJoin, LinePattern, PerformanceConfig, PlotPoint, PlotPoints, PlotSeries,
+    Plotter, PlotterOptions, SeriesStyle, ViewState,
+};
+use iced_plotter::testing::Harness;
+
+/// A noisy sine over X in `0..n`.
+fn wave(n: usize) -> Vec<PlotPoint> {
+    (0..n)
+        .map(|i| {
+            let x = i as f64;
+            let y = (x * 0.001).sin() + 0.1 * (x * 0.37).sin();
+            (x, y).into()
+        })
+        .collect()
+}
+
+fn build(series: Vec<PlotSeries<'_>>, view: &ViewState, options: PlotterOptions) {
+    let plotter: Plotter<'_, ()> = Plotter::new(series, view).with_options(options);
+    black_box(Harness::new(&plotter).primitive());
+}
+
+fn primitive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("primitive");
+    group.sample_size(10);
+    for n in [100_000, 1_000_000] {
+        let points = wave(n);
+        let view = ViewState::with_ranges((0.0, n as f64), (-1.5, 1.5));
+        group.bench_with_input(BenchmarkId::new("line", n), &points, |b, points| {
+            b.iter(|| {
+                let series = PlotSeries::new("wave", PlotPoints::borrowed(points));
+                build(vec![series], &view, PlotterOptions::default())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("scatter", n), &points, |b, points| {
+            let style = SeriesStyle::new(ColorMode::solid(Color::WHITE))
+                .with_line_pattern(LinePattern::None);
+            b.iter(|| {
+                let series = PlotSeries::new("wave", PlotPoints::borrowed(points))
+                    .with_style(style.clone())
+                    .with_decimation(false);
+                build(vec![series], &view, PlotterOptions::default())
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Line quads, joins and patterns of an undecimated thick line.
+fn lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lines");
+    let points = wave(100_000);
+    let view = ViewState::with_ranges((0.0, 100_000.0), (-1.5, 1.5));
+    for (name, pattern, join) in [
+        ("solid", LinePattern::Solid, LineJoin::Miter),
+        ("dashed", LinePattern::Dashed, LineJoin::Miter),
+        ("round", LinePattern::Solid, LineJoin::Round),
+    ] {
+        let style = SeriesStyle::new(ColorMode::solid(Color::WHITE))
+            .with_line_width(3.0)
+            .with_line_pattern(pattern)
+            .with_line_join(join)
+            .with_marker_size(0.0);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let series = PlotSeries::new("wave", PlotPoints::borrowed(&points))
+                    .with_style(style.clone())
+                    .with_decimation(false);
+                build(vec![series], &view, PlotterOptions::default())
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Per-point colors of a million markers.
+fn colors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("colors");
+    group.sample_size(10);
+    let points = wave(1_000_000);
+    let view = ViewState::with_ranges((0.0, 1_000_000.0), (-1.5, 1.5));
+    for (name, color) in [
+        ("solid", ColorMode::solid(Color::WHITE)),
+        (
+            "value_gradient",
+            ColorMode::value_gradient(Color::from_rgb(0.0, 0.0, 1.0), Color::WHITE),
+        ),
+    ] {
+        let style = SeriesStyle::new(color).with_line_pattern(LinePattern::None);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let series = PlotSeries::new("wave", PlotPoints::borrowed(&points))
+                    .with_style(style.clone())
+                    .with_decimation(false);
+                build(vec![series], &view, PlotterOptions::default())
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Panning across 10M points in performance mode, a new view each frame.
+fn pan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pan");
+    group.sample_size(10);
+    let n = 10_000_000;
+    let points = wave(n);
+    let width = n as f64 / 10.0;
+    group.bench_function("10m", |b| {
+        let mut step = 0;
+        b.iter(|| {
+            let x = (step % 90) as f64 * 0.01 * n as f64;
+            step += 1;
+            let view = ViewState::with_ranges((x, x + width), (-1.5, 1.5));
+            let series = PlotSeries::new("wave", PlotPoints::borrowed(&points));
+            let options = PlotterOptions {
+                performance: Some(PerformanceConfig::default()),
+                ..PlotterOptions::default()
+            };
+            build(vec![series], &view, options)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, primitive, lines, colors, pan);
+criterion_main!(benches);
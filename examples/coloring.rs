@@ -1,4 +1,4 @@
-use iced::widget::{column, row, text, Container};
+use iced::widget::{Container, column, row, text};
 use iced::{Color, Element, Length, Theme};
 use iced_plotter::colormap::ColormapName;
 use iced_plotter::plotter::{
@@ -55,6 +55,10 @@ impl ColoringExample {
             text("3. Colormap:"),
             text("   Viridis (scientific)"),
             text("   Perceptually uniform"),
+            text(""),
+            text("4. Value Function:"),
+            text("   Green (flat) → Orange (steep)"),
+            text("   Computed per point from the slope"),
         ]
         .spacing(5)
         .width(Length::Shrink);
@@ -64,7 +68,7 @@ impl ColoringExample {
                 // Value gradient based on Y
                 PlotSeries::new(
                     "sin(x) - Value Gradient",
-                    PlotPoints::generator(f32::sin, (0.0, 10.0), 500),
+                    PlotPoints::generator(f64::sin, (0.0, 10.0), 500),
                 )
                 .with_style(SeriesStyle::new(ColorMode::value_gradient(
                     Color::from_rgb(0.2, 0.2, 0.8),
@@ -73,7 +77,7 @@ impl ColoringExample {
                 // Index gradient
                 PlotSeries::new(
                     "cos(x) - Index Gradient",
-                    PlotPoints::generator(f32::cos, (0.0, 10.0), 500),
+                    PlotPoints::generator(f64::cos, (0.0, 10.0), 500),
                 )
                 .with_style(SeriesStyle::new(ColorMode::index_gradient(
                     Color::from_rgb(0.2, 0.4, 0.8),
@@ -85,6 +89,16 @@ impl ColoringExample {
                     PlotPoints::generator(|x| (2.0 * x).sin(), (0.0, 10.0), 500),
                 )
                 .with_style(SeriesStyle::new(ColorMode::colormap(ColormapName::Viridis))),
+                // Value computed on the fly from the local slope
+                PlotSeries::new(
+                    "0.5 sin(3x) - 2 - Value Function",
+                    PlotPoints::generator(|x| 0.5 * (3.0 * x).sin() - 2.0, (0.0, 10.0), 500),
+                )
+                .with_style(SeriesStyle::new(ColorMode::value_fn(
+                    Color::from_rgb(0.2, 0.8, 0.4),
+                    Color::from_rgb(0.9, 0.5, 0.1),
+                    |_, x, _| (3.0 * x).cos().abs() as f32,
+                ))),
             ],
             &self.view_state,
         )
@@ -1,8 +1,9 @@
-use iced::widget::{column, row, text, Container};
+use iced::widget::{Container, column, row, text};
 use iced::{Color, Element, Length, Theme};
 use iced_plotter::plotter::{
-    AxisConfig, ColorMode, InteractionConfig, LegendConfig, LegendState, PlotPoints, PlotSeries,
-    Plotter, PlotterOptions, SeriesStyle, TooltipConfig, TooltipState, ViewState,
+    AxisConfig, ColorMode, CrosshairConfig, CursorReadoutConfig, HoveredPoint, InteractionConfig,
+    LegendConfig, LegendState, PlotPoints, PlotSeries, Plotter, PlotterOptions, SeriesStyle,
+    TooltipConfig, TooltipState, ViewState,
 };
 
 pub fn main() {
@@ -19,12 +20,14 @@ pub fn main() {
 #[derive(Debug, Clone)]
 enum Message {
     ViewChanged(ViewState),
+    PointClicked(HoveredPoint),
 }
 
 struct InteractiveExample {
     view_state: ViewState,
     legend_state: LegendState,
     tooltip_state: TooltipState,
+    clicked: Option<HoveredPoint>,
 }
 
 impl InteractiveExample {
@@ -33,6 +36,7 @@ impl InteractiveExample {
             view_state: ViewState::auto_fit(),
             legend_state: LegendState::default(),
             tooltip_state: TooltipState::default(),
+            clicked: None,
         }
     }
 
@@ -41,6 +45,9 @@ impl InteractiveExample {
             Message::ViewChanged(new_view) => {
                 self.view_state = new_view;
             }
+            Message::PointClicked(point) => {
+                self.clicked = Some(point);
+            }
         }
     }
 
@@ -53,6 +60,10 @@ impl InteractiveExample {
             Some((lo, hi)) => format!("Y: [{:.2}, {:.2}]", lo, hi),
             None => "Y: auto-fit".to_string(),
         };
+        let clicked_info = match &self.clicked {
+            Some(p) => format!("{} at ({:.2}, {:.2})", p.series_label, p.x, p.y),
+            None => "Click a point".to_string(),
+        };
 
         let info = column![
             text("Interactive Plot"),
@@ -68,21 +79,25 @@ impl InteractiveExample {
             text("  - Rectangle zoom select"),
             text("  - Boundary clamping"),
             text("  - Hover tooltip"),
+            text("  - Cursor readout"),
             text("  - X bounds: [0, 20]"),
             text(""),
             text("Current View:"),
             text(x_info),
             text(y_info),
+            text(""),
+            text("Clicked:"),
+            text(clicked_info),
         ]
         .spacing(5);
 
         let plotter = Plotter::new(
             vec![
-                PlotSeries::new("sin(x)", PlotPoints::generator(f32::sin, (0.0, 20.0), 2000))
+                PlotSeries::new("sin(x)", PlotPoints::generator(f64::sin, (0.0, 20.0), 2000))
                     .with_style(SeriesStyle::new(ColorMode::solid(Color::from_rgb(
                         0.8, 0.4, 0.2,
                     )))),
-                PlotSeries::new("cos(x)", PlotPoints::generator(f32::cos, (0.0, 20.0), 2000))
+                PlotSeries::new("cos(x)", PlotPoints::generator(f64::cos, (0.0, 20.0), 2000))
                     .with_style(SeriesStyle::new(ColorMode::solid(Color::from_rgb(
                         0.2, 0.6, 0.8,
                     )))),
@@ -99,6 +114,8 @@ impl InteractiveExample {
         .with_options(PlotterOptions {
             legend: Some(LegendConfig::default()),
             tooltip: Some(TooltipConfig::default()),
+            cursor_readout: Some(CursorReadoutConfig::default()),
+            crosshair: Some(CrosshairConfig::default().with_format_x(|v| format!("{v:.2}"))),
             x_axis: AxisConfig::default().with_title("X Axis"),
             y_axis: AxisConfig::default().with_title("Amplitude"),
             ..PlotterOptions::default()
@@ -120,8 +137,12 @@ impl InteractiveExample {
             elastic: true,
             elastic_limit: 0.3,
             elastic_duration_ms: 200,
+            // Flinging keeps the view moving and bounces off the bounds
+            inertia: true,
+            ..InteractionConfig::default()
         })
-        .on_view_change(Message::ViewChanged);
+        .on_view_change(Message::ViewChanged)
+        .on_point_click(Message::PointClicked);
 
         row![
             Container::new(plotter.draw())
@@ -1,9 +1,10 @@
 use iced::time::{self, Duration};
-use iced::widget::{column, row, text, Container};
+use iced::widget::{Container, column, row, text};
 use iced::{Color, Element, Length, Subscription, Theme};
 use iced_plotter::plotter::{
-    AxisConfig, ColorMode, InteractionConfig, LegendConfig, LegendPosition, LegendState, PlotPoint,
-    PlotPoints, PlotSeries, Plotter, PlotterOptions, SeriesStyle, ViewState,
+    Annotation, AxisConfig, AxisReference, ColorMode, CursorReadoutConfig, ExtraYAxis,
+    InteractionConfig, LegendConfig, LegendPosition, LegendState, LegendValue, LinePattern,
+    PlotPoint, PlotPoints, PlotSeries, Plotter, PlotterOptions, SeriesStyle, TextBlock, ViewState,
 };
 
 pub fn main() {
@@ -30,12 +31,12 @@ impl SimpleRng {
         Self { state: seed }
     }
 
-    /// Returns a pseudo-random f32 in [-1.0, 1.0].
-    fn next_f32(&mut self) -> f32 {
+    /// Returns a pseudo-random f64 in [-1.0, 1.0].
+    fn next_f64(&mut self) -> f64 {
         // LCG parameters from Numerical Recipes
         self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
         // Take upper bits, map to [-1, 1]
-        let bits = (self.state >> 33) as f32 / (1u64 << 31) as f32;
+        let bits = (self.state >> 33) as f64 / (1u64 << 31) as f64;
         bits * 2.0 - 1.0
     }
 }
@@ -45,9 +46,9 @@ const MAX_POINTS: usize = 500;
 struct SensorData {
     temperature: Vec<PlotPoint>,
     humidity: Vec<PlotPoint>,
-    time: f32,
-    current_temp: f32,
-    current_humidity: f32,
+    time: f64,
+    current_temp: f64,
+    current_humidity: f64,
     rng: SimpleRng,
     view_state: ViewState,
     legend_state: LegendState,
@@ -77,12 +78,12 @@ impl SensorData {
                 self.time += 0.2; // 200ms in seconds
 
                 // Random walk for temperature: drift toward 22°C with noise
-                let temp_noise = self.rng.next_f32() * 0.3;
+                let temp_noise = self.rng.next_f64() * 0.3;
                 let temp_drift = (22.0 - self.current_temp) * 0.02;
                 self.current_temp += temp_drift + temp_noise;
 
                 // Random walk for humidity: drift toward 55% with noise
-                let hum_noise = self.rng.next_f32() * 0.5;
+                let hum_noise = self.rng.next_f64() * 0.5;
                 let hum_drift = (55.0 - self.current_humidity) * 0.02;
                 self.current_humidity += hum_drift + hum_noise;
 
@@ -123,18 +124,25 @@ impl SensorData {
             text("  Drag X: Pan"),
             text("  Double-click: Reset"),
             text(""),
-            text("Y-axis auto-fits to data"),
+            text("Y-axes auto-fit to data"),
         ]
         .spacing(5);
 
         let plotter = Plotter::new(
             vec![
-                PlotSeries::new("Temperature", PlotPoints::borrowed(&self.temperature)).with_style(
-                    SeriesStyle::new(ColorMode::solid(Color::from_rgb(0.9, 0.3, 0.2))),
-                ),
-                PlotSeries::new("Humidity", PlotPoints::borrowed(&self.humidity)).with_style(
-                    SeriesStyle::new(ColorMode::solid(Color::from_rgb(0.2, 0.6, 0.9))),
-                ),
+                PlotSeries::new("Temperature (max)", PlotPoints::borrowed(&self.temperature))
+                    .with_style(SeriesStyle::new(ColorMode::solid(Color::from_rgb(
+                        0.9, 0.3, 0.2,
+                    ))))
+                    .with_legend_value(LegendValue::Max)
+                    .with_unit("°C"),
+                PlotSeries::new("Humidity", PlotPoints::borrowed(&self.humidity))
+                    .with_style(SeriesStyle::new(ColorMode::solid(Color::from_rgb(
+                        0.2, 0.6, 0.9,
+                    ))))
+                    .with_value_format(|v| format!("{v:.0}"))
+                    .with_unit("%")
+                    .on_secondary_y(),
             ],
             &self.view_state,
         )
@@ -143,8 +151,30 @@ impl SensorData {
                 position: LegendPosition::TopLeft,
                 ..LegendConfig::default()
             }),
-            x_axis: AxisConfig::default().with_title("Time (s)"),
-            y_axis: AxisConfig::default().with_title("Value"),
+            x_axis: AxisConfig::default()
+                .with_title("Time relative to latest sample (s)")
+                .with_reference(AxisReference::LastSample)
+                .with_format(|t| {
+                    if t.value == 0.0 {
+                        "now".to_string()
+                    } else {
+                        format!("{:.*}", t.decimals(), t.value)
+                    }
+                }),
+            y_axis: AxisConfig::default().with_title("Temperature (°C)"),
+            extra_y_axes: vec![ExtraYAxis::new(
+                AxisConfig::default()
+                    .with_title("Humidity (%)")
+                    .with_format(|t| format!("{:.0}", t.value)),
+            )],
+            annotations: vec![
+                Annotation::hline(30.0)
+                    .with_color(Color::from_rgba(0.9, 0.3, 0.2, 0.6))
+                    .with_pattern(LinePattern::Dashed)
+                    .with_label("Alarm threshold"),
+            ],
+            caption: Some(TextBlock::new("Source: simulated sensor random walk")),
+            cursor_readout: Some(CursorReadoutConfig::default().with_series_values(true)),
             ..PlotterOptions::default()
         })
         .with_legend_state(self.legend_state.clone())
@@ -1,7 +1,8 @@
-use iced::widget::{column, row, text, Container};
+use iced::widget::{Container, column, row, text};
 use iced::{Color, Element, Length, Theme};
 use iced_plotter::plotter::{
-    ColorMode, InteractionConfig, PlotPoints, PlotSeries, Plotter, SeriesStyle, ViewState,
+    Annotation, ColorMode, InteractionConfig, LinePattern, MarkerShape, PlotPoint, PlotPoints,
+    PlotSeries, Plotter, PlotterOptions, SeriesStyle, ViewState,
 };
 
 pub fn main() {
@@ -40,13 +41,66 @@ impl StaticGraph {
 
         let plotter = Plotter::new(
             vec![
-                PlotSeries::new("sin(x)", PlotPoints::generator(f32::sin, (0.0, 10.0), 1000))
+                PlotSeries::new(
+                    "sin(x) bins",
+                    PlotPoints::owned(
+                        (0..10)
+                            .map(|i| {
+                                let x = i as f64 + 0.5;
+                                PlotPoint { x, y: x.sin() }
+                            })
+                            .collect(),
+                    ),
+                )
+                .with_style(SeriesStyle::new(ColorMode::solid(Color::from_rgba(
+                    0.8, 0.4, 0.2, 0.3,
+                ))))
+                .bars(0.8, 0.0),
+                PlotSeries::new("sin(x)", PlotPoints::generator(f64::sin, (0.0, 10.0), 1000))
                     .with_style(SeriesStyle::new(ColorMode::solid(Color::from_rgb(
                         0.8, 0.4, 0.2,
                     )))),
+                PlotSeries::new(
+                    "Lissajous",
+                    PlotPoints::parametric(
+                        |t| 5.0 + 4.0 * (3.0 * t).sin(),
+                        |t| (2.0 * t).sin(),
+                        (0.0, std::f64::consts::TAU),
+                        1000,
+                    ),
+                )
+                .with_style(
+                    SeriesStyle::new(ColorMode::solid(Color::from_rgb(0.2, 0.6, 0.8)))
+                        .with_line_pattern(LinePattern::Dashed),
+                ),
+                PlotSeries::new(
+                    "cos(x) samples",
+                    PlotPoints::owned(
+                        (0..=10)
+                            .map(|i| PlotPoint {
+                                x: i as f64,
+                                y: (i as f64).cos(),
+                            })
+                            .collect(),
+                    ),
+                )
+                .with_style(
+                    SeriesStyle::new(ColorMode::solid(Color::from_rgb(0.6, 0.8, 0.3)))
+                        .with_marker_shape(MarkerShape::Diamond)
+                        .with_marker_size(7.0),
+                ),
             ],
             &self.view_state,
         )
+        .with_options(PlotterOptions {
+            annotations: vec![
+                Annotation::arrow((3.0, 0.6), (std::f64::consts::FRAC_PI_2 + 0.1, 0.97))
+                    .with_label("sin(x) peak"),
+                Annotation::text(5.0, -0.9, "Lissajous bounds: x ∈ [1, 9]")
+                    .with_color(Color::from_rgb(0.2, 0.6, 0.8)),
+            ],
+            ..PlotterOptions::default()
+        })
         .with_interaction(InteractionConfig::full())
         .on_view_change(Message::ViewChanged);
 
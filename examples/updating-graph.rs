@@ -1,10 +1,10 @@
 use iced::time::{self, Duration};
-use iced::widget::{column, row, text, Container};
+use iced::widget::{Container, column, row, text};
 use iced::{Color, Element, Length, Subscription, Theme};
 use iced_plotter::plotter::{
-    ColorMode, InteractionConfig, PlotPoint, PlotPoints, PlotSeries, Plotter, SeriesStyle,
-    ViewState,
+    ColorMode, InteractionConfig, PlotSeries, Plotter, SeriesStyle, ViewState,
 };
+use iced_plotter::streaming::StreamingSeries;
 
 pub fn main() {
     iced::application(
@@ -25,17 +25,18 @@ enum Message {
 }
 
 struct UpdatingGraph {
-    points: Vec<PlotPoint>,
-    time: f32,
+    points: StreamingSeries,
+    time: f64,
     view_state: ViewState,
 }
 
 impl UpdatingGraph {
     pub fn new() -> Self {
         Self {
-            points: Vec::new(),
+            points: StreamingSeries::new(100_000),
             time: 0.0,
-            view_state: ViewState::auto_fit(),
+            // The window slides along with the data; scroll to change its width
+            view_state: ViewState::auto_fit().with_x_range(Some((0.0, 2000.0))),
         }
     }
 
@@ -47,13 +48,9 @@ impl UpdatingGraph {
         match message {
             Message::Tick => {
                 let x = self.time;
-                let y = (x * 0.001).sin() + (x * 0.000314).cos() * 6.28;
+                let y = (x * 0.001).sin() + (x * 0.000314).cos() * std::f64::consts::TAU;
 
-                self.points.push(PlotPoint { x, y });
-
-                if self.points.len() > 100000 {
-                    self.points.remove(0);
-                }
+                self.points.push((x, y));
 
                 self.time += 0.1;
             }
@@ -75,13 +72,13 @@ impl UpdatingGraph {
 
         let plotter = Plotter::new(
             vec![
-                PlotSeries::new("wave", PlotPoints::borrowed(&self.points)).with_style(
-                    SeriesStyle::new(ColorMode::solid(Color::from_rgb(0.2, 0.8, 0.4))),
-                ),
+                PlotSeries::new("wave", self.points.points()).with_style(SeriesStyle::new(
+                    ColorMode::solid(Color::from_rgb(0.2, 0.8, 0.4)),
+                )),
             ],
             &self.view_state,
         )
-        .with_interaction(InteractionConfig::pan_x_autofit_y())
+        .with_interaction(InteractionConfig::streaming())
         .on_view_change(Message::ViewChanged);
 
         row![
@@ -6,7 +6,10 @@
 use iced::Color;
 
 /// Named colormaps for value-based coloring.
-#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+///
+/// Any map can be flipped with [`ColormapName::reversed`] or cut into flat
+/// bands with [`ColormapName::discrete`].
+#[derive(Clone, Debug, PartialEq)]
 pub enum ColormapName {
     /// Perceptually uniform colormap (blue → green → yellow).
     /// Colorblind-friendly and good for scientific visualization.
@@ -26,6 +29,38 @@ pub enum ColormapName {
 
     /// Simple grayscale (black → white).
     Grayscale,
+
+    /// Perceptually uniform (black → purple → orange → pale yellow).
+    Inferno,
+
+    /// Perceptually uniform (black → purple → pink → pale yellow).
+    Magma,
+
+    /// Perceptually uniform and readable with color vision deficiency
+    /// (dark blue → gray → yellow).
+    Cividis,
+
+    /// Diverging (blue → light gray → red), for values around a midpoint.
+    Coolwarm,
+
+    /// Legacy rainbow (dark blue → cyan → yellow → dark red). Not perceptually
+    /// uniform; prefer [`ColormapName::Turbo`] unless matching older plots.
+    Jet,
+
+    /// User-defined color stops as `(position, color)` pairs, positions in
+    /// [0, 1]. Colors are interpolated linearly between stops. See
+    /// [`ColormapName::custom`].
+    Custom(Vec<(f32, Color)>),
+
+    /// Another colormap running from its end to its start.
+    Reversed(Box<ColormapName>),
+
+    /// Another colormap quantized to `levels` flat bands, sampled evenly
+    /// from its start to its end.
+    Discrete {
+        base: Box<ColormapName>,
+        levels: u32,
+    },
 }
 
 impl ColormapName {
@@ -44,6 +79,51 @@ impl ColormapName {
                 let v = t;
                 Color::from_rgb(v, v, v)
             }
+            ColormapName::Inferno => sample_inferno(t),
+            ColormapName::Magma => sample_magma(t),
+            ColormapName::Cividis => sample_cividis(t),
+            ColormapName::Coolwarm => sample_coolwarm(t),
+            ColormapName::Jet => sample_jet(t),
+            ColormapName::Custom(stops) => sample_palette(stops, t),
+            ColormapName::Reversed(base) => base.sample(1.0 - t),
+            ColormapName::Discrete { base, levels } => {
+                // Band k of n takes the color at k / (n - 1), so the first and
+                // last bands keep the map's end colors
+                let levels = (*levels).max(1);
+                if levels == 1 {
+                    return base.sample(0.5);
+                }
+                let band = ((t * levels as f32) as u32).min(levels - 1);
+                base.sample(band as f32 / (levels - 1) as f32)
+            }
+        }
+    }
+
+    /// A colormap from `(position, color)` stops, sorted by position and
+    /// with positions clamped to [0, 1].
+    pub fn custom(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        let mut stops: Vec<(f32, Color)> = stops
+            .into_iter()
+            .map(|(t, color)| (t.clamp(0.0, 1.0), color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ColormapName::Custom(stops)
+    }
+
+    /// This colormap running from its end to its start.
+    pub fn reversed(self) -> Self {
+        match self {
+            ColormapName::Reversed(base) => *base,
+            base => ColormapName::Reversed(Box::new(base)),
+        }
+    }
+
+    /// This colormap quantized to `levels` flat bands, e.g. for contour-like
+    /// value classes.
+    pub fn discrete(self, levels: u32) -> Self {
+        ColormapName::Discrete {
+            base: Box::new(self),
+            levels,
         }
     }
 }
@@ -62,6 +142,9 @@ fn lerp_color(a: Color, b: Color, t: f32) -> Color {
 fn sample_palette(palette: &[(f32, Color)], t: f32) -> Color {
     let t = t.clamp(0.0, 1.0);
 
+    if palette.is_empty() {
+        return Color::from_rgb(0.5, 0.5, 0.5);
+    }
     if t <= palette[0].0 {
         return palette[0].1;
     }
@@ -137,6 +220,67 @@ fn sample_heat(t: f32) -> Color {
     sample_palette(&palette, t)
 }
 
+/// Inferno colormap: perceptually uniform, black to pale yellow through red.
+fn sample_inferno(t: f32) -> Color {
+    let palette = [
+        (0.0, Color::from_rgb(0.001, 0.000, 0.014)),  // Black
+        (0.25, Color::from_rgb(0.341, 0.062, 0.429)), // Purple
+        (0.5, Color::from_rgb(0.735, 0.216, 0.330)),  // Red-magenta
+        (0.75, Color::from_rgb(0.978, 0.557, 0.035)), // Orange
+        (1.0, Color::from_rgb(0.988, 1.000, 0.645)),  // Pale yellow
+    ];
+    sample_palette(&palette, t)
+}
+
+/// Magma colormap: perceptually uniform, black to pale yellow through pink.
+fn sample_magma(t: f32) -> Color {
+    let palette = [
+        (0.0, Color::from_rgb(0.001, 0.000, 0.014)),  // Black
+        (0.25, Color::from_rgb(0.316, 0.071, 0.485)), // Purple
+        (0.5, Color::from_rgb(0.716, 0.215, 0.475)),  // Pink
+        (0.75, Color::from_rgb(0.987, 0.535, 0.382)), // Salmon
+        (1.0, Color::from_rgb(0.987, 0.991, 0.750)),  // Pale yellow
+    ];
+    sample_palette(&palette, t)
+}
+
+/// Cividis colormap: perceptually uniform, colorblind-friendly.
+fn sample_cividis(t: f32) -> Color {
+    let palette = [
+        (0.0, Color::from_rgb(0.000, 0.135, 0.304)),  // Dark blue
+        (0.25, Color::from_rgb(0.263, 0.302, 0.428)), // Slate blue
+        (0.5, Color::from_rgb(0.487, 0.485, 0.471)),  // Gray
+        (0.75, Color::from_rgb(0.738, 0.684, 0.410)), // Khaki
+        (1.0, Color::from_rgb(0.995, 0.909, 0.217)),  // Yellow
+    ];
+    sample_palette(&palette, t)
+}
+
+/// Coolwarm colormap: diverging blue to red with a neutral midpoint.
+fn sample_coolwarm(t: f32) -> Color {
+    let palette = [
+        (0.0, Color::from_rgb(0.230, 0.299, 0.754)),  // Blue
+        (0.25, Color::from_rgb(0.552, 0.690, 0.996)), // Light blue
+        (0.5, Color::from_rgb(0.865, 0.865, 0.865)),  // Light gray
+        (0.75, Color::from_rgb(0.958, 0.604, 0.482)), // Light red
+        (1.0, Color::from_rgb(0.706, 0.016, 0.150)),  // Red
+    ];
+    sample_palette(&palette, t)
+}
+
+/// Jet colormap: the classic MATLAB rainbow.
+fn sample_jet(t: f32) -> Color {
+    let palette = [
+        (0.0, Color::from_rgb(0.0, 0.0, 0.5)),   // Dark blue
+        (0.125, Color::from_rgb(0.0, 0.0, 1.0)), // Blue
+        (0.375, Color::from_rgb(0.0, 1.0, 1.0)), // Cyan
+        (0.625, Color::from_rgb(1.0, 1.0, 0.0)), // Yellow
+        (0.875, Color::from_rgb(1.0, 0.0, 0.0)), // Red
+        (1.0, Color::from_rgb(0.5, 0.0, 0.0)),   // Dark red
+    ];
+    sample_palette(&palette, t)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,4 +311,31 @@ mod tests {
         // End should be yellow-ish
         assert!(end.r > 0.9 && end.g > 0.8 && end.b < 0.3);
     }
+
+    #[test]
+    fn test_custom_reversed_and_discrete() {
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+        let blue = Color::from_rgb(0.0, 0.0, 1.0);
+        let map = ColormapName::custom([(1.0, blue), (0.0, red)]);
+        assert_eq!(map.sample(0.0), red);
+        assert_eq!(map.sample(0.5), Color::from_rgb(0.5, 0.0, 0.5));
+
+        let reversed = map.clone().reversed();
+        assert_eq!(reversed.sample(0.0), blue);
+        assert_eq!(reversed.sample(0.25), map.sample(0.75));
+        assert_eq!(reversed.reversed(), map);
+
+        // Three bands keeping the end colors
+        let discrete = map.discrete(3);
+        assert_eq!(discrete.sample(0.1), red);
+        assert_eq!(discrete.sample(0.3), red);
+        assert_eq!(discrete.sample(0.5), Color::from_rgb(0.5, 0.0, 0.5));
+        assert_eq!(discrete.sample(0.9), blue);
+        assert_eq!(discrete.sample(1.0), blue);
+
+        // Repeated positions step without interpolating
+        let step = ColormapName::custom([(0.0, red), (0.5, red), (0.5, blue), (1.0, blue)]);
+        assert_eq!(step.sample(0.49), red);
+        assert_eq!(step.sample(0.51), blue);
+    }
 }
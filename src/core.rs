@@ -0,0 +1,1009 @@
+//! Renderer-agnostic data model and view math.
+//!
+//! Points and the point sources of a series (stored or generated), view
+//! ranges and their auto-fitting, range clamping, elastic over-scroll,
+//! animation easing, data/screen transforms, line decimation and hover
+//! bucketing used by the widget. Nothing in here depends on iced, so it can
+//! be reused headlessly and unit-tested without a GPU. Tick generation and
+//! polygon triangulation live in [`crate::ticks`] and [`crate::triangulate`],
+//! which are iced-free as well. Series styling (colors, markers, legend
+//! entries) stays with the widget in [`crate::plotter`].
+
+#[derive(Clone, Debug)]
+pub struct PlotPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl From<(f64, f64)> for PlotPoint {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Describes a function y = f(x) with an optional range for x and a number of
+/// points.
+pub struct ExplicitGenerator<'a> {
+    pub function: Box<dyn Fn(f64) -> f64 + 'a>,
+    pub x_range: (f64, f64), // start, end
+    pub points: usize,
+    /// Adaptive refinement. `None` = `points` uniform samples over `x_range`.
+    pub adaptive: Option<AdaptiveSampling>,
+}
+
+/// Settings for adaptive refinement of an [`ExplicitGenerator`].
+///
+/// The generator starts from its uniform samples over the visible part of
+/// its range and repeatedly bisects segments whose midpoint deviates from
+/// the straight line by more than `tolerance` pixels, until the curve is
+/// smooth on screen or `max_points` is reached.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveSampling {
+    /// Upper bound on the number of samples per frame.
+    pub max_points: usize,
+    /// Maximum on-screen deviation in pixels before a segment is subdivided.
+    pub tolerance: f32,
+}
+
+impl Default for AdaptiveSampling {
+    fn default() -> Self {
+        Self {
+            max_points: 10_000,
+            tolerance: 0.5,
+        }
+    }
+}
+
+impl ExplicitGenerator<'_> {
+    /// Evaluate the function for rendering.
+    ///
+    /// `visible_x` is the current X view range and `px_per_unit` the pixel
+    /// scale of each axis; both are only used with adaptive sampling.
+    pub fn sample(&self, visible_x: (f64, f64), px_per_unit: (f64, f64)) -> Vec<(f64, f64)> {
+        let Some(adaptive) = self.adaptive else {
+            return Self::uniform(&self.function, self.x_range, self.points);
+        };
+
+        // Only refine the part of the range that is on screen
+        let lo = self.x_range.0.max(visible_x.0.min(visible_x.1));
+        let hi = self.x_range.1.min(visible_x.0.max(visible_x.1));
+        if lo > hi {
+            return Vec::new();
+        }
+
+        let mut samples = Self::uniform(&self.function, (lo, hi), self.points.max(2));
+        let (x_scale, y_scale) = px_per_unit;
+        const MAX_PASSES: usize = 16;
+
+        for _ in 0..MAX_PASSES {
+            if samples.len() >= adaptive.max_points {
+                break;
+            }
+            let mut refined = Vec::with_capacity(samples.len() * 2);
+            let mut split = false;
+
+            for (i, pair) in samples.windows(2).enumerate() {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                refined.push((x0, y0));
+
+                // Leave room for the remaining original samples
+                let remaining = samples.len() - i - 1;
+                if refined.len() + remaining >= adaptive.max_points || (x1 - x0) * x_scale < 0.5 {
+                    continue;
+                }
+
+                let xm = 0.5 * (x0 + x1);
+                let ym = (self.function)(xm);
+                if !ym.is_finite() {
+                    continue;
+                }
+                let error = ((ym - 0.5 * (y0 + y1)) * y_scale).abs();
+                if !y0.is_finite() || !y1.is_finite() || error > f64::from(adaptive.tolerance) {
+                    refined.push((xm, ym));
+                    split = true;
+                }
+            }
+            if let Some(&last) = samples.last() {
+                refined.push(last);
+            }
+
+            samples = refined;
+            if !split {
+                break;
+            }
+        }
+
+        samples
+    }
+
+    fn uniform(function: &dyn Fn(f64) -> f64, (x0, x1): (f64, f64), n: usize) -> Vec<(f64, f64)> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64 / (n - 1).max(1) as f64;
+                let x = x0 + t * (x1 - x0);
+                (x, function(x))
+            })
+            .collect()
+    }
+}
+
+/// Describes a parametric curve (x(t), y(t)) sampled at `points` uniformly
+/// spaced values of t.
+pub struct ParametricGenerator<'a> {
+    pub x: Box<dyn Fn(f64) -> f64 + 'a>,
+    pub y: Box<dyn Fn(f64) -> f64 + 'a>,
+    pub t_range: (f64, f64), // start, end
+    pub points: usize,
+}
+
+impl ParametricGenerator<'_> {
+    /// Evaluate the curve at every sample of t.
+    pub fn sample(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let (t0, t1) = self.t_range;
+        let n = self.points;
+        (0..n).map(move |i| {
+            let t = t0 + (t1 - t0) * (i as f64 / (n - 1).max(1) as f64);
+            ((self.x)(t), (self.y)(t))
+        })
+    }
+}
+
+pub enum PlotPoints<'a> {
+    Owned(Vec<PlotPoint>),
+    Borrowed(&'a [PlotPoint]),
+    Generator(ExplicitGenerator<'a>),
+    Parametric(ParametricGenerator<'a>),
+}
+
+impl<'a> PlotPoints<'a> {
+    pub fn owned(points: Vec<PlotPoint>) -> Self {
+        PlotPoints::Owned(points)
+    }
+
+    pub fn borrowed(points: &'a [PlotPoint]) -> Self {
+        PlotPoints::Borrowed(points)
+    }
+
+    pub fn generator<F>(function: F, x_range: (f64, f64), points: usize) -> Self
+    where
+        F: Fn(f64) -> f64 + 'a,
+    {
+        PlotPoints::Generator(ExplicitGenerator {
+            function: Box::new(function),
+            x_range,
+            points,
+            adaptive: None,
+        })
+    }
+
+    /// A parametric curve, e.g. circles, Lissajous figures or trajectories.
+    pub fn parametric<X, Y>(x: X, y: Y, t_range: (f64, f64), points: usize) -> Self
+    where
+        X: Fn(f64) -> f64 + 'a,
+        Y: Fn(f64) -> f64 + 'a,
+    {
+        PlotPoints::Parametric(ParametricGenerator {
+            x: Box::new(x),
+            y: Box::new(y),
+            t_range,
+            points,
+        })
+    }
+
+    /// Enable adaptive refinement for a generated series.
+    ///
+    /// `points` becomes the initial sample count over the visible range, and
+    /// segments are subdivided where the curve bends sharply on screen.
+    /// Has no effect on stored points.
+    pub fn with_adaptive_sampling(mut self, sampling: AdaptiveSampling) -> Self {
+        if let PlotPoints::Generator(generator) = &mut self {
+            generator.adaptive = Some(sampling);
+        }
+        self
+    }
+}
+
+impl From<Vec<PlotPoint>> for PlotPoints<'_> {
+    fn from(points: Vec<PlotPoint>) -> Self {
+        PlotPoints::Owned(points)
+    }
+}
+
+impl<'a> From<&'a [PlotPoint]> for PlotPoints<'a> {
+    fn from(points: &'a [PlotPoint]) -> Self {
+        PlotPoints::Borrowed(points)
+    }
+}
+
+impl Default for PlotPoints<'_> {
+    fn default() -> Self {
+        PlotPoints::Owned(Vec::new())
+    }
+}
+
+impl PlotPoints<'_> {
+    /// Get the last Y value in the series (for legend display).
+    pub fn last_y(&self) -> Option<f64> {
+        match self {
+            PlotPoints::Owned(pts) => pts.last().map(|p| p.y),
+            PlotPoints::Borrowed(pts) => pts.last().map(|p| p.y),
+            // generators don't have a "latest" point
+            PlotPoints::Generator(_) | PlotPoints::Parametric(_) => None,
+        }
+    }
+
+    /// The stored points, or `None` for generated series.
+    pub fn as_slice(&self) -> Option<&[PlotPoint]> {
+        match self {
+            PlotPoints::Owned(pts) => Some(pts),
+            PlotPoints::Borrowed(pts) => Some(pts),
+            PlotPoints::Generator(_) | PlotPoints::Parametric(_) => None,
+        }
+    }
+}
+
+/// Represents the visible range of the plot.
+///
+/// Each axis range is `Option` — `None` means "auto-fit to data bounds".
+/// This allows the common pattern of panning X while auto-fitting Y.
+///
+/// Owned by the user's application state and passed to
+/// [`Plotter`](crate::plotter::Plotter).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewState {
+    /// Visible X range. `None` = auto-fit to data bounds.
+    pub x_range: Option<(f64, f64)>,
+    /// Visible Y range. `None` = auto-fit to data bounds.
+    pub y_range: Option<(f64, f64)>,
+    /// Visible range of the secondary Y axis (the first of
+    /// [`PlotterOptions::extra_y_axes`](crate::plotter::PlotterOptions::extra_y_axes)).
+    /// `None` = auto-fit to the series on it. Pans and zooms of Y carry an
+    /// explicit range along.
+    pub secondary_y_range: Option<(f64, f64)>,
+}
+
+impl ViewState {
+    /// Create a new ViewState with both axes auto-fitting to data.
+    pub fn auto_fit() -> Self {
+        Self {
+            x_range: None,
+            y_range: None,
+            secondary_y_range: None,
+        }
+    }
+
+    /// Create a new ViewState with explicit ranges for both axes.
+    pub fn with_ranges(x_range: (f64, f64), y_range: (f64, f64)) -> Self {
+        Self {
+            x_range: Some(x_range),
+            y_range: Some(y_range),
+            secondary_y_range: None,
+        }
+    }
+
+    /// Set the X range (or None to auto-fit).
+    pub fn with_x_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.x_range = range;
+        self
+    }
+
+    /// Set the Y range (or None to auto-fit).
+    pub fn with_y_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.y_range = range;
+        self
+    }
+
+    /// Set the secondary Y range (or None to auto-fit).
+    pub fn with_secondary_y_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.secondary_y_range = range;
+        self
+    }
+
+    /// Show exactly `x_range` and `y_range`, e.g. for a "Zoom to event"
+    /// button. The secondary Y range is kept.
+    pub fn zoom_to(self, x_range: (f64, f64), y_range: (f64, f64)) -> Self {
+        self.with_x_range(Some(x_range)).with_y_range(Some(y_range))
+    }
+}
+
+/// Bounding box of data points, grown one point at a time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Extent {
+    pub x: [f64; 2],
+    pub y: [f64; 2],
+}
+
+impl Default for Extent {
+    fn default() -> Self {
+        Self {
+            x: [f64::INFINITY, f64::NEG_INFINITY],
+            y: [f64::INFINITY, f64::NEG_INFINITY],
+        }
+    }
+}
+
+impl Extent {
+    /// Grow to include `(x, y)`.
+    pub fn include(&mut self, x: f64, y: f64) {
+        self.include_x(x);
+        self.y = [self.y[0].min(y), self.y[1].max(y)];
+    }
+
+    /// Grow along X only, for points whose Y is on another axis.
+    pub fn include_x(&mut self, x: f64) {
+        self.x = [self.x[0].min(x), self.x[1].max(x)];
+    }
+
+    /// Whether no point has been included.
+    pub fn is_empty(&self) -> bool {
+        self.x[0] > self.x[1]
+    }
+
+    /// The X and Y ranges to fit the view to. An axis without points spans
+    /// `[0, 1]`, and a flat Y range is widened to one unit.
+    pub fn ranges(self) -> ([f64; 2], [f64; 2]) {
+        let x = if self.x[0] > self.x[1] {
+            [0.0, 1.0]
+        } else {
+            self.x
+        };
+        let y = if self.y[0] > self.y[1] {
+            [0.0, 1.0]
+        } else if (self.y[1] - self.y[0]).abs() < f64::EPSILON {
+            [self.y[0] - 0.5, self.y[1] + 0.5]
+        } else {
+            self.y
+        };
+        (x, y)
+    }
+}
+
+/// `range` with `padding` (a fraction of its span) added on each side.
+pub fn pad_range(range: [f64; 2], padding: f32) -> [f64; 2] {
+    let margin = (range[1] - range[0]) * f64::from(padding);
+    [range[0] - margin, range[1] + margin]
+}
+
+/// How one axis' visible range follows the data, see [`AxisFit::resolve`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AxisFit {
+    /// Fraction of the data span added on each side when auto-fitting.
+    pub padding: f32,
+    /// Slide an explicit range to end at the data's end, keeping its width.
+    pub follow: bool,
+    /// Keep an explicit range within these bounds, with this fraction of
+    /// their span allowed beyond them, see [`clamp_range_to_bounds`].
+    pub clamp: Option<((f64, f64), f32)>,
+}
+
+impl AxisFit {
+    /// The visible range: `view` when set, following and clamped as
+    /// configured, else the `data` range padded.
+    pub fn resolve(&self, view: Option<(f64, f64)>, data: [f64; 2]) -> [f64; 2] {
+        let Some((lo, hi)) = view else {
+            return pad_range(data, self.padding);
+        };
+        let (lo, hi) = if self.follow && data[1].is_finite() {
+            (data[1] - (hi - lo), data[1])
+        } else {
+            (lo, hi)
+        };
+        match self.clamp {
+            Some((bounds, padding)) => {
+                let (lo, hi) = clamp_range_to_bounds((lo, hi), Some(bounds), padding);
+                [lo, hi]
+            }
+            None => [lo, hi],
+        }
+    }
+}
+
+/// Map a data value into `[0, 1]` across `range` (unclamped).
+pub fn normalize(value: f64, range: [f64; 2]) -> f64 {
+    (value - range[0]) / (range[1] - range[0])
+}
+
+/// Inverse of [`normalize`].
+pub fn denormalize(t: f64, range: [f64; 2]) -> f64 {
+    range[0] + t * (range[1] - range[0])
+}
+
+/// Convert a position inside a plot area of `size` pixels to data coordinates.
+///
+/// `pos` is relative to the top-left corner of the plot area; screen Y grows
+/// downward while data Y grows upward.
+pub fn screen_to_data(
+    pos: [f32; 2],
+    size: [f32; 2],
+    view_x: [f64; 2],
+    view_y: [f64; 2],
+) -> [f64; 2] {
+    [
+        denormalize(f64::from(pos[0] / size[0]), view_x),
+        denormalize(1.0 - f64::from(pos[1] / size[1]), view_y),
+    ]
+}
+
+/// Convert data coordinates to a position inside a plot area of `size` pixels.
+pub fn data_to_screen(
+    data: [f64; 2],
+    size: [f32; 2],
+    view_x: [f64; 2],
+    view_y: [f64; 2],
+) -> [f32; 2] {
+    [
+        normalize(data[0], view_x) as f32 * size[0],
+        (1.0 - normalize(data[1], view_y)) as f32 * size[1],
+    ]
+}
+
+/// Clamp a view range to bounds, keeping the range size the same (shift rather than squash).
+/// If the view range exceeds bounds+padding, clamp it to the bounds size.
+pub fn clamp_range_to_bounds(
+    range: (f64, f64),
+    bounds: Option<(f64, f64)>,
+    padding_frac: f32,
+) -> (f64, f64) {
+    let (mut lo, mut hi) = range;
+    if let Some((b_lo, b_hi)) = bounds {
+        let pad = (b_hi - b_lo) * f64::from(padding_frac);
+        let min_bound = b_lo - pad;
+        let max_bound = b_hi + pad;
+        let bounds_size = max_bound - min_bound;
+        let range_size = hi - lo;
+
+        // If the view is wider than bounds+padding, clamp to bounds size and center
+        if range_size > bounds_size {
+            let center = (min_bound + max_bound) / 2.0;
+            lo = center - bounds_size / 2.0;
+            hi = center + bounds_size / 2.0;
+        } else {
+            // Shift to stay within bounds
+            if lo < min_bound {
+                lo = min_bound;
+                hi = lo + range_size;
+            }
+            if hi > max_bound {
+                hi = max_bound;
+                lo = hi - range_size;
+            }
+        }
+    }
+    (lo, hi)
+}
+
+/// Apply elastic resistance when dragging past bounds.
+/// Returns the elastically-damped range (allows slight over-scroll).
+pub fn apply_elastic_resistance(
+    range: (f64, f64),
+    bounds: Option<(f64, f64)>,
+    padding_frac: f32,
+    elastic_limit: f32,
+) -> (f64, f64) {
+    let (lo, hi) = range;
+    if let Some((b_lo, b_hi)) = bounds {
+        let pad = (b_hi - b_lo) * f64::from(padding_frac);
+        let min_bound = b_lo - pad;
+        let max_bound = b_hi + pad;
+        let range_size = hi - lo;
+        let max_overscroll = range_size * f64::from(elastic_limit);
+
+        let mut new_lo = lo;
+        let mut new_hi = hi;
+
+        // Apply damping when past bounds (exponential decay)
+        if lo < min_bound {
+            let over = min_bound - lo;
+            let damped = max_overscroll * (1.0 - (-over / max_overscroll).exp());
+            new_lo = min_bound - damped;
+            new_hi = new_lo + range_size;
+        } else if hi > max_bound {
+            let over = hi - max_bound;
+            let damped = max_overscroll * (1.0 - (-over / max_overscroll).exp());
+            new_hi = max_bound + damped;
+            new_lo = new_hi - range_size;
+        }
+
+        (new_lo, new_hi)
+    } else {
+        (lo, hi)
+    }
+}
+
+/// Check if a range is outside its bounds (needs spring-back).
+pub fn is_out_of_bounds(range: (f64, f64), bounds: Option<(f64, f64)>, padding_frac: f32) -> bool {
+    if let Some((b_lo, b_hi)) = bounds {
+        let pad = (b_hi - b_lo) * f64::from(padding_frac);
+        let min_bound = b_lo - pad;
+        let max_bound = b_hi + pad;
+        range.0 < min_bound - 0.001 || range.1 > max_bound + 0.001
+    } else {
+        false
+    }
+}
+
+/// Ease-out cubic: decelerating to zero velocity.
+pub fn ease_out_cubic(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Interpolate between two ranges using an easing function.
+pub fn lerp_range(from: (f64, f64), to: (f64, f64), t: f32) -> (f64, f64) {
+    let t = f64::from(ease_out_cubic(t));
+    (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+}
+
+/// Clip the segment `start`→`end` to the rectangle `[left, top, right, bottom]`
+/// (Liang–Barsky). Returns the visible part as parameters `[t0, t1]` along the
+/// segment, or `None` when it misses the rectangle entirely.
+pub fn clip_segment(start: [f32; 2], end: [f32; 2], rect: [f32; 4]) -> Option<[f32; 2]> {
+    let (dx, dy) = (end[0] - start[0], end[1] - start[1]);
+    let (mut t0, mut t1) = (0.0_f32, 1.0_f32);
+    for (p, q) in [
+        (-dx, start[0] - rect[0]),
+        (dx, rect[2] - start[0]),
+        (-dy, start[1] - rect[1]),
+        (dy, rect[3] - start[1]),
+    ] {
+        if p == 0.0 {
+            // Parallel to this edge: either fully inside or fully outside
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    (t0 <= t1).then_some([t0, t1])
+}
+
+/// Where a widget's logical pixels fall on the device's physical pixels,
+/// for drawing hairlines crisply.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PixelGrid {
+    /// Physical pixels per logical pixel.
+    pub scale: f32,
+    /// Position of the widget in the window, in logical pixels.
+    pub offset: [f32; 2],
+}
+
+impl Default for PixelGrid {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            offset: [0.0, 0.0],
+        }
+    }
+}
+
+impl PixelGrid {
+    /// Move the ends of the span `[lo, hi]` along `axis` (0 = X, 1 = Y,
+    /// widget-local logical pixels) to the nearest physical pixel
+    /// boundaries, keeping it at least one physical pixel wide.
+    pub fn snap(self, axis: usize, lo: f32, hi: f32) -> [f32; 2] {
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            return [lo, hi];
+        }
+        let offset = self.offset[axis];
+        let [lo, hi] = [lo, hi].map(|v| (v + offset) * self.scale);
+        let [mut a, mut b] = [lo.round(), hi.round()];
+        if b - a < 1.0 {
+            // Thinner than a pixel: the pixel holding the span's middle
+            a = ((lo + hi) / 2.0).floor();
+            b = a + 1.0;
+        }
+        [a, b].map(|v| v / self.scale - offset)
+    }
+}
+
+/// Points per pixel column above which [`m4_indices`] decimates.
+pub const M4_POINTS_PER_COLUMN: usize = 4;
+
+/// Indices of the samples to draw for a line sorted by X, reduced with M4
+/// decimation: within each of `columns` equal slices of `view_x`, only the
+/// first, last, lowest and highest samples are kept. The line drawn through
+/// them covers the same pixels as the full line, peaks included.
+///
+/// The samples just outside the view are kept so the line still runs to the
+/// edges, and non-finite samples are kept so gaps in the line survive.
+/// Returns `None` when the samples aren't sorted by X or there are
+/// few enough of them in view to draw all.
+pub fn m4_indices(
+    xs: impl Fn(usize) -> f64,
+    ys: impl Fn(usize) -> f64,
+    len: usize,
+    view_x: [f64; 2],
+    columns: usize,
+) -> Option<Vec<usize>> {
+    if !(1..len).all(|i| xs(i - 1) <= xs(i)) {
+        return None;
+    }
+    let lo = partition_point(len, |i| xs(i) < view_x[0]);
+    let hi = partition_point(len, |i| xs(i) <= view_x[1]);
+    let columns = columns.max(1);
+    if hi - lo <= columns * M4_POINTS_PER_COLUMN {
+        return None;
+    }
+
+    let mut kept = Vec::with_capacity(columns * 4 + 2);
+    kept.extend(lo.checked_sub(1));
+    // Column being filled, with its [first, lowest, highest, last] samples
+    let mut bucket: Option<(usize, [usize; 4])> = None;
+    let flush = |kept: &mut Vec<usize>, mut picks: [usize; 4]| {
+        picks.sort_unstable();
+        for i in picks {
+            if kept.last() != Some(&i) {
+                kept.push(i);
+            }
+        }
+    };
+    for i in lo..hi {
+        if !ys(i).is_finite() {
+            if let Some((_, picks)) = bucket.take() {
+                flush(&mut kept, picks);
+            }
+            kept.push(i);
+            continue;
+        }
+        let column = ((normalize(xs(i), view_x) * columns as f64) as usize).min(columns - 1);
+        match bucket.as_mut() {
+            Some((c, [_, low, high, last])) if *c == column => {
+                *last = i;
+                if ys(i) < ys(*low) {
+                    *low = i;
+                }
+                if ys(i) > ys(*high) {
+                    *high = i;
+                }
+            }
+            _ => {
+                if let Some((_, picks)) = bucket {
+                    flush(&mut kept, picks);
+                }
+                bucket = Some((column, [i; 4]));
+            }
+        }
+    }
+    if let Some((_, picks)) = bucket {
+        flush(&mut kept, picks);
+    }
+    if hi < len {
+        kept.push(hi);
+    }
+    Some(kept)
+}
+
+/// First index in `0..len` for which `pred` is false, assuming `pred` holds
+/// for a prefix.
+fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Widen `view_x` or `view_y` about its center so one data unit spans the
+/// same number of pixels along both axes of a plot area of `size`.
+///
+/// The range with fewer data units per pixel grows; the other is kept.
+pub fn equalize_aspect(view_x: [f64; 2], view_y: [f64; 2], size: [f32; 2]) -> ([f64; 2], [f64; 2]) {
+    let widen = |[lo, hi]: [f64; 2], span: f64| {
+        let center = (lo + hi) / 2.0;
+        let half = span / 2.0 * (hi - lo).signum();
+        [center - half, center + half]
+    };
+    let [width, height] = size.map(f64::from);
+    let (x_span, y_span) = ((view_x[1] - view_x[0]).abs(), (view_y[1] - view_y[0]).abs());
+    if !(width > 0.0 && height > 0.0 && x_span > 0.0 && y_span > 0.0) {
+        return (view_x, view_y);
+    }
+    let per_px = (x_span / width).max(y_span / height);
+    if x_span / width < per_px {
+        (widen(view_x, per_px * width), view_y)
+    } else if y_span / height < per_px {
+        (view_x, widen(view_y, per_px * height))
+    } else {
+        (view_x, view_y)
+    }
+}
+
+/// Most cells a [`PointGrid`] has along either side of the plot area.
+const GRID_MAX_CELLS: f32 = 256.0;
+
+/// Points bucketed into square cells of the plot area by screen position,
+/// for finding the points near a position without visiting all of them.
+///
+/// Built once for a view and reused across cursor moves. Cells cover the
+/// plot area plus one cell around it; points farther out, or with
+/// non-finite positions, are left out.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PointGrid {
+    /// Side of a cell in pixels.
+    cell: f32,
+    columns: usize,
+    rows: usize,
+    /// Offset into `indices` of each cell's first point, plus the end.
+    starts: Vec<u32>,
+    /// Point indices, cell by cell, in index order within a cell.
+    indices: Vec<u32>,
+}
+
+impl PointGrid {
+    /// Bucket the `len` points at `position` (pixels from the top-left
+    /// corner of a plot area of `size`) into cells at least `cell` wide.
+    pub fn new(
+        position: impl Fn(usize) -> [f32; 2],
+        len: usize,
+        size: [f32; 2],
+        cell: f32,
+    ) -> Self {
+        let cell = cell
+            .max(size[0] / GRID_MAX_CELLS)
+            .max(size[1] / GRID_MAX_CELLS)
+            .max(1.0);
+        let columns = (size[0] / cell).ceil().max(0.0) as usize + 2;
+        let rows = (size[1] / cell).ceil().max(0.0) as usize + 2;
+        let cell_of = |i: usize| {
+            let [x, y] = position(i);
+            let (column, row) = (x / cell + 1.0, y / cell + 1.0);
+            // Also false for NaN
+            let inside =
+                (0.0..columns as f32).contains(&column) && (0.0..rows as f32).contains(&row);
+            inside.then(|| row as usize * columns + column as usize)
+        };
+
+        // Counting sort: size each cell, then place the points
+        let cells: Vec<Option<usize>> = (0..len).map(cell_of).collect();
+        let mut starts = vec![0u32; columns * rows + 1];
+        for &c in cells.iter().flatten() {
+            starts[c + 1] += 1;
+        }
+        for c in 1..starts.len() {
+            starts[c] += starts[c - 1];
+        }
+        let mut next = starts.clone();
+        let mut indices = vec![0; starts[columns * rows] as usize];
+        for (i, c) in cells.into_iter().enumerate() {
+            if let Some(c) = c {
+                indices[next[c] as usize] = i as u32;
+                next[c] += 1;
+            }
+        }
+        Self {
+            cell,
+            columns,
+            rows,
+            starts,
+            indices,
+        }
+    }
+
+    /// Indices of the points in the cells touching the square of `radius`
+    /// around `pos`: every point within `radius`, and some farther away.
+    pub fn near(&self, pos: [f32; 2], radius: f32) -> impl Iterator<Item = usize> + '_ {
+        let span = |p: f32, cells: usize| {
+            let lo = ((p - radius) / self.cell + 1.0).floor().max(0.0) as usize;
+            let hi = ((p + radius) / self.cell + 1.0).floor().max(0.0) as usize;
+            lo..(hi + 1).min(cells)
+        };
+        let columns = span(pos[0], self.columns);
+        span(pos[1], self.rows).flat_map(move |row| {
+            let cells = row * self.columns + columns.start..row * self.columns + columns.end;
+            let points = if cells.is_empty() {
+                0..0
+            } else {
+                self.starts[cells.start] as usize..self.starts[cells.end] as usize
+            };
+            self.indices[points].iter().map(|&i| i as usize)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_sampling_refines_at_a_step() {
+        let step = |x: f64| if x < 0.55 { 0.0 } else { 1.0 };
+        let generator = ExplicitGenerator {
+            function: Box::new(step),
+            x_range: (0.0, 1.0),
+            points: 11,
+            adaptive: Some(AdaptiveSampling::default()),
+        };
+        // 1000 pixels across X, 100 pixels per unit of Y
+        let samples = generator.sample((0.0, 1.0), (1000.0, 100.0));
+        assert!(samples.len() <= AdaptiveSampling::default().max_points);
+        assert!(samples.windows(2).all(|w| w[0].0 < w[1].0));
+
+        // Bisected down to a pixel around the step, and left alone elsewhere
+        let gap = |(lo, hi): (f64, f64)| {
+            samples
+                .windows(2)
+                .filter(|w| w[0].0 >= lo && w[1].0 <= hi)
+                .map(|w| w[1].0 - w[0].0)
+                .fold(f64::INFINITY, f64::min)
+        };
+        assert!(gap((0.5, 0.6)) < 0.001);
+        assert!((gap((0.0, 0.5)) - 0.1).abs() < 1e-9);
+        assert!((gap((0.6, 1.0)) - 0.1).abs() < 1e-9);
+        let rise = samples
+            .windows(2)
+            .find(|w| w[0].1 == 0.0 && w[1].1 == 1.0)
+            .unwrap();
+        assert!(rise[0].0 < 0.55 && rise[1].0 >= 0.55);
+        assert!(rise[1].0 - rise[0].0 < 0.001);
+
+        // Only the visible part is sampled
+        let zoomed = generator.sample((0.2, 0.4), (5000.0, 100.0));
+        assert_eq!(zoomed.first().map(|p| p.0), Some(0.2));
+        assert_eq!(zoomed.last().map(|p| p.0), Some(0.4));
+        assert_eq!(zoomed.len(), 11);
+    }
+
+    #[test]
+    fn test_screen_data_roundtrip() {
+        let size = [200.0, 100.0];
+        let (view_x, view_y) = ([-1.0, 3.0], [10.0, 20.0]);
+        assert_eq!(
+            data_to_screen([-1.0, 20.0], size, view_x, view_y),
+            [0.0, 0.0]
+        );
+        let data = screen_to_data([50.0, 25.0], size, view_x, view_y);
+        assert_eq!(data, [0.0, 17.5]);
+        assert_eq!(data_to_screen(data, size, view_x, view_y), [50.0, 25.0]);
+    }
+
+    #[test]
+    fn test_equalize_aspect_widens_one_axis() {
+        // 10 units over 200 px vs 10 units over 100 px: X widens to 20
+        let (x, y) = equalize_aspect([0.0, 10.0], [0.0, 10.0], [200.0, 100.0]);
+        assert_eq!((x, y), ([-5.0, 15.0], [0.0, 10.0]));
+        let (x, y) = equalize_aspect([0.0, 40.0], [5.0, 15.0], [200.0, 100.0]);
+        assert_eq!((x, y), ([0.0, 40.0], [0.0, 20.0]));
+        // Degenerate input is left alone
+        assert_eq!(
+            equalize_aspect([0.0, 0.0], [0.0, 1.0], [200.0, 100.0]),
+            ([0.0, 0.0], [0.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_clip_segment() {
+        let rect = [0.0, 0.0, 10.0, 10.0];
+        assert_eq!(clip_segment([2.0, 2.0], [8.0, 8.0], rect), Some([0.0, 1.0]));
+        assert_eq!(
+            clip_segment([-10.0, 5.0], [20.0, 5.0], rect),
+            Some([1.0 / 3.0, 2.0 / 3.0])
+        );
+        assert_eq!(clip_segment([-1.0, 11.0], [11.0, 11.0], rect), None);
+        assert_eq!(clip_segment([-5.0, 0.0], [0.0, 15.0], rect), None);
+    }
+
+    #[test]
+    fn test_pixel_grid_snaps_hairlines() {
+        // A 1px line centered between pixels covers exactly one pixel
+        let grid = PixelGrid::default();
+        assert_eq!(grid.snap(0, 9.8, 10.8), [10.0, 11.0]);
+        assert_eq!(grid.snap(0, 10.4, 10.6), [10.0, 11.0]);
+
+        // At 1.5x, in a widget at a fractional position
+        let grid = PixelGrid {
+            scale: 1.5,
+            offset: [0.0, 10.5],
+        };
+        let [top, bottom] = grid.snap(1, 19.5, 20.5);
+        let physical = [top, bottom].map(|v| (v + 10.5) * 1.5);
+        assert!((physical[0] - 45.0).abs() < 1e-4);
+        assert!((physical[1] - 47.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_clamp_range_shifts_into_bounds() {
+        assert_eq!(
+            clamp_range_to_bounds((-5.0, 5.0), Some((0.0, 100.0)), 0.0),
+            (0.0, 10.0)
+        );
+        assert_eq!(
+            clamp_range_to_bounds((-50.0, 250.0), Some((0.0, 100.0)), 0.0),
+            (0.0, 100.0)
+        );
+        assert!(!is_out_of_bounds((0.0, 10.0), Some((0.0, 100.0)), 0.0));
+        assert!(is_out_of_bounds((-1.0, 10.0), Some((0.0, 100.0)), 0.0));
+    }
+
+    #[test]
+    fn test_m4_keeps_extremes() {
+        let ys: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.1).sin()).collect();
+        let ys = |i: usize| if i == 500 { 5.0 } else { ys[i] };
+        let xs = |i: usize| i as f64;
+
+        let kept = m4_indices(xs, ys, 1000, [100.0, 900.0], 10).expect("decimated");
+        assert!(kept.len() <= 10 * 4 + 2);
+        assert!(kept.is_sorted());
+        assert_eq!((kept[0], kept[kept.len() - 1]), (99, 901));
+        assert!(kept.contains(&500));
+
+        // Gaps are kept, and don't hide their column's extremes
+        let gappy = |i: usize| if i == 300 { f64::NAN } else { ys(i) };
+        let kept = m4_indices(xs, gappy, 1000, [100.0, 900.0], 10).expect("decimated");
+        assert!(kept.contains(&300) && kept.contains(&500));
+
+        // Few samples in view, or unsorted ones, are drawn in full
+        assert_eq!(m4_indices(xs, ys, 1000, [100.0, 110.0], 10), None);
+        assert_eq!(
+            m4_indices(|i| -(i as f64), ys, 1000, [-900.0, -100.0], 10),
+            None
+        );
+    }
+
+    #[test]
+    fn test_point_grid_finds_near_points() {
+        let positions: Vec<[f32; 2]> = (0..1000)
+            .map(|i| [(i * 37 % 1000) as f32 * 0.2, (i * 91 % 1000) as f32 * 0.1])
+            .chain([[f32::NAN, 5.0], [-500.0, 5.0]])
+            .collect();
+        let grid = PointGrid::new(|i| positions[i], positions.len(), [200.0, 100.0], 4.0);
+
+        for pos in [positions[0], positions[500], positions[999], [199.0, 99.0]] {
+            let near: Vec<usize> = grid.near(pos, 4.0).collect();
+            let expected: Vec<usize> = (0..positions.len())
+                .filter(|&i| {
+                    let [x, y] = positions[i];
+                    (x - pos[0]).hypot(y - pos[1]) <= 4.0
+                })
+                .collect();
+            assert!(expected.iter().all(|i| near.contains(i)));
+            // Only a few cells are visited
+            assert!(near.len() < 100);
+        }
+        // Non-finite and far-away points are left out
+        assert_eq!(grid.indices.len(), 1000);
+    }
+    #[test]
+    fn test_extent_ranges() {
+        assert_eq!(Extent::default().ranges(), ([0.0, 1.0], [0.0, 1.0]));
+
+        let mut extent = Extent::default();
+        extent.include(2.0, 5.0);
+        extent.include(-1.0, 5.0);
+        extent.include_x(4.0);
+        // A flat Y range is widened to one unit
+        assert_eq!(extent.ranges(), ([-1.0, 4.0], [4.5, 5.5]));
+    }
+
+    #[test]
+    fn test_axis_fit_resolve() {
+        let data = [0.0, 100.0];
+        let fit = AxisFit {
+            padding: 0.25,
+            ..AxisFit::default()
+        };
+        assert_eq!(fit.resolve(None, data), [-25.0, 125.0]);
+        assert_eq!(fit.resolve(Some((20.0, 30.0)), data), [20.0, 30.0]);
+
+        // Following keeps the width and ends at the data's end
+        let follow = AxisFit {
+            follow: true,
+            ..fit
+        };
+        assert_eq!(follow.resolve(Some((20.0, 30.0)), data), [90.0, 100.0]);
+
+        // Clamping shifts the range back within the bounds
+        let clamped = AxisFit {
+            clamp: Some(((0.0, 100.0), 0.0)),
+            ..fit
+        };
+        assert_eq!(clamped.resolve(Some((95.0, 115.0)), data), [80.0, 100.0]);
+    }
+}
@@ -2,20 +2,19 @@
 
 use bytemuck::{Pod, Zeroable};
 
-/// A point with position, color, and rendering options, ready for GPU upload.
+/// A marker instance with position, color, and rendering options, ready for GPU upload.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct RawPoint {
-    /// Position in data coordinates (x, y)
+    /// Position in the local frame (x, y), see [`Uniforms::origin`]
     pub position: [f32; 2],
     /// RGBA color
     pub color: [f32; 4],
     /// Marker shape as u32 (MarkerShape enum value)
     pub shape: u32,
-    /// Perpendicular distance from line center, normalised to [0, 1] at the
-    /// original (non-extended) half-width.  Used by the line fragment shader
-    /// for edge anti-aliasing.  Ignored for markers / grid.
-    pub edge_distance: f32,
+    /// Marker radius: pixels when positive, data units when negative
+    /// (see [`marker_radius`]).
+    pub size: f32,
 }
 
 impl RawPoint {
@@ -24,7 +23,7 @@ impl RawPoint {
             position: [x, y],
             color,
             shape: 0, // Default to circle
-            edge_distance: 0.0,
+            size: 0.0,
         }
     }
 
@@ -34,42 +33,139 @@ impl RawPoint {
             position: [x, y],
             color,
             shape,
-            edge_distance: 0.0,
-        }
-    }
-
-    /// Create a line vertex with edge distance for anti-aliasing.
-    /// `edge_dist` is the normalised perpendicular distance from the line
-    /// centre: 0.0 at the centre, 1.0 at the original half-width edge.
-    pub fn with_edge_distance(x: f32, y: f32, color: [f32; 4], edge_dist: f32) -> Self {
-        Self {
-            position: [x, y],
-            color,
-            shape: 0,
-            edge_distance: edge_dist,
+            size: 0.0,
         }
     }
 }
 
 /// Uniform data passed to shaders for coordinate transformation.
+///
+/// Sizes and positions are in logical pixels, like marker radii and line
+/// widths; [`Uniforms::scale_factor`] converts them to physical pixels.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct Uniforms {
-    /// Viewport size in pixels (width, height)
+    /// Viewport size in logical pixels (width, height)
     pub viewport_size: [f32; 2],
-    /// Data X range (min, max)
+    /// Visible X range (min, max), relative to `origin`
     pub x_range: [f32; 2],
-    /// Data Y range (min, max)
+    /// Visible Y range (min, max), relative to `origin`
     pub y_range: [f32; 2],
-    /// Padding in pixels (horizontal, vertical)
+    /// Offset of the plot area from the widget origin in pixels (left, top)
     pub padding: [f32; 2],
-    /// Marker radius in pixels
-    pub marker_radius: f32,
+    /// Size of the plot area in pixels (width, height)
+    pub plot_size: [f32; 2],
     /// Line width in pixels
     pub line_width: f32,
+    /// Physical pixels per logical pixel. Only known when the primitive is
+    /// prepared, so it is 1 until then.
+    pub scale_factor: f32,
+    /// Data-space origin of the local frame, rounded to `f32`. Marker
+    /// positions and the ranges above are relative to it; the shaders never
+    /// need absolute coordinates.
+    pub origin: [f32; 2],
+    /// Data units per local-frame unit for [`CompactPoint`] positions
+    pub compact_scale: [f32; 2],
+}
+
+// Downlevel backends reject uniform bindings that are not 16-byte multiples.
+const _: () = assert!(std::mem::size_of::<Uniforms>().is_multiple_of(16));
+
+/// A 12-byte marker instance for very large scatter plots.
+///
+/// Positions are half-precision floats in the local frame (see
+/// [`Uniforms::origin`]) divided by [`Uniforms::compact_scale`], so precision
+/// is always relative to the current view. Colors are packed RGBA8.
+///
+/// The shader reads `shape` and `size` as one little-endian `u32`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct CompactPoint {
+    /// Local-frame position as f16 bits (x, y)
+    pub position: [u16; 2],
+    /// RGBA8 color, red in the lowest byte
+    pub color: [u8; 4],
+    /// Marker shape (MarkerShape enum value)
+    pub shape: u16,
+    /// Marker radius as f16 bits, signed like [`RawPoint::size`]
+    pub size: u16,
+}
+
+impl CompactPoint {
+    /// Pack a [`RawPoint`] whose position is already in the local frame,
+    /// dividing it by `scale`.
+    pub fn from_raw(point: &RawPoint, scale: [f32; 2]) -> Self {
+        let local = |i: usize| f32_to_f16_bits(point.position[i] / scale[i]);
+        Self {
+            position: [local(0), local(1)],
+            color: point
+                .color
+                .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8),
+            shape: point.shape as u16,
+            size: f32_to_f16_bits(point.size),
+        }
+    }
+}
+
+/// Signed marker radius stored in [`RawPoint::size`].
+pub fn marker_radius(radius: f32, data_units: bool) -> f32 {
+    if data_units { -radius } else { radius }
+}
+
+/// Convert an `f32` to IEEE 754 half-precision bits, rounding to nearest.
+pub fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent == 0xff {
+        // Inf / NaN
+        let nan = if mantissa != 0 { 0x0200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        // Overflow: clamp to infinity
+        return sign | 0x7c00;
+    }
+    if half_exponent <= 0 {
+        // Subnormal half or zero
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        let rounded = (mantissa + (1 << (shift - 1))) >> shift;
+        return sign | rounded as u16;
+    }
+
+    // Round to nearest; a mantissa carry correctly bumps the exponent
+    let rounded = ((half_exponent as u32) << 10) + ((mantissa + 0x1000) >> 13);
+    if rounded >= 0x7c00 {
+        return sign | 0x7c00;
+    }
+    sign | rounded as u16
+}
+
+/// Convert IEEE 754 half-precision bits to an `f32`.
+pub fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x03ff);
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
 }
 
 /// A vertex for line rendering with distance tracking for patterns.
+///
+/// Everything drawn by the line pipeline uses it: series lines, polygon
+/// fills and outlines, the grid and interaction overlays.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct LineVertex {
@@ -77,10 +173,14 @@ pub struct LineVertex {
     pub position: [f32; 2],
     /// RGBA color
     pub color: [f32; 4],
-    /// Distance along line segment (for pattern rendering)
+    /// Distance along the line in multiples of its width (for pattern rendering)
     pub distance: f32,
     /// Line pattern as u32 (LinePattern enum value)
     pub pattern: u32,
+    /// Perpendicular distance from line center, normalised to [0, 1] at the
+    /// original (non-extended) half-width.  Used by the line fragment shader
+    /// for edge anti-aliasing.  0.0 renders fully opaque.
+    pub edge_distance: f32,
 }
 
 impl LineVertex {
@@ -90,8 +190,32 @@ impl LineVertex {
             color,
             distance,
             pattern,
+            edge_distance: 0.0,
         }
     }
+
+    /// A fully opaque vertex without a pattern, for fills and grid lines.
+    pub fn solid(x: f32, y: f32, color: [f32; 4]) -> Self {
+        Self::new(x, y, color, 0.0, 0)
+    }
+
+    /// Set the edge distance for anti-aliasing, see [`LineVertex::edge_distance`].
+    pub fn with_edge_distance(mut self, edge_distance: f32) -> Self {
+        self.edge_distance = edge_distance;
+        self
+    }
+}
+
+/// A vertex of a textured quad: an image or a spectrogram.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct ImageVertex {
+    /// Position in screen coordinates
+    pub position: [f32; 2],
+    /// Texture coordinates; U may exceed 1 to wrap around a ring of columns
+    pub uv: [f32; 2],
+    /// Multiplies the texture's alpha
+    pub opacity: f32,
 }
 
 /// A vertex for fill rendering (area under curves).
@@ -112,3 +236,34 @@ impl FillVertex {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_conversion() {
+        assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+        assert_eq!(f32_to_f16_bits(1.0), 0x3c00);
+        assert_eq!(f32_to_f16_bits(-2.0), 0xc000);
+        assert_eq!(f32_to_f16_bits(0.5), 0x3800);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16_bits(1.0e6), 0x7c00);
+        // Smallest subnormal half
+        assert_eq!(f32_to_f16_bits(5.96e-8), 0x0001);
+
+        for value in [0.0, 1.0, -2.0, 0.5, 65504.0, 5.960_464_5e-8] {
+            assert_eq!(f16_bits_to_f32(f32_to_f16_bits(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_compact_point_keeps_marker() {
+        let mut point = RawPoint::with_shape(0.5, -1.0, [1.0; 4], 3);
+        point.size = marker_radius(2.0, true);
+        let compact = CompactPoint::from_raw(&point, [1.0, 2.0]);
+        assert_eq!(compact.position, [0x3800, 0xb800]);
+        assert_eq!(compact.shape, 3);
+        assert_eq!(compact.size, f32_to_f16_bits(-2.0));
+    }
+}
@@ -1,6 +1,14 @@
+#[cfg(feature = "colormap")]
 pub mod colormap;
+pub mod core;
 pub mod gpu_types;
 pub mod pipeline;
 pub mod plotter;
 pub mod shader;
+mod software;
+pub mod spectrogram;
+pub mod streaming;
+pub mod testing;
 pub mod ticks;
+pub mod transform;
+pub mod triangulate;
@@ -1,14 +1,73 @@
 //! GPU rendering pipeline for the plotter.
 
-use crate::gpu_types::{RawPoint, Uniforms};
+use crate::core::PixelGrid;
+use crate::gpu_types::{CompactPoint, ImageVertex, LineVertex, RawPoint, Uniforms};
 use iced::wgpu;
 
+/// Granularity in bytes at which [`DynamicBuffer::upload`] compares new data
+/// with the buffer contents.
+const UPLOAD_CHUNK: usize = 256;
+
+/// The marker, line and image shaders.
+const SHADER_SOURCE: &str = include_str!("shaders/plot.wgsl");
+
+/// Blend state for transparency
+const BLEND_STATE: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::SrcAlpha,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+/// Additive blend for density plots: overlapping markers add up
+const ADDITIVE_BLEND_STATE: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::SrcAlpha,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+/// Samples per pixel of the marker and line passes when multisampling, see
+/// [`Pipeline::prepare_multisample`].
+pub const MULTISAMPLE_COUNT: u32 = 4;
+
+/// Whether frames of `format` can be drawn with [`MULTISAMPLE_COUNT`]
+/// samples and resolved.
+fn supports_multisample(format: wgpu::TextureFormat, device_features: wgpu::Features) -> bool {
+    let flags = format.guaranteed_format_features(device_features).flags;
+    flags.sample_count_supported(MULTISAMPLE_COUNT)
+        && flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE)
+}
+
+/// Source of [`next_image_id`].
+static NEXT_IMAGE_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// A new id for texels uploaded with [`Pipeline::update_image`].
+pub fn next_image_id() -> u64 {
+    NEXT_IMAGE_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
 /// A dynamically resizable GPU buffer.
 pub struct DynamicBuffer {
     pub buffer: wgpu::Buffer,
     capacity: u64,
     usage: wgpu::BufferUsages,
     label: &'static str,
+    /// Copy of the bytes last written to `buffer`, so uploads can skip the
+    /// part that didn't change.
+    contents: Vec<u8>,
 }
 
 impl DynamicBuffer {
@@ -30,6 +89,7 @@ impl DynamicBuffer {
             capacity: initial_capacity,
             usage: usage | wgpu::BufferUsages::COPY_DST,
             label,
+            contents: Vec::new(),
         }
     }
 
@@ -45,27 +105,390 @@ impl DynamicBuffer {
                 mapped_at_creation: false,
             });
             self.capacity = new_capacity;
+            self.contents.clear();
         }
     }
+
+    /// Write `data` to the start of the buffer, growing it if needed.
+    ///
+    /// Only the bytes from the first changed chunk onward are sent to the
+    /// GPU, so appending to data that is otherwise unchanged (e.g. a stream
+    /// filling up in a fixed view) uploads just the new part.
+    pub fn upload(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        self.ensure_capacity(device, data.len() as u64);
+        let unchanged = data
+            .chunks(UPLOAD_CHUNK)
+            .zip(self.contents.chunks(UPLOAD_CHUNK))
+            .take_while(|(new, old)| new == old)
+            .count()
+            * UPLOAD_CHUNK;
+        let start = unchanged.min(data.len());
+        if start < data.len() {
+            queue.write_buffer(&self.buffer, start as u64, &data[start..]);
+        }
+        self.contents.truncate(start);
+        self.contents.extend_from_slice(&data[start..]);
+    }
 }
 
 /// The GPU rendering pipeline for the plotter.
+///
+/// Stays within [`wgpu::Limits::downlevel_webgl2_defaults`] so the crate
+/// also runs in browsers and on older GPUs: a single uniform buffer padded
+/// to 16 bytes, no storage buffers, one vertex buffer per pipeline within
+/// the attribute and stride limits, and few enough values passed between
+/// the shader stages, as checked by this module's tests.
 pub struct Pipeline {
-    marker_pipeline: wgpu::RenderPipeline,
-    line_pipeline: wgpu::RenderPipeline,
+    series: SeriesPipelines,
+    image_pipeline: wgpu::RenderPipeline,
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    image_pipeline_layout: wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    /// Created on first use, see [`Self::prepare_multisample`].
+    multisample: Option<Multisample>,
     point_buffer: DynamicBuffer,
+    compact_point_buffer: DynamicBuffer,
     line_buffer: DynamicBuffer,
     grid_buffer: DynamicBuffer,
+    image_buffer: DynamicBuffer,
     uniform_buffer: wgpu::Buffer,
     #[allow(dead_code)]
     bind_group_layout: wgpu::BindGroupLayout,
     bind_group: wgpu::BindGroup,
+    image_bind_group_layout: wgpu::BindGroupLayout,
+    /// Nearest filtering, wrapping horizontally for rings of columns
+    sharp_sampler: wgpu::Sampler,
+    /// Linear filtering, clamped to the edges
+    smooth_sampler: wgpu::Sampler,
+    /// One texture per image layer, in drawing order.
+    image_slots: Vec<ImageSlot>,
     /// Scissor rect for the plot area (inside padding), in absolute physical pixels.
     /// Set during `prepare`, used during `draw`. Format: [x, y, width, height].
     pub plot_scissor: [u32; 4],
+    /// Scissor rect for markers: the plot area grown by the marker bleed margin.
+    /// Format: [x, y, width, height].
+    pub marker_scissor: [u32; 4],
     /// Scissor rect for the full widget bounds, in absolute physical pixels.
     /// Used to restore after plot-area clipping. Format: [x, y, width, height].
     pub widget_scissor: [u32; 4],
+    /// Key of the primitive whose data is currently in the GPU buffers.
+    /// Used to skip uploads when a cached primitive is drawn again.
+    pub uploaded_key: Option<u64>,
+    /// Data key of the primitive whose uniforms, markers and lines are in
+    /// the GPU buffers. Used to upload only the grid and overlays when just
+    /// those changed.
+    pub uploaded_data_key: Option<u64>,
+    /// Pixel grid the uploaded grid vertices were snapped to.
+    pub uploaded_pixels: Option<PixelGrid>,
+    /// Whether markers and lines go through the multisampled target this
+    /// frame. Set during `prepare`.
+    pub multisampling: bool,
+}
+
+/// Pipelines and offscreen targets for multisampled markers and lines.
+///
+/// The series are drawn into a transparent multisampled texture the size of
+/// the widget, resolved, and blended onto the frame, since the render pass
+/// iced hands out has a single sample.
+struct Multisample {
+    series: SeriesPipelines,
+    /// Blends the resolved texture, whose colors are premultiplied by alpha
+    composite_pipeline: wgpu::RenderPipeline,
+    targets: Option<MultisampleTargets>,
+}
+
+struct MultisampleTargets {
+    /// Widget size in physical pixels
+    size: [u32; 2],
+    view: wgpu::TextureView,
+    resolve_view: wgpu::TextureView,
+    /// The resolved texture, for the composite pass
+    bind_group: wgpu::BindGroup,
+}
+
+/// Point vertex buffer layout (per-instance data)
+const POINT_LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+    array_stride: std::mem::size_of::<RawPoint>() as u64,
+    step_mode: wgpu::VertexStepMode::Instance,
+    attributes: &[
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x2,
+            offset: 0,
+            shader_location: 0,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x4,
+            offset: 8,
+            shader_location: 1,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Uint32,
+            offset: 24,
+            shader_location: 2,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32,
+            offset: 28,
+            shader_location: 3,
+        },
+    ],
+};
+
+/// Compact point layout (per-instance): f16 position, RGBA8 color,
+/// shape and f16 size packed into one u32
+const COMPACT_POINT_LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+    array_stride: std::mem::size_of::<CompactPoint>() as u64,
+    step_mode: wgpu::VertexStepMode::Instance,
+    attributes: &[
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float16x2,
+            offset: 0,
+            shader_location: 0,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Unorm8x4,
+            offset: 4,
+            shader_location: 1,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Uint32,
+            offset: 8,
+            shader_location: 2,
+        },
+    ],
+};
+
+/// Line vertex buffer layout: position, color, pattern distance and
+/// kind, edge distance for anti-aliasing
+const LINE_LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+    array_stride: std::mem::size_of::<LineVertex>() as u64,
+    step_mode: wgpu::VertexStepMode::Vertex,
+    attributes: &[
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x2,
+            offset: 0,
+            shader_location: 0,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x4,
+            offset: 8,
+            shader_location: 1,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32,
+            offset: 32,
+            shader_location: 2,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32,
+            offset: 24,
+            shader_location: 3,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Uint32,
+            offset: 28,
+            shader_location: 4,
+        },
+    ],
+};
+
+/// Image vertex buffer layout: position, texture coordinates and opacity
+const IMAGE_LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+    array_stride: std::mem::size_of::<ImageVertex>() as u64,
+    step_mode: wgpu::VertexStepMode::Vertex,
+    attributes: &[
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x2,
+            offset: 0,
+            shader_location: 0,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x2,
+            offset: 8,
+            shader_location: 1,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32,
+            offset: 16,
+            shader_location: 2,
+        },
+    ],
+};
+
+/// Marker and line pipelines for one sample count.
+struct SeriesPipelines {
+    marker: wgpu::RenderPipeline,
+    compact_marker: wgpu::RenderPipeline,
+    /// Marker pipelines with additive blending, for density plots.
+    additive_marker: wgpu::RenderPipeline,
+    additive_compact_marker: wgpu::RenderPipeline,
+    line: wgpu::RenderPipeline,
+}
+
+impl SeriesPipelines {
+    fn new(
+        device: &wgpu::Device,
+        shader: &wgpu::ShaderModule,
+        pipeline_layout: &wgpu::PipelineLayout,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        // Marker pipelines share the fragment stage and differ in instance
+        // format and blending
+        let create_marker_pipeline =
+            |label: &str,
+             entry_point: &str,
+             layout: &wgpu::VertexBufferLayout<'_>,
+             blend: wgpu::BlendState| {
+                device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(label),
+                    layout: Some(pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: shader,
+                        entry_point: Some(entry_point),
+                        buffers: std::slice::from_ref(layout),
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    },
+                    primitive: wgpu::PrimitiveState {
+                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: sample_count,
+                        ..Default::default()
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: shader,
+                        entry_point: Some("fs_marker"),
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format,
+                            blend: Some(blend),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    }),
+                    multiview: None,
+                    cache: None,
+                })
+            };
+        let marker_pipeline =
+            create_marker_pipeline("marker_pipeline", "vs_marker", &POINT_LAYOUT, BLEND_STATE);
+        let additive_marker_pipeline = create_marker_pipeline(
+            "additive_marker_pipeline",
+            "vs_marker",
+            &POINT_LAYOUT,
+            ADDITIVE_BLEND_STATE,
+        );
+        let compact_marker_pipeline = create_marker_pipeline(
+            "compact_marker_pipeline",
+            "vs_marker_compact",
+            &COMPACT_POINT_LAYOUT,
+            BLEND_STATE,
+        );
+        let additive_compact_marker_pipeline = create_marker_pipeline(
+            "additive_compact_marker_pipeline",
+            "vs_marker_compact",
+            &COMPACT_POINT_LAYOUT,
+            ADDITIVE_BLEND_STATE,
+        );
+
+        // Create line pipeline
+        let line_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("line_pipeline"),
+            layout: Some(pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some("vs_line"),
+                buffers: &[LINE_LAYOUT],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some("fs_line"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(BLEND_STATE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            marker: marker_pipeline,
+            compact_marker: compact_marker_pipeline,
+            additive_marker: additive_marker_pipeline,
+            additive_compact_marker: additive_compact_marker_pipeline,
+            line: line_pipeline,
+        }
+    }
+}
+
+/// A pipeline drawing textured quads of [`ImageVertex`], at one sample per
+/// pixel.
+fn create_image_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    label: &str,
+    fragment_entry_point: &str,
+    blend: wgpu::BlendState,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_image"),
+            buffers: &[IMAGE_LAYOUT],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(fragment_entry_point),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// The texture of one image layer.
+struct ImageSlot {
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+    size: [u32; 2],
+    smooth: bool,
+    /// Key of the texels last written, see [`Pipeline::update_image`].
+    uploaded: Option<(u64, u64)>,
 }
 
 impl Pipeline {
@@ -73,9 +496,7 @@ impl Pipeline {
         // Create shader module
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("plot_shader"),
-            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!(
-                "shaders/plot.wgsl"
-            ))),
+            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(SHADER_SOURCE)),
         });
 
         // Create uniform buffer
@@ -95,7 +516,7 @@ impl Pipeline {
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
-                    min_binding_size: None,
+                    min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<Uniforms>() as u64),
                 },
                 count: None,
             }],
@@ -111,6 +532,47 @@ impl Pipeline {
             }],
         });
 
+        // Image layer texture and sampler, in a second group so the other
+        // pipelines don't need one
+        let image_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("image_bind_group_layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+
+        // Ring columns wrap around horizontally, see `ImageVertex::uv`
+        let sharp_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("sharp_image_sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let smooth_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("smooth_image_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
         // Create pipeline layout
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("plot_pipeline_layout"),
@@ -118,131 +580,24 @@ impl Pipeline {
             push_constant_ranges: &[],
         });
 
-        // Point vertex buffer layout (per-instance data)
-        let point_vertex_layout = wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<RawPoint>() as u64,
-            step_mode: wgpu::VertexStepMode::Instance,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x2,
-                    offset: 0,
-                    shader_location: 0,
-                },
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x4,
-                    offset: 8,
-                    shader_location: 1,
-                },
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Uint32,
-                    offset: 24,
-                    shader_location: 2,
-                },
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Uint32,
-                    offset: 28,
-                    shader_location: 3,
-                },
-            ],
-        };
-
-        // Line vertex buffer layout - uses RawPoint: position, color, and edge_distance
-        let line_vertex_layout = wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<RawPoint>() as u64,
-            step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x2,
-                    offset: 0,
-                    shader_location: 0,
-                },
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x4,
-                    offset: 8,
-                    shader_location: 1,
-                },
-                // edge_distance for line anti-aliasing (offset 28 = after shape u32)
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32,
-                    offset: 28,
-                    shader_location: 2,
-                },
-            ],
-        };
-
-        // Blend state for transparency
-        let blend_state = wgpu::BlendState {
-            color: wgpu::BlendComponent {
-                src_factor: wgpu::BlendFactor::SrcAlpha,
-                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                operation: wgpu::BlendOperation::Add,
-            },
-            alpha: wgpu::BlendComponent {
-                src_factor: wgpu::BlendFactor::One,
-                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                operation: wgpu::BlendOperation::Add,
-            },
-        };
+        let series = SeriesPipelines::new(device, &shader, &pipeline_layout, format, 1);
 
-        // Create marker pipeline
-        let marker_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("marker_pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_marker"),
-                buffers: &[point_vertex_layout],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_marker"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: Some(blend_state),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        let image_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("image_pipeline_layout"),
+                bind_group_layouts: &[&bind_group_layout, &image_bind_group_layout],
+                push_constant_ranges: &[],
+            });
 
-        // Create line pipeline
-        let line_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("line_pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_line"),
-                buffers: &[line_vertex_layout],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_line"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: Some(blend_state),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        let image_pipeline = create_image_pipeline(
+            device,
+            &shader,
+            &image_pipeline_layout,
+            format,
+            "image_pipeline",
+            "fs_image",
+            BLEND_STATE,
+        );
 
         // Create dynamic buffers
         let point_buffer = DynamicBuffer::new(
@@ -252,31 +607,61 @@ impl Pipeline {
             wgpu::BufferUsages::VERTEX,
         );
 
+        let compact_point_buffer = DynamicBuffer::new(
+            device,
+            "compact_point_buffer",
+            1024 * std::mem::size_of::<CompactPoint>() as u64,
+            wgpu::BufferUsages::VERTEX,
+        );
+
         let line_buffer = DynamicBuffer::new(
             device,
             "line_buffer",
-            1024 * std::mem::size_of::<RawPoint>() as u64,
+            1024 * std::mem::size_of::<LineVertex>() as u64,
             wgpu::BufferUsages::VERTEX,
         );
 
         let grid_buffer = DynamicBuffer::new(
             device,
             "grid_buffer",
-            1024 * std::mem::size_of::<RawPoint>() as u64,
+            1024 * std::mem::size_of::<LineVertex>() as u64,
+            wgpu::BufferUsages::VERTEX,
+        );
+
+        let image_buffer = DynamicBuffer::new(
+            device,
+            "image_buffer",
+            6 * std::mem::size_of::<ImageVertex>() as u64,
             wgpu::BufferUsages::VERTEX,
         );
 
         Self {
-            marker_pipeline,
-            line_pipeline,
+            series,
+            image_pipeline,
+            shader,
+            pipeline_layout,
+            image_pipeline_layout,
+            format,
+            multisample: None,
             point_buffer,
+            compact_point_buffer,
             line_buffer,
             grid_buffer,
+            image_buffer,
             uniform_buffer,
             bind_group_layout,
             bind_group,
+            image_bind_group_layout,
+            sharp_sampler,
+            smooth_sampler,
+            image_slots: Vec::new(),
             plot_scissor: [0, 0, 1, 1],
+            marker_scissor: [0, 0, 1, 1],
             widget_scissor: [0, 0, 1, 1],
+            uploaded_key: None,
+            uploaded_data_key: None,
+            uploaded_pixels: None,
+            multisampling: false,
         }
     }
 
@@ -287,59 +672,287 @@ impl Pipeline {
         queue: &wgpu::Queue,
         uniforms: &Uniforms,
         points: &[RawPoint],
-        line_vertices: &[RawPoint],
-        grid_vertices: &[RawPoint],
+        line_vertices: &[LineVertex],
     ) {
         // Update uniforms
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
 
-        // Update point buffer
-        if !points.is_empty() {
-            let point_data = bytemuck::cast_slice(points);
-            self.point_buffer
-                .ensure_capacity(device, point_data.len() as u64);
-            queue.write_buffer(&self.point_buffer.buffer, 0, point_data);
+        self.point_buffer
+            .upload(device, queue, bytemuck::cast_slice(points));
+        self.line_buffer
+            .upload(device, queue, bytemuck::cast_slice(line_vertices));
+    }
+
+    /// Upload only the uniforms, e.g. when the scale factor changed.
+    pub fn update_uniforms(&mut self, queue: &wgpu::Queue, uniforms: &Uniforms) {
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
+    }
+
+    /// Upload grid vertices, along with any overlays drawn in the grid pass.
+    pub fn update_grid(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        grid_vertices: &[LineVertex],
+    ) {
+        self.grid_buffer
+            .upload(device, queue, bytemuck::cast_slice(grid_vertices));
+    }
+
+    /// Upload the image layer quads, six vertices per layer.
+    pub fn update_image_vertices(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertices: &[ImageVertex],
+    ) {
+        self.image_buffer
+            .upload(device, queue, bytemuck::cast_slice(vertices));
+    }
+
+    /// Upload the texels of image layer `slot`, RGBA rows of `size[0]`
+    /// pixels.
+    ///
+    /// `key` is an id for the texels and the number of columns written to
+    /// them, which a ring of columns (a spectrogram) writes to slot
+    /// `count % width`. Only the columns added since the last upload with
+    /// the same id are written; images that never change keep the count at
+    /// zero and are written once.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_image(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        slot: usize,
+        size: [u32; 2],
+        smooth: bool,
+        key: (u64, u64),
+        texels: &[u8],
+    ) {
+        self.ensure_image(device, slot, size, smooth);
+        let image = &mut self.image_slots[slot];
+        let (id, count) = key;
+        let width = u64::from(size[0]);
+        let dirty = match image.uploaded {
+            Some((uploaded_id, uploaded)) if uploaded_id == id => count.saturating_sub(uploaded),
+            _ => width,
+        };
+        image.uploaded = Some(key);
+        if dirty == 0 || width == 0 {
+            return;
         }
+        let image = &self.image_slots[slot];
+        if dirty >= width {
+            Self::write_image_columns(queue, image, texels, 0..size[0]);
+        } else {
+            // The newest `dirty` slots of the ring, which may wrap around its end
+            let start = ((count - dirty) % width) as u32;
+            let end = (count % width) as u32;
+            if start < end {
+                Self::write_image_columns(queue, image, texels, start..end);
+            } else {
+                Self::write_image_columns(queue, image, texels, start..size[0]);
+                Self::write_image_columns(queue, image, texels, 0..end);
+            }
+        }
+    }
+
+    /// Drop the textures of image layers from `count` on.
+    pub fn truncate_images(&mut self, count: usize) {
+        self.image_slots.truncate(count);
+    }
 
-        // Update line buffer
-        if !line_vertices.is_empty() {
-            let line_data = bytemuck::cast_slice(line_vertices);
-            self.line_buffer
-                .ensure_capacity(device, line_data.len() as u64);
-            queue.write_buffer(&self.line_buffer.buffer, 0, line_data);
+    /// Make sure image layer `slot` has a `size` texture sampled as asked,
+    /// (re)creating it if needed.
+    fn ensure_image(&mut self, device: &wgpu::Device, slot: usize, size: [u32; 2], smooth: bool) {
+        if self
+            .image_slots
+            .get(slot)
+            .is_some_and(|image| image.size == size && image.smooth == smooth)
+        {
+            return;
         }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("image_texture"),
+            size: wgpu::Extent3d {
+                width: size[0].max(1),
+                height: size[1].max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = if smooth {
+            &self.smooth_sampler
+        } else {
+            &self.sharp_sampler
+        };
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("image_bind_group"),
+            layout: &self.image_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+        let image = ImageSlot {
+            texture,
+            bind_group,
+            size,
+            smooth,
+            uploaded: None,
+        };
+        if slot < self.image_slots.len() {
+            self.image_slots[slot] = image;
+        } else {
+            self.image_slots.push(image);
+        }
+    }
 
-        if !grid_vertices.is_empty() {
-            let grid_data = bytemuck::cast_slice(grid_vertices);
-            self.grid_buffer
-                .ensure_capacity(device, grid_data.len() as u64);
-            queue.write_buffer(&self.grid_buffer.buffer, 0, grid_data);
+    /// Write `columns` of an image from `texels`, RGBA rows of the full
+    /// texture width.
+    fn write_image_columns(
+        queue: &wgpu::Queue,
+        image: &ImageSlot,
+        texels: &[u8],
+        columns: std::ops::Range<u32>,
+    ) {
+        let [width, height] = image.size;
+        let needed = (width as usize * height as usize) * 4;
+        if columns.is_empty() || height == 0 || texels.len() < needed {
+            return;
         }
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &image.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: columns.start,
+                    y: 0,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &texels[columns.start as usize * 4..],
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width: columns.end - columns.start,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Upload compact marker instances (used instead of `points` in [`Self::update`]).
+    pub fn update_compact_points(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        points: &[CompactPoint],
+    ) {
+        self.compact_point_buffer
+            .upload(device, queue, bytemuck::cast_slice(points));
     }
 
-    /// Render markers (points).
-    pub fn render_markers(&self, render_pass: &mut wgpu::RenderPass<'_>, num_points: u32) {
-        if num_points == 0 {
+    /// Render marker `instances` from the compact instance buffer, blended
+    /// additively if `additive`.
+    pub fn render_compact_markers(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        instances: std::ops::Range<u32>,
+        additive: bool,
+        multisampled: bool,
+    ) {
+        let Some(series) = self.series(multisampled) else {
+            return;
+        };
+        if instances.is_empty() {
             return;
         }
 
-        render_pass.set_pipeline(&self.marker_pipeline);
+        render_pass.set_pipeline(if additive {
+            &series.additive_compact_marker
+        } else {
+            &series.compact_marker
+        });
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.compact_point_buffer.buffer.slice(..));
+        render_pass.draw(0..6, instances);
+    }
+
+    /// Render marker (point) `instances`, blended additively if `additive`.
+    pub fn render_markers(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        instances: std::ops::Range<u32>,
+        additive: bool,
+        multisampled: bool,
+    ) {
+        let Some(series) = self.series(multisampled) else {
+            return;
+        };
+        if instances.is_empty() {
+            return;
+        }
+
+        render_pass.set_pipeline(if additive {
+            &series.additive_marker
+        } else {
+            &series.marker
+        });
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.point_buffer.buffer.slice(..));
         // 6 vertices per quad (2 triangles), one instance per point
-        render_pass.draw(0..6, 0..num_points);
+        render_pass.draw(0..6, instances);
     }
 
-    /// Render lines.
-    pub fn render_lines(&self, render_pass: &mut wgpu::RenderPass<'_>, num_vertices: u32) {
-        if num_vertices == 0 {
+    /// Render a range of the line vertices.
+    pub fn render_lines(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        vertices: std::ops::Range<u32>,
+        multisampled: bool,
+    ) {
+        let Some(series) = self.series(multisampled) else {
+            return;
+        };
+        if vertices.is_empty() {
             return;
         }
 
-        render_pass.set_pipeline(&self.line_pipeline);
+        render_pass.set_pipeline(&series.line);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.line_buffer.buffer.slice(..));
-        render_pass.draw(0..num_vertices, 0..1);
+        render_pass.draw(vertices, 0..1);
+    }
+
+    /// Render image layer `slot`.
+    pub fn render_image(&self, render_pass: &mut wgpu::RenderPass<'_>, slot: usize) {
+        let Some(image) = self.image_slots.get(slot) else {
+            return;
+        };
+
+        render_pass.set_pipeline(&self.image_pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_bind_group(1, &image.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.image_buffer.buffer.slice(..));
+        let first = slot as u32 * 6;
+        render_pass.draw(first..first + 6, 0..1);
     }
 
     pub fn render_grid(&self, render_pass: &mut wgpu::RenderPass<'_>, num_vertices: u32) {
@@ -347,9 +960,365 @@ impl Pipeline {
             return;
         }
 
-        render_pass.set_pipeline(&self.line_pipeline);
+        render_pass.set_pipeline(&self.series.line);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.grid_buffer.buffer.slice(..));
         render_pass.draw(0..num_vertices, 0..1);
     }
+
+    /// Marker and line pipelines for a pass with one sample per pixel, or
+    /// for the multisampled target.
+    fn series(&self, multisampled: bool) -> Option<&SeriesPipelines> {
+        if multisampled {
+            self.multisample
+                .as_ref()
+                .map(|multisample| &multisample.series)
+        } else {
+            Some(&self.series)
+        }
+    }
+
+    /// Get multisampled rendering ready for a widget of `size` physical
+    /// pixels, creating its pipelines and textures as needed.
+    ///
+    /// Returns `false` when the frame's texture format can't be rendered
+    /// with [`MULTISAMPLE_COUNT`] samples; markers and lines should then be
+    /// drawn single-sampled.
+    pub fn prepare_multisample(&mut self, device: &wgpu::Device, size: [u32; 2]) -> bool {
+        if !supports_multisample(self.format, device.features()) {
+            return false;
+        }
+
+        let multisample = self.multisample.get_or_insert_with(|| Multisample {
+            series: SeriesPipelines::new(
+                device,
+                &self.shader,
+                &self.pipeline_layout,
+                self.format,
+                MULTISAMPLE_COUNT,
+            ),
+            composite_pipeline: create_image_pipeline(
+                device,
+                &self.shader,
+                &self.image_pipeline_layout,
+                self.format,
+                "composite_pipeline",
+                "fs_composite",
+                wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            ),
+            targets: None,
+        });
+
+        let size = size.map(|v| v.max(1));
+        if multisample
+            .targets
+            .as_ref()
+            .is_some_and(|targets| targets.size == size)
+        {
+            return true;
+        }
+        let create_view = |label: &str, sample_count: u32, usage: wgpu::TextureUsages| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width: size[0],
+                        height: size[1],
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: self.format,
+                    usage,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+        let view = create_view(
+            "multisample_texture",
+            MULTISAMPLE_COUNT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+        let resolve_view = create_view(
+            "multisample_resolve_texture",
+            1,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        );
+        // One texel per pixel, so nearest filtering copies it exactly
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("multisample_resolve_bind_group"),
+            layout: &self.image_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&resolve_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sharp_sampler),
+                },
+            ],
+        });
+        multisample.targets = Some(MultisampleTargets {
+            size,
+            view,
+            resolve_view,
+            bind_group,
+        });
+        true
+    }
+
+    /// Begin a pass into the multisampled target, cleared to transparent and
+    /// resolved when the pass ends. `None` unless [`Self::prepare_multisample`]
+    /// succeeded.
+    pub fn begin_multisampled_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+    ) -> Option<wgpu::RenderPass<'a>> {
+        let targets = self.multisample.as_ref()?.targets.as_ref()?;
+        Some(encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("multisampled_series_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &targets.view,
+                depth_slice: None,
+                resolve_target: Some(&targets.resolve_view),
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Discard,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        }))
+    }
+
+    /// Blend the resolved multisampled target onto the frame, through the
+    /// image quad at `slot` (see [`Self::update_image_vertices`]).
+    pub fn render_composite(&self, render_pass: &mut wgpu::RenderPass<'_>, slot: usize) {
+        let Some(multisample) = &self.multisample else {
+            return;
+        };
+        let Some(targets) = &multisample.targets else {
+            return;
+        };
+
+        render_pass.set_pipeline(&multisample.composite_pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_bind_group(1, &targets.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.image_buffer.buffer.slice(..));
+        let first = slot as u32 * 6;
+        render_pass.draw(first..first + 6, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::wgpu::naga;
+
+    #[test]
+    fn test_vertex_layouts_fit_webgl2_limits() {
+        let limits = wgpu::Limits::downlevel_webgl2_defaults();
+        for layout in [
+            POINT_LAYOUT,
+            COMPACT_POINT_LAYOUT,
+            LINE_LAYOUT,
+            IMAGE_LAYOUT,
+        ] {
+            assert!(layout.array_stride <= u64::from(limits.max_vertex_buffer_array_stride));
+            assert!(layout.attributes.len() <= limits.max_vertex_attributes as usize);
+            for attribute in layout.attributes {
+                assert!(attribute.shader_location < limits.max_vertex_attributes);
+                assert!(attribute.offset + attribute.format.size() <= layout.array_stride);
+            }
+        }
+
+        let uniforms = std::mem::size_of::<Uniforms>() as u32;
+        assert!(uniforms.is_multiple_of(16));
+        assert!(uniforms <= limits.max_uniform_buffer_binding_size);
+    }
+
+    #[test]
+    fn test_shader_fits_webgl2_limits() {
+        let limits = wgpu::Limits::downlevel_webgl2_defaults();
+        let module = naga::front::wgsl::parse_str(SHADER_SOURCE).unwrap();
+        // `unpack2x16float` of the compact markers; the GL backends always
+        // support it
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::SHADER_FLOAT16_IN_FLOAT32,
+        )
+        .validate(&module)
+        .unwrap();
+
+        assert!(
+            module
+                .global_variables
+                .iter()
+                .all(|(_, global)| !matches!(global.space, naga::AddressSpace::Storage { .. }))
+        );
+
+        // Components of the values each vertex stage hands to the fragment stage
+        let components = |ty: naga::Handle<naga::Type>| match module.types[ty].inner {
+            naga::TypeInner::Scalar(_) => 1,
+            naga::TypeInner::Vector { size, .. } => size as u32,
+            ref inner => panic!("unexpected inter-stage type {inner:?}"),
+        };
+        for entry in &module.entry_points {
+            if entry.stage != naga::ShaderStage::Vertex {
+                continue;
+            }
+            let result = entry.function.result.as_ref().unwrap();
+            let naga::TypeInner::Struct { ref members, .. } = module.types[result.ty].inner else {
+                panic!("{} returns no struct", entry.name);
+            };
+            let total: u32 = members
+                .iter()
+                .filter(|m| matches!(m.binding, Some(naga::Binding::Location { .. })))
+                .map(|m| components(m.ty))
+                .sum();
+            assert!(
+                total <= limits.max_inter_stage_shader_components,
+                "{} passes {total} components",
+                entry.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_uniforms_match_shader_layout() {
+        use std::mem::offset_of;
+
+        let module = naga::front::wgsl::parse_str(SHADER_SOURCE).unwrap();
+        let (_, ty) = module
+            .types
+            .iter()
+            .find(|(_, ty)| ty.name.as_deref() == Some("Uniforms"))
+            .unwrap();
+        let naga::TypeInner::Struct { ref members, span } = ty.inner else {
+            panic!("Uniforms is not a struct");
+        };
+        let offsets: Vec<(&str, u32)> = members
+            .iter()
+            .map(|m| (m.name.as_deref().unwrap(), m.offset))
+            .collect();
+
+        // The shaders must read each field, the scale factor among them,
+        // from the bytes the CPU writes it to
+        let expected = [
+            ("viewport_size", offset_of!(Uniforms, viewport_size)),
+            ("x_range", offset_of!(Uniforms, x_range)),
+            ("y_range", offset_of!(Uniforms, y_range)),
+            ("padding", offset_of!(Uniforms, padding)),
+            ("plot_size", offset_of!(Uniforms, plot_size)),
+            ("line_width", offset_of!(Uniforms, line_width)),
+            ("scale_factor", offset_of!(Uniforms, scale_factor)),
+            ("origin", offset_of!(Uniforms, origin)),
+            ("compact_scale", offset_of!(Uniforms, compact_scale)),
+        ]
+        .map(|(name, offset)| (name, offset as u32));
+        assert_eq!(offsets, expected);
+        assert_eq!(span as usize, std::mem::size_of::<Uniforms>());
+
+        // Marker edges are softened over physical pixels
+        let (uniforms, _) = module
+            .global_variables
+            .iter()
+            .find(|(_, global)| global.name.as_deref() == Some("uniforms"))
+            .unwrap();
+        let scale_factor = members
+            .iter()
+            .position(|m| m.name.as_deref() == Some("scale_factor"))
+            .unwrap() as u32;
+        let (_, marker_vertex) = module
+            .functions
+            .iter()
+            .find(|(_, f)| f.name.as_deref() == Some("marker_vertex"))
+            .unwrap();
+        assert!(marker_vertex.expressions.iter().any(|(_, e)| matches!(
+            *e,
+            naga::Expression::AccessIndex { base, index }
+                if index == scale_factor
+                    && matches!(
+                        marker_vertex.expressions[base],
+                        naga::Expression::GlobalVariable(g) if g == uniforms
+                    )
+        )));
+    }
+
+    #[test]
+    fn test_multisampling_falls_back_on_unsupported_formats() {
+        let none = wgpu::Features::empty();
+        // The formats iced draws frames in
+        assert!(supports_multisample(
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+            none
+        ));
+        assert!(supports_multisample(wgpu::TextureFormat::Rgba8Unorm, none));
+        // Integer targets can't be resolved, and 32-bit float ones aren't
+        // guaranteed to multisample
+        assert!(!supports_multisample(wgpu::TextureFormat::Rgba8Uint, none));
+        assert!(!supports_multisample(
+            wgpu::TextureFormat::Rgba32Float,
+            none
+        ));
+    }
+
+    /// Blends `src` over `dst` as the GPU would with `state`.
+    fn blend(state: wgpu::BlendState, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let factor = |factor, channel: f32| match factor {
+            wgpu::BlendFactor::One => channel,
+            wgpu::BlendFactor::SrcAlpha => channel * src[3],
+            wgpu::BlendFactor::OneMinusSrcAlpha => channel * (1.0 - src[3]),
+            other => panic!("unexpected blend factor {other:?}"),
+        };
+        let apply = |component: wgpu::BlendComponent, s: f32, d: f32| {
+            assert_eq!(component.operation, wgpu::BlendOperation::Add);
+            (factor(component.src_factor, s) + factor(component.dst_factor, d)).min(1.0)
+        };
+        [
+            apply(state.color, src[0], dst[0]),
+            apply(state.color, src[1], dst[1]),
+            apply(state.color, src[2], dst[2]),
+            apply(state.alpha, src[3], dst[3]),
+        ]
+    }
+
+    #[test]
+    fn test_additive_markers_accumulate_density() {
+        let faint_red = [1.0, 0.0, 0.0, 0.05];
+        let faint_blue = [0.0, 0.0, 1.0, 0.05];
+        let stack = |state, markers: &[[f32; 4]]| {
+            markers
+                .iter()
+                .fold([0.0; 4], |dst, &src| blend(state, src, dst))
+        };
+
+        // Brightness grows with the number of overlapping markers until it
+        // saturates
+        let red = |count| stack(ADDITIVE_BLEND_STATE, &vec![faint_red; count])[0];
+        assert!((red(4) - 0.2).abs() < 1e-6);
+        assert!((red(8) - 2.0 * red(4)).abs() < 1e-6);
+        assert_eq!(red(40), 1.0);
+
+        // The result doesn't depend on draw order, unlike covering markers
+        let order = |state| {
+            let mut markers = vec![faint_red; 10];
+            markers.extend([faint_blue; 10]);
+            let forward = stack(state, &markers);
+            markers.reverse();
+            (forward, stack(state, &markers))
+        };
+        let (forward, reverse) = order(ADDITIVE_BLEND_STATE);
+        assert!(
+            forward
+                .iter()
+                .zip(reverse)
+                .all(|(a, b)| (a - b).abs() < 1e-6)
+        );
+        let (forward, reverse) = order(BLEND_STATE);
+        assert!(forward[0] < reverse[0] && forward[2] > reverse[2]);
+    }
 }
@@ -1,13 +1,21 @@
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use iced::widget::canvas;
 use iced::widget::shader;
 use iced::widget::stack;
 use iced::{Element, Font, Length, Point, Renderer, Theme};
 
+pub use crate::core::{
+    AdaptiveSampling, ExplicitGenerator, ParametricGenerator, PlotPoint, PlotPoints, ViewState,
+};
+use crate::core::{AxisFit, Extent};
+use crate::software::{self, SoftwareFrame, SoftwareLayer};
+use crate::spectrogram::Spectrogram;
+
 /// Shared state for the legend, including visibility toggles and layout info.
 ///
 /// Store this in your application state and pass it to [`Plotter::with_legend_state`]
@@ -18,57 +26,342 @@ use iced::{Element, Font, Length, Point, Renderer, Theme};
 pub struct LegendState {
     pub hidden_series: Rc<RefCell<HashSet<usize>>>,
     pub layout: Rc<RefCell<LegendLayout>>,
+    /// Where the legend was dragged to: its top-left corner relative to the
+    /// plot area's, in pixels. Overrides [`LegendConfig::position`] unless
+    /// that places the legend outside the plot area.
+    pub offset: Rc<RefCell<Option<iced::Point>>>,
+    /// Series whose legend entry is under the cursor, emphasized as set by
+    /// [`LegendConfig::hover_emphasis`].
+    pub hovered: Rc<RefCell<Option<usize>>>,
 }
 
-/// For backwards compatibility — alias for the hidden series set.
-pub type HiddenSeries = Rc<RefCell<HashSet<usize>>>;
-
-// ================================================================================
-// Interaction Types
-// ================================================================================
-
-/// Represents the visible range of the plot.
+/// Shared selection for linked brushing between plots.
 ///
-/// Each axis range is `Option` — `None` means "auto-fit to data bounds".
-/// This allows the common pattern of panning X while auto-fitting Y.
+/// Pass the same handle to several plots with [`Plotter::with_selection_link`].
+/// A rectangle drawn with [`ActiveTool::Select`] in any of them stores the
+/// indices of the enclosed records (point index within each series), and every
+/// linked plot highlights or filters those records.
 ///
-/// Owned by the user's application state and passed to [`Plotter`].
+/// Create with `SelectionLink::default()`.
 #[derive(Clone, Debug, Default)]
-pub struct ViewState {
-    /// Visible X range. `None` = auto-fit to data bounds.
-    pub x_range: Option<(f32, f32)>,
-    /// Visible Y range. `None` = auto-fit to data bounds.
-    pub y_range: Option<(f32, f32)>,
+pub struct SelectionLink {
+    pub selected: Rc<RefCell<HashSet<usize>>>,
 }
 
-impl ViewState {
-    /// Create a new ViewState with both axes auto-fitting to data.
-    pub fn auto_fit() -> Self {
+impl SelectionLink {
+    /// Clear the selection in all linked plots.
+    pub fn clear(&self) {
+        self.selected.borrow_mut().clear();
+    }
+}
+
+/// Shape of the regions of interest drawn with [`ActiveTool::Roi`].
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum RoiKind {
+    /// A rectangle in X and Y.
+    #[default]
+    Rect,
+    /// An X range over the full height of the plot, e.g. a time window.
+    XSpan,
+}
+
+/// A region of interest in data coordinates, Y on the primary axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Roi {
+    pub x_range: (f64, f64),
+    /// `None` for an X span.
+    pub y_range: Option<(f64, f64)>,
+    /// Outline color; the inside is filled with a faint version of it.
+    pub color: iced::Color,
+    /// Text drawn in the top-left corner.
+    pub label: Option<String>,
+}
+
+impl Roi {
+    pub const DEFAULT_COLOR: iced::Color = iced::Color::from_rgb(0.3, 0.7, 1.0);
+
+    pub fn rect(x_range: (f64, f64), y_range: (f64, f64)) -> Self {
         Self {
-            x_range: None,
-            y_range: None,
+            x_range,
+            y_range: Some(y_range),
+            color: Self::DEFAULT_COLOR,
+            label: None,
         }
     }
 
-    /// Create a new ViewState with explicit ranges for both axes.
-    pub fn with_ranges(x_range: (f32, f32), y_range: (f32, f32)) -> Self {
+    pub fn x_span(x_range: (f64, f64)) -> Self {
         Self {
-            x_range: Some(x_range),
-            y_range: Some(y_range),
+            x_range,
+            y_range: None,
+            color: Self::DEFAULT_COLOR,
+            label: None,
         }
     }
 
-    /// Set the X range (or None to auto-fit).
-    pub fn with_x_range(mut self, range: Option<(f32, f32)>) -> Self {
-        self.x_range = range;
+    pub fn with_color(mut self, color: iced::Color) -> Self {
+        self.color = color;
         self
     }
 
-    /// Set the Y range (or None to auto-fit).
-    pub fn with_y_range(mut self, range: Option<(f32, f32)>) -> Self {
-        self.y_range = range;
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
         self
     }
+
+    pub fn kind(&self) -> RoiKind {
+        match self.y_range {
+            Some(_) => RoiKind::Rect,
+            None => RoiKind::XSpan,
+        }
+    }
+
+    /// Whether the data point `(x, y)` lies inside, edges included.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let within = |v: f64, (lo, hi): (f64, f64)| lo <= v && v <= hi;
+        within(x, self.x_range) && self.y_range.is_none_or(|range| within(y, range))
+    }
+}
+
+/// Regions of interest, shared between the app and the plot.
+///
+/// With [`ActiveTool::Roi`], dragging over empty plot area draws a new ROI,
+/// dragging inside one moves it and dragging its edges or corners resizes it.
+/// Clicking an ROI selects it, and Delete removes the selected one. The plot
+/// edits `rois` in place while dragging and reports each finished edit via
+/// [`Plotter::on_roi_change`]. The ROIs are drawn whichever tool is active.
+///
+/// Create with `RoiState::default()` and pass to [`Plotter::with_roi_state`].
+#[derive(Clone, Debug, Default)]
+pub struct RoiState {
+    pub rois: Rc<RefCell<Vec<Roi>>>,
+    /// Index of the selected ROI, drawn with resize handles.
+    pub selected: Rc<RefCell<Option<usize>>>,
+}
+
+impl RoiState {
+    /// Add an ROI, returning its index.
+    pub fn push(&self, roi: Roi) -> usize {
+        let mut rois = self.rois.borrow_mut();
+        rois.push(roi);
+        rois.len() - 1
+    }
+
+    /// Remove the ROI at `index`, keeping the selection on the same ROI.
+    pub fn remove(&self, index: usize) -> Option<Roi> {
+        let mut rois = self.rois.borrow_mut();
+        if index >= rois.len() {
+            return None;
+        }
+        let mut selected = self.selected.borrow_mut();
+        *selected = match *selected {
+            Some(i) if i == index => None,
+            Some(i) if i > index => Some(i - 1),
+            other => other,
+        };
+        Some(rois.remove(index))
+    }
+
+    /// A copy of the ROI at `index`.
+    pub fn get(&self, index: usize) -> Option<Roi> {
+        self.rois.borrow().get(index).cloned()
+    }
+
+    /// Remove all ROIs.
+    pub fn clear(&self) {
+        self.rois.borrow_mut().clear();
+        *self.selected.borrow_mut() = None;
+    }
+}
+
+/// A finished edit of a [`RoiState`], published via [`Plotter::on_roi_change`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoiChange {
+    /// A new ROI was drawn at this index.
+    Added(usize),
+    /// The ROI at this index was moved or resized.
+    Changed(usize),
+    /// This ROI was deleted from the given index.
+    Removed(usize, Roi),
+}
+
+/// A two-point measurement in data coordinates, Y on the primary axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    /// `(x, y)` of the first click.
+    pub from: (f64, f64),
+    /// `(x, y)` of the second click.
+    pub to: (f64, f64),
+}
+
+impl Measurement {
+    pub fn dx(&self) -> f64 {
+        self.to.0 - self.from.0
+    }
+
+    pub fn dy(&self) -> f64 {
+        self.to.1 - self.from.1
+    }
+
+    /// Δy / Δx; infinite for a vertical measurement.
+    pub fn slope(&self) -> f64 {
+        self.dy() / self.dx()
+    }
+
+    /// Euclidean distance in data units.
+    pub fn distance(&self) -> f64 {
+        self.dx().hypot(self.dy())
+    }
+}
+
+/// Shared state for [`ActiveTool::Measure`].
+///
+/// The first click stores `pending` and the plot draws a line from it to
+/// the cursor; the second click completes the [`Measurement`], which stays
+/// on the plot with its Δx, Δy, slope and distance and is published via
+/// [`Plotter::on_measure`]. Clicks snap to data points within hover
+/// distance. Escape clears the measurement.
+///
+/// Create with `MeasureState::default()` and pass to
+/// [`Plotter::with_measure_state`].
+#[derive(Clone, Debug, Default)]
+pub struct MeasureState {
+    /// Start of a measurement waiting for its second click.
+    pub pending: Rc<RefCell<Option<(f64, f64)>>>,
+    /// The last completed measurement.
+    pub measurement: Rc<RefCell<Option<Measurement>>>,
+}
+
+impl MeasureState {
+    /// Forget the measurement, and the pending start if any.
+    pub fn clear(&self) {
+        *self.pending.borrow_mut() = None;
+        *self.measurement.borrow_mut() = None;
+    }
+}
+
+/// Views left behind by zooms, pans and fits, for stepping back and forward
+/// through them like a browser's history.
+///
+/// Pass a handle to [`Plotter::with_view_history`]. The plot records the
+/// view before every zoom rectangle, pan drag and fit to data (scroll-wheel
+/// and arrow-key steps are not recorded), and steps back with the mouse
+/// back button, forward with the forward button. With
+/// [`InteractionConfig::keyboard`], Backspace steps back and
+/// Shift+Backspace forward. Apps can drive their own back/forward buttons with
+/// [`ViewHistory::back`] and [`ViewHistory::forward`].
+///
+/// Create with `ViewHistory::default()`.
+#[derive(Clone, Debug, Default)]
+pub struct ViewHistory {
+    /// Earlier views, most recent last.
+    pub back: Rc<RefCell<Vec<ViewState>>>,
+    /// Views stepped back from, most recent last.
+    pub forward: Rc<RefCell<Vec<ViewState>>>,
+}
+
+impl ViewHistory {
+    /// Most views kept; the oldest ones are dropped first.
+    pub const LIMIT: usize = 100;
+
+    /// Record `view` as left behind by a new navigation, which also drops
+    /// the views stepped back from.
+    pub fn push(&self, view: ViewState) {
+        let mut back = self.back.borrow_mut();
+        if back.last() != Some(&view) {
+            back.push(view);
+            let excess = back.len().saturating_sub(Self::LIMIT);
+            back.drain(..excess);
+        }
+        self.forward.borrow_mut().clear();
+    }
+
+    /// Step back from `current`, returning the view to show.
+    pub fn back(&self, current: &ViewState) -> Option<ViewState> {
+        let view = self.back.borrow_mut().pop()?;
+        self.forward.borrow_mut().push(current.clone());
+        Some(view)
+    }
+
+    /// Step forward from `current` after stepping back, returning the view
+    /// to show.
+    pub fn forward(&self, current: &ViewState) -> Option<ViewState> {
+        let view = self.forward.borrow_mut().pop()?;
+        self.back.borrow_mut().push(current.clone());
+        Some(view)
+    }
+
+    /// Whether [`ViewHistory::back`] has a view to return.
+    pub fn can_go_back(&self) -> bool {
+        !self.back.borrow().is_empty()
+    }
+
+    /// Whether [`ViewHistory::forward`] has a view to return.
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.borrow().is_empty()
+    }
+
+    /// Forget all views.
+    pub fn clear(&self) {
+        self.back.borrow_mut().clear();
+        self.forward.borrow_mut().clear();
+    }
+}
+
+/// How a plot displays the records brushed in a linked plot.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum LinkMode {
+    /// Dim unselected records; selected ones keep their normal style.
+    #[default]
+    Highlight,
+    /// Hide unselected records entirely.
+    Filter,
+}
+
+/// For backwards compatibility — alias for the hidden series set.
+pub type HiddenSeries = Rc<RefCell<HashSet<usize>>>;
+
+// ================================================================================
+// Interaction Types
+// ================================================================================
+
+impl ViewState {
+    /// Explicit ranges around one series' points, with `padding` (a
+    /// fraction of each span, like [`PlotterOptions::autofit_padding`]) on
+    /// each side. Generators are sampled across their range and non-finite
+    /// points skipped; the Y range is in the units of the series' own axis.
+    /// A series without finite points gives [`ViewState::auto_fit`].
+    pub fn fit_series(series: &PlotSeries<'_>, padding: f32) -> Self {
+        let mut extent = Extent::default();
+        for (x, y) in Plotter::<()>::extent_points(series) {
+            extent.include(x, y);
+        }
+        if extent.is_empty() {
+            return Self::auto_fit();
+        }
+        let pad = |[lo, hi]: [f64; 2]| {
+            // A single value gets a unit-wide range, like auto-fit
+            let range = if (hi - lo).abs() < f64::EPSILON {
+                [lo - 0.5, hi + 0.5]
+            } else {
+                [lo, hi]
+            };
+            let [lo, hi] = crate::core::pad_range(range, padding);
+            (lo, hi)
+        };
+        Self::auto_fit().zoom_to(pad(extent.x), pad(extent.y))
+    }
+}
+
+/// Whether a view change is a step of an ongoing gesture or animation, or
+/// where the view settles. See [`Plotter::on_view_change_with_kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ViewChangeKind {
+    /// A step of a pan, fling or spring-back, throttled by
+    /// [`InteractionConfig::view_change_rate`].
+    Interactive,
+    /// The end of a gesture or animation, or a one-off change such as a
+    /// scroll wheel step, a key press or a zoom selection.
+    Final,
 }
 
 /// Configuration for what interactions are enabled on the plot.
@@ -83,9 +376,9 @@ pub struct InteractionConfig {
     /// Allow zooming along the Y axis.
     pub zoom_y: bool,
     /// Hard limits for X scrolling. `None` = no limits.
-    pub x_bounds: Option<(f32, f32)>,
+    pub x_bounds: Option<(f64, f64)>,
     /// Hard limits for Y scrolling. `None` = no limits.
-    pub y_bounds: Option<(f32, f32)>,
+    pub y_bounds: Option<(f64, f64)>,
     /// Percentage of visible range to show as padding beyond data bounds (0.0 - 1.0).
     pub boundary_padding: f32,
     /// Zoom speed multiplier (default 0.1 = 10% per scroll tick).
@@ -94,6 +387,8 @@ pub struct InteractionConfig {
     pub double_click_to_fit: bool,
     /// Enable Ctrl+drag rectangle zoom selection.
     pub zoom_select: bool,
+    /// What a finished rectangle selection does.
+    pub selection_mode: SelectionMode,
     /// Enable elastic over-scroll with spring-back animation.
     pub elastic: bool,
     /// How far past bounds you can over-scroll (fraction of view range, 0.0 - 1.0).
@@ -101,6 +396,161 @@ pub struct InteractionConfig {
     pub elastic_limit: f32,
     /// Duration of the spring-back animation in milliseconds. Default 200.
     pub elastic_duration_ms: u64,
+    /// Keep panning after a fling (a drag released while moving), slowing
+    /// down by [`InteractionConfig::inertia_friction`]. Bounces off bounds
+    /// when [`InteractionConfig::elastic`] is on.
+    pub inertia: bool,
+    /// How quickly a fling slows down: the velocity decays by `e^-friction`
+    /// per second. Default 4.0.
+    pub inertia_friction: f32,
+    /// Point that stays fixed while zooming with the scroll wheel.
+    pub zoom_anchor: ZoomAnchor,
+    /// Keep the newest data in view: an explicit X range slides so its right
+    /// edge sits at the largest X, keeping its width. Zooming still changes
+    /// the width; panning X is overridden.
+    pub follow_x: bool,
+    /// Navigate with the keyboard while the cursor is over the plot: arrow
+    /// keys pan, `+`/`-` zoom about the center, Home fits all data and
+    /// PageUp/PageDown move X by a full window. Default off, as it takes
+    /// these keys from the rest of the application.
+    pub keyboard: bool,
+    /// Fraction of the visible range an arrow key pans. Default 0.1.
+    pub keyboard_pan_step: f32,
+    /// Fraction of the visible range a `+` press zooms in by (`-` undoes
+    /// it). Default 0.2.
+    pub keyboard_zoom_step: f32,
+    /// Dragging from an axis's tick-label strip pans that axis alone.
+    /// Default off.
+    pub axis_drag: bool,
+    /// Scrolling over an axis's tick-label strip zooms that axis alone.
+    /// Default off.
+    pub axis_scroll: bool,
+    /// What the scroll wheel does with each modifier key.
+    pub wheel_bindings: WheelBindings,
+    /// Constraints on dragging points with [`ActiveTool::EditPoints`].
+    pub point_drag: PointDragConfig,
+    /// Publish [`ViewChangeKind::Interactive`] view changes at most this many
+    /// times per second. The latest one held back is published as the
+    /// final change. `None` = every change.
+    pub view_change_rate: Option<f32>,
+}
+
+/// Constraints on dragging points with [`ActiveTool::EditPoints`].
+#[derive(Clone, Debug, Copy, Default, PartialEq)]
+pub struct PointDragConfig {
+    /// Keep X fixed, moving points vertically only.
+    pub lock_x: bool,
+    /// Keep Y fixed, moving points horizontally only.
+    pub lock_y: bool,
+    /// Round a dragged X to a multiple of this step.
+    pub snap_x: Option<f64>,
+    /// Round a dragged Y to a multiple of this step, on the series' Y axis.
+    pub snap_y: Option<f64>,
+}
+
+/// The point that scroll-wheel zoom scales around.
+#[derive(Clone, Debug, Copy, Default, PartialEq)]
+pub enum ZoomAnchor {
+    /// Zoom about the data point under the cursor.
+    #[default]
+    Cursor,
+    /// Zoom about the center of the visible range.
+    Center,
+    /// Zoom about fixed data values. `None` on an axis falls back to the
+    /// cursor position for that axis.
+    ///
+    /// E.g. `Fixed { x: Some(latest_t), y: None }` keeps the latest-data edge
+    /// pinned in a monitoring dashboard.
+    Fixed { x: Option<f64>, y: Option<f64> },
+}
+
+/// What a rectangle selection (Ctrl+drag, or a drag with
+/// [`ActiveTool::ZoomSelect`]) does on release.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Zoom the view to the rectangle, published via [`Plotter::on_view_change`].
+    #[default]
+    ZoomToSelection,
+    /// Leave the view alone and publish the rectangle's data ranges via
+    /// [`Plotter::on_select`], e.g. for region-of-interest or cropping tools.
+    ReportOnly,
+}
+
+/// What turning the scroll wheel does.
+///
+/// Zooming and panning still need the axis enabled in [`InteractionConfig`]
+/// (e.g. `zoom_y` for [`WheelAction::ZoomY`]).
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum WheelAction {
+    /// Zoom every zoomable axis.
+    #[default]
+    Zoom,
+    /// Zoom X only.
+    ZoomX,
+    /// Zoom Y only.
+    ZoomY,
+    /// Pan X; scrolling down moves towards larger X.
+    PanX,
+    /// Pan Y; scrolling up moves towards larger Y.
+    PanY,
+    /// Leave the scroll to the rest of the application.
+    Ignore,
+}
+
+/// Scroll-wheel actions by held modifier key.
+///
+/// When several modifiers are held, Ctrl wins over Shift, which wins over Alt.
+/// The default zooms regardless of modifiers.
+///
+/// ```
+/// use iced_plotter::plotter::{WheelAction, WheelBindings};
+///
+/// // Scroll zooms X, Shift+scroll zooms Y, Ctrl+scroll zooms both, Alt+scroll pans
+/// let bindings = WheelBindings {
+///     plain: WheelAction::ZoomX,
+///     shift: WheelAction::ZoomY,
+///     ctrl: WheelAction::Zoom,
+///     alt: WheelAction::PanX,
+///     ..WheelBindings::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct WheelBindings {
+    /// No modifier held.
+    pub plain: WheelAction,
+    pub shift: WheelAction,
+    /// Ctrl, or Cmd on macOS.
+    pub ctrl: WheelAction,
+    pub alt: WheelAction,
+    /// Fraction of the visible range one scroll line pans. Default 0.1.
+    pub pan_step: f32,
+}
+
+impl Default for WheelBindings {
+    fn default() -> Self {
+        Self {
+            plain: WheelAction::Zoom,
+            shift: WheelAction::Zoom,
+            ctrl: WheelAction::Zoom,
+            alt: WheelAction::Zoom,
+            pan_step: 0.1,
+        }
+    }
+}
+
+impl WheelBindings {
+    /// The action bound to the held `modifiers`.
+    pub fn action(&self, modifiers: iced::keyboard::Modifiers) -> WheelAction {
+        if modifiers.command() {
+            self.ctrl
+        } else if modifiers.shift() {
+            self.shift
+        } else if modifiers.alt() {
+            self.alt
+        } else {
+            self.plain
+        }
+    }
 }
 
 impl Default for InteractionConfig {
@@ -116,9 +566,22 @@ impl Default for InteractionConfig {
             zoom_speed: 0.1,
             double_click_to_fit: true,
             zoom_select: true,
+            selection_mode: SelectionMode::ZoomToSelection,
             elastic: true,
             elastic_limit: 0.3,
             elastic_duration_ms: 200,
+            inertia: false,
+            inertia_friction: 4.0,
+            zoom_anchor: ZoomAnchor::Cursor,
+            follow_x: false,
+            keyboard: false,
+            keyboard_pan_step: 0.1,
+            keyboard_zoom_step: 0.2,
+            axis_drag: false,
+            axis_scroll: false,
+            wheel_bindings: WheelBindings::default(),
+            point_drag: PointDragConfig::default(),
+            view_change_rate: None,
         }
     }
 }
@@ -137,9 +600,22 @@ impl InteractionConfig {
             zoom_speed: 0.1,
             double_click_to_fit: false,
             zoom_select: false,
+            selection_mode: SelectionMode::ZoomToSelection,
             elastic: false,
             elastic_limit: 0.3,
             elastic_duration_ms: 200,
+            inertia: false,
+            inertia_friction: 4.0,
+            zoom_anchor: ZoomAnchor::Cursor,
+            follow_x: false,
+            keyboard: false,
+            keyboard_pan_step: 0.1,
+            keyboard_zoom_step: 0.2,
+            axis_drag: false,
+            axis_scroll: false,
+            wheel_bindings: WheelBindings::default(),
+            point_drag: PointDragConfig::default(),
+            view_change_rate: None,
         }
     }
 
@@ -158,6 +634,45 @@ impl InteractionConfig {
     pub fn pan_x_autofit_y() -> Self {
         Self::default()
     }
+
+    /// Follow live data along X, zoom X to change the window width, auto-fit Y.
+    pub fn streaming() -> Self {
+        Self {
+            pan_x: false,
+            follow_x: true,
+            elastic: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// The tool that the primary (left) mouse drag activates.
+///
+/// Set from application state via [`Plotter::with_active_tool`] so toolbar-driven
+/// UIs can switch drag behavior without requiring modifier keys.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum ActiveTool {
+    /// Drag pans the view; Ctrl+drag zoom-selects (if enabled).
+    #[default]
+    Pan,
+    /// Drag draws a rectangle and zooms to it on release.
+    ZoomSelect,
+    /// Drag selects a region. Panning is disabled.
+    Select,
+    /// Clicks measure between points, see [`MeasureState`]. Panning is
+    /// disabled.
+    Measure,
+    /// Clicks in the plot area report their data position to
+    /// [`Plotter::on_annotate`], for the app to place an annotation there.
+    /// Panning is disabled.
+    #[cfg(feature = "annotations")]
+    Annotate,
+    /// Drag draws, moves or resizes regions of interest, see [`RoiState`];
+    /// new ones are of this kind. Panning is disabled.
+    Roi(RoiKind),
+    /// Drag moves points of [`PlotSeries::editable`] series, reported via
+    /// [`Plotter::on_point_moved`]. Panning is disabled.
+    EditPoints,
 }
 
 // ================================================================================
@@ -165,7 +680,7 @@ impl InteractionConfig {
 // ================================================================================
 
 /// Shape of markers to render for a series
-#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
 pub enum MarkerShape {
     Circle = 0,
     Square = 1,
@@ -184,7 +699,7 @@ impl MarkerShape {
 }
 
 /// Pattern for rendering lines
-#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
 pub enum LinePattern {
     Solid = 0,
     Dashed = 1,
@@ -199,6 +714,119 @@ impl LinePattern {
     }
 }
 
+/// How a line connects consecutive points
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LineInterpolation {
+    /// Straight segments between points
+    #[default]
+    Linear,
+    /// Jump to each value at the previous point's X, then hold it
+    StepBefore,
+    /// Hold each value until the next point's X, then jump
+    StepAfter,
+    /// Jump halfway between points
+    StepMid,
+}
+
+impl LineInterpolation {
+    /// Value drawn at fraction `t` of the way from a point with value `y0`
+    /// to the next one with value `y1`.
+    pub fn value_between(self, y0: f64, y1: f64, t: f64) -> f64 {
+        match self {
+            LineInterpolation::Linear => y0 + (y1 - y0) * t,
+            LineInterpolation::StepBefore => y1,
+            LineInterpolation::StepAfter => y0,
+            LineInterpolation::StepMid if t < 0.5 => y0,
+            LineInterpolation::StepMid => y1,
+        }
+    }
+}
+
+/// How a thick line turns a corner
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LineJoin {
+    /// Extend the outer edges to a sharp point; corners sharper than about
+    /// 29° fall back to [`LineJoin::Bevel`] so the point doesn't shoot off
+    #[default]
+    Miter,
+    /// Round off the outer corner
+    Round,
+    /// Cut the outer corner flat
+    Bevel,
+}
+
+/// How a line's first and last points are finished
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LineCap {
+    /// End exactly at the point
+    #[default]
+    Butt,
+    /// Add a half circle around the point
+    Round,
+    /// Extend past the point by half the line width
+    Square,
+}
+
+/// Unit in which a marker or line size is expressed
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SizeUnit {
+    /// Screen pixels; the size stays constant when zooming
+    #[default]
+    Pixels,
+    /// Data coordinates; the size scales with zoom
+    Data,
+}
+
+/// A soft halo or drop shadow rendered behind a series' line.
+///
+/// Drawn as an extra, wider, lower-alpha pass underneath the line itself,
+/// which is useful for emphasizing a primary trace in dashboards.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct LineGlow {
+    /// Halo color. `None` = use the line's own color.
+    pub color: Option<iced::Color>,
+    /// Extra width (in pixels) the halo extends beyond each side of the line.
+    pub width: f32,
+    /// Opacity of the halo at the line center (0.0 - 1.0).
+    pub alpha: f32,
+    /// Screen-space offset in pixels. Zero for a glow; non-zero for a drop shadow.
+    pub offset: iced::Vector,
+}
+
+impl LineGlow {
+    /// A halo in the line's own color, centered on the line.
+    pub fn glow(width: f32) -> Self {
+        Self {
+            color: None,
+            width,
+            alpha: 0.35,
+            offset: iced::Vector::new(0.0, 0.0),
+        }
+    }
+
+    /// A dark drop shadow offset down and to the right of the line.
+    pub fn shadow(offset: iced::Vector) -> Self {
+        Self {
+            color: Some(iced::Color::BLACK),
+            width: 2.0,
+            alpha: 0.5,
+            offset,
+        }
+    }
+
+    /// Set the halo color.
+    pub fn with_color(mut self, color: iced::Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the halo opacity.
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+}
+
 /// Styling options for a plot series
 #[derive(Clone, Debug)]
 pub struct SeriesStyle<'a> {
@@ -206,12 +834,30 @@ pub struct SeriesStyle<'a> {
     pub color: ColorMode<'a>,
     /// Shape of markers
     pub marker_shape: MarkerShape,
-    /// Marker size in pixels
+    /// Marker size (radius), in pixels unless `marker_size_unit` says otherwise
     pub marker_size: f32,
+    /// Unit of `marker_size`
+    pub marker_size_unit: SizeUnit,
     /// Line pattern
     pub line_pattern: LinePattern,
-    /// Line width in pixels
+    /// How the line connects points, e.g. as steps for counters
+    pub interpolation: LineInterpolation,
+    /// Line width, in pixels unless `line_width_unit` says otherwise
     pub line_width: f32,
+    /// Unit of `line_width`
+    pub line_width_unit: SizeUnit,
+    /// Optional glow / drop shadow drawn behind the line
+    pub glow: Option<LineGlow>,
+    /// How the line turns corners
+    pub line_join: LineJoin,
+    /// How the line's ends are finished
+    pub line_cap: LineCap,
+    /// Per-point marker radius, in `marker_size_unit`, e.g. for bubble
+    /// charts. Non-finite entries use `marker_size`.
+    pub marker_sizes: Option<Cow<'a, [f32]>>,
+    /// Per-point colors, e.g. a class color each, taking the place of the
+    /// [`ColorMode`]. Opacity and brushing still apply.
+    pub point_colors: Option<Cow<'a, [iced::Color]>>,
 }
 
 impl<'a> SeriesStyle<'a> {
@@ -221,8 +867,16 @@ impl<'a> SeriesStyle<'a> {
             color,
             marker_shape: MarkerShape::Circle,
             marker_size: 4.0,
+            marker_size_unit: SizeUnit::Pixels,
             line_pattern: LinePattern::Solid,
+            interpolation: LineInterpolation::Linear,
             line_width: 2.0,
+            line_width_unit: SizeUnit::Pixels,
+            glow: None,
+            line_join: LineJoin::Miter,
+            line_cap: LineCap::Butt,
+            marker_sizes: None,
+            point_colors: None,
         }
     }
 
@@ -238,17 +892,72 @@ impl<'a> SeriesStyle<'a> {
         self
     }
 
+    /// Set the marker size in data units, so markers scale with zoom.
+    ///
+    /// The radius is applied independently along each axis, so a circle in
+    /// data space renders as an ellipse when the axes have different scales.
+    pub fn with_marker_size_in_data(mut self, radius: f32) -> Self {
+        self.marker_size = radius;
+        self.marker_size_unit = SizeUnit::Data;
+        self
+    }
+
     /// Set line pattern
     pub fn with_line_pattern(mut self, pattern: LinePattern) -> Self {
         self.line_pattern = pattern;
         self
     }
 
+    /// Set how the line connects points
+    pub fn with_interpolation(mut self, interpolation: LineInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
     /// Set line width
     pub fn with_line_width(mut self, width: f32) -> Self {
         self.line_width = width;
         self
     }
+
+    /// Set the line width in Y data units, so the line scales with the Y zoom.
+    ///
+    /// Useful for drawing tolerance bands, e.g. a width of `1.0` covers ±0.5 units.
+    pub fn with_line_width_in_data(mut self, width: f32) -> Self {
+        self.line_width = width;
+        self.line_width_unit = SizeUnit::Data;
+        self
+    }
+
+    /// Set a glow / drop shadow behind the line
+    pub fn with_glow(mut self, glow: LineGlow) -> Self {
+        self.glow = Some(glow);
+        self
+    }
+
+    /// Set how the line turns corners
+    pub fn with_line_join(mut self, join: LineJoin) -> Self {
+        self.line_join = join;
+        self
+    }
+
+    /// Set how the line's ends are finished
+    pub fn with_line_cap(mut self, cap: LineCap) -> Self {
+        self.line_cap = cap;
+        self
+    }
+
+    /// Set a marker radius per point, one entry per point of the series.
+    pub fn with_marker_sizes(mut self, sizes: impl Into<Cow<'a, [f32]>>) -> Self {
+        self.marker_sizes = Some(sizes.into());
+        self
+    }
+
+    /// Set a color per point, one entry per point of the series.
+    pub fn with_point_colors(mut self, colors: impl Into<Cow<'a, [iced::Color]>>) -> Self {
+        self.point_colors = Some(colors.into());
+        self
+    }
 }
 
 impl Default for SeriesStyle<'_> {
@@ -257,8 +966,16 @@ impl Default for SeriesStyle<'_> {
             color: ColorMode::solid(iced::Color::WHITE),
             marker_shape: MarkerShape::Circle,
             marker_size: 4.0,
+            marker_size_unit: SizeUnit::Pixels,
             line_pattern: LinePattern::Solid,
+            interpolation: LineInterpolation::Linear,
             line_width: 2.0,
+            line_width_unit: SizeUnit::Pixels,
+            glow: None,
+            line_join: LineJoin::Miter,
+            line_cap: LineCap::Butt,
+            marker_sizes: None,
+            point_colors: None,
         }
     }
 }
@@ -268,7 +985,7 @@ impl Default for SeriesStyle<'_> {
 // ================================================================================
 
 /// How points in a series should be colored
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub enum ColorMode<'a> {
     /// Single solid color for all points
     Solid(iced::Color),
@@ -282,6 +999,20 @@ pub enum ColorMode<'a> {
         /// Optional: use separate value array instead of Y coordinate
         /// If None, Y coordinate is used
         values: Option<Cow<'a, [f32]>>,
+        /// Value range and normalization, see [`ColorMode::with_range`]
+        scale: ColorScale,
+    },
+
+    /// Gradient based on a value computed per point
+    ValueFn {
+        /// Color at minimum value
+        low: iced::Color,
+        /// Color at maximum value
+        high: iced::Color,
+        /// Called as `function(index, x, y)` for every point of the series
+        function: Rc<dyn Fn(usize, f64, f64) -> f32 + 'a>,
+        /// Value range and normalization, see [`ColorMode::with_range`]
+        scale: ColorScale,
     },
 
     /// Gradient based on point index (0 = start, 1 = end)
@@ -293,15 +1024,64 @@ pub enum ColorMode<'a> {
     },
 
     /// Use a named colormap
+    #[cfg(feature = "colormap")]
     Colormap {
         /// Name of the colormap to use
         name: crate::colormap::ColormapName,
         /// Optional: use separate value array instead of Y coordinate
         /// If None, Y coordinate is used
         values: Option<Cow<'a, [f32]>>,
+        /// Value range and normalization, see [`ColorMode::with_range`]
+        scale: ColorScale,
     },
 }
 
+/// How values map onto a gradient or colormap.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorScale {
+    /// Values at the low and high end of the gradient. `None` = the data
+    /// min/max, which moves as data streams in.
+    pub range: Option<(f32, f32)>,
+    pub normalization: Normalization,
+}
+
+/// Curve from a value's position in the color range to its gradient
+/// position.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Normalization {
+    /// Evenly spaced values get evenly spaced colors.
+    #[default]
+    Linear,
+    /// Each decade gets the same share of the gradient. Needs a positive
+    /// range (falls back to linear otherwise); values at or below zero take
+    /// the low end.
+    Log,
+    /// The linear position raised to this power: above 1 spends more of the
+    /// gradient on high values, below 1 on low values.
+    Power(f32),
+}
+
+impl Normalization {
+    /// Gradient position of `value` within `[low, high]`, clamped to
+    /// [0, 1]. `None` when the range is empty or the value is NaN.
+    pub fn apply(self, value: f64, (low, high): (f64, f64)) -> Option<f32> {
+        let linear = |v: f64, lo: f64, hi: f64| {
+            let t = (v - lo) / (hi - lo);
+            ((hi - lo).abs() > f64::EPSILON && !t.is_nan()).then(|| t.clamp(0.0, 1.0))
+        };
+        let t = match self {
+            Normalization::Log if low > 0.0 && high > 0.0 => linear(
+                value.max(f64::MIN_POSITIVE).log10(),
+                low.log10(),
+                high.log10(),
+            )?,
+            Normalization::Linear | Normalization::Log => linear(value, low, high)?,
+            Normalization::Power(gamma) => linear(value, low, high)?.powf(f64::from(gamma)),
+        };
+        Some(t as f32)
+    }
+}
+
 impl<'a> ColorMode<'a> {
     /// Convert a solid Color to ColorMode for convenience
     pub fn solid(color: iced::Color) -> Self {
@@ -313,6 +1093,7 @@ impl<'a> ColorMode<'a> {
             low,
             high,
             values: None,
+            scale: ColorScale::default(),
         }
     }
 
@@ -324,6 +1105,24 @@ impl<'a> ColorMode<'a> {
             low,
             high,
             values: Some(values.into()),
+            scale: ColorScale::default(),
+        }
+    }
+
+    /// Gradient over a value derived from each point, without a parallel
+    /// `values` array to keep in sync.
+    ///
+    /// `function` receives the point index within the series and its
+    /// coordinates.
+    pub fn value_fn<F>(low: iced::Color, high: iced::Color, function: F) -> Self
+    where
+        F: Fn(usize, f64, f64) -> f32 + 'a,
+    {
+        ColorMode::ValueFn {
+            low,
+            high,
+            function: Rc::new(function),
+            scale: ColorScale::default(),
         }
     }
 
@@ -331,10 +1130,16 @@ impl<'a> ColorMode<'a> {
         ColorMode::IndexGradient { start, end }
     }
 
+    #[cfg(feature = "colormap")]
     pub fn colormap(name: crate::colormap::ColormapName) -> Self {
-        ColorMode::Colormap { name, values: None }
+        ColorMode::Colormap {
+            name,
+            values: None,
+            scale: ColorScale::default(),
+        }
     }
 
+    #[cfg(feature = "colormap")]
     pub fn colormap_values<V>(name: crate::colormap::ColormapName, values: V) -> Self
     where
         V: Into<Cow<'a, [f32]>>,
@@ -342,14 +1147,57 @@ impl<'a> ColorMode<'a> {
         ColorMode::Colormap {
             name,
             values: Some(values.into()),
+            scale: ColorScale::default(),
         }
     }
 
-    /// Get a single representative color for this color mode (used in legends).
-    pub fn representative_color(&self) -> iced::Color {
+    /// Pin the values at the low and high end of the gradient, so colors
+    /// stay put as data streams in and match across plots. No effect on
+    /// solid and index-based modes.
+    pub fn with_range(mut self, low: f32, high: f32) -> Self {
+        if let Some(scale) = self.scale_mut() {
+            scale.range = Some((low, high));
+        }
+        self
+    }
+
+    /// Set how values are spread over the gradient. No effect on solid and
+    /// index-based modes.
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        if let Some(scale) = self.scale_mut() {
+            scale.normalization = normalization;
+        }
+        self
+    }
+
+    /// Value range and normalization of value-based modes.
+    pub fn scale(&self) -> Option<ColorScale> {
         match self {
-            ColorMode::Solid(c) => *c,
-            ColorMode::ValueGradient { low, high, .. } => {
+            ColorMode::ValueGradient { scale, .. } | ColorMode::ValueFn { scale, .. } => {
+                Some(*scale)
+            }
+            #[cfg(feature = "colormap")]
+            ColorMode::Colormap { scale, .. } => Some(*scale),
+            ColorMode::Solid(_) | ColorMode::IndexGradient { .. } => None,
+        }
+    }
+
+    fn scale_mut(&mut self) -> Option<&mut ColorScale> {
+        match self {
+            ColorMode::ValueGradient { scale, .. } | ColorMode::ValueFn { scale, .. } => {
+                Some(scale)
+            }
+            #[cfg(feature = "colormap")]
+            ColorMode::Colormap { scale, .. } => Some(scale),
+            ColorMode::Solid(_) | ColorMode::IndexGradient { .. } => None,
+        }
+    }
+
+    /// Get a single representative color for this color mode (used in legends).
+    pub fn representative_color(&self) -> iced::Color {
+        match self {
+            ColorMode::Solid(c) => *c,
+            ColorMode::ValueGradient { low, high, .. } | ColorMode::ValueFn { low, high, .. } => {
                 // Midpoint blend
                 iced::Color::from_rgb(
                     (low.r + high.r) / 2.0,
@@ -365,6 +1213,7 @@ impl<'a> ColorMode<'a> {
                     (start.b + end.b) / 2.0,
                 )
             }
+            #[cfg(feature = "colormap")]
             ColorMode::Colormap { name, .. } => {
                 // Sample at midpoint
                 name.sample(0.5)
@@ -373,90 +1222,336 @@ impl<'a> ColorMode<'a> {
     }
 }
 
+impl std::fmt::Debug for ColorMode<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorMode::Solid(c) => f.debug_tuple("Solid").field(c).finish(),
+            ColorMode::ValueGradient {
+                low,
+                high,
+                values,
+                scale,
+            } => f
+                .debug_struct("ValueGradient")
+                .field("low", low)
+                .field("high", high)
+                .field("values", values)
+                .field("scale", scale)
+                .finish(),
+            ColorMode::ValueFn {
+                low, high, scale, ..
+            } => f
+                .debug_struct("ValueFn")
+                .field("low", low)
+                .field("high", high)
+                .field("scale", scale)
+                .finish_non_exhaustive(),
+            ColorMode::IndexGradient { start, end } => f
+                .debug_struct("IndexGradient")
+                .field("start", start)
+                .field("end", end)
+                .finish(),
+            #[cfg(feature = "colormap")]
+            ColorMode::Colormap {
+                name,
+                values,
+                scale,
+            } => f
+                .debug_struct("Colormap")
+                .field("name", name)
+                .field("values", values)
+                .field("scale", scale)
+                .finish(),
+        }
+    }
+}
+
 // ================================================================================
 // Utility Types
 // ================================================================================
 
-#[derive(Clone)]
-pub struct PlotPoint {
-    pub x: f32,
-    pub y: f32,
+/// How a series is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SeriesKind {
+    /// Markers joined by lines, as set up by the [`SeriesStyle`].
+    #[default]
+    Line,
+    /// A filled bar per point, from `baseline` up (or down) to the point's Y.
+    /// `width` is in X data units. Bars take their colors from the series'
+    /// [`ColorMode`]; line and marker styles are ignored. Needs stored points.
+    Bars { width: f64, baseline: f64 },
 }
 
-impl From<(f32, f32)> for PlotPoint {
-    fn from((x, y): (f32, f32)) -> Self {
-        Self { x, y }
-    }
+/// How bars of different series at the same X are arranged, see
+/// [`PlotterOptions::bar_layout`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BarLayout {
+    /// Bars are drawn at their X, later series over earlier ones.
+    #[default]
+    Overlap,
+    /// Bars are placed side by side, each series taking an equal share of
+    /// its width.
+    Grouped,
+    /// Bars on the same Y axis are stacked: positive values upward and
+    /// negative values downward from the baseline.
+    Stacked,
 }
 
-/// Describes a function y = f(x) with an optional range for x and a number of
-/// points.
-pub struct ExplicitGenerator<'a> {
-    pub function: Box<dyn Fn(f32) -> f32 + 'a>,
-    pub x_range: (f32, f32), // start, end
-    pub points: usize,
+/// How stacked area series are summed, see [`PlotterOptions::stack_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StackMode {
+    /// Each series adds its Y on top of the series before it.
+    #[default]
+    Sum,
+    /// Like [`StackMode::Sum`], scaled so each stack's total at every X is
+    /// 100.
+    Percent,
 }
 
-pub enum PlotPoints<'a> {
-    Owned(Vec<PlotPoint>),
-    Borrowed(&'a [PlotPoint]),
-    Generator(ExplicitGenerator<'a>),
+/// How markers are blended over what is already drawn, see
+/// [`PlotterOptions::marker_blend`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MarkerBlend {
+    /// Later markers cover earlier ones.
+    #[default]
+    Over,
+    /// Marker colors add up, so overlapping markers get brighter. With faint
+    /// colors (alpha around 0.02 - 0.1) a dense scatter plot shows where its
+    /// points cluster instead of a saturated blob.
+    Additive,
 }
 
-impl<'a> PlotPoints<'a> {
-    pub fn owned(points: Vec<PlotPoint>) -> Self {
-        PlotPoints::Owned(points)
-    }
+/// Which renderer draws the markers, lines and grid, see
+/// [`PlotterOptions::backend`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RenderBackend {
+    /// The shaders on wgpu, or the canvas API when iced falls back to
+    /// tiny-skia (e.g. `ICED_BACKEND=tiny_skia`, or no GPU adapter).
+    #[default]
+    Auto,
+    /// Always the shaders; nothing is drawn without wgpu.
+    Gpu,
+    /// Always the canvas API, on either renderer. Slower for large data,
+    /// and images, spectrograms and [`MarkerBlend::Additive`] are not
+    /// supported.
+    Software,
+}
 
-    pub fn borrowed(points: &'a [PlotPoint]) -> Self {
-        PlotPoints::Borrowed(points)
-    }
+/// Settings for panning and zooming through millions of points, see
+/// [`PlotterOptions::performance`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerformanceConfig {
+    /// Plot area pixels per visible point below which a series skips its
+    /// markers, which would only blur into its line. Series without a line
+    /// keep them.
+    pub min_pixels_per_marker: f32,
+}
 
-    pub fn generator<F>(function: F, x_range: (f32, f32), points: usize) -> Self
-    where
-        F: Fn(f32) -> f32 + 'a,
-    {
-        PlotPoints::Generator(ExplicitGenerator {
-            function: Box::new(function),
-            x_range,
-            points,
-        })
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            min_pixels_per_marker: 4.0,
+        }
     }
 }
 
-impl From<Vec<PlotPoint>> for PlotPoints<'_> {
-    fn from(points: Vec<PlotPoint>) -> Self {
-        PlotPoints::Owned(points)
-    }
+/// Extent of one bar in data coordinates, on its series' own Y axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BarRect {
+    /// Left and right edge.
+    pub x: [f64; 2],
+    /// Start (baseline or top of the bar below) and end of the bar.
+    pub y: [f64; 2],
 }
 
-impl<'a> From<&'a [PlotPoint]> for PlotPoints<'a> {
-    fn from(points: &'a [PlotPoint]) -> Self {
-        PlotPoints::Borrowed(points)
+impl BarRect {
+    /// Center of the bar's far end, where tooltips anchor.
+    pub fn tip(&self) -> (f64, f64) {
+        (0.5 * (self.x[0] + self.x[1]), self.y[1])
     }
 }
 
-impl Default for PlotPoints<'_> {
-    fn default() -> Self {
-        PlotPoints::Owned(Vec::new())
-    }
+/// Bars of every series, indexed like `series`, with one [`BarRect`] per
+/// stored point of each [`SeriesKind::Bars`] series and none for others.
+///
+/// Hidden series get no bars and take no room in grouped or stacked layouts.
+pub fn bar_rects(
+    series: &[PlotSeries<'_>],
+    hidden: &HashSet<usize>,
+    layout: BarLayout,
+) -> Vec<Vec<BarRect>> {
+    let is_bars = |(i, s): &(usize, &PlotSeries<'_>)| {
+        !hidden.contains(i) && matches!(s.kind, SeriesKind::Bars { .. })
+    };
+    let groups = series.iter().enumerate().filter(is_bars).count();
+    let mut slot = 0;
+    // Stack ends keyed by (axis, x, upward)
+    let mut stacks: HashMap<(usize, u64, bool), f64> = HashMap::new();
+
+    series
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let SeriesKind::Bars { width, baseline } = s.kind else {
+                return Vec::new();
+            };
+            if !is_bars(&(i, s)) {
+                return Vec::new();
+            }
+            let (offset, width) = match layout {
+                BarLayout::Grouped => {
+                    let share = width / groups as f64;
+                    slot += 1;
+                    ((slot as f64 - 0.5) * share - 0.5 * width, share)
+                }
+                BarLayout::Overlap | BarLayout::Stacked => (0.0, width),
+            };
+            let points = s.points.as_slice().unwrap_or_default();
+            points
+                .iter()
+                .map(|p| {
+                    // Non-finite values are gaps and don't add to the stack
+                    let y = if layout == BarLayout::Stacked && p.y.is_finite() {
+                        let key = (s.y_axis, p.x.to_bits(), p.y >= baseline);
+                        let end = stacks.entry(key).or_insert(baseline);
+                        let start = *end;
+                        *end += p.y - baseline;
+                        [start, *end]
+                    } else {
+                        [baseline, p.y]
+                    };
+                    let x = p.x + offset;
+                    BarRect {
+                        x: [x - 0.5 * width, x + 0.5 * width],
+                        y,
+                    }
+                })
+                .collect()
+        })
+        .collect()
 }
 
-impl PlotPoints<'_> {
-    /// Get the last Y value in the series (for legend display).
-    pub fn last_y(&self) -> Option<f32> {
-        match self {
-            PlotPoints::Owned(pts) => pts.last().map(|p| p.y),
-            PlotPoints::Borrowed(pts) => pts.last().map(|p| p.y),
-            PlotPoints::Generator(_) => None, // generators don't have a "latest" point
+/// Bottom and top of every point of stacked series, indexed like `series`,
+/// with one `[bottom, top]` per stored point of each series that has a
+/// [`PlotSeries::stack_id`] and none for others.
+///
+/// A point stacks on the points of earlier series in the same stack, on the
+/// same Y axis, at exactly the same X, so stacked series should share their X
+/// samples. Non-finite values are gaps and don't add to the stack; their top
+/// is NaN. Hidden series take no room.
+pub fn stack_ranges(
+    series: &[PlotSeries<'_>],
+    hidden: &HashSet<usize>,
+    mode: StackMode,
+) -> Vec<Vec<[f64; 2]>> {
+    let stacked = |i: usize, s: &PlotSeries<'_>| {
+        !hidden.contains(&i) && s.kind == SeriesKind::Line && s.stack_id.is_some()
+    };
+    let key = |s: &PlotSeries<'_>, p: &PlotPoint| (s.stack_id.clone(), s.y_axis, p.x.to_bits());
+
+    // Totals per (stack, axis, x) for normalizing
+    let mut totals: HashMap<(Option<String>, usize, u64), f64> = HashMap::new();
+    if mode == StackMode::Percent {
+        for (i, s) in series.iter().enumerate() {
+            if !stacked(i, s) {
+                continue;
+            }
+            for p in s.points.as_slice().unwrap_or_default() {
+                if p.y.is_finite() {
+                    *totals.entry(key(s, p)).or_insert(0.0) += p.y;
+                }
+            }
         }
     }
+
+    // Stack tops keyed like the totals
+    let mut tops: HashMap<(Option<String>, usize, u64), f64> = HashMap::new();
+    series
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            if !stacked(i, s) {
+                return Vec::new();
+            }
+            let points = s.points.as_slice().unwrap_or_default();
+            points
+                .iter()
+                .map(|p| {
+                    let k = key(s, p);
+                    let scale = match mode {
+                        StackMode::Sum => 1.0,
+                        StackMode::Percent => match totals.get(&k) {
+                            Some(&total) if total != 0.0 => 100.0 / total,
+                            _ => 0.0,
+                        },
+                    };
+                    let top = tops.entry(k).or_insert(0.0);
+                    let bottom = *top;
+                    if !p.y.is_finite() {
+                        return [bottom, f64::NAN];
+                    }
+                    *top += p.y * scale;
+                    [bottom, *top]
+                })
+                .collect()
+        })
+        .collect()
 }
 
 pub struct PlotSeries<'a> {
     pub label: String,
     pub style: SeriesStyle<'a>,
     pub points: PlotPoints<'a>,
+    /// Whether the series is drawn as a line or as bars.
+    pub kind: SeriesKind,
+    /// Whether this series participates in hover hit testing (tooltips).
+    /// Disable for background/reference curves so the tooltip snaps to
+    /// the interactive foreground series instead.
+    pub hoverable: bool,
+    /// Per-series hover snap distance in pixels. `None` = use
+    /// [`TooltipConfig::max_distance`].
+    pub hover_distance: Option<f32>,
+    /// Draw the line of a large stored series sorted by X through a few
+    /// points per pixel column, keeping each column's extremes (see
+    /// [`crate::core::m4_indices`]). Markers are all drawn, except in
+    /// performance mode (see [`PlotterOptions::performance`]), where a
+    /// decimated series skips them. Series without a line are never
+    /// decimated.
+    pub decimate: bool,
+    /// Y axis this series is plotted against: 0 = [`PlotterOptions::y_axis`],
+    /// `k` = [`PlotterOptions::extra_y_axes`]`[k - 1]`.
+    pub y_axis: usize,
+    /// Value shown next to the label when [`LegendConfig::show_value`] is set.
+    #[cfg(feature = "legend")]
+    pub legend_value: LegendValue,
+    /// Unit appended to the legend value, e.g. `"°C"`.
+    #[cfg(feature = "legend")]
+    pub unit: Option<String>,
+    /// Legend value formatter for this series. `None` = use
+    /// [`LegendConfig::value_format`].
+    #[cfg(feature = "legend")]
+    pub value_format: Option<Box<dyn Fn(f64) -> String + 'a>>,
+    /// Change counter. Stored points, values and styles are compared in
+    /// full; bump it when a generator's function changes, so the widget
+    /// knows to re-render.
+    pub generation: u64,
+    /// Leave the series out of the plot, as if toggled off in the legend.
+    pub hidden: bool,
+    /// Multiplies the alpha of the series' markers, lines and bars (0.0 - 1.0).
+    pub opacity: f32,
+    /// Line series with the same stack id are summed and drawn as stacked
+    /// filled areas, in series order. See [`PlotterOptions::stack_mode`].
+    pub stack_id: Option<String>,
+    /// Whether [`ActiveTool::EditPoints`] can drag this series' points, see
+    /// [`Plotter::on_point_moved`]. Only stored points can be edited.
+    pub editable: bool,
+    /// Drawing order relative to the other series: higher values are drawn
+    /// on top, lines and markers both. Series with equal values keep their
+    /// order, and polygons, bands, box plots and quivers are drawn with 0.
+    pub z_order: i32,
+    /// Text per point, e.g. a run ID, shown in its tooltip and reported
+    /// with it as [`HoveredPoint::metadata`]. Stored points only.
+    pub metadata: Option<Cow<'a, [String]>>,
 }
 
 impl<'a> PlotSeries<'a> {
@@ -465,217 +1560,2115 @@ impl<'a> PlotSeries<'a> {
             label: label.into(),
             style: SeriesStyle::default(),
             points,
+            kind: SeriesKind::Line,
+            hoverable: true,
+            hover_distance: None,
+            decimate: true,
+            y_axis: 0,
+            #[cfg(feature = "legend")]
+            legend_value: LegendValue::default(),
+            #[cfg(feature = "legend")]
+            unit: None,
+            #[cfg(feature = "legend")]
+            value_format: None,
+            generation: 0,
+            hidden: false,
+            opacity: 1.0,
+            stack_id: None,
+            editable: false,
+            z_order: 0,
+            metadata: None,
         }
     }
 
+    /// A series computed from `source` by `transform`, labeled after both
+    /// and plotted against the same Y axis. Generators are sampled across
+    /// their range. The points are computed right away; see
+    /// [`DerivedSeries`](crate::transform::DerivedSeries) to cache them for
+    /// live data.
+    pub fn derived(source: &PlotSeries<'_>, transform: crate::transform::Transform) -> Self {
+        let points = match source.points.as_slice() {
+            Some(points) => transform.apply(points),
+            None => {
+                let sampled: Vec<PlotPoint> = Plotter::<()>::extent_points(source)
+                    .map(PlotPoint::from)
+                    .collect();
+                transform.apply(&sampled)
+            }
+        };
+        Self::new(
+            format!("{} ({transform})", source.label),
+            PlotPoints::owned(points),
+        )
+        .with_y_axis(source.y_axis)
+    }
+
     pub fn with_style(mut self, style: SeriesStyle<'a>) -> Self {
         self.style = style;
         self
     }
-}
 
-// ================================================================================
-// Legend Types
-// ================================================================================
+    /// Draw the series as a line or as bars, see [`SeriesKind`].
+    pub fn with_kind(mut self, kind: SeriesKind) -> Self {
+        self.kind = kind;
+        self
+    }
 
-/// Position of the legend within the plot area.
-#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
-pub enum LegendPosition {
-    #[default]
-    TopRight,
-    TopLeft,
-    BottomRight,
-    BottomLeft,
-}
+    /// Draw the series as bars `width` data units wide, rising from `baseline`.
+    pub fn bars(self, width: f64, baseline: f64) -> Self {
+        self.with_kind(SeriesKind::Bars { width, baseline })
+    }
 
-/// Configuration for the plot legend.
-pub struct LegendConfig {
-    /// Position of the legend within the plot area.
-    pub position: LegendPosition,
-    /// Color of the label text.
-    pub text_color: iced::Color,
-    /// Font size for legend labels.
-    pub text_size: f32,
-    /// Background color of the legend box.
-    pub background_color: iced::Color,
-    /// Internal padding within the legend box.
-    pub padding: f32,
-    /// Distance from the plot edge.
-    pub margin: f32,
-    /// Size of the color toggle square.
-    pub toggle_size: f32,
-    /// Whether to show the latest value next to the label.
-    pub show_value: bool,
-    /// Format function for the latest value.
-    pub value_format: Box<dyn Fn(f32) -> String>,
-}
+    /// Enable or disable hover hit testing for this series.
+    pub fn with_hover(mut self, hoverable: bool) -> Self {
+        self.hoverable = hoverable;
+        self
+    }
 
-impl Default for LegendConfig {
-    fn default() -> Self {
-        Self {
-            position: LegendPosition::default(),
-            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.7),
-            text_size: 12.0,
-            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.8),
-            padding: 8.0,
-            margin: 10.0,
-            toggle_size: 12.0,
-            show_value: true,
-            value_format: Box::new(|v| format!("{v:.2}")),
-        }
+    /// Let [`ActiveTool::EditPoints`] drag this series' points.
+    pub fn with_editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
     }
-}
 
-impl Clone for LegendConfig {
-    fn clone(&self) -> Self {
-        Self {
-            position: self.position,
-            text_color: self.text_color,
-            text_size: self.text_size,
-            background_color: self.background_color,
-            padding: self.padding,
-            margin: self.margin,
-            toggle_size: self.toggle_size,
-            show_value: self.show_value,
-            value_format: Box::new(|v| format!("{v:.2}")),
-        }
+    /// Draw the series above those with a lower z-order (0 by default), e.g.
+    /// to keep a trace visible over filled areas and dense scatter plots.
+    pub fn with_z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
     }
-}
 
-impl std::fmt::Debug for LegendConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("LegendConfig")
-            .field("position", &self.position)
-            .field("text_color", &self.text_color)
-            .field("text_size", &self.text_size)
-            .field("background_color", &self.background_color)
-            .field("padding", &self.padding)
-            .field("margin", &self.margin)
-            .field("toggle_size", &self.toggle_size)
-            .field("show_value", &self.show_value)
-            .finish()
+    /// Attach text to each point, one entry per point of the series, for
+    /// tooltips and the point callbacks.
+    pub fn with_metadata(mut self, metadata: impl Into<Cow<'a, [String]>>) -> Self {
+        self.metadata = Some(metadata.into());
+        self
     }
-}
 
-impl LegendConfig {
-    /// Set the value format function.
-    pub fn with_value_format(mut self, f: impl Fn(f32) -> String + 'static) -> Self {
-        self.value_format = Box::new(f);
+    /// Override the tooltip snap distance (in pixels) for this series.
+    pub fn with_hover_distance(mut self, distance: f32) -> Self {
+        self.hover_distance = Some(distance);
         self
     }
-}
 
-// ================================================================================
-// Tooltip Types
-// ================================================================================
+    /// Enable or disable decimation, see [`PlotSeries::decimate`].
+    pub fn with_decimation(mut self, decimate: bool) -> Self {
+        self.decimate = decimate;
+        self
+    }
 
-/// Configuration for hover tooltips on data points.
-pub struct TooltipConfig {
-    /// Maximum screen-space distance (in pixels) to snap to a point.
-    pub max_distance: f32,
-    /// Background color of the tooltip box.
-    pub background_color: iced::Color,
-    /// Text color inside the tooltip.
-    pub text_color: iced::Color,
-    /// Font size for tooltip text.
-    pub text_size: f32,
-    /// Internal padding within the tooltip box.
-    pub padding: f32,
-    /// Format function for the X value.
-    pub format_x: Box<dyn Fn(f32) -> String>,
-    /// Format function for the Y value.
-    pub format_y: Box<dyn Fn(f32) -> String>,
-    /// Color of the highlight ring drawn around the hovered point.
-    pub highlight_color: iced::Color,
-    /// Radius of the highlight ring (in pixels).
-    pub highlight_radius: f32,
-    /// Line width of the highlight ring (in pixels).
-    pub highlight_width: f32,
-}
+    /// Hide or show the series, independently of the legend toggles.
+    ///
+    /// A hidden series is skipped when drawing, auto-fitting and hit testing.
+    /// The legend still lists it, greyed out.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
 
-impl Default for TooltipConfig {
-    fn default() -> Self {
-        Self {
-            max_distance: 10.0,
-            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.9),
-            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.9),
-            text_size: 12.0,
-            padding: 6.0,
-            format_x: Box::new(|v| format!("{v:.2}")),
-            format_y: Box::new(|v| format!("{v:.2}")),
-            highlight_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.8),
-            highlight_radius: 8.0,
-            highlight_width: 2.0,
-        }
+    /// Fade the series, see [`PlotSeries::opacity`].
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
     }
-}
 
-impl Clone for TooltipConfig {
-    fn clone(&self) -> Self {
-        Self {
-            max_distance: self.max_distance,
-            background_color: self.background_color,
-            text_color: self.text_color,
-            text_size: self.text_size,
-            padding: self.padding,
-            format_x: Box::new(|v| format!("{v:.2}")),
-            format_y: Box::new(|v| format!("{v:.2}")),
-            highlight_color: self.highlight_color,
-            highlight_radius: self.highlight_radius,
-            highlight_width: self.highlight_width,
+    /// Stack this series on the earlier series with the same id, see
+    /// [`PlotSeries::stack_id`].
+    pub fn with_stack(mut self, id: impl Into<String>) -> Self {
+        self.stack_id = Some(id.into());
+        self
+    }
+
+    /// Plot this series against another Y axis, see [`PlotSeries::y_axis`].
+    pub fn with_y_axis(mut self, axis: usize) -> Self {
+        self.y_axis = axis;
+        self
+    }
+
+    /// Plot this series against the secondary Y axis, the first of
+    /// [`PlotterOptions::extra_y_axes`].
+    pub fn on_secondary_y(self) -> Self {
+        self.with_y_axis(1)
+    }
+
+    /// Check this series for misconfigurations, see [`Plotter::validate`].
+    pub fn validate(&self) -> Result<(), PlotterError> {
+        let series = || self.label.clone();
+        if let SeriesKind::Bars { width, baseline } = self.kind
+            && (self.points.as_slice().is_none()
+                || !(width.is_finite() && width > 0.0 && baseline.is_finite()))
+        {
+            return Err(PlotterError::InvalidBars { series: series() });
+        }
+        if self.stack_id.is_some()
+            && (self.points.as_slice().is_none() || self.kind != SeriesKind::Line)
+        {
+            return Err(PlotterError::InvalidStack { series: series() });
+        }
+        let expected = match &self.points {
+            PlotPoints::Owned(_) | PlotPoints::Borrowed(_) => {
+                self.points.as_slice().map(<[PlotPoint]>::len)
+            }
+            PlotPoints::Generator(g) => {
+                if g.points == 0 {
+                    return Err(PlotterError::EmptyGenerator { series: series() });
+                }
+                if !(g.x_range.0.is_finite() && g.x_range.1.is_finite()) {
+                    return Err(PlotterError::InvalidGeneratorRange {
+                        series: series(),
+                        range: g.x_range,
+                    });
+                }
+                // Adaptive sampling produces a different count every frame
+                g.adaptive.is_none().then_some(g.points)
+            }
+            PlotPoints::Parametric(g) => {
+                if g.points == 0 {
+                    return Err(PlotterError::EmptyGenerator { series: series() });
+                }
+                if !(g.t_range.0.is_finite() && g.t_range.1.is_finite()) {
+                    return Err(PlotterError::InvalidGeneratorRange {
+                        series: series(),
+                        range: g.t_range,
+                    });
+                }
+                Some(g.points)
+            }
+        };
+
+        let values = match &self.style.color {
+            ColorMode::ValueGradient { values, .. } => values.as_deref(),
+            #[cfg(feature = "colormap")]
+            ColorMode::Colormap { values, .. } => values.as_deref(),
+            _ => None,
+        };
+        if let Some(values) = values {
+            match expected {
+                None => {
+                    return Err(PlotterError::ColorValuesWithAdaptiveSampling { series: series() });
+                }
+                Some(expected) if expected != values.len() => {
+                    return Err(PlotterError::ColorValuesLength {
+                        series: series(),
+                        expected,
+                        found: values.len(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        for (array, len) in [
+            (
+                "marker_sizes",
+                self.style.marker_sizes.as_ref().map(|v| v.len()),
+            ),
+            (
+                "point_colors",
+                self.style.point_colors.as_ref().map(|v| v.len()),
+            ),
+        ] {
+            let Some(found) = len else {
+                continue;
+            };
+            match expected {
+                None => {
+                    return Err(PlotterError::PointArrayWithAdaptiveSampling {
+                        series: series(),
+                        array,
+                    });
+                }
+                Some(expected) if expected != found => {
+                    return Err(PlotterError::PointArrayLength {
+                        series: series(),
+                        array,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
         }
+        Ok(())
     }
-}
 
-impl std::fmt::Debug for TooltipConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("TooltipConfig")
-            .field("max_distance", &self.max_distance)
-            .field("text_size", &self.text_size)
-            .field("highlight_radius", &self.highlight_radius)
-            .finish()
+    /// Choose the value shown in the legend, e.g. [`LegendValue::Max`] for
+    /// the peak within the visible window.
+    #[cfg(feature = "legend")]
+    pub fn with_legend_value(mut self, value: LegendValue) -> Self {
+        self.legend_value = value;
+        self
     }
-}
 
-impl TooltipConfig {
-    /// Set the X value format function.
-    pub fn with_format_x(mut self, f: impl Fn(f32) -> String + 'static) -> Self {
-        self.format_x = Box::new(f);
+    /// Append a unit to the legend value, e.g. `"°C"` or `"%"`.
+    #[cfg(feature = "legend")]
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
         self
     }
 
-    /// Set the Y value format function.
-    pub fn with_format_y(mut self, f: impl Fn(f32) -> String + 'static) -> Self {
-        self.format_y = Box::new(f);
+    /// Format this series' legend value, overriding [`LegendConfig::value_format`].
+    #[cfg(feature = "legend")]
+    pub fn with_value_format(mut self, f: impl Fn(f64) -> String + 'a) -> Self {
+        self.value_format = Some(Box::new(f));
         self
     }
-}
 
-/// Information about a data point that the cursor is hovering near.
-#[derive(Clone, Debug)]
-pub struct HoveredPoint {
-    /// Index of the series this point belongs to.
-    pub series_index: usize,
-    /// Label of the series.
-    pub series_label: String,
-    /// Data-space X coordinate.
-    pub x: f32,
-    /// Data-space Y coordinate.
-    pub y: f32,
-    /// Screen-space position of the point (relative to widget bounds).
-    pub screen_pos: Point,
+    /// Set the change counter, see [`PlotSeries::generation`].
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
 }
 
-/// Shared state for tooltip hover detection.
-///
-/// Store this in your application state and pass it to [`Plotter::with_tooltip_state`]
-/// to enable tooltip rendering. The shader layer writes hovered point info,
-/// and the canvas overlay reads it to draw the tooltip.
+/// A set of closed vertex loops rendered as filled shapes, e.g. regions of
+/// interest, geofences or convex hulls overlaid on scatter data.
 ///
-/// Create with `TooltipState::default()`.
-#[derive(Clone, Debug, Default)]
-pub struct TooltipState {
-    pub hovered: Rc<RefCell<Option<HoveredPoint>>>,
+/// Each loop is filled independently (no holes) and drawn beneath the
+/// series lines and markers.
+#[derive(Clone, Debug)]
+pub struct PolygonSeries {
+    /// Closed vertex loops in data coordinates. Repeating the first vertex
+    /// at the end is optional.
+    pub loops: Vec<Vec<PlotPoint>>,
+    /// Fill color.
+    pub fill_color: iced::Color,
+    /// Outline color. `None` = no outline.
+    pub outline_color: Option<iced::Color>,
+    /// Outline width in pixels.
+    pub outline_width: f32,
+}
+
+impl PolygonSeries {
+    /// A single filled loop.
+    pub fn new(points: Vec<PlotPoint>, fill_color: iced::Color) -> Self {
+        Self {
+            loops: vec![points],
+            fill_color,
+            outline_color: None,
+            outline_width: 1.5,
+        }
+    }
+
+    /// Add another loop with the same style.
+    pub fn with_loop(mut self, points: Vec<PlotPoint>) -> Self {
+        self.loops.push(points);
+        self
+    }
+
+    /// Draw an outline around each loop.
+    pub fn with_outline(mut self, color: iced::Color, width: f32) -> Self {
+        self.outline_color = Some(color);
+        self.outline_width = width;
+        self
+    }
+}
+
+/// One sample of a [`BandSeries`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BandSample {
+    pub x: f64,
+    /// Lower edge of the band.
+    pub low: f64,
+    /// Upper edge of the band.
+    pub high: f64,
+    /// Where the center line passes through.
+    pub center: f64,
+}
+
+/// A filled band between a lower and an upper curve, e.g. a confidence
+/// interval or a min/max envelope around an aggregated mean.
+///
+/// Drawn beneath the series lines and markers, after polygons. Samples with
+/// a non-finite value split the band, like gaps in a line.
+#[derive(Clone, Debug)]
+pub struct BandSeries {
+    /// Samples in ascending X order.
+    pub samples: Vec<BandSample>,
+    /// Fill color, usually translucent.
+    pub fill_color: iced::Color,
+    /// Center line color. `None` = no center line.
+    pub center_color: Option<iced::Color>,
+    /// Center line width in pixels.
+    pub center_width: f32,
+}
+
+impl BandSeries {
+    /// A band from `(x, low, high)` samples. The center line, if enabled,
+    /// runs halfway between the edges.
+    pub fn new(points: Vec<(f64, f64, f64)>, fill_color: iced::Color) -> Self {
+        let samples = points
+            .into_iter()
+            .map(|(x, low, high)| BandSample {
+                x,
+                low,
+                high,
+                center: (low + high) / 2.0,
+            })
+            .collect();
+        Self {
+            samples,
+            fill_color,
+            center_color: None,
+            center_width: 1.5,
+        }
+    }
+
+    /// A band from `(x, mean, std)` samples, spanning `mean ± n_std * std`.
+    /// The center line, if enabled, follows the mean.
+    pub fn from_mean_std(
+        points: Vec<(f64, f64, f64)>,
+        n_std: f64,
+        fill_color: iced::Color,
+    ) -> Self {
+        let samples = points
+            .into_iter()
+            .map(|(x, mean, std)| BandSample {
+                x,
+                low: mean - n_std * std,
+                high: mean + n_std * std,
+                center: mean,
+            })
+            .collect();
+        Self {
+            samples,
+            fill_color,
+            center_color: None,
+            center_width: 1.5,
+        }
+    }
+
+    /// Draw a line through the band's center.
+    pub fn with_center_line(mut self, color: iced::Color, width: f32) -> Self {
+        self.center_color = Some(color);
+        self.center_width = width;
+        self
+    }
+}
+
+/// Five-number summary of one category in a [`BoxPlotSeries`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxSummary {
+    /// Category name, see [`BoxPlotSeries::labels`].
+    pub label: String,
+    /// Lower whisker end.
+    pub min: f64,
+    /// First quartile, the bottom of the box.
+    pub q1: f64,
+    pub median: f64,
+    /// Third quartile, the top of the box.
+    pub q3: f64,
+    /// Upper whisker end.
+    pub max: f64,
+    /// Values beyond the whiskers, drawn as markers.
+    pub outliers: Vec<f64>,
+}
+
+impl BoxSummary {
+    /// A precomputed `[min, q1, median, q3, max]` summary without outliers.
+    pub fn new(label: impl Into<String>, [min, q1, median, q3, max]: [f64; 5]) -> Self {
+        Self {
+            label: label.into(),
+            min,
+            q1,
+            median,
+            q3,
+            max,
+            outliers: Vec::new(),
+        }
+    }
+
+    /// Summarize raw samples. Quartiles interpolate linearly between samples,
+    /// whiskers reach the furthest samples within 1.5 interquartile ranges of
+    /// the box, and samples beyond them become outliers.
+    ///
+    /// Non-finite samples are ignored. Returns `None` without finite samples.
+    pub fn from_samples(label: impl Into<String>, samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+        let quantile = |q: f64| {
+            let pos = q * (sorted.len() - 1) as f64;
+            let (i, t) = (pos.floor() as usize, pos.fract());
+            let next = sorted[(i + 1).min(sorted.len() - 1)];
+            sorted[i] + t * (next - sorted[i])
+        };
+        let (q1, median, q3) = (quantile(0.25), quantile(0.5), quantile(0.75));
+        let reach = 1.5 * (q3 - q1);
+        let (lo, hi) = (q1 - reach, q3 + reach);
+        let inside = || sorted.iter().copied().filter(|v| (lo..=hi).contains(v));
+        Some(Self {
+            label: label.into(),
+            min: inside().fold(q1, f64::min),
+            q1,
+            median,
+            q3,
+            max: inside().fold(q3, f64::max),
+            outliers: sorted
+                .iter()
+                .copied()
+                .filter(|v| !(lo..=hi).contains(v))
+                .collect(),
+        })
+    }
+
+    /// Set the values drawn beyond the whiskers.
+    pub fn with_outliers(mut self, outliers: Vec<f64>) -> Self {
+        self.outliers = outliers;
+        self
+    }
+}
+
+/// Box-and-whisker plots, one per category, e.g. latency distributions per
+/// endpoint.
+///
+/// Box `i` is centered on `x = i`; label the X axis with
+/// [`AxisConfig::with_categories`] and [`BoxPlotSeries::labels`]. Drawn
+/// beneath the line and marker series, with outliers as markers on top.
+///
+/// ```
+/// use iced_plotter::plotter::{AxisConfig, BoxPlotSeries, BoxSummary, PlotterOptions};
+///
+/// let get_ms = [12.0, 15.0, 14.0, 90.0, 13.0];
+/// let post_ms = [30.0, 28.0, 35.0, 33.0];
+/// let boxes = BoxPlotSeries::new(vec![
+///     BoxSummary::from_samples("GET", &get_ms).unwrap(),
+///     BoxSummary::from_samples("POST", &post_ms).unwrap(),
+/// ]);
+/// let options = PlotterOptions {
+///     x_axis: AxisConfig::default().with_categories(boxes.labels()),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoxPlotSeries {
+    pub boxes: Vec<BoxSummary>,
+    /// Box width in X data units; categories are one unit apart.
+    pub box_width: f64,
+    /// Box fill color.
+    pub fill_color: iced::Color,
+    /// Color of the box outline, median, whiskers and outliers.
+    pub line_color: iced::Color,
+    /// Outline, median and whisker width in pixels.
+    pub line_width: f32,
+    /// Outlier marker shape.
+    pub outlier_shape: MarkerShape,
+    /// Outlier marker radius in pixels.
+    pub outlier_size: f32,
+}
+
+impl BoxPlotSeries {
+    pub fn new(boxes: Vec<BoxSummary>) -> Self {
+        Self {
+            boxes,
+            box_width: 0.6,
+            fill_color: iced::Color::from_rgba(0.3, 0.5, 0.8, 0.4),
+            line_color: iced::Color::from_rgb(0.6, 0.75, 1.0),
+            line_width: 1.5,
+            outlier_shape: MarkerShape::Circle,
+            outlier_size: 3.0,
+        }
+    }
+
+    /// Set the box fill and line colors.
+    pub fn with_colors(mut self, fill_color: iced::Color, line_color: iced::Color) -> Self {
+        self.fill_color = fill_color;
+        self.line_color = line_color;
+        self
+    }
+
+    /// Set the box width in X data units.
+    pub fn with_box_width(mut self, width: f64) -> Self {
+        self.box_width = width;
+        self
+    }
+
+    /// Set the outlier marker shape and radius in pixels.
+    pub fn with_outliers(mut self, shape: MarkerShape, size: f32) -> Self {
+        self.outlier_shape = shape;
+        self.outlier_size = size;
+        self
+    }
+
+    /// Category labels, in box order.
+    pub fn labels(&self) -> Vec<String> {
+        self.boxes.iter().map(|b| b.label.clone()).collect()
+    }
+}
+
+/// One arrow of a [`QuiverSeries`]: the vector `(u, v)` at `(x, y)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuiverArrow {
+    pub x: f64,
+    pub y: f64,
+    pub u: f64,
+    pub v: f64,
+}
+
+impl QuiverArrow {
+    /// Length of the vector.
+    pub fn magnitude(&self) -> f64 {
+        self.u.hypot(self.v)
+    }
+}
+
+impl From<(f64, f64, f64, f64)> for QuiverArrow {
+    fn from((x, y, u, v): (f64, f64, f64, f64)) -> Self {
+        Self { x, y, u, v }
+    }
+}
+
+/// How long a [`QuiverSeries`] draws its arrows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuiverScale {
+    /// Arrows are in data units: `(u, v)` times this factor, so they zoom
+    /// with the plot.
+    Data(f64),
+    /// The longest arrow is this many pixels and the others proportional
+    /// to their magnitude, pointing the way the vector does on screen.
+    Pixels(f32),
+}
+
+/// A vector field drawn as arrows, e.g. flow velocities or field strengths
+/// on a grid.
+///
+/// Arrow length and, with a value-based [`ColorMode`], color follow each
+/// vector's magnitude. Drawn beneath the series lines and markers, after
+/// box plots. Arrows with a non-finite component are skipped.
+///
+/// ```
+/// use iced_plotter::plotter::{ColorMode, QuiverSeries};
+///
+/// // A vortex on a 10 x 10 grid
+/// let arrows = (0..100).map(|i| {
+///     let (x, y) = (f64::from(i % 10) - 4.5, f64::from(i / 10) - 4.5);
+///     (x, y, -y, x)
+/// });
+/// let quiver = QuiverSeries::new(arrows)
+///     .with_color(ColorMode::value_gradient(iced::Color::BLACK, iced::Color::WHITE));
+/// assert_eq!(quiver.magnitude_range(), Some((0.5_f64.hypot(0.5), 4.5_f64.hypot(4.5))));
+/// ```
+#[derive(Clone, Debug)]
+pub struct QuiverSeries {
+    pub arrows: Vec<QuiverArrow>,
+    pub scale: QuiverScale,
+    /// Arrow color; value-based modes color by magnitude.
+    pub color: ColorMode<'static>,
+    /// Shaft width in pixels.
+    pub line_width: f32,
+    /// Arrow head length in pixels, at most half the arrow.
+    pub head_size: f32,
+}
+
+impl QuiverSeries {
+    /// Arrows from `(x, y, u, v)` tuples, the longest 30 pixels.
+    pub fn new(arrows: impl IntoIterator<Item = impl Into<QuiverArrow>>) -> Self {
+        Self {
+            arrows: arrows.into_iter().map(Into::into).collect(),
+            scale: QuiverScale::Pixels(30.0),
+            color: ColorMode::Solid(iced::Color::from_rgb(0.6, 0.75, 1.0)),
+            line_width: 1.5,
+            head_size: 8.0,
+        }
+    }
+
+    /// Set how long arrows are drawn.
+    pub fn with_scale(mut self, scale: QuiverScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the arrow color, e.g. a [`ColorMode::Colormap`] to color by
+    /// magnitude.
+    pub fn with_color(mut self, color: ColorMode<'static>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the shaft width and head length in pixels.
+    pub fn with_line_width(mut self, line_width: f32, head_size: f32) -> Self {
+        self.line_width = line_width;
+        self.head_size = head_size;
+        self
+    }
+
+    /// Smallest and largest magnitude of the drawn arrows.
+    pub fn magnitude_range(&self) -> Option<(f64, f64)> {
+        self.drawn()
+            .map(QuiverArrow::magnitude)
+            .fold(None, |range, m| match range {
+                None => Some((m, m)),
+                Some((lo, hi)) => Some((lo.min(m), hi.max(m))),
+            })
+    }
+
+    /// Color of an arrow of `magnitude`, given the series' magnitude range.
+    pub(crate) fn arrow_color(&self, magnitude: f64, range: (f64, f64)) -> iced::Color {
+        let Some(scale) = self.color.scale() else {
+            return self.color.representative_color();
+        };
+        let range = scale
+            .range
+            .map_or(range, |(lo, hi)| (f64::from(lo), f64::from(hi)));
+        let t = scale.normalization.apply(magnitude, range).unwrap_or(0.0);
+        colorbar_color(&self.color, t).unwrap_or_else(|| self.color.representative_color())
+    }
+
+    /// Arrows with finite components.
+    pub(crate) fn drawn(&self) -> impl Iterator<Item = &QuiverArrow> {
+        self.arrows
+            .iter()
+            .filter(|a| [a.x, a.y, a.u, a.v].iter().all(|c| c.is_finite()))
+    }
+}
+
+/// A raster image placed on a data-space rectangle, e.g. a map tile, a
+/// microscope image or a reference chart to trace over.
+///
+/// Drawn above the grid and beneath everything else, panning and zooming
+/// with the data. The pixels are uploaded to the GPU once; clones share
+/// them.
+///
+/// ```
+/// use iced_plotter::plotter::PlotImage;
+///
+/// // A 2 x 2 checkerboard over x in [0, 10] and y in [0, 5]
+/// let (black, white) = ([0, 0, 0, 255], [255; 4]);
+/// let pixels = [black, white, white, black].concat();
+/// let image = PlotImage::new(2, 2, pixels, (0.0, 10.0), (0.0, 5.0)).with_smooth(false);
+/// assert_eq!(image.size(), [2, 2]);
+/// ```
+#[derive(Clone)]
+pub struct PlotImage {
+    pub(crate) pixels: Arc<[u8]>,
+    size: [u32; 2],
+    id: u64,
+    /// X range the image spans, from its left to its right edge.
+    pub x_range: (f64, f64),
+    /// Y range the image spans, from its bottom to its top edge.
+    pub y_range: (f64, f64),
+    /// Multiplies the image's alpha.
+    pub opacity: f32,
+    /// Interpolate between pixels; `false` shows them as sharp squares.
+    pub smooth: bool,
+}
+
+impl PlotImage {
+    /// An image of `width` x `height` RGBA8 pixels, row by row from the
+    /// top, stretched over `x_range` and `y_range`. [`Plotter::validate`]
+    /// reports pixel buffers of the wrong length; they aren't drawn.
+    pub fn new(
+        width: u32,
+        height: u32,
+        pixels: impl Into<Arc<[u8]>>,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+    ) -> Self {
+        Self {
+            pixels: pixels.into(),
+            size: [width, height],
+            id: crate::pipeline::next_image_id(),
+            x_range,
+            y_range,
+            opacity: 1.0,
+            smooth: true,
+        }
+    }
+
+    /// Set the image's opacity.
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Set whether pixels are interpolated when zoomed in.
+    pub fn with_smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Width and height in pixels.
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// The RGBA8 pixels, row by row from the top.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Identifies the pixels for upload caching.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Pixel bytes the size calls for.
+    pub(crate) fn expected_len(&self) -> usize {
+        self.size[0] as usize * self.size[1] as usize * 4
+    }
+}
+
+/// Leaves out the pixels, which would swamp the output (and the content
+/// hashes built from it).
+impl std::fmt::Debug for PlotImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlotImage")
+            .field("size", &self.size)
+            .field("id", &self.id)
+            .field("x_range", &self.x_range)
+            .field("y_range", &self.y_range)
+            .field("opacity", &self.opacity)
+            .field("smooth", &self.smooth)
+            .finish()
+    }
+}
+
+// ================================================================================
+// Legend Types
+// ================================================================================
+
+/// Which value a legend entry shows next to its label.
+#[cfg(feature = "legend")]
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum LegendValue {
+    /// The last stored point with a finite Y.
+    #[default]
+    Last,
+    /// The smallest Y within the visible X range.
+    Min,
+    /// The largest Y within the visible X range.
+    Max,
+    /// The mean Y within the visible X range.
+    Mean,
+}
+
+#[cfg(feature = "legend")]
+impl LegendValue {
+    /// Compute the value for `points`.
+    ///
+    /// Non-finite Y values are gaps and skipped. Windowed modes only
+    /// consider points whose X lies within `x_window` and return `None` when
+    /// nothing falls inside it.
+    pub fn aggregate(self, points: &[PlotPoint], x_window: [f64; 2]) -> Option<f64> {
+        let mut finite = points.iter().filter(|p| p.y.is_finite());
+        let visible = finite
+            .clone()
+            .filter(|p| p.x >= x_window[0] && p.x <= x_window[1])
+            .map(|p| p.y);
+        match self {
+            LegendValue::Last => finite.next_back().map(|p| p.y),
+            LegendValue::Min => visible.reduce(f64::min),
+            LegendValue::Max => visible.reduce(f64::max),
+            LegendValue::Mean => {
+                let (sum, count) = visible.fold((0.0, 0usize), |(s, n), y| (s + y, n + 1));
+                (count > 0).then(|| sum / count as f64)
+            }
+        }
+    }
+}
+
+/// How the series under the cursor in the legend stands out from the others.
+#[cfg(feature = "legend")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LegendEmphasis {
+    /// Multiplies the alpha of every other series.
+    pub dim_alpha: f32,
+    /// Multiplies the line width and marker size of the hovered series.
+    pub width_scale: f32,
+}
+
+#[cfg(feature = "legend")]
+impl Default for LegendEmphasis {
+    fn default() -> Self {
+        Self {
+            dim_alpha: 0.25,
+            width_scale: 1.5,
+        }
+    }
+}
+
+/// Position of the legend relative to the plot area.
+#[cfg(feature = "legend")]
+#[derive(Clone, Debug, Copy, Default, PartialEq)]
+pub enum LegendPosition {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+    /// Outside the plot area, to the right. The right padding grows to fit,
+    /// and entries wrap into more columns when they don't fit the plot height.
+    Right,
+    /// Outside the plot area, below the X axis. The bottom padding grows to
+    /// fit, with as many entries per row as fit the plot width.
+    Bottom,
+    /// Top-left corner at this offset from the plot area's, in pixels. Kept
+    /// inside the plot area.
+    Custom(iced::Point),
+}
+
+#[cfg(feature = "legend")]
+impl LegendPosition {
+    /// Whether the legend is placed outside the plot area.
+    pub fn is_outside(self) -> bool {
+        matches!(self, LegendPosition::Right | LegendPosition::Bottom)
+    }
+}
+
+/// Configuration for the plot legend.
+#[cfg(feature = "legend")]
+#[derive(Clone)]
+pub struct LegendConfig {
+    /// Position of the legend within the plot area.
+    pub position: LegendPosition,
+    /// Color of the label text.
+    pub text_color: iced::Color,
+    /// Font size for legend labels.
+    pub text_size: f32,
+    /// Background color of the legend box.
+    pub background_color: iced::Color,
+    /// Internal padding within the legend box.
+    pub padding: f32,
+    /// Distance from the plot edge.
+    pub margin: f32,
+    /// Size of the color toggle square.
+    pub toggle_size: f32,
+    /// Whether to show each series' [`PlotSeries::legend_value`] next to its label.
+    pub show_value: bool,
+    /// Format function for the legend value.
+    pub value_format: Rc<dyn Fn(f64) -> String>,
+    /// Whether the legend can be dragged around the plot area, storing the
+    /// result in [`LegendState::offset`]. No effect on outside positions.
+    pub draggable: bool,
+    /// Emphasize the series whose entry the cursor is over. `None` = off.
+    pub hover_emphasis: Option<LegendEmphasis>,
+}
+
+#[cfg(feature = "legend")]
+impl Default for LegendConfig {
+    fn default() -> Self {
+        Self {
+            position: LegendPosition::default(),
+            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.7),
+            text_size: 12.0,
+            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.8),
+            padding: 8.0,
+            margin: 10.0,
+            toggle_size: 12.0,
+            show_value: true,
+            value_format: Rc::new(|v| format!("{v:.2}")),
+            draggable: true,
+            hover_emphasis: Some(LegendEmphasis::default()),
+        }
+    }
+}
+
+#[cfg(feature = "legend")]
+impl std::fmt::Debug for LegendConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LegendConfig")
+            .field("position", &self.position)
+            .field("text_color", &self.text_color)
+            .field("text_size", &self.text_size)
+            .field("background_color", &self.background_color)
+            .field("padding", &self.padding)
+            .field("margin", &self.margin)
+            .field("toggle_size", &self.toggle_size)
+            .field("show_value", &self.show_value)
+            .field("draggable", &self.draggable)
+            .field("hover_emphasis", &self.hover_emphasis)
+            .finish()
+    }
+}
+
+#[cfg(feature = "legend")]
+impl LegendConfig {
+    /// Set the value format function.
+    pub fn with_value_format(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.value_format = Rc::new(f);
+        self
+    }
+}
+
+// ================================================================================
+// Tooltip Types
+// ================================================================================
+
+/// Snapping distance in pixels for hover hit testing when no
+/// [`TooltipConfig`] sets one, and for grabbing points to drag.
+pub const DEFAULT_HOVER_DISTANCE: f32 = 10.0;
+
+/// Configuration for hover tooltips on data points.
+#[cfg(feature = "tooltip")]
+#[derive(Clone)]
+pub struct TooltipConfig {
+    /// Maximum screen-space distance (in pixels) to snap to a point.
+    pub max_distance: f32,
+    /// Background color of the tooltip box.
+    pub background_color: iced::Color,
+    /// Text color inside the tooltip.
+    pub text_color: iced::Color,
+    /// Font size for tooltip text.
+    pub text_size: f32,
+    /// Internal padding within the tooltip box.
+    pub padding: f32,
+    /// Format function for the X value.
+    pub format_x: Rc<dyn Fn(f64) -> String>,
+    /// Format function for the Y value.
+    pub format_y: Rc<dyn Fn(f64) -> String>,
+    /// Color of the highlight ring drawn around the hovered point.
+    pub highlight_color: iced::Color,
+    /// Radius of the highlight ring (in pixels).
+    pub highlight_radius: f32,
+    /// Line width of the highlight ring (in pixels).
+    pub highlight_width: f32,
+    /// How many tooltips clicking on points can pin, the oldest going first
+    /// when more are pinned (`0` disables pinning).
+    pub max_pinned: usize,
+}
+
+#[cfg(feature = "tooltip")]
+impl Default for TooltipConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: DEFAULT_HOVER_DISTANCE,
+            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.9),
+            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.9),
+            text_size: 12.0,
+            padding: 6.0,
+            format_x: Rc::new(|v| format!("{v:.2}")),
+            format_y: Rc::new(|v| format!("{v:.2}")),
+            highlight_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.8),
+            highlight_radius: 8.0,
+            highlight_width: 2.0,
+            max_pinned: 4,
+        }
+    }
+}
+
+#[cfg(feature = "tooltip")]
+impl std::fmt::Debug for TooltipConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TooltipConfig")
+            .field("max_distance", &self.max_distance)
+            .field("text_size", &self.text_size)
+            .field("highlight_radius", &self.highlight_radius)
+            .field("max_pinned", &self.max_pinned)
+            .finish()
+    }
+}
+
+#[cfg(feature = "tooltip")]
+impl TooltipConfig {
+    /// Set the X value format function.
+    pub fn with_format_x(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format_x = Rc::new(f);
+        self
+    }
+
+    /// Set the Y value format function.
+    pub fn with_format_y(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format_y = Rc::new(f);
+        self
+    }
+}
+
+/// Information about a data point that the cursor is hovering near.
+#[cfg(feature = "tooltip")]
+#[derive(Clone, Debug)]
+pub struct HoveredPoint {
+    /// Index of the series this point belongs to.
+    pub series_index: usize,
+    /// Label of the series.
+    pub series_label: String,
+    /// Data-space X coordinate.
+    pub x: f64,
+    /// Data-space Y coordinate.
+    pub y: f64,
+    /// Screen-space position of the point (relative to widget bounds).
+    pub screen_pos: Point,
+    /// Index of the point in the series' stored points (`None` for
+    /// generated points).
+    pub index: Option<usize>,
+    /// The point's entry in [`PlotSeries::metadata`], if any.
+    pub metadata: Option<String>,
+}
+
+#[cfg(feature = "tooltip")]
+impl HoveredPoint {
+    /// Whether both refer to the same data point, wherever it is on screen.
+    pub fn is_same_point(&self, other: &HoveredPoint) -> bool {
+        self.series_index == other.series_index && self.x == other.x && self.y == other.y
+    }
+}
+
+/// A tooltip left on screen by clicking on its point.
+#[cfg(feature = "tooltip")]
+#[derive(Clone, Debug)]
+pub struct PinnedTooltip {
+    pub point: HoveredPoint,
+    /// Where the point is drawn, in primary axis data coordinates, so the
+    /// tooltip follows it when the view changes.
+    pub anchor: (f64, f64),
+}
+
+/// Shared state for tooltip hover detection.
+///
+/// Store this in your application state and pass it to [`Plotter::with_tooltip_state`]
+/// to enable tooltip rendering. The shader layer writes hovered point info,
+/// and the canvas overlay reads it to draw the tooltip.
+///
+/// Clicking on a point pins its tooltip (up to [`TooltipConfig::max_pinned`]);
+/// the `×` on a pinned tooltip closes it.
+///
+/// Create with `TooltipState::default()`.
+#[cfg(feature = "tooltip")]
+#[derive(Clone, Debug, Default)]
+pub struct TooltipState {
+    pub hovered: Rc<RefCell<Option<HoveredPoint>>>,
+    /// Pinned tooltips, oldest first.
+    pub pinned: Rc<RefCell<Vec<PinnedTooltip>>>,
+    /// Close buttons of the pinned tooltips as last drawn, with their index
+    /// in `pinned`. Written by the overlay, read for hit testing.
+    pub(crate) close_buttons: Rc<RefCell<Vec<(usize, iced::Rectangle)>>>,
+}
+
+#[cfg(feature = "tooltip")]
+impl TooltipState {
+    /// Pin a tooltip, unless its point already has one, dropping the oldest
+    /// beyond `max`. Returns whether it was pinned.
+    pub fn pin(&self, tooltip: PinnedTooltip, max: usize) -> bool {
+        let mut pinned = self.pinned.borrow_mut();
+        if max == 0 || pinned.iter().any(|p| p.point.is_same_point(&tooltip.point)) {
+            return false;
+        }
+        pinned.push(tooltip);
+        let excess = pinned.len().saturating_sub(max);
+        pinned.drain(..excess);
+        true
+    }
+
+    /// Close all pinned tooltips.
+    pub fn clear_pinned(&self) {
+        self.pinned.borrow_mut().clear();
+        self.close_buttons.borrow_mut().clear();
+    }
+}
+
+// ================================================================================
+// Context Menu Types
+// ================================================================================
+
+/// What a [`ContextMenuConfig`] entry does when clicked.
+///
+/// [`ContextAction::ResetView`] is carried out by the plot through
+/// [`Plotter::on_view_change`]; everything else is reported to
+/// [`Plotter::on_context_action`] for the app to carry out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContextAction {
+    /// Fit the view to all data, like a double-click.
+    ResetView,
+    /// Copy the data coordinates where the menu was opened.
+    CopyValue,
+    /// Save the plot as a PNG, e.g. with [`Plotter::render_to_image`].
+    ExportPng,
+    /// Save the visible data as CSV, e.g. with [`Plotter::export_visible_csv`].
+    ExportCsv,
+    /// Show or hide the grid.
+    ToggleGrid,
+    /// An app-defined entry, by the id given to [`ContextMenuConfig::with_custom`].
+    Custom(u32),
+}
+
+/// A labeled entry of the context menu.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextMenuEntry {
+    pub label: String,
+    pub action: ContextAction,
+}
+
+/// Configuration for the menu opened by right-clicking the plot area.
+///
+/// Entries whose action has no handler are left out: [`ContextAction::ResetView`]
+/// needs [`Plotter::on_view_change`], the others [`Plotter::on_context_action`].
+/// Escape or a click elsewhere closes the menu.
+#[derive(Clone, Debug)]
+pub struct ContextMenuConfig {
+    /// Entries from top to bottom. Defaults to the built-in actions.
+    pub entries: Vec<ContextMenuEntry>,
+    /// Background color of the menu.
+    pub background_color: iced::Color,
+    /// Background color of the entry under the cursor.
+    pub highlight_color: iced::Color,
+    /// Color of the entry labels.
+    pub text_color: iced::Color,
+    /// Font size of the entry labels.
+    pub text_size: f32,
+    /// Internal padding around each entry.
+    pub padding: f32,
+}
+
+impl Default for ContextMenuConfig {
+    fn default() -> Self {
+        let entry = |label: &str, action| ContextMenuEntry {
+            label: label.to_string(),
+            action,
+        };
+        Self {
+            entries: vec![
+                entry("Reset view", ContextAction::ResetView),
+                entry("Copy value", ContextAction::CopyValue),
+                entry("Export PNG", ContextAction::ExportPng),
+                entry("Export CSV", ContextAction::ExportCsv),
+                entry("Toggle grid", ContextAction::ToggleGrid),
+            ],
+            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.95),
+            highlight_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.15),
+            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.9),
+            text_size: 12.0,
+            padding: 6.0,
+        }
+    }
+}
+
+impl ContextMenuConfig {
+    /// Append an app-defined entry, reported as [`ContextAction::Custom`]`(id)`.
+    pub fn with_custom(mut self, label: impl Into<String>, id: u32) -> Self {
+        self.entries.push(ContextMenuEntry {
+            label: label.into(),
+            action: ContextAction::Custom(id),
+        });
+        self
+    }
+
+    /// Replace the entries, e.g. to drop or reorder built-in actions.
+    pub fn with_entries(mut self, entries: Vec<ContextMenuEntry>) -> Self {
+        self.entries = entries;
+        self
+    }
+}
+
+/// A context menu being shown.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenContextMenu {
+    /// Where it was opened, relative to the widget bounds.
+    pub anchor: Point,
+    /// Data coordinates (primary Y axis) where it was opened.
+    pub data: (f64, f64),
+    /// The entries shown, those with a handler.
+    pub entries: Vec<ContextMenuEntry>,
+}
+
+impl OpenContextMenu {
+    /// Height of one entry.
+    pub(crate) fn row_height(config: &ContextMenuConfig) -> f32 {
+        config.text_size + config.padding * 2.0
+    }
+
+    /// The menu's box in a widget of `size`: below and right of the anchor,
+    /// flipped to stay inside the widget.
+    pub(crate) fn bounds(&self, config: &ContextMenuConfig, size: iced::Size) -> iced::Rectangle {
+        // Estimate text dimensions
+        let char_width = config.text_size * 0.6;
+        let max_chars = self.entries.iter().map(|e| e.label.chars().count()).max();
+        let width = max_chars.unwrap_or(0) as f32 * char_width + config.padding * 4.0;
+        let height = self.entries.len() as f32 * Self::row_height(config);
+        let mut x = self.anchor.x;
+        let mut y = self.anchor.y;
+        if x + width > size.width {
+            x = (x - width).max(0.0);
+        }
+        if y + height > size.height {
+            y = (y - height).max(0.0);
+        }
+        iced::Rectangle::new(Point::new(x, y), iced::Size::new(width, height))
+    }
+
+    /// Index of the entry at widget-local `pos`, if any.
+    pub(crate) fn entry_at(
+        &self,
+        config: &ContextMenuConfig,
+        size: iced::Size,
+        pos: Point,
+    ) -> Option<usize> {
+        let bounds = self.bounds(config, size);
+        if !bounds.contains(pos) {
+            return None;
+        }
+        let row = ((pos.y - bounds.y) / Self::row_height(config)) as usize;
+        (row < self.entries.len()).then_some(row)
+    }
+}
+
+/// Shared state for the context menu.
+///
+/// The shader layer opens and closes the menu, and the canvas overlay draws
+/// it. Pass a handle stored in your application state to
+/// [`Plotter::with_context_menu_state`] to keep the menu open while the app
+/// updates for other reasons.
+///
+/// Create with `ContextMenuState::default()`.
+#[derive(Clone, Debug, Default)]
+pub struct ContextMenuState {
+    pub open: Rc<RefCell<Option<OpenContextMenu>>>,
+}
+
+// ================================================================================
+// Cursor Readout Types
+// ================================================================================
+
+/// A corner of the plot area.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Vertical color scale right of the plot area, decoding the colors of a
+/// [`ColorMode::ValueGradient`], [`ColorMode::ValueFn`] or `Colormap` series
+/// back to values. See [`PlotterOptions::colorbar`].
+///
+/// The right padding grows by [`ColorbarConfig::width`]; the bar sits
+/// outside any extra right Y axes.
+#[derive(Clone)]
+pub struct ColorbarConfig {
+    /// Series whose colors are shown. `None` = the first visible series
+    /// colored by value.
+    pub series: Option<usize>,
+    /// Values at the bottom and top of the bar. `None` = the range the
+    /// series is colored over: its [`ColorScale::range`], else its `values`
+    /// array, its value function over the stored points, or the Y range of
+    /// the visible data.
+    pub range: Option<(f64, f64)>,
+    /// Width reserved for the bar and its labels, in pixels.
+    pub width: f32,
+    /// Width of the color strip, in pixels.
+    pub strip_width: f32,
+    pub ticks: crate::ticks::TickConfig,
+    /// Tick label formatter, like [`AxisConfig::format`].
+    pub format: Rc<dyn Fn(&crate::ticks::TickContext) -> String>,
+    pub label_color: iced::Color,
+    pub label_size: f32,
+    /// Title drawn above the bar, e.g. the unit.
+    pub title: Option<String>,
+}
+
+impl Default for ColorbarConfig {
+    fn default() -> Self {
+        Self {
+            series: None,
+            range: None,
+            width: 64.0,
+            strip_width: 12.0,
+            ticks: crate::ticks::TickConfig::auto(3, 6),
+            format: Rc::new(crate::ticks::number_label),
+            label_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.7),
+            label_size: 11.0,
+            title: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ColorbarConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColorbarConfig")
+            .field("series", &self.series)
+            .field("range", &self.range)
+            .field("width", &self.width)
+            .field("strip_width", &self.strip_width)
+            .field("ticks", &self.ticks)
+            .field("title", &self.title)
+            .finish()
+    }
+}
+
+impl ColorbarConfig {
+    /// Show the colors of the series at `index`.
+    pub fn for_series(mut self, index: usize) -> Self {
+        self.series = Some(index);
+        self
+    }
+
+    /// Fix the values at the bottom and top of the bar.
+    pub fn with_range(mut self, low: f64, high: f64) -> Self {
+        self.range = Some((low, high));
+        self
+    }
+
+    /// Format tick labels, see [`AxisConfig::with_format`].
+    pub fn with_format(
+        mut self,
+        f: impl Fn(&crate::ticks::TickContext) -> String + 'static,
+    ) -> Self {
+        self.format = Rc::new(f);
+        self
+    }
+
+    /// Set the title drawn above the bar.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Configuration for the corner cursor-position readout.
+///
+/// Shows the data coordinates under the cursor in a small box pinned to a
+/// corner of the plot area, independent of tooltips.
+#[derive(Clone)]
+pub struct CursorReadoutConfig {
+    /// Corner of the plot area the readout is anchored to.
+    pub corner: Corner,
+    /// Background color of the readout box.
+    pub background_color: iced::Color,
+    /// Text color inside the readout box.
+    pub text_color: iced::Color,
+    /// Font size for the readout text.
+    pub text_size: f32,
+    /// Internal padding within the readout box.
+    pub padding: f32,
+    /// Distance from the plot edge.
+    pub margin: f32,
+    /// Format function for the X value.
+    pub format_x: Rc<dyn Fn(f64) -> String>,
+    /// Format function for the Y value.
+    pub format_y: Rc<dyn Fn(f64) -> String>,
+    /// Also list each stored series' value at the cursor X, linearly
+    /// interpolated between the two surrounding samples. Assumes the points
+    /// are sorted by X.
+    pub series_values: bool,
+}
+
+impl Default for CursorReadoutConfig {
+    fn default() -> Self {
+        Self {
+            corner: Corner::default(),
+            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.8),
+            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.8),
+            text_size: 12.0,
+            padding: 4.0,
+            margin: 6.0,
+            format_x: Rc::new(|v| format!("{v:.3}")),
+            format_y: Rc::new(|v| format!("{v:.3}")),
+            series_values: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for CursorReadoutConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorReadoutConfig")
+            .field("corner", &self.corner)
+            .field("text_size", &self.text_size)
+            .field("padding", &self.padding)
+            .field("margin", &self.margin)
+            .field("series_values", &self.series_values)
+            .finish()
+    }
+}
+
+impl CursorReadoutConfig {
+    /// Set the corner the readout is anchored to.
+    pub fn with_corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Set the X value format function.
+    pub fn with_format_x(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format_x = Rc::new(f);
+        self
+    }
+
+    /// Set the Y value format function.
+    pub fn with_format_y(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format_y = Rc::new(f);
+        self
+    }
+
+    /// List each series' interpolated value at the cursor X.
+    pub fn with_series_values(mut self, enabled: bool) -> Self {
+        self.series_values = enabled;
+        self
+    }
+}
+
+/// Configuration for the crosshair cursor.
+///
+/// Draws full-width and full-height lines through the cursor, with the exact
+/// data coordinates in small boxes on the X and Y axes.
+#[derive(Clone)]
+pub struct CrosshairConfig {
+    /// Color of the crosshair lines.
+    pub line_color: iced::Color,
+    /// Width of the crosshair lines in pixels.
+    pub line_width: f32,
+    /// Background color of the axis value boxes.
+    pub background_color: iced::Color,
+    /// Text color inside the axis value boxes.
+    pub text_color: iced::Color,
+    /// Font size for the axis values.
+    pub text_size: f32,
+    /// Internal padding within the axis value boxes.
+    pub padding: f32,
+    /// Format function for the X value.
+    pub format_x: Rc<dyn Fn(f64) -> String>,
+    /// Format function for the Y value.
+    pub format_y: Rc<dyn Fn(f64) -> String>,
+}
+
+impl Default for CrosshairConfig {
+    fn default() -> Self {
+        Self {
+            line_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.4),
+            line_width: 1.0,
+            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.9),
+            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.9),
+            text_size: 11.0,
+            padding: 3.0,
+            format_x: Rc::new(|v| format!("{v:.3}")),
+            format_y: Rc::new(|v| format!("{v:.3}")),
+        }
+    }
+}
+
+impl std::fmt::Debug for CrosshairConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrosshairConfig")
+            .field("line_color", &self.line_color)
+            .field("line_width", &self.line_width)
+            .field("text_size", &self.text_size)
+            .field("padding", &self.padding)
+            .finish()
+    }
+}
+
+impl CrosshairConfig {
+    /// Set the line color and width.
+    pub fn with_line(mut self, color: iced::Color, width: f32) -> Self {
+        self.line_color = color;
+        self.line_width = width;
+        self
+    }
+
+    /// Set the X value format function.
+    pub fn with_format_x(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format_x = Rc::new(f);
+        self
+    }
+
+    /// Set the Y value format function.
+    pub fn with_format_y(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format_y = Rc::new(f);
+        self
+    }
+}
+
+/// Shared state for the crosshair.
+///
+/// The shader layer writes the data coordinates under the cursor (on the
+/// primary Y axis) while it is over the plot area, and the canvas overlay
+/// reads them to draw the crosshair. Share one state between plots with the
+/// same X and Y axes to move their crosshairs together; plots with their own
+/// Y scales share a [`SharedCursor`] instead.
+///
+/// Create with `CursorState::default()`.
+#[derive(Clone, Debug, Default)]
+pub struct CursorState {
+    pub position: Rc<RefCell<Option<PlotPoint>>>,
+}
+
+/// The X under the cursor, shared between plots with the same X axis.
+///
+/// Hovering one plot draws a vertical marker at that X on all the others,
+/// styled like their [`CrosshairConfig`] (or its default), and moves their
+/// [`CursorReadoutConfig`] readouts to it, each listing its own series'
+/// values.
+///
+/// Create with `SharedCursor::default()` and hand a clone to each plot with
+/// [`Plotter::with_shared_cursor`].
+#[derive(Clone, Debug, Default)]
+pub struct SharedCursor {
+    pub x: Rc<RefCell<Option<f64>>>,
+}
+
+/// Interpolate the Y value at `x` between the two samples around it, the
+/// way the line is drawn. `points` must be sorted by X; returns `None`
+/// outside their X range, and in gaps where the line breaks at a
+/// non-finite Y.
+fn interpolate_y(points: &[PlotPoint], x: f64, interpolation: LineInterpolation) -> Option<f64> {
+    let i = points.partition_point(|p| p.x < x);
+    let next = points.get(i)?;
+    if next.x == x {
+        return next.y.is_finite().then_some(next.y);
+    }
+    let prev = points.get(i.checked_sub(1)?)?;
+    if !(prev.y.is_finite() && next.y.is_finite()) {
+        return None;
+    }
+    let t = (x - prev.x) / (next.x - prev.x);
+    Some(interpolation.value_between(prev.y, next.y, t))
+}
+
+// ================================================================================
+// Caption Types
+// ================================================================================
+
+/// A styled line of text laid out below the X axis, such as a caption
+/// (data source, units note) or a footnote.
+#[derive(Clone, Debug)]
+pub struct TextBlock {
+    pub text: String,
+    pub color: iced::Color,
+    pub size: f32,
+    /// Horizontal alignment relative to the plot area.
+    pub align: iced::alignment::Horizontal,
+}
+
+impl TextBlock {
+    /// Create a centered text block with default styling.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.6),
+            size: 12.0,
+            align: iced::alignment::Horizontal::Center,
+        }
+    }
+
+    /// Set the text color.
+    pub fn with_color(mut self, color: iced::Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the font size.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the horizontal alignment.
+    pub fn with_align(mut self, align: iced::alignment::Horizontal) -> Self {
+        self.align = align;
+        self
+    }
+}
+
+/// Vertical gap around caption/footnote lines.
+const TEXT_BLOCK_GAP: f32 = 4.0;
+
+/// Color steps drawn in a colorbar strip.
+const COLORBAR_STEPS: usize = 64;
+
+/// Color of a value-colored `mode` at normalized position `t`, like the
+/// renderer picks it; `None` for modes that don't encode a value.
+pub(crate) fn colorbar_color(mode: &ColorMode<'_>, t: f32) -> Option<iced::Color> {
+    let lerp = |a: iced::Color, b: iced::Color| {
+        iced::Color::from_rgb(
+            a.r + (b.r - a.r) * t,
+            a.g + (b.g - a.g) * t,
+            a.b + (b.b - a.b) * t,
+        )
+    };
+    match mode {
+        ColorMode::ValueGradient { low, high, .. } | ColorMode::ValueFn { low, high, .. } => {
+            Some(lerp(*low, *high))
+        }
+        #[cfg(feature = "colormap")]
+        ColorMode::Colormap { name, .. } => Some(name.sample(t)),
+        ColorMode::Solid(_) | ColorMode::IndexGradient { .. } => None,
+    }
+}
+
+/// Labels for ticks from [`visible_ticks`](crate::ticks::visible_ticks):
+/// the ones a [`TickConfig::Labeled`](crate::ticks::TickConfig::Labeled)
+/// config gives, else `format`'s.
+fn tick_labels(
+    ticks: &[(f64, crate::ticks::TickContext)],
+    config: &crate::ticks::TickConfig,
+    format: &dyn Fn(&crate::ticks::TickContext) -> String,
+) -> Vec<String> {
+    ticks
+        .iter()
+        .map(|(tick, context)| match config.label(*tick) {
+            Some(label) => label.to_owned(),
+            None => format(context),
+        })
+        .collect()
+}
+
+/// Where an [`Annotation`] sits, in data coordinates of the X and primary Y axis.
+#[cfg(feature = "annotations")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnnotationKind {
+    /// Horizontal line at a Y value, e.g. an alarm threshold.
+    HLine { y: f64 },
+    /// Vertical line at an X value, e.g. a deployment time.
+    VLine { x: f64 },
+    /// Horizontal band between two Y values, e.g. a normal operating range.
+    HBand { y: (f64, f64) },
+    /// Vertical band between two X values, e.g. a maintenance window.
+    VBand { x: (f64, f64) },
+    /// The annotation's label as free text at a data point. `anchor` picks
+    /// which side of the text sits on the point.
+    Text {
+        x: f64,
+        y: f64,
+        anchor: (iced::alignment::Horizontal, iced::alignment::Vertical),
+    },
+    /// An arrow pointing from one data point to another, e.g. at an event.
+    /// The label, if any, is drawn at the tail.
+    Arrow { from: (f64, f64), to: (f64, f64) },
+}
+
+/// A reference line, band, text or arrow glued to data coordinates. Lines,
+/// bands and arrows are drawn with the grid, behind the data; everything is
+/// clipped to the plot area. See [`PlotterOptions::annotations`].
+#[cfg(feature = "annotations")]
+#[derive(Clone, Debug)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    /// Line color, fill color for bands, or text color.
+    pub color: iced::Color,
+    /// Line width in pixels. Bands draw their edges with it; 0 = no edges.
+    pub width: f32,
+    pub pattern: LinePattern,
+    /// Text drawn next to the line, inside the band's top left corner, at
+    /// the arrow's tail, or as the whole of a text annotation.
+    pub label: Option<String>,
+    pub label_size: f32,
+}
+
+#[cfg(feature = "annotations")]
+impl Annotation {
+    fn new(kind: AnnotationKind, color: iced::Color, width: f32) -> Self {
+        Self {
+            kind,
+            color,
+            width,
+            pattern: LinePattern::Solid,
+            label: None,
+            label_size: 12.0,
+        }
+    }
+
+    /// A horizontal line at `y`.
+    pub fn hline(y: f64) -> Self {
+        Self::new(
+            AnnotationKind::HLine { y },
+            iced::Color::from_rgba(1.0, 1.0, 1.0, 0.6),
+            1.0,
+        )
+    }
+
+    /// A vertical line at `x`.
+    pub fn vline(x: f64) -> Self {
+        Self::new(
+            AnnotationKind::VLine { x },
+            iced::Color::from_rgba(1.0, 1.0, 1.0, 0.6),
+            1.0,
+        )
+    }
+
+    /// A horizontal band from `y0` to `y1`.
+    pub fn hband(y0: f64, y1: f64) -> Self {
+        Self::new(
+            AnnotationKind::HBand { y: (y0, y1) },
+            iced::Color::from_rgba(1.0, 1.0, 1.0, 0.1),
+            0.0,
+        )
+    }
+
+    /// A vertical band from `x0` to `x1`.
+    pub fn vband(x0: f64, x1: f64) -> Self {
+        Self::new(
+            AnnotationKind::VBand { x: (x0, x1) },
+            iced::Color::from_rgba(1.0, 1.0, 1.0, 0.1),
+            0.0,
+        )
+    }
+
+    /// Text `text` at (`x`, `y`), centered on the point.
+    pub fn text(x: f64, y: f64, text: impl Into<String>) -> Self {
+        Self::new(
+            AnnotationKind::Text {
+                x,
+                y,
+                anchor: (
+                    iced::alignment::Horizontal::Center,
+                    iced::alignment::Vertical::Center,
+                ),
+            },
+            iced::Color::from_rgba(1.0, 1.0, 1.0, 0.9),
+            0.0,
+        )
+        .with_label(text)
+    }
+
+    /// An arrow from `from` to `to`, with its head at `to`.
+    pub fn arrow(from: (f64, f64), to: (f64, f64)) -> Self {
+        Self::new(
+            AnnotationKind::Arrow { from, to },
+            iced::Color::from_rgba(1.0, 1.0, 1.0, 0.8),
+            1.5,
+        )
+    }
+
+    /// Set which side of a text annotation sits on its point. No effect on
+    /// other kinds.
+    pub fn with_anchor(
+        mut self,
+        horizontal: iced::alignment::Horizontal,
+        vertical: iced::alignment::Vertical,
+    ) -> Self {
+        if let AnnotationKind::Text { ref mut anchor, .. } = self.kind {
+            *anchor = (horizontal, vertical);
+        }
+        self
+    }
+
+    /// Set the line, fill or text color.
+    pub fn with_color(mut self, color: iced::Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the line width in pixels.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the dash pattern of the line or band edges.
+    pub fn with_pattern(mut self, pattern: LinePattern) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    /// Label the annotation.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// One labeled X interval of an [`EventSeries`], e.g. an alarm or a test
+/// phase. A zero-length interval is drawn as a line.
+#[cfg(feature = "annotations")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotEvent {
+    pub start: f64,
+    pub end: f64,
+    pub label: Option<String>,
+    /// Band color; its alpha is scaled by [`EventSeries::fill_alpha`].
+    pub color: iced::Color,
+}
+
+#[cfg(feature = "annotations")]
+impl PlotEvent {
+    /// An unlabeled event from `start` to `end`.
+    pub fn new(start: f64, end: f64, color: iced::Color) -> Self {
+        Self {
+            start,
+            end,
+            label: None,
+            color,
+        }
+    }
+
+    /// Label the event.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// Labeled X intervals, e.g. from a log, drawn as translucent bands
+/// spanning the plot height with their labels at the top. Drawn like
+/// [`Annotation::vband`]s, beneath the annotations. See
+/// [`Plotter::with_events`].
+#[cfg(feature = "annotations")]
+#[derive(Clone, Debug)]
+pub struct EventSeries {
+    pub events: Vec<PlotEvent>,
+    /// Multiplies the alpha of the event colors for the band fill.
+    pub fill_alpha: f32,
+    /// Width in pixels of the band edges, in the full event color;
+    /// 0 = no edges.
+    pub edge_width: f32,
+    /// Whether labels are drawn at the top of the bands.
+    pub show_labels: bool,
+    pub label_size: f32,
+}
+
+#[cfg(feature = "annotations")]
+impl EventSeries {
+    pub fn new(events: Vec<PlotEvent>) -> Self {
+        Self {
+            events,
+            fill_alpha: 0.25,
+            edge_width: 0.0,
+            show_labels: true,
+            label_size: 12.0,
+        }
+    }
+
+    /// Set the fill alpha multiplier.
+    pub fn with_fill_alpha(mut self, alpha: f32) -> Self {
+        self.fill_alpha = alpha;
+        self
+    }
+
+    /// Draw the band edges `width` pixels wide.
+    pub fn with_edges(mut self, width: f32) -> Self {
+        self.edge_width = width;
+        self
+    }
+
+    /// Show or hide the labels.
+    pub fn with_labels(mut self, show: bool) -> Self {
+        self.show_labels = show;
+        self
+    }
+
+    /// The bands (and lines, for zero-length events) as annotations.
+    pub(crate) fn annotations(&self) -> impl Iterator<Item = Annotation> + '_ {
+        self.events.iter().flat_map(|event| {
+            let (start, end) = (event.start.min(event.end), event.start.max(event.end));
+            let mut bands = Vec::with_capacity(2);
+            if start < end {
+                let fill = iced::Color {
+                    a: event.color.a * self.fill_alpha,
+                    ..event.color
+                };
+                bands.push(Annotation::vband(start, end).with_color(fill));
+                if self.edge_width > 0.0 {
+                    bands.extend([start, end].map(|x| {
+                        Annotation::vline(x)
+                            .with_color(event.color)
+                            .with_width(self.edge_width)
+                    }));
+                }
+            } else if start.is_finite() {
+                bands.push(
+                    Annotation::vline(start)
+                        .with_color(event.color)
+                        .with_width(self.edge_width.max(1.0)),
+                );
+            }
+            if self.show_labels
+                && let (Some(first), Some(label)) = (bands.first_mut(), &event.label)
+            {
+                first.label = Some(label.clone());
+                first.label_size = self.label_size;
+            }
+            bands
+        })
+    }
+}
+
+/// The axis a [`CursorLine`] marks a value on.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum CursorAxis {
+    /// A vertical line at an X value.
+    X,
+    /// A horizontal line at a Y value on the primary axis.
+    Y,
+}
+
+/// A line across the plot area at an X or Y value that can be dragged,
+/// like an oscilloscope cursor. See [`Plotter::with_cursor_line`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CursorLine {
+    pub axis: CursorAxis,
+    pub value: f64,
+    pub color: iced::Color,
+    /// Line width in pixels.
+    pub width: f32,
+    /// Text drawn at the top of a vertical line or the left end of a
+    /// horizontal one.
+    pub label: Option<String>,
+}
+
+impl CursorLine {
+    pub const DEFAULT_COLOR: iced::Color = iced::Color::from_rgb(1.0, 0.8, 0.2);
+
+    /// A vertical line at `x`.
+    pub fn vertical(x: f64) -> Self {
+        Self::new(CursorAxis::X, x)
+    }
+
+    /// A horizontal line at `y`.
+    pub fn horizontal(y: f64) -> Self {
+        Self::new(CursorAxis::Y, y)
+    }
+
+    fn new(axis: CursorAxis, value: f64) -> Self {
+        Self {
+            axis,
+            value,
+            color: Self::DEFAULT_COLOR,
+            width: 1.0,
+            label: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: iced::Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Distance from the first to the second vertical line and from the
+    /// first to the second horizontal line in `lines`, as `[Δx, Δy]`.
+    pub fn deltas(lines: &[CursorLine]) -> [Option<f64>; 2] {
+        [CursorAxis::X, CursorAxis::Y].map(|axis| {
+            let mut values = lines.iter().filter(|l| l.axis == axis).map(|l| l.value);
+            let first = values.next()?;
+            Some(values.next()? - first)
+        })
+    }
+}
+
+/// Configuration for the readout of the distance between paired
+/// [`CursorLine`]s: Δx and 1/Δx between the first two vertical lines and
+/// Δy between the first two horizontal ones. See [`CursorLine::deltas`].
+#[derive(Clone)]
+pub struct CursorDeltaConfig {
+    /// Corner of the plot area the readout is anchored to.
+    pub corner: Corner,
+    pub background_color: iced::Color,
+    pub text_color: iced::Color,
+    pub text_size: f32,
+    /// Internal padding within the readout box.
+    pub padding: f32,
+    /// Distance from the plot edge.
+    pub margin: f32,
+    /// Format function for the values.
+    pub format: Rc<dyn Fn(f64) -> String>,
+}
+
+impl Default for CursorDeltaConfig {
+    fn default() -> Self {
+        Self {
+            corner: Corner::TopRight,
+            background_color: iced::Color::from_rgba(0.1, 0.1, 0.1, 0.8),
+            text_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.8),
+            text_size: 12.0,
+            padding: 4.0,
+            margin: 6.0,
+            format: Rc::new(|v| format!("{v:.4}")),
+        }
+    }
+}
+
+impl std::fmt::Debug for CursorDeltaConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorDeltaConfig")
+            .field("corner", &self.corner)
+            .field("text_size", &self.text_size)
+            .field("padding", &self.padding)
+            .field("margin", &self.margin)
+            .finish()
+    }
+}
+
+impl CursorDeltaConfig {
+    /// Set the value format function.
+    pub fn with_format(mut self, f: impl Fn(f64) -> String + 'static) -> Self {
+        self.format = Rc::new(f);
+        self
+    }
+}
+
+// ================================================================================
+// Responsive Layout Types
+// ================================================================================
+
+/// Widget-size thresholds below which plot chrome is reduced, so small
+/// dashboard tiles stay readable instead of being all chrome.
+///
+/// Each threshold is in logical pixels; the behavior applies when the widget
+/// is smaller than the threshold in either dimension.
+#[derive(Clone, Debug)]
+pub struct ResponsiveConfig {
+    /// Hide the legend below this widget width.
+    pub hide_legend_width: f32,
+    /// Hide the legend below this widget height.
+    pub hide_legend_height: f32,
+    /// Drop axis titles below this widget width.
+    pub hide_titles_width: f32,
+    /// Drop axis titles below this widget height.
+    pub hide_titles_height: f32,
+    /// Use [`Self::compact_ticks`] on the X axis below this widget width.
+    pub compact_x_ticks_width: f32,
+    /// Use [`Self::compact_ticks`] on the Y axis below this widget height.
+    pub compact_y_ticks_height: f32,
+    /// Tick configuration used for compact axes. Axes with explicit ticks
+    /// keep them.
+    pub compact_ticks: crate::ticks::TickConfig,
+}
+
+impl Default for ResponsiveConfig {
+    fn default() -> Self {
+        Self {
+            hide_legend_width: 300.0,
+            hide_legend_height: 200.0,
+            hide_titles_width: 250.0,
+            hide_titles_height: 160.0,
+            compact_x_ticks_width: 350.0,
+            compact_y_ticks_height: 220.0,
+            compact_ticks: crate::ticks::TickConfig::auto(2, 4),
+        }
+    }
+}
+
+impl ResponsiveConfig {
+    /// Resolve which chrome elements are shown at the given widget size.
+    pub fn layout(&self, size: iced::Size) -> ResponsiveLayout {
+        ResponsiveLayout {
+            show_legend: size.width >= self.hide_legend_width
+                && size.height >= self.hide_legend_height,
+            show_titles: size.width >= self.hide_titles_width
+                && size.height >= self.hide_titles_height,
+            compact_x_ticks: size.width < self.compact_x_ticks_width,
+            compact_y_ticks: size.height < self.compact_y_ticks_height,
+        }
+    }
+}
+
+/// The chrome elements shown at a particular widget size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResponsiveLayout {
+    pub show_legend: bool,
+    pub show_titles: bool,
+    pub compact_x_ticks: bool,
+    pub compact_y_ticks: bool,
+}
+
+impl Default for ResponsiveLayout {
+    fn default() -> Self {
+        Self {
+            show_legend: true,
+            show_titles: true,
+            compact_x_ticks: false,
+            compact_y_ticks: false,
+        }
+    }
 }
 
 // ================================================================================
@@ -699,6 +3692,34 @@ impl Default for GridStyle {
     }
 }
 
+/// What axis labels are measured from.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AxisReference {
+    /// Show absolute values.
+    #[default]
+    Absolute,
+    /// Show the offset from the smallest data value, so the first sample is 0.
+    FirstSample,
+    /// Show the offset from the largest data value, so the latest sample is 0
+    /// and earlier ones are negative ("seconds ago").
+    LastSample,
+    /// Show the offset from a fixed value.
+    Value(f64),
+}
+
+impl AxisReference {
+    /// The data value labeled 0, given the data extent on this axis.
+    pub fn resolve(self, data_range: [f64; 2]) -> f64 {
+        match self {
+            AxisReference::Absolute => 0.0,
+            AxisReference::FirstSample => data_range[0],
+            AxisReference::LastSample => data_range[1],
+            AxisReference::Value(v) => v,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct AxisConfig {
     pub show: bool,
     pub color: iced::Color,
@@ -706,30 +3727,26 @@ pub struct AxisConfig {
     pub label_color: iced::Color,
     pub label_size: f32,
     pub ticks: crate::ticks::TickConfig,
-    pub format: Box<dyn Fn(f32) -> String>,
+    /// Numeric or time axis, see [`AxisConfig::with_kind`].
+    pub kind: crate::ticks::AxisKind,
+    /// Tick label formatter, see [`AxisConfig::with_format`].
+    pub format: Rc<dyn Fn(&crate::ticks::TickContext) -> String>,
     /// Optional axis title (e.g. "Time (s)", "Temperature (°C)").
     pub title: Option<String>,
     /// Color for the axis title text.
     pub title_color: iced::Color,
     /// Font size for the axis title.
     pub title_size: f32,
-}
-
-impl Clone for AxisConfig {
-    fn clone(&self) -> Self {
-        Self {
-            show: self.show,
-            color: self.color,
-            line_width: self.line_width,
-            label_color: self.label_color,
-            label_size: self.label_size,
-            ticks: self.ticks.clone(),
-            format: Box::new(|v| format!("{v:.2}")),
-            title: self.title.clone(),
-            title_color: self.title_color,
-            title_size: self.title_size,
-        }
-    }
+    /// What tick labels are measured from. Ticks land on round values
+    /// relative to it, and labels follow as data streams in.
+    pub reference: AxisReference,
+    /// Counter-clockwise tilt of the tick labels in degrees, 0 to 90. Only
+    /// the X axis uses it; tilted labels hang below the axis, so leave room
+    /// for them in [`PlotterOptions::padding`].
+    pub label_rotation: f32,
+    /// Skip tick labels (keeping every 2nd, 3rd, ...) when they would
+    /// overlap. Only the X axis uses it.
+    pub thin_labels: bool,
 }
 
 impl std::fmt::Debug for AxisConfig {
@@ -741,6 +3758,10 @@ impl std::fmt::Debug for AxisConfig {
             .field("label_color", &self.label_color)
             .field("label_size", &self.label_size)
             .field("ticks", &self.ticks)
+            .field("kind", &self.kind)
+            .field("reference", &self.reference)
+            .field("label_rotation", &self.label_rotation)
+            .field("thin_labels", &self.thin_labels)
             .finish()
     }
 }
@@ -754,17 +3775,128 @@ impl Default for AxisConfig {
             label_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.7),
             label_size: 12.0,
             ticks: crate::ticks::TickConfig::default(),
-            format: Box::new(|v| format!("{v:.2}")),
+            kind: crate::ticks::AxisKind::default(),
+            format: Rc::new(crate::ticks::number_label),
             title: None,
             title_color: iced::Color::from_rgba(1.0, 1.0, 1.0, 0.7),
             title_size: 14.0,
+            reference: AxisReference::default(),
+            label_rotation: 0.0,
+            thin_labels: true,
+        }
+    }
+}
+
+impl AxisConfig {
+    /// Format tick labels. The callback sees the tick's position, the
+    /// visible range and the tick step, so it can adapt precision to the
+    /// zoom level or only spell out context (a date, a unit) on one tick.
+    pub fn with_format(
+        mut self,
+        f: impl Fn(&crate::ticks::TickContext) -> String + 'static,
+    ) -> Self {
+        self.format = Rc::new(f);
+        self
+    }
+
+    /// Label ticks with SI prefixes ("1.2k", "3.4M"), see
+    /// [`si_label`](crate::ticks::si_label).
+    pub fn format_si(self) -> Self {
+        self.with_format(crate::ticks::si_label)
+    }
+
+    /// Label ticks in engineering notation ("1.5e3"), see
+    /// [`engineering_label`](crate::ticks::engineering_label).
+    pub fn format_engineering(self) -> Self {
+        self.with_format(crate::ticks::engineering_label)
+    }
+
+    /// Label ticks in scientific notation ("2.5e-4"), see
+    /// [`scientific_label`](crate::ticks::scientific_label).
+    pub fn format_scientific(self) -> Self {
+        self.with_format(crate::ticks::scientific_label)
+    }
+
+    /// Label fractions as percentages (0.25 as "25%"), see
+    /// [`percent_label`](crate::ticks::percent_label).
+    pub fn format_percent(self) -> Self {
+        self.with_format(crate::ticks::percent_label)
+    }
+
+    /// Label values in seconds as durations ("1h 30m"), see
+    /// [`duration_label`](crate::ticks::duration_label).
+    pub fn format_duration(self) -> Self {
+        self.with_format(crate::ticks::duration_label)
+    }
+
+    /// Label ticks with category names: value `i` shows `labels[i]`. Sets
+    /// [`AxisKind::Category`](crate::ticks::AxisKind::Category), which ticks
+    /// whole numbers only, and leaves ticks without a name blank.
+    pub fn with_categories(mut self, labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let labels: Vec<String> = labels.into_iter().map(Into::into).collect();
+        self.kind = crate::ticks::AxisKind::Category;
+        self.format = Rc::new(move |t| {
+            let i = t.value.round();
+            if i >= 0.0 && (t.value - i).abs() < 1e-9 {
+                labels.get(i as usize).cloned().unwrap_or_default()
+            } else {
+                String::new()
+            }
+        });
+        self
+    }
+
+    /// Set where ticks go, e.g. [`TickConfig::Labeled`](crate::ticks::TickConfig::Labeled)
+    /// to mark shift boundaries by name.
+    pub fn with_ticks(mut self, ticks: crate::ticks::TickConfig) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Treat values as plain numbers or as timestamps. A time axis ticks on
+    /// calendar boundaries and switches to
+    /// [`time_label`](crate::ticks::time_label) labels; call
+    /// [`with_format`](Self::with_format) afterwards to replace them.
+    pub fn with_kind(mut self, kind: crate::ticks::AxisKind) -> Self {
+        self.kind = kind;
+        if let crate::ticks::AxisKind::Time { epoch } = kind {
+            self.format = Rc::new(move |t| crate::ticks::time_label(t, epoch));
         }
+        self
     }
-}
 
-impl AxisConfig {
-    pub fn with_format(mut self, f: impl Fn(f32) -> String + 'static) -> Self {
-        self.format = Box::new(f);
+    /// Labels for ticks from [`visible_ticks`](crate::ticks::visible_ticks):
+    /// the ones `config` gives, else the formatter's.
+    fn labels(
+        &self,
+        ticks: &[(f64, crate::ticks::TickContext)],
+        config: &crate::ticks::TickConfig,
+    ) -> Vec<String> {
+        tick_labels(ticks, config, &*self.format)
+    }
+
+    /// Positions and labels of the ticks across `range` with `config`.
+    fn ticks_in(
+        &self,
+        range: [f64; 2],
+        offset: f64,
+        config: &crate::ticks::TickConfig,
+    ) -> (Vec<f64>, Vec<String>) {
+        let visible = crate::ticks::visible_ticks(self.kind, range[0], range[1], offset, config);
+        let labels = self.labels(&visible, config);
+        (visible.into_iter().map(|(t, _)| t).collect(), labels)
+    }
+
+    /// Tilt the tick labels counter-clockwise by `degrees` (clamped to 0 to
+    /// 90), so long labels such as timestamps fit closer together.
+    pub fn with_label_rotation(mut self, degrees: f32) -> Self {
+        self.label_rotation = degrees.clamp(0.0, 90.0);
+        self
+    }
+
+    /// Skip overlapping tick labels, or draw them all.
+    pub fn with_thin_labels(mut self, thin: bool) -> Self {
+        self.thin_labels = thin;
         self
     }
 
@@ -785,171 +3917,1225 @@ impl AxisConfig {
         self.title_size = size;
         self
     }
+
+    /// Label values relative to a reference, e.g. [`AxisReference::LastSample`]
+    /// for a live "seconds ago" time axis.
+    pub fn with_reference(mut self, reference: AxisReference) -> Self {
+        self.reference = reference;
+        self
+    }
+}
+
+/// Which side of the plot area an extra Y axis is drawn on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AxisSide {
+    Left,
+    #[default]
+    Right,
+}
+
+/// An additional Y axis with its own scale, for series in other units.
+///
+/// Series opt in with [`PlotSeries::with_y_axis`]. Extra axes are stacked
+/// outward from the plot area on their side, in the order they are listed in
+/// [`PlotterOptions::extra_y_axes`]. Zooming or panning Y moves every axis
+/// together, so series keep their relative placement.
+#[derive(Clone, Debug)]
+pub struct ExtraYAxis {
+    pub config: AxisConfig,
+    /// Name to look the axis up by, see [`PlotterOptions::y_axis_index`].
+    pub name: Option<String>,
+    pub side: AxisSide,
+    /// Fixed data range mapped onto the plot height. `None` = auto-fit to the
+    /// series assigned to this axis.
+    pub range: Option<(f64, f64)>,
+    /// Horizontal space reserved for the axis labels and title, in pixels.
+    pub width: f32,
+}
+
+impl ExtraYAxis {
+    pub fn new(config: AxisConfig) -> Self {
+        Self {
+            config,
+            name: None,
+            side: AxisSide::default(),
+            range: None,
+            width: 60.0,
+        }
+    }
+
+    /// Name the axis, e.g. after its channel, so series can be assigned by
+    /// name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Draw the axis line, tick labels and title in `color`, to match the
+    /// series plotted against it.
+    pub fn with_color(mut self, color: iced::Color) -> Self {
+        self.config.color = color;
+        self.config.label_color = color;
+        self.config.title_color = color;
+        self
+    }
+
+    /// Draw the axis on the given side of the plot area.
+    pub fn with_side(mut self, side: AxisSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Use a fixed range instead of auto-fitting.
+    pub fn with_range(mut self, range: (f64, f64)) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Reserve `width` pixels for the axis.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+/// Linear map from an extra Y axis' values onto the primary Y axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct YAxisMap {
+    /// Range on the extra axis.
+    pub from: [f64; 2],
+    /// Primary-axis range it occupies.
+    pub to: [f64; 2],
+}
+
+impl YAxisMap {
+    /// Primary-axis value of `y`.
+    pub fn apply(self, y: f64) -> f64 {
+        self.to[0] + (y - self.from[0]) * self.scale()
+    }
+
+    /// Extra-axis value of the primary-axis value `y`.
+    pub fn invert(self, y: f64) -> f64 {
+        self.from[0] + (y - self.to[0]) / self.scale()
+    }
+
+    /// Primary-axis units per extra-axis unit.
+    pub fn scale(self) -> f64 {
+        (self.to[1] - self.to[0]) / (self.from[1] - self.from[0])
+    }
+}
+
+/// How the X and Y scales relate, see [`PlotterOptions::aspect`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AspectMode {
+    /// Each axis shows its own range, stretched to the plot area.
+    #[default]
+    Free,
+    /// One data unit spans as many pixels on X as on Y, for trajectories,
+    /// maps or Lissajous figures. The view is widened along one axis to fit
+    /// the plot area, and zooming either axis zooms both.
+    ///
+    /// Ignored with [`PlotterOptions::extra_y_axes`].
+    EqualData,
 }
 
 #[derive(Clone, Debug)]
 pub struct PlotterOptions {
     /// Legend configuration. `None` = no legend, `Some(config)` = show legend.
+    #[cfg(feature = "legend")]
     pub legend: Option<LegendConfig>,
     /// Tooltip configuration. `None` = no tooltip, `Some(config)` = show tooltip on hover.
+    #[cfg(feature = "tooltip")]
     pub tooltip: Option<TooltipConfig>,
+    /// Cursor readout configuration. `None` = no readout, `Some(config)` = show the
+    /// cursor's data coordinates in a corner of the plot area.
+    pub cursor_readout: Option<CursorReadoutConfig>,
+    /// Crosshair configuration. `None` = no crosshair, `Some(config)` = draw
+    /// lines through the cursor with its coordinates on the axes.
+    pub crosshair: Option<CrosshairConfig>,
+    /// Colorbar configuration. `None` = no colorbar, `Some(config)` = show a
+    /// color scale for a gradient or colormap series right of the plot.
+    pub colorbar: Option<ColorbarConfig>,
+    /// Context menu configuration. `None` = no menu, `Some(config)` = open a
+    /// menu of actions on right-click.
+    pub context_menu: Option<ContextMenuConfig>,
     pub padding: f32,
     pub grid: GridStyle,
+    /// How the X and Y scales relate. Default [`AspectMode::Free`].
+    pub aspect: AspectMode,
     pub x_axis: AxisConfig,
     pub y_axis: AxisConfig,
+    /// Additional Y axes. Axis index `k` in [`PlotSeries::y_axis`] refers to
+    /// `extra_y_axes[k - 1]`; index 0 is `y_axis`.
+    pub extra_y_axes: Vec<ExtraYAxis>,
     /// Fractional padding added around the data extent when auto-fitting.
     /// 0.05 means 5% of the data span is added on each side.
     /// Set to 0.0 to disable.
     pub autofit_padding: f32,
+    /// Caption line below the X axis (e.g. data source). The bottom padding
+    /// grows to fit.
+    pub caption: Option<TextBlock>,
+    /// Footnote line below the caption. The bottom padding grows to fit.
+    pub footnote: Option<TextBlock>,
+    /// Size thresholds for hiding the legend, axis titles and reducing tick
+    /// counts on small widgets. `None` = always show everything.
+    pub responsive: Option<ResponsiveConfig>,
+    /// Extra margin in pixels around the plot area in which markers are still
+    /// drawn, so points on the boundary aren't cut in half. Lines are always
+    /// clipped to the plot area. `None` = the marker radius.
+    pub marker_bleed: Option<f32>,
+    /// Marker count above which markers are uploaded in the 12-byte
+    /// [`CompactPoint`](crate::gpu_types::CompactPoint) format instead of
+    /// 32 bytes per point, trading some position precision for bandwidth.
+    /// `None` = never.
+    pub compact_markers_threshold: Option<usize>,
+    /// Blending of markers; [`MarkerBlend::Additive`] for density plots.
+    pub marker_blend: MarkerBlend,
+    /// Render markers and lines with 4x multisampling, for smoother diagonal
+    /// lines and small markers than the shaders' own anti-aliasing gives.
+    /// Costs an extra offscreen pass; ignored when the display's texture
+    /// format doesn't support it.
+    pub multisample: bool,
+    /// Renderer for markers, lines and grid. Default [`RenderBackend::Auto`].
+    pub backend: RenderBackend,
+    /// Performance mode for very large series. `None` = markers for every
+    /// point, and vertex arrays grown as they fill. `Some(config)` = skip
+    /// the markers of dense series, and size the vertex arrays up front.
+    pub performance: Option<PerformanceConfig>,
+    /// Arrangement of bar series sharing X positions.
+    pub bar_layout: BarLayout,
+    /// How stacked area series are summed, see [`PlotSeries::stack_id`].
+    pub stack_mode: StackMode,
     /// Optional background color for the plot area (inside the padding).
     /// `Some(color)` draws a filled rectangle behind the grid and data.
     /// Defaults to a subtle darkening overlay for visual separation.
     pub background_color: Option<iced::Color>,
+    /// Reference lines and bands, drawn in order over the grid.
+    #[cfg(feature = "annotations")]
+    pub annotations: Vec<Annotation>,
+    /// Readout of the distances between [`Plotter::with_cursor_line`]
+    /// lines. `None` = no readout.
+    pub cursor_delta: Option<CursorDeltaConfig>,
+}
+
+impl PlotterOptions {
+    /// Axis index of the extra Y axis named `name`, to pass to
+    /// [`PlotSeries::with_y_axis`].
+    pub fn y_axis_index(&self, name: &str) -> Option<usize> {
+        self.extra_y_axes
+            .iter()
+            .position(|axis| axis.name.as_deref() == Some(name))
+            .map(|i| i + 1)
+    }
+
+    /// Resolve the responsive layout for the given widget size.
+    pub fn responsive_layout(&self, size: iced::Size) -> ResponsiveLayout {
+        self.responsive
+            .as_ref()
+            .map(|r| r.layout(size))
+            .unwrap_or_default()
+    }
+
+    /// Tick configurations (x, y) in effect at the given widget size.
+    pub fn tick_configs(
+        &self,
+        size: iced::Size,
+    ) -> (&crate::ticks::TickConfig, &crate::ticks::TickConfig) {
+        let layout = self.responsive_layout(size);
+        match self.responsive {
+            Some(ref r) => (
+                if layout.compact_x_ticks && self.x_axis.ticks.is_auto() {
+                    &r.compact_ticks
+                } else {
+                    &self.x_axis.ticks
+                },
+                if layout.compact_y_ticks && self.y_axis.ticks.is_auto() {
+                    &r.compact_ticks
+                } else {
+                    &self.y_axis.ticks
+                },
+            ),
+            None => (&self.x_axis.ticks, &self.y_axis.ticks),
+        }
+    }
 }
 
 impl Default for PlotterOptions {
     fn default() -> Self {
         Self {
+            #[cfg(feature = "legend")]
             legend: None,
+            #[cfg(feature = "tooltip")]
             tooltip: None,
+            cursor_readout: None,
+            crosshair: None,
+            colorbar: None,
+            context_menu: None,
             padding: 50.0,
             grid: GridStyle::default(),
+            aspect: AspectMode::default(),
             x_axis: AxisConfig::default(),
             y_axis: AxisConfig::default(),
+            extra_y_axes: Vec::new(),
             autofit_padding: 0.05,
+            caption: None,
+            footnote: None,
+            responsive: None,
+            marker_bleed: None,
+            compact_markers_threshold: Some(1_000_000),
+            marker_blend: MarkerBlend::Over,
+            multisample: false,
+            backend: RenderBackend::default(),
+            performance: None,
+            bar_layout: BarLayout::default(),
+            stack_mode: StackMode::default(),
             background_color: Some(iced::Color::from_rgba(0.0, 0.0, 0.0, 0.15)),
+            #[cfg(feature = "annotations")]
+            annotations: Vec::new(),
+            cursor_delta: None,
+        }
+    }
+}
+
+/// A plot misconfiguration reported by [`Plotter::validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlotterError {
+    /// A `values` array in the series' [`ColorMode`] doesn't have one entry per point.
+    ColorValuesLength {
+        series: String,
+        expected: usize,
+        found: usize,
+    },
+    /// A `values` array was combined with adaptive sampling, whose point
+    /// count changes from frame to frame.
+    ColorValuesWithAdaptiveSampling { series: String },
+    /// A generator was asked for zero samples.
+    EmptyGenerator { series: String },
+    /// A generator range is NaN or infinite.
+    InvalidGeneratorRange { series: String, range: (f64, f64) },
+    /// A [`ViewState`] range is reversed, empty or not finite.
+    InvalidViewRange {
+        axis: &'static str,
+        range: (f64, f64),
+    },
+    /// An [`InteractionConfig`] bound is reversed, empty or not finite.
+    InvalidBounds {
+        axis: &'static str,
+        range: (f64, f64),
+    },
+    /// A polygon loop has fewer than three vertices.
+    DegeneratePolygon { polygon: usize, loop_index: usize },
+    /// A series refers to a Y axis that isn't configured.
+    InvalidYAxis { series: String, axis: usize },
+    /// An [`ExtraYAxis`] range is reversed, empty or not finite.
+    InvalidYAxisRange { axis: usize, range: (f64, f64) },
+    /// A bar series is generated, or its width or baseline is invalid.
+    InvalidBars { series: String },
+    /// A stacked series is generated or drawn as bars.
+    InvalidStack { series: String },
+    /// A per-point [`SeriesStyle`] array doesn't have one entry per point.
+    PointArrayLength {
+        series: String,
+        array: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// A per-point [`SeriesStyle`] array was combined with adaptive
+    /// sampling, whose point count changes from frame to frame.
+    PointArrayWithAdaptiveSampling { series: String, array: &'static str },
+    /// A [`PlotImage`]'s pixel buffer doesn't hold width x height RGBA pixels.
+    ImageSize {
+        image: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A [`PlotImage`] range is reversed, empty or not finite.
+    InvalidImageRange {
+        image: usize,
+        axis: &'static str,
+        range: (f64, f64),
+    },
+}
+
+impl std::fmt::Display for PlotterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlotterError::ColorValuesLength {
+                series,
+                expected,
+                found,
+            } => write!(
+                f,
+                "series \"{series}\" has {expected} points but its color values array has {found} entries"
+            ),
+            PlotterError::ColorValuesWithAdaptiveSampling { series } => write!(
+                f,
+                "series \"{series}\" uses a color values array with adaptive sampling; \
+                 use a Y-based gradient or disable adaptive sampling"
+            ),
+            PlotterError::EmptyGenerator { series } => write!(
+                f,
+                "series \"{series}\" has a generator with zero points; request at least one sample"
+            ),
+            PlotterError::InvalidGeneratorRange { series, range } => write!(
+                f,
+                "series \"{series}\" has a non-finite generator range {range:?}"
+            ),
+            PlotterError::InvalidViewRange { axis, range } => write!(
+                f,
+                "view {axis} range {range:?} must be finite with min < max; \
+                 use None to auto-fit"
+            ),
+            PlotterError::InvalidBounds { axis, range } => write!(
+                f,
+                "interaction {axis} bounds {range:?} must be finite with min < max"
+            ),
+            PlotterError::DegeneratePolygon {
+                polygon,
+                loop_index,
+            } => write!(
+                f,
+                "polygon {polygon} loop {loop_index} needs at least three vertices"
+            ),
+            PlotterError::InvalidYAxis { series, axis } => write!(
+                f,
+                "series \"{series}\" uses Y axis {axis}, which has no entry in extra_y_axes"
+            ),
+            PlotterError::InvalidYAxisRange { axis, range } => write!(
+                f,
+                "Y axis {axis} range {range:?} must be finite with min < max; \
+                 use None to auto-fit"
+            ),
+            PlotterError::InvalidBars { series } => write!(
+                f,
+                "series \"{series}\" draws bars, which need stored points, \
+                 a finite positive width and a finite baseline"
+            ),
+            PlotterError::InvalidStack { series } => write!(
+                f,
+                "series \"{series}\" is stacked, which needs stored points drawn as a line; \
+                 use BarLayout::Stacked for bars"
+            ),
+            PlotterError::PointArrayLength {
+                series,
+                array,
+                expected,
+                found,
+            } => write!(
+                f,
+                "series \"{series}\" has {expected} points but its {array} array has {found} entries"
+            ),
+            PlotterError::PointArrayWithAdaptiveSampling { series, array } => write!(
+                f,
+                "series \"{series}\" uses a {array} array with adaptive sampling; \
+                 disable adaptive sampling"
+            ),
+            PlotterError::ImageSize {
+                image,
+                expected,
+                found,
+            } => write!(
+                f,
+                "image {image} needs {expected} bytes of RGBA pixels but has {found}"
+            ),
+            PlotterError::InvalidImageRange { image, axis, range } => write!(
+                f,
+                "image {image} {axis} range {range:?} must be finite with min < max"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlotterError {}
+
+/// Whether `range` is finite with `min < max`.
+fn is_valid_range((lo, hi): (f64, f64)) -> bool {
+    lo.is_finite() && hi.is_finite() && lo < hi
+}
+
+/// Maps the (x, y) data ranges of a rectangle selection to a message.
+type SelectCallback<'a, Message> = Box<dyn Fn((f64, f64), (f64, f64)) -> Message + 'a>;
+
+/// Maps a context menu action and where the menu was opened to a message.
+type ContextCallback<'a, Message> = Box<dyn Fn(ContextAction, (f64, f64)) -> Message + 'a>;
+
+/// Maps a new view and whether it is final to a message.
+type ViewChangeCallback<'a, Message> = Box<dyn Fn(ViewState, ViewChangeKind) -> Message + 'a>;
+
+/// Maps a dragged point's series and point index and new position to a message.
+type PointMovedCallback<'a, Message> = Box<dyn Fn(usize, usize, f64, f64) -> Message + 'a>;
+
+pub struct Plotter<'a, Message> {
+    // data related
+    pub series: Vec<PlotSeries<'a>>,
+    pub polygons: Vec<PolygonSeries>,
+    pub bands: Vec<BandSeries>,
+    #[cfg(feature = "annotations")]
+    pub events: Vec<EventSeries>,
+    pub box_plots: Vec<BoxPlotSeries>,
+    pub quivers: Vec<QuiverSeries>,
+    pub images: Vec<PlotImage>,
+    pub spectrogram: Option<&'a Spectrogram>,
+    /// Draggable lines at X or Y values, drawn over the data.
+    pub cursor_lines: Vec<CursorLine>,
+
+    // configuration related
+    pub options: PlotterOptions,
+
+    // interaction
+    pub view_state: &'a ViewState,
+    pub interaction: InteractionConfig,
+    pub active_tool: ActiveTool,
+    /// Screen rectangles (widget-local coordinates) where pan, zoom and
+    /// selection events are ignored, in addition to the legend bounds.
+    pub exclusion_zones: Vec<iced::Rectangle>,
+
+    // layout
+    pub width: Length,
+    pub height: Length,
+    /// Width / height ratio applied when only one dimension is fixed.
+    pub aspect_ratio: Option<f32>,
+
+    // callback: maps a new ViewState to the user's Message type
+    pub(crate) on_view_change: Option<ViewChangeCallback<'a, Message>>,
+
+    // callback: maps a selected rectangle's (x, y) data ranges to the user's Message type
+    pub(crate) on_select: Option<SelectCallback<'a, Message>>,
+
+    // shared legend state (visibility toggles + layout for hit testing)
+    pub(crate) legend_state: LegendState,
+
+    // callback: maps a legend toggle (series index, now visible) to the user's Message type
+    #[cfg(feature = "legend")]
+    pub(crate) on_legend_toggle: Option<Box<dyn Fn(usize, bool) -> Message + 'a>>,
+
+    // shared tooltip state (hovered point info for tooltip rendering)
+    #[cfg(feature = "tooltip")]
+    pub(crate) tooltip_state: TooltipState,
+
+    // callbacks: map the hovered / clicked point to the user's Message type
+    #[cfg(feature = "tooltip")]
+    pub(crate) on_point_hover: Option<Box<dyn Fn(HoveredPoint) -> Message + 'a>>,
+    #[cfg(feature = "tooltip")]
+    pub(crate) on_point_click: Option<Box<dyn Fn(HoveredPoint) -> Message + 'a>>,
+
+    // shared crosshair position (data coordinates under the cursor)
+    pub(crate) cursor_state: CursorState,
+
+    // X under the cursor, shared with other plots
+    pub(crate) shared_cursor: Option<SharedCursor>,
+
+    // linked brushing selection shared with other plots
+    pub(crate) selection_link: Option<(SelectionLink, LinkMode)>,
+
+    // back/forward history of views shared with the app
+    pub(crate) view_history: Option<ViewHistory>,
+
+    // regions of interest shared with the app, edited with ActiveTool::Roi
+    pub(crate) roi_state: Option<RoiState>,
+
+    // callback: maps a finished ROI edit to the user's Message type
+    pub(crate) on_roi_change: Option<Box<dyn Fn(RoiChange) -> Message + 'a>>,
+
+    // two-point measurement shared with the app, made with ActiveTool::Measure
+    pub(crate) measure_state: Option<MeasureState>,
+
+    // callback: maps a completed measurement to the user's Message type
+    pub(crate) on_measure: Option<Box<dyn Fn(Measurement) -> Message + 'a>>,
+
+    // callback: maps the data position of an ActiveTool::Annotate click to
+    // the user's Message type
+    #[cfg(feature = "annotations")]
+    pub(crate) on_annotate: Option<Box<dyn Fn(f64, f64) -> Message + 'a>>,
+
+    // callback: maps a dragged cursor line (index, new value) to the user's
+    // Message type
+    pub(crate) on_cursor_line_move: Option<Box<dyn Fn(usize, f64) -> Message + 'a>>,
+
+    // callback: maps a dragged point (series, index, new x, new y) to the
+    // user's Message type
+    pub(crate) on_point_moved: Option<PointMovedCallback<'a, Message>>,
+
+    // shared context menu state (open menu, for the overlay to draw)
+    pub(crate) context_menu_state: ContextMenuState,
+
+    // callback: maps a context menu action and where the menu was opened to
+    // the user's Message type
+    pub(crate) on_context_action: Option<ContextCallback<'a, Message>>,
+
+    // time source for double-click detection and animations
+    pub(crate) clock: Option<Box<dyn Fn() -> std::time::Instant + 'a>>,
+
+    // primitive of the last draw, for the canvas to render without shaders;
+    // `None` when no canvas draws it
+    pub(crate) software_frame: Option<SoftwareFrame>,
+}
+
+// ================================================================================
+// Public Methods
+// ================================================================================
+
+impl<'a, Message> Plotter<'a, Message> {
+    pub fn new(series: Vec<PlotSeries<'a>>, view_state: &'a ViewState) -> Self {
+        Self {
+            series,
+            polygons: Vec::new(),
+            bands: Vec::new(),
+            #[cfg(feature = "annotations")]
+            events: Vec::new(),
+            box_plots: Vec::new(),
+            quivers: Vec::new(),
+            images: Vec::new(),
+            spectrogram: None,
+            cursor_lines: Vec::new(),
+            options: PlotterOptions::default(),
+            view_state,
+            interaction: InteractionConfig::default(),
+            active_tool: ActiveTool::default(),
+            exclusion_zones: Vec::new(),
+            width: Length::Fill,
+            height: Length::Fill,
+            aspect_ratio: None,
+            on_view_change: None,
+            on_select: None,
+            legend_state: LegendState::default(),
+            #[cfg(feature = "legend")]
+            on_legend_toggle: None,
+            #[cfg(feature = "tooltip")]
+            tooltip_state: TooltipState::default(),
+            #[cfg(feature = "tooltip")]
+            on_point_hover: None,
+            #[cfg(feature = "tooltip")]
+            on_point_click: None,
+            cursor_state: CursorState::default(),
+            shared_cursor: None,
+            selection_link: None,
+            view_history: None,
+            roi_state: None,
+            on_roi_change: None,
+            on_point_moved: None,
+            on_cursor_line_move: None,
+            measure_state: None,
+            on_measure: None,
+            #[cfg(feature = "annotations")]
+            on_annotate: None,
+            context_menu_state: ContextMenuState::default(),
+            on_context_action: None,
+            clock: None,
+            software_frame: None,
+        }
+    }
+
+    /// Check the series, polygons, view state and interaction bounds for
+    /// misconfigurations that would otherwise panic or render incorrectly.
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), PlotterError> {
+        for s in &self.series {
+            s.validate()?;
+            if s.y_axis > self.options.extra_y_axes.len() {
+                return Err(PlotterError::InvalidYAxis {
+                    series: s.label.clone(),
+                    axis: s.y_axis,
+                });
+            }
+        }
+        for (i, axis) in self.options.extra_y_axes.iter().enumerate() {
+            if let Some(range) = axis.range
+                && !is_valid_range(range)
+            {
+                return Err(PlotterError::InvalidYAxisRange { axis: i + 1, range });
+            }
+        }
+        for (polygon, p) in self.polygons.iter().enumerate() {
+            for (loop_index, l) in p.loops.iter().enumerate() {
+                let closed =
+                    l.len() >= 2 && l[0].x == l[l.len() - 1].x && l[0].y == l[l.len() - 1].y;
+                if l.len() - usize::from(closed) < 3 {
+                    return Err(PlotterError::DegeneratePolygon {
+                        polygon,
+                        loop_index,
+                    });
+                }
+            }
+        }
+        for (axis, range) in [
+            ("X", self.view_state.x_range),
+            ("Y", self.view_state.y_range),
+            ("secondary Y", self.view_state.secondary_y_range),
+        ] {
+            if let Some(range) = range
+                && !is_valid_range(range)
+            {
+                return Err(PlotterError::InvalidViewRange { axis, range });
+            }
+        }
+        for (axis, range) in [
+            ("X", self.interaction.x_bounds),
+            ("Y", self.interaction.y_bounds),
+        ] {
+            if let Some(range) = range
+                && !is_valid_range(range)
+            {
+                return Err(PlotterError::InvalidBounds { axis, range });
+            }
+        }
+        for (index, image) in self.images.iter().enumerate() {
+            if image.pixels.len() != image.expected_len() {
+                return Err(PlotterError::ImageSize {
+                    image: index,
+                    expected: image.expected_len(),
+                    found: image.pixels.len(),
+                });
+            }
+            for (axis, range) in [("X", image.x_range), ("Y", image.y_range)] {
+                if !is_valid_range(range) {
+                    return Err(PlotterError::InvalidImageRange {
+                        image: index,
+                        axis,
+                        range,
+                    });
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Set the shared legend state.
+    ///
+    /// This allows you to persist legend toggle state and hit-test layout across frames.
+    /// Create with `LegendState::default()` and store in your app state.
+    pub fn with_legend_state(mut self, state: LegendState) -> Self {
+        self.legend_state = state;
+        self
+    }
+
+    /// Set the shared tooltip state.
+    ///
+    /// This allows you to persist tooltip hover state across frames.
+    /// Create with `TooltipState::default()` and store in your app state.
+    #[cfg(feature = "tooltip")]
+    pub fn with_tooltip_state(mut self, state: TooltipState) -> Self {
+        self.tooltip_state = state;
+        self
+    }
+
+    /// Set the shared crosshair state, see [`CursorState`].
+    pub fn with_cursor_state(mut self, state: CursorState) -> Self {
+        self.cursor_state = state;
+        self
+    }
+
+    /// Show where the cursor is on other plots with the same X axis, see
+    /// [`SharedCursor`].
+    pub fn with_shared_cursor(mut self, cursor: SharedCursor) -> Self {
+        self.shared_cursor = Some(cursor);
+        self
+    }
+
+    /// Link this plot's record selection with other plots.
+    ///
+    /// Dragging with [`ActiveTool::Select`] brushes records; `mode` controls
+    /// how this plot shows the current selection.
+    pub fn with_selection_link(mut self, link: SelectionLink, mode: LinkMode) -> Self {
+        self.selection_link = Some((link, mode));
+        self
+    }
+
+    /// Record zooms, pans and fits in `history` and navigate it with
+    /// Backspace and the mouse back/forward buttons, see [`ViewHistory`].
+    pub fn with_view_history(mut self, history: ViewHistory) -> Self {
+        self.view_history = Some(history);
+        self
+    }
+
+    /// Draw the regions of interest in `state` and edit them with
+    /// [`ActiveTool::Roi`], see [`RoiState`].
+    pub fn with_roi_state(mut self, state: RoiState) -> Self {
+        self.roi_state = Some(state);
+        self
+    }
+
+    /// Measure between points with [`ActiveTool::Measure`], see
+    /// [`MeasureState`].
+    pub fn with_measure_state(mut self, state: MeasureState) -> Self {
+        self.measure_state = Some(state);
+        self
+    }
+
+    /// Set the shared context menu state, see [`ContextMenuState`].
+    pub fn with_context_menu_state(mut self, state: ContextMenuState) -> Self {
+        self.context_menu_state = state;
+        self
+    }
+
+    /// Set the shared hidden series state (convenience method).
+    ///
+    /// This allows you to persist legend toggle state across frames.
+    /// Create with `Rc::new(RefCell::new(HashSet::new()))` and store in your app state.
+    pub fn with_hidden_series(mut self, hidden: HiddenSeries) -> Self {
+        self.legend_state.hidden_series = hidden;
+        self
+    }
+
+    /// Replace the time source used for double-click detection and the
+    /// elastic spring-back animation. Defaults to [`std::time::Instant::now`].
+    ///
+    /// Mostly useful in tests, see [`crate::testing::ManualClock`].
+    pub fn with_clock(mut self, clock: impl Fn() -> std::time::Instant + 'a) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Add a filled polygon series, drawn beneath the line and marker series.
+    pub fn with_polygon(mut self, polygon: PolygonSeries) -> Self {
+        self.polygons.push(polygon);
+        self
+    }
+
+    /// Add a filled band series, drawn beneath the line and marker series.
+    pub fn with_band(mut self, band: BandSeries) -> Self {
+        self.bands.push(band);
+        self
+    }
+
+    /// Add labeled X intervals, drawn as bands behind the data.
+    #[cfg(feature = "annotations")]
+    pub fn with_events(mut self, events: EventSeries) -> Self {
+        self.events.push(events);
+        self
+    }
+
+    /// Add box-and-whisker plots, drawn beneath the line and marker series.
+    pub fn with_box_plot(mut self, box_plot: BoxPlotSeries) -> Self {
+        self.box_plots.push(box_plot);
+        self
+    }
+
+    /// Add a vector field, drawn as arrows beneath the line and marker series.
+    pub fn with_quiver(mut self, quiver: QuiverSeries) -> Self {
+        self.quivers.push(quiver);
+        self
+    }
+
+    /// Add a raster image, drawn above the grid and beneath everything else.
+    /// Images are drawn in the order they were added.
+    pub fn with_image(mut self, image: PlotImage) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Draw a scrolling spectrogram beneath the series, with time in
+    /// seconds on X and frequency in Hz on the primary Y axis. Only the
+    /// columns pushed since the last frame are uploaded.
+    pub fn with_spectrogram(mut self, spectrogram: &'a Spectrogram) -> Self {
+        self.spectrogram = Some(spectrogram);
+        self
+    }
+
+    /// Add a cursor line, which can be dragged with any tool once
+    /// [`Plotter::on_cursor_line_move`] is set.
+    pub fn with_cursor_line(mut self, line: CursorLine) -> Self {
+        self.cursor_lines.push(line);
+        self
+    }
+
+    pub fn with_options(mut self, options: PlotterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_interaction(mut self, interaction: InteractionConfig) -> Self {
+        self.interaction = interaction;
+        self
     }
-}
 
-pub struct Plotter<'a, Message> {
-    // data related
-    pub series: Vec<PlotSeries<'a>>,
+    /// Set the tool activated by the primary mouse drag.
+    pub fn with_active_tool(mut self, tool: ActiveTool) -> Self {
+        self.active_tool = tool;
+        self
+    }
 
-    // configuration related
-    pub options: PlotterOptions,
+    /// Register a screen rectangle (widget-local coordinates) where plot
+    /// interactions are ignored.
+    ///
+    /// Use this for custom overlays or embedded controls drawn on top of the
+    /// plot so their mouse events don't pan or zoom the view.
+    pub fn with_exclusion_zone(mut self, zone: iced::Rectangle) -> Self {
+        self.exclusion_zones.push(zone);
+        self
+    }
 
-    // interaction
-    pub view_state: &'a ViewState,
-    pub interaction: InteractionConfig,
+    /// Set the width of the plot widget. Defaults to [`Length::Fill`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
 
-    // callback: maps a new ViewState to the user's Message type
-    pub(crate) on_view_change: Option<Box<dyn Fn(ViewState) -> Message + 'a>>,
+    /// Set the height of the plot widget. Defaults to [`Length::Fill`].
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
 
-    // shared legend state (visibility toggles + layout for hit testing)
-    pub(crate) legend_state: LegendState,
+    /// Keep the widget at a fixed width / height ratio.
+    ///
+    /// Takes effect when exactly one of [`Self::width`] and [`Self::height`]
+    /// is [`Length::Fixed`]; the other dimension is derived from it.
+    pub fn aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
 
-    // shared tooltip state (hovered point info for tooltip rendering)
-    pub(crate) tooltip_state: TooltipState,
-}
+    /// Resolve the widget (width, height), applying the aspect ratio if set.
+    fn resolved_size(&self) -> (Length, Length) {
+        match (self.aspect_ratio, self.width, self.height) {
+            // Both fixed: nothing to derive
+            (_, Length::Fixed(_), Length::Fixed(_)) => (self.width, self.height),
+            (Some(ratio), Length::Fixed(w), _) if ratio > 0.0 => {
+                (Length::Fixed(w), Length::Fixed(w / ratio))
+            }
+            (Some(ratio), _, Length::Fixed(h)) if ratio > 0.0 => {
+                (Length::Fixed(h * ratio), Length::Fixed(h))
+            }
+            _ => (self.width, self.height),
+        }
+    }
 
-// ================================================================================
-// Public Methods
-// ================================================================================
+    /// Set a callback that maps view state changes to your app's Message type.
+    /// Without this, pan/zoom interactions will not be communicated back.
+    pub fn on_view_change(mut self, f: impl Fn(ViewState) -> Message + 'a) -> Self {
+        self.on_view_change = Some(Box::new(move |view, _| f(view)));
+        self
+    }
 
-impl<'a, Message> Plotter<'a, Message> {
-    pub fn new(series: Vec<PlotSeries<'a>>, view_state: &'a ViewState) -> Self {
-        Self {
-            series,
-            options: PlotterOptions::default(),
-            view_state,
-            interaction: InteractionConfig::default(),
-            on_view_change: None,
-            legend_state: LegendState::default(),
-            tooltip_state: TooltipState::default(),
-        }
+    /// Like [`Plotter::on_view_change`], also telling whether the change is
+    /// [`ViewChangeKind::Final`], e.g. to reload data only once a pan ends.
+    /// Every gesture ends with a final change.
+    pub fn on_view_change_with_kind(
+        mut self,
+        f: impl Fn(ViewState, ViewChangeKind) -> Message + 'a,
+    ) -> Self {
+        self.on_view_change = Some(Box::new(f));
+        self
     }
 
-    /// Set the shared legend state.
-    ///
-    /// This allows you to persist legend toggle state and hit-test layout across frames.
-    /// Create with `LegendState::default()` and store in your app state.
-    pub fn with_legend_state(mut self, state: LegendState) -> Self {
-        self.legend_state = state;
+    /// Set a callback for clicks on [`PlotterOptions::context_menu`] entries,
+    /// receiving the action and the data coordinates (on the primary Y axis)
+    /// where the menu was opened.
+    pub fn on_context_action(
+        mut self,
+        f: impl Fn(ContextAction, (f64, f64)) -> Message + 'a,
+    ) -> Self {
+        self.on_context_action = Some(Box::new(f));
         self
     }
 
-    /// Set the shared tooltip state.
+    /// Set a callback that receives the X and Y data ranges of a finished
+    /// rectangle selection, with [`SelectionMode::ReportOnly`].
     ///
-    /// This allows you to persist tooltip hover state across frames.
-    /// Create with `TooltipState::default()` and store in your app state.
-    pub fn with_tooltip_state(mut self, state: TooltipState) -> Self {
-        self.tooltip_state = state;
+    /// Y ranges are on the primary Y axis.
+    pub fn on_select(mut self, f: impl Fn((f64, f64), (f64, f64)) -> Message + 'a) -> Self {
+        self.on_select = Some(Box::new(f));
         self
     }
 
-    /// Set the shared hidden series state (convenience method).
+    /// Set a callback for finished edits of the [`RoiState`]: an ROI drawn,
+    /// moved, resized or deleted.
+    pub fn on_roi_change(mut self, f: impl Fn(RoiChange) -> Message + 'a) -> Self {
+        self.on_roi_change = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback for measurements completed with [`ActiveTool::Measure`].
+    pub fn on_measure(mut self, f: impl Fn(Measurement) -> Message + 'a) -> Self {
+        self.on_measure = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback for clicks with [`ActiveTool::Annotate`], receiving
+    /// the clicked X and Y in data coordinates of the primary Y axis, e.g.
+    /// to add an [`Annotation::text`] there.
+    #[cfg(feature = "annotations")]
+    pub fn on_annotate(mut self, f: impl Fn(f64, f64) -> Message + 'a) -> Self {
+        self.on_annotate = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback for dragged [`CursorLine`]s, receiving the line's index
+    /// in [`Plotter::cursor_lines`] and its new value. Published on every
+    /// cursor move of the drag; write the value back to move the line.
+    pub fn on_cursor_line_move(mut self, f: impl Fn(usize, f64) -> Message + 'a) -> Self {
+        self.on_cursor_line_move = Some(Box::new(f));
+        self
+    }
+
+    /// Set a callback for points dragged with [`ActiveTool::EditPoints`],
+    /// receiving the series index, point index and the point's new X and Y
+    /// (on the series' own Y axis).
     ///
-    /// This allows you to persist legend toggle state across frames.
-    /// Create with `Rc::new(RefCell::new(HashSet::new()))` and store in your app state.
-    pub fn with_hidden_series(mut self, hidden: HiddenSeries) -> Self {
-        self.legend_state.hidden_series = hidden;
+    /// Published on every cursor move of the drag; the plot only shows the
+    /// point moving once the app writes it back into the series.
+    pub fn on_point_moved(mut self, f: impl Fn(usize, usize, f64, f64) -> Message + 'a) -> Self {
+        self.on_point_moved = Some(Box::new(f));
         self
     }
 
-    pub fn with_options(mut self, options: PlotterOptions) -> Self {
-        self.options = options;
+    /// Set a callback for clicks on a legend toggle, receiving the series
+    /// index and whether the series is now visible.
+    ///
+    /// The toggle itself is applied to the [`LegendState`] either way; this
+    /// is for apps that keep visibility elsewhere too, or refit the view.
+    #[cfg(feature = "legend")]
+    pub fn on_legend_toggle(mut self, f: impl Fn(usize, bool) -> Message + 'a) -> Self {
+        self.on_legend_toggle = Some(Box::new(f));
         self
     }
 
-    pub fn with_interaction(mut self, interaction: InteractionConfig) -> Self {
-        self.interaction = interaction;
+    /// Set a callback for when the cursor moves onto a different point.
+    ///
+    /// Uses the same hit testing as tooltips, which need not be enabled;
+    /// the snapping distance is [`TooltipConfig::max_distance`] or
+    /// [`DEFAULT_HOVER_DISTANCE`].
+    #[cfg(feature = "tooltip")]
+    pub fn on_point_hover(mut self, f: impl Fn(HoveredPoint) -> Message + 'a) -> Self {
+        self.on_point_hover = Some(Box::new(f));
         self
     }
 
-    /// Set a callback that maps view state changes to your app's Message type.
-    /// Without this, pan/zoom interactions will not be communicated back.
-    pub fn on_view_change(mut self, f: impl Fn(ViewState) -> Message + 'a) -> Self {
-        self.on_view_change = Some(Box::new(f));
+    /// Set a callback for left clicks on a point, e.g. to open a detail
+    /// panel. A press and release without dragging counts as a click, so
+    /// panning still works when it starts on a point.
+    #[cfg(feature = "tooltip")]
+    pub fn on_point_click(mut self, f: impl Fn(HoveredPoint) -> Message + 'a) -> Self {
+        self.on_point_click = Some(Box::new(f));
         self
     }
 
-    /// Compute the bounding box of all visible (non-hidden) data points.
-    pub fn compute_data_ranges(&self) -> ([f32; 2], [f32; 2]) {
-        let mut x_min = f32::INFINITY;
-        let mut x_max = f32::NEG_INFINITY;
-        let mut y_min = f32::INFINITY;
-        let mut y_max = f32::NEG_INFINITY;
+    /// Indices of the series left out of the plot: hidden with
+    /// [`PlotSeries::hidden`] or toggled off in the legend.
+    pub(crate) fn hidden_series(&self) -> HashSet<usize> {
+        let mut hidden = self.legend_state.hidden_series.borrow().clone();
+        hidden.extend(
+            self.series
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.hidden)
+                .map(|(i, _)| i),
+        );
+        hidden
+    }
 
-        let hidden = self.legend_state.hidden_series.borrow();
+    /// Compute the bounding box of all visible (non-hidden) data points.
+    ///
+    /// The Y range only covers series on the primary Y axis; see
+    /// [`Plotter::y_axis_maps`] for the others.
+    pub fn compute_data_ranges(&self) -> ([f64; 2], [f64; 2]) {
+        let mut extent = Extent::default();
+
+        let hidden = self.hidden_series();
+        let bars = bar_rects(&self.series, &hidden, self.options.bar_layout);
+        let stacks = stack_ranges(&self.series, &hidden, self.options.stack_mode);
         for (idx, s) in self.series.iter().enumerate() {
             if hidden.contains(&idx) {
                 continue;
             }
-            let iter: Box<dyn Iterator<Item = (f32, f32)> + '_> = match &s.points {
-                PlotPoints::Owned(pts) => Box::new(pts.iter().map(|p| (p.x, p.y))),
-                PlotPoints::Borrowed(pts) => Box::new(pts.iter().map(|p| (p.x, p.y))),
-                PlotPoints::Generator(generator) => {
-                    let (x0, x1) = generator.x_range;
-                    let span = x1 - x0;
-                    let n = generator.points;
-                    Box::new((0..n).map(move |i| {
-                        let t = i as f32 / (n - 1).max(1) as f32;
-                        let x = x0 + t * span;
-                        let y = (generator.function)(x);
-                        (x, y)
-                    }))
+            // Bars span their width and reach down to the baseline, stacked
+            // areas from the stack's bottom to their top
+            let corners: Box<dyn Iterator<Item = (f64, f64)>> = match s.kind {
+                SeriesKind::Line if s.stack_id.is_some() => {
+                    let points = s.points.as_slice().unwrap_or_default();
+                    Box::new(
+                        stacks[idx]
+                            .iter()
+                            .zip(points)
+                            .flat_map(|(range, p)| range.map(|y| (p.x, y)))
+                            .filter(|(x, y)| x.is_finite() && y.is_finite()),
+                    )
                 }
+                SeriesKind::Line => Self::extent_points(s),
+                SeriesKind::Bars { .. } => Box::new(
+                    bars[idx]
+                        .iter()
+                        .flat_map(|b| [(b.x[0], b.y[0]), (b.x[1], b.y[1])])
+                        .filter(|(x, y)| x.is_finite() && y.is_finite()),
+                ),
             };
-            for (x, y) in iter {
-                x_min = x_min.min(x);
-                x_max = x_max.max(x);
-                y_min = y_min.min(y);
-                y_max = y_max.max(y);
+            for (x, y) in corners {
+                if s.y_axis == 0 {
+                    extent.include(x, y);
+                } else {
+                    extent.include_x(x);
+                }
+            }
+        }
+        for p in self
+            .polygons
+            .iter()
+            .flat_map(|poly| poly.loops.iter().flatten())
+        {
+            extent.include(p.x, p.y);
+        }
+        for s in self.bands.iter().flat_map(|band| &band.samples) {
+            if !(s.x.is_finite() && s.low.is_finite() && s.high.is_finite()) {
+                continue;
+            }
+            extent.include(s.x, s.low);
+            extent.include(s.x, s.high);
+        }
+        for b in &self.box_plots {
+            for (i, summary) in b.boxes.iter().enumerate() {
+                let values = [summary.min, summary.max]
+                    .into_iter()
+                    .chain(summary.outliers.iter().copied());
+                for y in values.filter(|y| y.is_finite()) {
+                    extent.include(i as f64 - 0.5 * b.box_width, y);
+                    extent.include(i as f64 + 0.5 * b.box_width, y);
+                }
+            }
+        }
+        // Pixel-scaled arrows only reach a view-dependent distance from
+        // their base, so only data-scaled tips count
+        for q in &self.quivers {
+            for a in q.drawn() {
+                let tip = match q.scale {
+                    QuiverScale::Data(k) => (a.x + k * a.u, a.y + k * a.v),
+                    QuiverScale::Pixels(_) => (a.x, a.y),
+                };
+                for (x, y) in [(a.x, a.y), tip] {
+                    extent.include(x, y);
+                }
+            }
+        }
+        for image in &self.images {
+            for (x, y) in [
+                (image.x_range.0, image.y_range.0),
+                (image.x_range.1, image.y_range.1),
+            ] {
+                if x.is_finite() && y.is_finite() {
+                    extent.include(x, y);
+                }
             }
         }
+        if let Some(spectrogram) = self.spectrogram
+            && let Some([t0, t1]) = spectrogram.time_range()
+        {
+            let [f0, f1] = spectrogram.frequency_range();
+            extent.include(t0, f0);
+            extent.include(t1, f1);
+        }
+
+        extent.ranges()
+    }
+
+    /// Points of a series used for auto-fitting. Generators are sampled
+    /// across their whole range. Non-finite points, drawn as gaps, are
+    /// skipped.
+    fn extent_points<'s>(s: &'s PlotSeries<'_>) -> Box<dyn Iterator<Item = (f64, f64)> + 's> {
+        let points: Box<dyn Iterator<Item = (f64, f64)> + 's> = match &s.points {
+            PlotPoints::Owned(pts) => Box::new(pts.iter().map(|p| (p.x, p.y))),
+            PlotPoints::Borrowed(pts) => Box::new(pts.iter().map(|p| (p.x, p.y))),
+            PlotPoints::Generator(generator) => {
+                let (x0, x1) = generator.x_range;
+                let span = x1 - x0;
+                let n = generator.points;
+                Box::new((0..n).map(move |i| {
+                    let t = i as f64 / (n - 1).max(1) as f64;
+                    let x = x0 + t * span;
+                    let y = (generator.function)(x);
+                    (x, y)
+                }))
+            }
+            PlotPoints::Parametric(generator) => Box::new(generator.sample()),
+        };
+        Box::new(points.filter(|(x, y)| x.is_finite() && y.is_finite()))
+    }
 
-        if x_min > x_max {
-            x_min = 0.0;
-            x_max = 1.0;
-            y_min = 0.0;
-            y_max = 1.0;
-        } else if (y_max - y_min).abs() < f32::EPSILON {
-            y_min -= 0.5;
-            y_max += 0.5;
+    /// The view auto-fit shows, with explicit ranges: the visible data plus
+    /// [`PlotterOptions::autofit_padding`], as [`Plotter::resolve_view_ranges`]
+    /// computes it. Use it for a "Fit" button that records a concrete view.
+    pub fn fit_all(&self) -> ViewState {
+        let (data_x, data_y) = self.compute_data_ranges();
+        let [x0, x1] = self.autofit_range(data_x);
+        let [y0, y1] = self.autofit_range(data_y);
+        ViewState::with_ranges((x0, x1), (y0, y1))
+    }
+
+    /// Data range with [`PlotterOptions::autofit_padding`] added on each side.
+    fn autofit_range(&self, data: [f64; 2]) -> [f64; 2] {
+        crate::core::pad_range(data, self.options.autofit_padding)
+    }
+
+    /// How each [`ExtraYAxis`] maps onto the primary Y axis, in the order of
+    /// [`PlotterOptions::extra_y_axes`], given the visible primary range.
+    ///
+    /// Each axis' range (fixed, or auto-fitted to its visible series) occupies
+    /// the primary axis' auto-fit range, so a fresh plot shows every series
+    /// filling the height and zooming Y scales all axes together. An explicit
+    /// [`ViewState::secondary_y_range`] instead spans `view_y` exactly.
+    pub fn extra_y_axis_maps(&self, view_y: [f64; 2]) -> Vec<YAxisMap> {
+        if self.options.extra_y_axes.is_empty() {
+            return Vec::new();
         }
+        let to = self.autofit_range(self.compute_data_ranges().1);
+        let hidden = self.hidden_series();
+        let stacks = stack_ranges(&self.series, &hidden, self.options.stack_mode);
+        self.options
+            .extra_y_axes
+            .iter()
+            .enumerate()
+            .map(|(i, axis)| {
+                if i == 0
+                    && let Some((lo, hi)) = self.view_state.secondary_y_range
+                {
+                    return YAxisMap {
+                        from: [lo, hi],
+                        to: view_y,
+                    };
+                }
+                let from = match axis.range {
+                    Some((lo, hi)) => [lo, hi],
+                    None => {
+                        let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+                        for (idx, s) in self
+                            .series
+                            .iter()
+                            .enumerate()
+                            .filter(|(idx, s)| s.y_axis == i + 1 && !hidden.contains(idx))
+                        {
+                            if s.stack_id.is_some() {
+                                for y in stacks[idx].iter().flatten().filter(|y| y.is_finite()) {
+                                    lo = lo.min(*y);
+                                    hi = hi.max(*y);
+                                }
+                                continue;
+                            }
+                            for (_, y) in Self::extent_points(s) {
+                                lo = lo.min(y);
+                                hi = hi.max(y);
+                            }
+                        }
+                        if lo > hi {
+                            (lo, hi) = (0.0, 1.0);
+                        } else if (hi - lo).abs() < f64::EPSILON {
+                            lo -= 0.5;
+                            hi += 0.5;
+                        }
+                        self.autofit_range([lo, hi])
+                    }
+                };
+                YAxisMap { from, to }
+            })
+            .collect()
+    }
 
-        ([x_min, x_max], [y_min, y_max])
+    /// Per-series map onto the primary Y axis, `None` for series on it.
+    pub fn y_axis_maps(&self, view_y: [f64; 2]) -> Vec<Option<YAxisMap>> {
+        let maps = self.extra_y_axis_maps(view_y);
+        self.series
+            .iter()
+            .map(|s| s.y_axis.checked_sub(1).and_then(|k| maps.get(k).copied()))
+            .collect()
     }
 
     /// Resolve the actual view ranges by combining ViewState with data bounds.
@@ -960,94 +5146,376 @@ impl<'a, Message> Plotter<'a, Message> {
     /// that over-scroll is still visible.
     ///
     /// Returns (view_x_range, view_y_range, data_x_range, data_y_range).
-    pub fn resolve_view_ranges(&self, enforce_bounds: bool) -> ([f32; 2], [f32; 2], [f32; 2], [f32; 2]) {
+    pub fn resolve_view_ranges(
+        &self,
+        enforce_bounds: bool,
+    ) -> ([f64; 2], [f64; 2], [f64; 2], [f64; 2]) {
         let (data_x, data_y) = self.compute_data_ranges();
-        let af = self.options.autofit_padding;
         let interaction = &self.interaction;
-
-        let view_x = match self.view_state.x_range {
-            Some((lo, hi)) => {
-                if enforce_bounds && interaction.elastic && interaction.pan_x {
-                    let bounds = interaction.x_bounds.or(Some((data_x[0], data_x[1])));
-                    let (clo, chi) =
-                        crate::shader::clamp_range_to_bounds((lo, hi), bounds, interaction.boundary_padding);
-                    [clo, chi]
-                } else {
-                    [lo, hi]
-                }
-            }
-            None => {
-                let span = data_x[1] - data_x[0];
-                let margin = span * af;
-                [data_x[0] - margin, data_x[1] + margin]
-            }
+        let clamp = |pan: bool, bounds: Option<(f64, f64)>, data: [f64; 2]| {
+            (enforce_bounds && interaction.elastic && pan).then(|| {
+                (
+                    bounds.unwrap_or((data[0], data[1])),
+                    interaction.boundary_padding,
+                )
+            })
         };
-        let view_y = match self.view_state.y_range {
-            Some((lo, hi)) => {
-                if enforce_bounds && interaction.elastic && interaction.pan_y {
-                    let bounds = interaction.y_bounds.or(Some((data_y[0], data_y[1])));
-                    let (clo, chi) =
-                        crate::shader::clamp_range_to_bounds((lo, hi), bounds, interaction.boundary_padding);
-                    [clo, chi]
-                } else {
-                    [lo, hi]
-                }
-            }
-            None => {
-                let span = data_y[1] - data_y[0];
-                let margin = span * af;
-                [data_y[0] - margin, data_y[1] + margin]
-            }
+        let fit_x = AxisFit {
+            padding: self.options.autofit_padding,
+            follow: interaction.follow_x,
+            clamp: clamp(interaction.pan_x, interaction.x_bounds, data_x),
         };
+        let fit_y = AxisFit {
+            padding: self.options.autofit_padding,
+            follow: false,
+            clamp: clamp(interaction.pan_y, interaction.y_bounds, data_y),
+        };
+        let view_x = fit_x.resolve(self.view_state.x_range, data_x);
+        let view_y = fit_y.resolve(self.view_state.y_range, data_y);
 
         (view_x, view_y, data_x, data_y)
     }
 
-    /// Build the plotter widget. Consumes `self` (the Plotter is a builder).
-    pub fn draw(self) -> Element<'a, Message>
-    where
-        Message: Clone + 'a,
-    {
-        let (view_x, view_y, _, _) = self.resolve_view_ranges(true);
+    /// Whether [`AspectMode::EqualData`] applies.
+    pub(crate) fn aspect_locked(&self) -> bool {
+        self.options.aspect == AspectMode::EqualData && self.options.extra_y_axes.is_empty()
+    }
+
+    /// View ranges from [`Plotter::resolve_view_ranges`] as shown in a plot
+    /// area of `plot_size`, widened along one axis when the aspect is locked.
+    pub(crate) fn aspect_ranges(
+        &self,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        plot_size: iced::Size,
+    ) -> ([f64; 2], [f64; 2]) {
+        if !self.aspect_locked() {
+            return (view_x, view_y);
+        }
+        crate::core::equalize_aspect(view_x, view_y, [plot_size.width, plot_size.height])
+    }
 
-        let x_ticks = crate::ticks::compute_ticks(view_x[0], view_x[1], &self.options.x_axis.ticks);
-        let y_ticks = crate::ticks::compute_ticks(view_y[0], view_y[1], &self.options.y_axis.ticks);
+    /// Data values the X and Y tick labels are measured from, see
+    /// [`AxisConfig::reference`].
+    pub(crate) fn tick_offsets(&self, data_x: [f64; 2], data_y: [f64; 2]) -> [f64; 2] {
+        [
+            self.options.x_axis.reference.resolve(data_x),
+            self.options.y_axis.reference.resolve(data_y),
+        ]
+    }
+
+    /// Build the legend entries (one per series).
+    ///
+    /// Windowed [`LegendValue`]s are aggregated over the visible X range.
+    #[cfg(feature = "legend")]
+    fn legend_entries(&self, config: &LegendConfig) -> Vec<LegendEntry> {
+        let windowed = self
+            .series
+            .iter()
+            .any(|s| s.legend_value != LegendValue::Last);
+        let view_x = if windowed {
+            self.resolve_view_ranges(true).0
+        } else {
+            [f64::NEG_INFINITY, f64::INFINITY]
+        };
+        self.series
+            .iter()
+            .map(|s| LegendEntry {
+                label: s.label.clone(),
+                color: s.style.color.representative_color().scale_alpha(s.opacity),
+                hidden: s.hidden,
+                value: s
+                    .points
+                    .as_slice()
+                    .and_then(|pts| s.legend_value.aggregate(pts, view_x))
+                    .map(|v| {
+                        let text = match &s.value_format {
+                            Some(format) => format(v),
+                            None => (config.value_format)(v),
+                        };
+                        match &s.unit {
+                            Some(unit) => format!("{text} {unit}"),
+                            None => text,
+                        }
+                    }),
+            })
+            .collect()
+    }
 
-        let x_labels: Vec<String> = x_ticks
+    /// The event bands followed by [`PlotterOptions::annotations`], in
+    /// drawing order.
+    #[cfg(feature = "annotations")]
+    pub(crate) fn annotations(&self) -> Vec<Annotation> {
+        self.events
             .iter()
-            .map(|v| (self.options.x_axis.format)(*v))
+            .flat_map(EventSeries::annotations)
+            .chain(self.options.annotations.iter().cloned())
+            .collect()
+    }
+
+    /// Padding between the widget edges and the plot area for a widget of
+    /// the given size.
+    ///
+    /// Starts from [`PlotterOptions::padding`] on all sides and grows the
+    /// left/right sides by the width of each extra Y axis, the right/bottom
+    /// side when the legend is placed outside the plot area, and the bottom
+    /// side for the caption and footnote.
+    #[cfg_attr(not(feature = "legend"), allow(unused_mut, unused_variables))]
+    pub fn plot_padding(&self, size: iced::Size) -> iced::Padding {
+        let mut padding = self.padding_without_legend();
+        #[cfg(feature = "legend")]
+        if self.options.responsive_layout(size).show_legend
+            && let Some(ref config) = self.options.legend
+        {
+            pad_for_outside_legend(
+                &mut padding,
+                self.options.padding,
+                config,
+                &self.legend_entries(config),
+                size,
+            );
+        }
+        padding
+    }
+
+    fn padding_without_legend(&self) -> iced::Padding {
+        let base = self.options.padding;
+        let mut padding = iced::Padding::new(base);
+
+        // Extra Y axes stack outward from the plot area
+        for axis in &self.options.extra_y_axes {
+            match axis.side {
+                AxisSide::Left => padding.left += axis.width,
+                AxisSide::Right => padding.right += axis.width,
+            }
+        }
+
+        // The colorbar sits outside the extra right axes
+        if let Some(colorbar) = &self.options.colorbar {
+            padding.right += colorbar.width;
+        }
+
+        for block in [&self.options.caption, &self.options.footnote]
+            .into_iter()
+            .flatten()
+        {
+            padding.bottom += block.size + TEXT_BLOCK_GAP;
+        }
+
+        padding
+    }
+
+    /// The colorbar's strip colors, range and ticks, or `None` when it is
+    /// off or there is no value-colored series to decode.
+    fn colorbar_overlay(&self, view_y: [f64; 2]) -> Option<ColorbarOverlay> {
+        let config = self.options.colorbar.as_ref()?;
+        let hidden = self.hidden_series();
+        let index = match config.series {
+            Some(index) => index,
+            None => (0..self.series.len()).find(|i| {
+                !hidden.contains(i) && colorbar_color(&self.series[*i].style.color, 0.0).is_some()
+            })?,
+        };
+        let mode = &self.series.get(index)?.style.color;
+        colorbar_color(mode, 0.0)?;
+
+        let scale = mode.scale().unwrap_or_default();
+        let (low, high) = match (config.range, scale.range) {
+            (Some(range), _) => range,
+            (None, Some((lo, hi))) => (f64::from(lo), f64::from(hi)),
+            (None, None) => self.color_value_range(index, view_y)?,
+        };
+        if !is_valid_range((low, high)) {
+            return None;
+        }
+        // Steps are evenly spaced in value, so log and power scales show
+        // their curve
+        let colors = (0..COLORBAR_STEPS)
+            .filter_map(|i| {
+                let value = low + (high - low) * i as f64 / (COLORBAR_STEPS - 1) as f64;
+                let t = scale.normalization.apply(value, (low, high)).unwrap_or(0.5);
+                colorbar_color(mode, t)
+            })
             .collect();
-        let y_labels: Vec<String> = y_ticks
+        let visible = crate::ticks::visible_ticks(
+            crate::ticks::AxisKind::Linear,
+            low,
+            high,
+            0.0,
+            &config.ticks,
+        );
+        Some(ColorbarOverlay {
+            colors,
+            range: [low, high],
+            labels: tick_labels(&visible, &config.ticks, &*config.format),
+            ticks: visible.into_iter().map(|(t, _)| t).collect(),
+            config: config.clone(),
+        })
+    }
+
+    /// Range of the values a value-colored series is normalized over, as
+    /// the renderer computes it. Y-based gradients span the Y range of all
+    /// visible series on the primary axis; large decimated or generated
+    /// series are measured over all their stored points rather than the
+    /// samples drawn.
+    pub(crate) fn color_value_range(&self, index: usize, view_y: [f64; 2]) -> Option<(f64, f64)> {
+        let fold = |values: &mut dyn Iterator<Item = f64>| {
+            values
+                .filter(|v| v.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                    (lo.min(v), hi.max(v))
+                })
+        };
+        let series = &self.series[index];
+        let range = match &series.style.color {
+            ColorMode::ValueGradient {
+                values: Some(v), ..
+            } => fold(&mut v.iter().map(|&v| f64::from(v))),
+            #[cfg(feature = "colormap")]
+            ColorMode::Colormap {
+                values: Some(v), ..
+            } => fold(&mut v.iter().map(|&v| f64::from(v))),
+            ColorMode::ValueFn { function, .. } => {
+                let points = series.points.as_slice()?;
+                fold(
+                    &mut points
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| f64::from(function(i, p.x, p.y))),
+                )
+            }
+            _ => {
+                let hidden = self.hidden_series();
+                let maps = self.y_axis_maps(view_y);
+                fold(
+                    &mut self
+                        .series
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| !hidden.contains(i))
+                        .flat_map(|(i, s)| Self::extent_points(s).map(move |(_, y)| (i, y)))
+                        .map(|(i, y)| maps[i].map_or(y, |m| m.apply(y))),
+                )
+            }
+        };
+        Some(range)
+    }
+
+    /// The canvas drawing the axes, labels, legend, tooltips and readouts
+    /// over the shader.
+    fn overlay(&self) -> AxisOverlay<'a> {
+        let (view_x, view_y, data_x, data_y) = self.resolve_view_ranges(true);
+        let [x_offset, y_offset] = self.tick_offsets(data_x, data_y);
+
+        let (x_axis, y_axis) = (&self.options.x_axis, &self.options.y_axis);
+        let (x_ticks, x_labels) = x_axis.ticks_in(view_x, x_offset, &x_axis.ticks);
+        let (y_ticks, y_labels) = y_axis.ticks_in(view_y, y_offset, &y_axis.ticks);
+
+        // Reduced tick sets for small widgets; the overlay picks by size
+        let compact_ticks = self.options.responsive.as_ref().map(|r| {
+            let (x_ticks, x_labels) = x_axis.ticks_in(view_x, x_offset, &r.compact_ticks);
+            let (y_ticks, y_labels) = y_axis.ticks_in(view_y, y_offset, &r.compact_ticks);
+            CompactTicks {
+                x_ticks,
+                y_ticks,
+                x_labels,
+                y_labels,
+            }
+        });
+
+        // Axes to tick again once the overlay knows its size
+        let aspect_axes = self.aspect_locked().then(|| AspectAxes {
+            x: x_axis.clone(),
+            y: y_axis.clone(),
+            offsets: [x_offset, y_offset],
+        });
+
+        // Extra Y axes, labeled over the part of their range that is visible
+        let extra_y_axes = self
+            .options
+            .extra_y_axes
             .iter()
-            .map(|v| (self.options.y_axis.format)(*v))
+            .zip(self.extra_y_axis_maps(view_y))
+            .map(|(axis, map)| {
+                let range = view_y.map(|y| map.invert(y));
+                let offset = axis.config.reference.resolve(map.from);
+                let visible = crate::ticks::visible_ticks(
+                    axis.config.kind,
+                    range[0],
+                    range[1],
+                    offset,
+                    &axis.config.ticks,
+                );
+                ExtraAxisOverlay {
+                    side: axis.side,
+                    width: axis.width,
+                    range,
+                    labels: axis.config.labels(&visible, &axis.config.ticks),
+                    ticks: visible.into_iter().map(|(t, _)| t).collect(),
+                    config: axis.config.clone(),
+                }
+            })
             .collect();
 
         // Build legend entries if legend is enabled
-        let legend_entries: Vec<LegendEntry> = if self.options.legend.is_some() {
-            self.series
-                .iter()
-                .map(|s| LegendEntry {
-                    label: s.label.clone(),
-                    color: s.style.color.representative_color(),
-                    latest_value: s.points.last_y(),
-                })
-                .collect()
-        } else {
-            Vec::new()
+        #[cfg(feature = "legend")]
+        let legend_entries: Vec<LegendEntry> = match &self.options.legend {
+            Some(config) => self.legend_entries(config),
+            None => Vec::new(),
+        };
+        let padding_without_legend = self.padding_without_legend();
+
+        // Series sampled by the cursor readout. Owned data is trimmed to the
+        // visible window (plus one neighbor per side) before copying.
+        let readout_series = match &self.options.cursor_readout {
+            Some(config) if config.series_values => {
+                let hidden = self.hidden_series();
+                self.series
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| !hidden.contains(idx))
+                    .filter_map(|(index, s)| {
+                        let points = match &s.points {
+                            PlotPoints::Borrowed(pts) => Cow::Borrowed(*pts),
+                            PlotPoints::Owned(pts) => {
+                                let lo = pts.partition_point(|p| p.x < view_x[0]);
+                                let hi = pts.partition_point(|p| p.x <= view_x[1]);
+                                let window = lo.saturating_sub(1)..(hi + 1).min(pts.len());
+                                Cow::Owned(pts[window].to_vec())
+                            }
+                            PlotPoints::Generator(_) | PlotPoints::Parametric(_) => return None,
+                        };
+                        Some(ReadoutSeries {
+                            index,
+                            label: s.label.clone(),
+                            color: s.style.color.representative_color(),
+                            interpolation: s.style.interpolation,
+                            points,
+                        })
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
         };
 
-        let overlay = AxisOverlay {
+        AxisOverlay {
             x_ticks,
             y_ticks,
             x_labels,
             y_labels,
             x_range: view_x,
             y_range: view_y,
-            padding: self.options.padding,
+            compact_ticks,
+            aspect_axes,
+            padding_without_legend,
+            base_padding: self.options.padding,
+            responsive: self.options.responsive.clone(),
             x_label_color: self.options.x_axis.label_color,
             y_label_color: self.options.y_axis.label_color,
             x_label_size: self.options.x_axis.label_size,
             y_label_size: self.options.y_axis.label_size,
+            x_label_rotation: self.options.x_axis.label_rotation,
+            x_thin_labels: self.options.x_axis.thin_labels,
             show_x: self.options.x_axis.show,
             show_y: self.options.y_axis.show,
             // Axis titles
@@ -1057,24 +5525,255 @@ impl<'a, Message> Plotter<'a, Message> {
             y_title: self.options.y_axis.title.clone(),
             y_title_color: self.options.y_axis.title_color,
             y_title_size: self.options.y_axis.title_size,
+            extra_y_axes,
+            colorbar: self.colorbar_overlay(view_y),
             // Legend
+            #[cfg(feature = "legend")]
             legend_config: self.options.legend.clone(),
+            #[cfg(feature = "legend")]
             legend_entries,
+            #[cfg(feature = "legend")]
             hidden_series: self.legend_state.hidden_series.clone(),
+            #[cfg(feature = "legend")]
             legend_layout: self.legend_state.layout.clone(),
+            #[cfg(feature = "legend")]
+            legend_offset: *self.legend_state.offset.borrow(),
             // Tooltip
+            #[cfg(feature = "tooltip")]
             tooltip_config: self.options.tooltip.clone(),
+            #[cfg(feature = "tooltip")]
             tooltip_state: self.tooltip_state.clone(),
+            // Context menu
+            context_menu: self.options.context_menu.clone(),
+            context_menu_state: self.context_menu_state.clone(),
+            // Cursor readout
+            cursor_readout: self.options.cursor_readout.clone(),
+            readout_series,
+            // Crosshair
+            crosshair: self.options.crosshair.clone(),
+            cursor_state: self.cursor_state.clone(),
+            shared_cursor: self.shared_cursor.clone(),
+            // Caption / footnote
+            caption: self.options.caption.clone(),
+            footnote: self.options.footnote.clone(),
+            #[cfg(feature = "annotations")]
+            annotations: self.annotations(),
+            rois: self.roi_state.clone(),
+            cursor_lines: self.cursor_lines.clone(),
+            cursor_delta: self.options.cursor_delta.clone(),
+            measure: self.measure_state.clone(),
+        }
+    }
+
+    /// Like [`Plotter::draw`], but checks the finished configuration first,
+    /// see [`Plotter::validate`].
+    pub fn try_draw(self) -> Result<Element<'a, Message>, PlotterError>
+    where
+        Message: Clone + 'a,
+    {
+        self.validate()?;
+        Ok(self.draw())
+    }
+
+    /// Build the plotter widget. Consumes `self` (the Plotter is a builder).
+    pub fn draw(self) -> Element<'a, Message>
+    where
+        Message: Clone + 'a,
+    {
+        // With `Auto`, a wgpu application only gets the canvas layer until
+        // its first frame tells which renderer iced picked
+        let software = match self.options.backend {
+            RenderBackend::Auto => software::software_renderer() != Some(false),
+            RenderBackend::Gpu => false,
+            RenderBackend::Software => true,
+        };
+        self.element(software)
+    }
+
+    /// The plotter widget, with a canvas layer drawing the plot if
+    /// `software`.
+    fn element(mut self, software: bool) -> Element<'a, Message>
+    where
+        Message: Clone + 'a,
+    {
+        let overlay = self.overlay();
+        let (width, height) = self.resolved_size();
+
+        // The shader still handles events, and hands its primitive to the
+        // canvas when that draws instead
+        let software = software.then(|| {
+            let frame = SoftwareFrame::default();
+            self.software_frame = Some(frame.clone());
+            SoftwareLayer {
+                frame,
+                forced: self.options.backend == RenderBackend::Software,
+            }
+        });
+        let mut layers = stack![shader(self).width(Length::Fill).height(Length::Fill)];
+        if let Some(software) = software {
+            layers = layers.push(canvas(software).width(Length::Fill).height(Length::Fill));
+        }
+        layers
+            .push(canvas(overlay).width(Length::Fill).height(Length::Fill))
+            .width(width)
+            .height(height)
+            .into()
+    }
+
+    /// Render the plot off-screen at `width` x `height` pixels and return
+    /// its RGBA8 pixels, row by row from the top.
+    ///
+    /// Uses the same pipelines as [`Plotter::draw`] plus the overlay (axes,
+    /// legend, captions) on the dark theme background, without a window, so
+    /// plots can be saved from scripts or tests. Hover and cursor state is
+    /// not drawn. Renders on the CPU with tiny-skia when
+    /// [`PlotterOptions::backend`] asks for software or no GPU adapter is
+    /// available; returns `None` when [`RenderBackend::Gpu`] finds none.
+    #[cfg(feature = "export")]
+    pub fn render_to_image(self, width: u32, height: u32) -> Option<Vec<u8>>
+    where
+        Message: Clone + 'a,
+    {
+        use iced::advanced::renderer::{Headless, Style};
+        use iced::advanced::widget::Tree;
+        use iced::advanced::{Layout, layout};
+
+        let backend = match self.options.backend {
+            RenderBackend::Auto => None,
+            RenderBackend::Gpu => Some("wgpu"),
+            RenderBackend::Software => Some("tiny-skia"),
         };
+        let mut renderer = iced::futures::executor::block_on(Renderer::new(
+            Font::DEFAULT,
+            iced::Pixels(16.0),
+            backend,
+        ))?;
+        let size = iced::Size::new(width as f32, height as f32);
+        let theme = Theme::Dark;
+
+        let software = self.options.backend == RenderBackend::Software
+            || matches!(renderer, Renderer::Secondary(_));
+        let mut element = self.element(software);
+        let mut tree = Tree::new(element.as_widget());
+        let node = element.as_widget_mut().layout(
+            &mut tree,
+            &renderer,
+            &layout::Limits::new(iced::Size::ZERO, size),
+        );
+        element.as_widget().draw(
+            &tree,
+            &mut renderer,
+            &theme,
+            &Style {
+                text_color: theme.palette().text,
+            },
+            Layout::new(&node),
+            iced::mouse::Cursor::Unavailable,
+            &iced::Rectangle::with_size(size),
+        );
+        Some(renderer.screenshot(
+            iced::Size::new(width, height),
+            1.0,
+            theme.palette().background,
+        ))
+    }
+
+    /// The visible series' points inside the current view as CSV, one
+    /// `series,x,y` row per point after a header row.
+    ///
+    /// Hidden series are left out, and so are points outside the view on
+    /// either axis (a stacked point is placed at its stack's top, a point on
+    /// an extra Y axis through that axis' mapping, but `y` is its own value).
+    /// Generators are sampled as for auto-fitting.
+    pub fn export_visible_csv(&self) -> String {
+        let mut csv = String::from("series,x,y\n");
+        for (label, points) in self.visible_points() {
+            let label = if label.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", label.replace('"', "\"\""))
+            } else {
+                label.to_string()
+            };
+            for (x, y) in points {
+                csv.push_str(&format!("{label},{x},{y}\n"));
+            }
+        }
+        csv
+    }
+
+    /// The same points as [`Plotter::export_visible_csv`] as JSON: an array
+    /// of `{"series": label, "points": [[x, y], ...]}` objects.
+    pub fn export_visible_json(&self) -> String {
+        let series: Vec<String> = self
+            .visible_points()
+            .into_iter()
+            .map(|(label, points)| {
+                let points: Vec<String> = points
+                    .into_iter()
+                    .map(|(x, y)| format!("[{x},{y}]"))
+                    .collect();
+                format!(
+                    "{{\"series\":{},\"points\":[{}]}}",
+                    json_string(label),
+                    points.join(",")
+                )
+            })
+            .collect();
+        format!("[{}]", series.join(","))
+    }
+
+    /// Label and points inside the view of every visible series, for export.
+    fn visible_points(&self) -> Vec<(&str, Vec<(f64, f64)>)> {
+        let (view_x, view_y, _, _) = self.resolve_view_ranges(true);
+        let hidden = self.hidden_series();
+        let maps = self.y_axis_maps(view_y);
+        let stacks = stack_ranges(&self.series, &hidden, self.options.stack_mode);
+        let inside = |[lo, hi]: [f64; 2], v: f64| lo.min(hi) <= v && v <= lo.max(hi);
+        self.series
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !hidden.contains(i))
+            .map(|(i, s)| {
+                // Also false for gaps
+                let visible = |x: f64, plotted_y: f64| {
+                    inside(view_x, x)
+                        && inside(view_y, maps[i].map_or(plotted_y, |m| m.apply(plotted_y)))
+                };
+                let points = match s.points.as_slice() {
+                    Some(points) => points
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, p)| {
+                            visible(p.x, stacks[i].get(j).map_or(p.y, |range| range[1]))
+                        })
+                        .map(|(_, p)| (p.x, p.y))
+                        .collect(),
+                    None => Self::extent_points(s)
+                        .filter(|&(x, y)| visible(x, y))
+                        .collect(),
+                };
+                (s.label.as_str(), points)
+            })
+            .collect()
+    }
+}
 
-        stack![
-            shader(self).width(Length::Fill).height(Length::Fill),
-            canvas(overlay).width(Length::Fill).height(Length::Fill),
-        ]
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .into()
+/// `text` as a JSON string literal.
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => json.push(c),
+        }
     }
+    json.push('"');
+    json
 }
 
 /// Computed rectangle for a legend toggle button (for hit testing).
@@ -1083,6 +5782,8 @@ pub struct LegendToggleRect {
     pub series_index: usize,
     /// Rectangle in widget-local coordinates.
     pub rect: iced::Rectangle,
+    /// The whole entry, toggle and label, in widget-local coordinates.
+    pub entry: iced::Rectangle,
 }
 
 /// Precomputed legend layout for hit testing from the shader.
@@ -1098,25 +5799,214 @@ pub struct LegendLayout {
 pub type LegendLayoutInfo = Rc<RefCell<LegendLayout>>;
 
 /// Data for a single legend entry.
+#[cfg(feature = "legend")]
 #[derive(Clone, Debug)]
 struct LegendEntry {
     label: String,
     color: iced::Color,
-    latest_value: Option<f32>,
+    /// Hidden with [`PlotSeries::hidden`], so drawn as toggled off.
+    hidden: bool,
+    /// Formatted value including the unit.
+    value: Option<String>,
+}
+
+/// Keep a legend of `legend` size dragged to `offset` inside a plot area of
+/// `plot` size (top-left if it doesn't fit).
+#[cfg(feature = "legend")]
+pub(crate) fn clamp_legend_offset(offset: Point, legend: iced::Size, plot: iced::Size) -> Point {
+    Point::new(
+        offset.x.min(plot.width - legend.width).max(0.0),
+        offset.y.min(plot.height - legend.height).max(0.0),
+    )
+}
+
+/// Horizontal gap between legend columns.
+#[cfg(feature = "legend")]
+const LEGEND_COLUMN_GAP: f32 = 12.0;
+
+/// Arrangement of legend entries. Legends outside the plot area wrap into
+/// several columns when a single one doesn't fit beside or below it.
+#[cfg(feature = "legend")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LegendGrid {
+    columns: usize,
+    rows: usize,
+    /// Width of one entry: toggle, gap and the widest text.
+    cell_width: f32,
+    row_height: f32,
+    /// Fill each column top to bottom before the next (right legends),
+    /// rather than each row left to right (bottom legends).
+    column_major: bool,
+}
+
+#[cfg(feature = "legend")]
+impl LegendGrid {
+    /// Lay out `entries` for `config.position`, given the plot area size
+    /// before any room is made for the legend.
+    fn new(config: &LegendConfig, entries: &[LegendEntry], plot: iced::Size) -> Self {
+        let gap = 6.0;
+        let row_height = config.toggle_size.max(config.text_size) + 4.0;
+        // Rough character width estimate: text_size * 0.6 per char (monospace)
+        let char_width = config.text_size * 0.6;
+        let max_text_width = entries
+            .iter()
+            .map(|entry| {
+                let label_width = entry.label.len() as f32 * char_width;
+                let value_width = match &entry.value {
+                    // +1 for space
+                    Some(value) if config.show_value => {
+                        (value.chars().count() as f32 + 1.0) * char_width
+                    }
+                    _ => 0.0,
+                };
+                label_width + value_width
+            })
+            .fold(0.0, f32::max);
+        let cell_width = config.toggle_size + gap + max_text_width;
+
+        let count = entries.len().max(1);
+        let columns = match config.position {
+            LegendPosition::Right => {
+                let fit = ((plot.height - config.padding * 2.0 + 4.0) / row_height).floor();
+                count.div_ceil((fit as usize).max(1))
+            }
+            LegendPosition::Bottom => {
+                let fit = ((plot.width - config.padding * 2.0 + LEGEND_COLUMN_GAP)
+                    / (cell_width + LEGEND_COLUMN_GAP))
+                    .floor();
+                (fit as usize).clamp(1, count)
+            }
+            _ => 1,
+        };
+        Self {
+            columns,
+            rows: count.div_ceil(columns),
+            cell_width,
+            row_height,
+            column_major: config.position == LegendPosition::Right,
+        }
+    }
+
+    /// Legend box size (width, height), zero for no entries.
+    fn size(&self, config: &LegendConfig, entries: usize) -> (f32, f32) {
+        if entries == 0 {
+            return (0.0, 0.0);
+        }
+        let columns = self.columns as f32;
+        (
+            config.padding * 2.0 + columns * self.cell_width + (columns - 1.0) * LEGEND_COLUMN_GAP,
+            config.padding * 2.0 + self.rows as f32 * self.row_height - 4.0,
+        )
+    }
+
+    /// Offset of entry `index` from the legend's inner top-left corner.
+    fn cell_offset(&self, index: usize) -> iced::Vector {
+        let (column, row) = if self.column_major {
+            (index / self.rows, index % self.rows)
+        } else {
+            (index % self.columns, index / self.columns)
+        };
+        iced::Vector::new(
+            column as f32 * (self.cell_width + LEGEND_COLUMN_GAP),
+            row as f32 * self.row_height,
+        )
+    }
+}
+
+/// Grow `padding` (everything but the legend) to make room for a legend
+/// placed outside the plot area of a widget of `size`. `base` is
+/// [`PlotterOptions::padding`].
+#[cfg(feature = "legend")]
+fn pad_for_outside_legend(
+    padding: &mut iced::Padding,
+    base: f32,
+    config: &LegendConfig,
+    entries: &[LegendEntry],
+    size: iced::Size,
+) {
+    if !config.position.is_outside() {
+        return;
+    }
+    let plot = iced::Size::new(size.width - padding.x(), size.height - padding.y());
+    let (width, height) = LegendGrid::new(config, entries, plot).size(config, entries.len());
+    match config.position {
+        LegendPosition::Right => {
+            let axes = padding.right - base;
+            padding.right = base.max(width + 2.0 * config.margin) + axes;
+        }
+        LegendPosition::Bottom => {
+            padding.bottom += height + config.margin;
+        }
+        _ => {}
+    }
+}
+
+/// Axes of a plot with [`AspectMode::EqualData`], for ticking a range the
+/// overlay widened to the plot area again.
+struct AspectAxes {
+    x: AxisConfig,
+    y: AxisConfig,
+    offsets: [f64; 2],
+}
+
+/// Tick positions and labels computed with [`ResponsiveConfig::compact_ticks`].
+struct CompactTicks {
+    x_ticks: Vec<f64>,
+    y_ticks: Vec<f64>,
+    x_labels: Vec<String>,
+    y_labels: Vec<String>,
+}
+
+/// Ticks, labels and style of an [`ExtraYAxis`], ready to draw.
+struct ExtraAxisOverlay {
+    side: AxisSide,
+    width: f32,
+    /// Visible range on this axis.
+    range: [f64; 2],
+    ticks: Vec<f64>,
+    labels: Vec<String>,
+    /// Style only; labels are already formatted.
+    config: AxisConfig,
+}
+
+/// Strip colors (bottom to top), ticks and style of the colorbar, ready to draw.
+struct ColorbarOverlay {
+    colors: Vec<iced::Color>,
+    /// Values at the bottom and top of the strip.
+    range: [f64; 2],
+    ticks: Vec<f64>,
+    labels: Vec<String>,
+    config: ColorbarConfig,
+}
+
+/// A stored series listed in the cursor readout.
+struct ReadoutSeries<'a> {
+    #[cfg_attr(not(feature = "legend"), allow(dead_code))]
+    index: usize,
+    label: String,
+    color: iced::Color,
+    interpolation: LineInterpolation,
+    points: Cow<'a, [PlotPoint]>,
 }
 
-struct AxisOverlay {
-    x_ticks: Vec<f32>,
-    y_ticks: Vec<f32>,
+struct AxisOverlay<'a> {
+    x_ticks: Vec<f64>,
+    y_ticks: Vec<f64>,
     x_labels: Vec<String>,
     y_labels: Vec<String>,
-    x_range: [f32; 2],
-    y_range: [f32; 2],
-    padding: f32,
+    x_range: [f64; 2],
+    y_range: [f64; 2],
+    compact_ticks: Option<CompactTicks>,
+    aspect_axes: Option<AspectAxes>,
+    padding_without_legend: iced::Padding,
+    base_padding: f32,
+    responsive: Option<ResponsiveConfig>,
     x_label_color: iced::Color,
     y_label_color: iced::Color,
     x_label_size: f32,
     y_label_size: f32,
+    x_label_rotation: f32,
+    x_thin_labels: bool,
     show_x: bool,
     show_y: bool,
     // Axis titles
@@ -1126,17 +6016,250 @@ struct AxisOverlay {
     y_title: Option<String>,
     y_title_color: iced::Color,
     y_title_size: f32,
+    extra_y_axes: Vec<ExtraAxisOverlay>,
+    colorbar: Option<ColorbarOverlay>,
     // Legend
+    #[cfg(feature = "legend")]
     legend_config: Option<LegendConfig>,
+    #[cfg(feature = "legend")]
     legend_entries: Vec<LegendEntry>,
+    #[cfg(feature = "legend")]
     hidden_series: HiddenSeries,
+    #[cfg(feature = "legend")]
     legend_layout: LegendLayoutInfo,
+    #[cfg(feature = "legend")]
+    legend_offset: Option<Point>,
     // Tooltip
+    #[cfg(feature = "tooltip")]
     tooltip_config: Option<TooltipConfig>,
+    #[cfg(feature = "tooltip")]
     tooltip_state: TooltipState,
+    // Context menu
+    context_menu: Option<ContextMenuConfig>,
+    context_menu_state: ContextMenuState,
+    // Cursor readout
+    cursor_readout: Option<CursorReadoutConfig>,
+    readout_series: Vec<ReadoutSeries<'a>>,
+    // Crosshair
+    crosshair: Option<CrosshairConfig>,
+    cursor_state: CursorState,
+    shared_cursor: Option<SharedCursor>,
+    // Caption / footnote
+    caption: Option<TextBlock>,
+    footnote: Option<TextBlock>,
+    // Annotation labels (the lines and bands are drawn by the shader)
+    #[cfg(feature = "annotations")]
+    annotations: Vec<Annotation>,
+    // Regions of interest
+    rois: Option<RoiState>,
+    // Cursor lines and the distances between them
+    cursor_lines: Vec<CursorLine>,
+    cursor_delta: Option<CursorDeltaConfig>,
+    // Two-point measurement
+    measure: Option<MeasureState>,
+}
+
+impl AxisOverlay<'_> {
+    /// Same as [`Plotter::plot_padding`], from the overlay's copy of the legend.
+    #[cfg_attr(not(feature = "legend"), allow(unused_mut, unused_variables))]
+    fn padding(&self, show_legend: bool, size: iced::Size) -> iced::Padding {
+        let mut padding = self.padding_without_legend;
+        #[cfg(feature = "legend")]
+        if show_legend && let Some(ref config) = self.legend_config {
+            pad_for_outside_legend(
+                &mut padding,
+                self.base_padding,
+                config,
+                &self.legend_entries,
+                size,
+            );
+        }
+        padding
+    }
+
+    /// Text and color of each line of the cursor readout at data `(x, y)`:
+    /// the position, then the value of each visible readout series.
+    fn readout_lines(
+        &self,
+        config: &CursorReadoutConfig,
+        x: f64,
+        y: Option<f64>,
+    ) -> Vec<(String, iced::Color)> {
+        let position = match y {
+            Some(y) => format!("x: {}  y: {}", (config.format_x)(x), (config.format_y)(y)),
+            None => format!("x: {}", (config.format_x)(x)),
+        };
+        let mut lines = vec![(position, config.text_color)];
+        for rs in &self.readout_series {
+            #[cfg(feature = "legend")]
+            if self.hidden_series.borrow().contains(&rs.index) {
+                continue;
+            }
+            if let Some(value) = interpolate_y(&rs.points, x, rs.interpolation) {
+                lines.push((
+                    format!("{}: {}", rs.label, (config.format_y)(value)),
+                    rs.color,
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// Draw a tooltip box next to a point, kept within the widget. A closable
+/// box gets a `×` button at its right end, whose bounds are returned.
+#[cfg(feature = "tooltip")]
+fn draw_tooltip_box(
+    frame: &mut canvas::Frame,
+    config: &TooltipConfig,
+    text: String,
+    point: Point,
+    size: iced::Size,
+    closable: bool,
+) -> Option<iced::Rectangle> {
+    // Estimate text dimensions
+    let char_width = config.text_size * 0.6;
+    let text_width = text.chars().count() as f32 * char_width;
+    let text_height = config.text_size;
+    let close_width = if closable {
+        text_height + config.padding
+    } else {
+        0.0
+    };
+
+    let box_width = text_width + config.padding * 2.0 + close_width;
+    let box_height = text_height + config.padding * 2.0;
+
+    // Position tooltip above and to the right of the point, with clamping
+    let offset_x = 12.0;
+    let offset_y = -12.0;
+
+    let mut tooltip_x = point.x + offset_x;
+    let mut tooltip_y = point.y + offset_y - box_height;
+
+    // Clamp to widget bounds
+    if tooltip_x + box_width > size.width {
+        tooltip_x = point.x - offset_x - box_width;
+    }
+    if tooltip_x < 0.0 {
+        tooltip_x = 0.0;
+    }
+    if tooltip_y < 0.0 {
+        tooltip_y = point.y + offset_x; // flip below
+    }
+    if tooltip_y + box_height > size.height {
+        tooltip_y = size.height - box_height;
+    }
+
+    // Draw background
+    frame.fill_rectangle(
+        Point::new(tooltip_x, tooltip_y),
+        iced::Size::new(box_width, box_height),
+        config.background_color,
+    );
+
+    // Draw border
+    frame.stroke_rectangle(
+        Point::new(tooltip_x, tooltip_y),
+        iced::Size::new(box_width, box_height),
+        canvas::Stroke::default()
+            .with_color(iced::Color::from_rgba(1.0, 1.0, 1.0, 0.3))
+            .with_width(1.0),
+    );
+
+    // Draw text
+    frame.fill_text(canvas::Text {
+        content: text,
+        size: iced::Pixels(config.text_size),
+        position: Point::new(
+            tooltip_x + config.padding,
+            tooltip_y + config.padding + text_height / 2.0,
+        ),
+        color: config.text_color,
+        align_x: iced::alignment::Horizontal::Left.into(),
+        align_y: iced::alignment::Vertical::Center,
+        font: Font::MONOSPACE,
+        ..canvas::Text::default()
+    });
+
+    if !closable {
+        return None;
+    }
+    let close = iced::Rectangle::new(
+        Point::new(tooltip_x + box_width - close_width, tooltip_y),
+        iced::Size::new(close_width, box_height),
+    );
+    frame.fill_text(canvas::Text {
+        content: "×".to_string(),
+        size: iced::Pixels(config.text_size),
+        position: close.center(),
+        color: config.text_color,
+        align_x: iced::alignment::Horizontal::Center.into(),
+        align_y: iced::alignment::Vertical::Center,
+        font: Font::MONOSPACE,
+        ..canvas::Text::default()
+    });
+    Some(close)
+}
+
+/// Size of the X tick labels as drawn, to keep them from overlapping.
+struct XLabelLayout {
+    /// Counter-clockwise tilt in radians.
+    angle: f32,
+    /// Width along the axis one label needs, including a gap.
+    footprint: f32,
+    /// Height the labels take below the axis.
+    height: f32,
+}
+
+impl XLabelLayout {
+    /// Gap kept between adjacent labels, in pixels.
+    const GAP: f32 = 6.0;
+
+    fn new(labels: &[String], size: f32, degrees: f32) -> Self {
+        // Monospace labels: text_size * 0.6 per char
+        let width = labels
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(0) as f32
+            * size
+            * 0.6;
+        let angle = degrees.clamp(0.0, 90.0).to_radians();
+        let (sin, cos) = angle.sin_cos();
+        let footprint = if angle == 0.0 {
+            width + Self::GAP
+        } else {
+            // Tilted labels run side by side, one line height apart across
+            // their direction, unless their horizontal spans don't overlap
+            ((size + Self::GAP) / sin).min(width * cos + size * sin + Self::GAP)
+        };
+        Self {
+            angle,
+            footprint,
+            height: width * sin + size * cos,
+        }
+    }
+
+    /// Draw every `stride`-th label so that labels at screen positions `xs`
+    /// don't overlap.
+    fn stride(&self, xs: impl Iterator<Item = f32>) -> usize {
+        let mut xs = xs.peekable();
+        let mut spacing = f32::INFINITY;
+        while let Some(x) = xs.next() {
+            if let Some(next) = xs.peek() {
+                spacing = spacing.min((next - x).abs());
+            }
+        }
+        if spacing.is_finite() && spacing > 0.0 {
+            (self.footprint / spacing).ceil().max(1.0) as usize
+        } else {
+            1
+        }
+    }
 }
 
-impl<Message> canvas::Program<Message> for AxisOverlay {
+impl<Message> canvas::Program<Message> for AxisOverlay<'_> {
     type State = ();
 
     fn draw(
@@ -1145,151 +6268,761 @@ impl<Message> canvas::Program<Message> for AxisOverlay {
         renderer: &Renderer,
         _theme: &Theme,
         bounds: iced::Rectangle,
-        _cursor: iced::mouse::Cursor,
+        cursor: iced::mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
         let mut frame = canvas::Frame::new(renderer, bounds.size());
 
-        let plot_width = bounds.width - 2.0 * self.padding;
-        let plot_height = bounds.height - 2.0 * self.padding;
-        let x_span = self.x_range[1] - self.x_range[0];
-        let y_span = self.y_range[1] - self.y_range[0];
+        let layout = self
+            .responsive
+            .as_ref()
+            .map(|r| r.layout(bounds.size()))
+            .unwrap_or_default();
+        let padding = self.padding(layout.show_legend, bounds.size());
+        let plot_width = bounds.width - padding.x();
+        let plot_height = bounds.height - padding.y();
+
+        // Widen a range to the plot area with a locked aspect, and tick it again
+        let (x_range, y_range) = match self.aspect_axes {
+            Some(_) => {
+                crate::core::equalize_aspect(self.x_range, self.y_range, [plot_width, plot_height])
+            }
+            None => (self.x_range, self.y_range),
+        };
+        let retick = |axis: &AxisConfig, range: [f64; 2], offset: f64, compact: bool| {
+            let config = match self.responsive {
+                Some(ref r) if compact => &r.compact_ticks,
+                _ => &axis.ticks,
+            };
+            axis.ticks_in(range, offset, config)
+        };
+        let x_reticked = self
+            .aspect_axes
+            .as_ref()
+            .filter(|_| x_range != self.x_range)
+            .map(|axes| retick(&axes.x, x_range, axes.offsets[0], layout.compact_x_ticks));
+        let y_reticked = self
+            .aspect_axes
+            .as_ref()
+            .filter(|_| y_range != self.y_range)
+            .map(|axes| retick(&axes.y, y_range, axes.offsets[1], layout.compact_y_ticks));
+        let (x_ticks, x_labels) = match (&x_reticked, &self.compact_ticks) {
+            (Some((ticks, labels)), _) => (ticks, labels),
+            (None, Some(c)) if layout.compact_x_ticks => (&c.x_ticks, &c.x_labels),
+            _ => (&self.x_ticks, &self.x_labels),
+        };
+        let (y_ticks, y_labels) = match (&y_reticked, &self.compact_ticks) {
+            (Some((ticks, labels)), _) => (ticks, labels),
+            (None, Some(c)) if layout.compact_y_ticks => (&c.y_ticks, &c.y_labels),
+            _ => (&self.y_ticks, &self.y_labels),
+        };
+        let x_span = x_range[1] - x_range[0];
+        let y_span = y_range[1] - y_range[0];
 
         // ---- X tick labels ----
-        if self.show_x && x_span.abs() > f32::EPSILON {
-            for (tick, label) in self.x_ticks.iter().zip(&self.x_labels) {
-                if *tick < self.x_range[0] || *tick > self.x_range[1] {
+        let x_layout = XLabelLayout::new(x_labels, self.x_label_size, self.x_label_rotation);
+        if self.show_x && x_span.abs() > f64::EPSILON {
+            let visible: Vec<(f32, &String)> = x_ticks
+                .iter()
+                .zip(x_labels)
+                .filter(|(tick, _)| **tick >= x_range[0] && **tick <= x_range[1])
+                .map(|(tick, label)| {
+                    let x_norm = ((tick - x_range[0]) / x_span) as f32;
+                    (padding.left + x_norm * plot_width, label)
+                })
+                .collect();
+            let stride = if self.x_thin_labels {
+                x_layout.stride(visible.iter().map(|(x, _)| *x))
+            } else {
+                1
+            };
+            let screen_y = padding.top + plot_height + 6.0;
+            for (screen_x, label) in visible.into_iter().step_by(stride) {
+                let text = canvas::Text {
+                    content: label.clone(),
+                    size: iced::Pixels(self.x_label_size),
+                    color: self.x_label_color,
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                };
+                if x_layout.angle == 0.0 {
+                    frame.fill_text(canvas::Text {
+                        position: Point::new(screen_x, screen_y),
+                        align_x: iced::alignment::Horizontal::Center.into(),
+                        align_y: iced::alignment::Vertical::Top,
+                        ..text
+                    });
+                } else {
+                    // The label ends at the tick and rises to the left of it
+                    frame.with_save(|frame| {
+                        frame.translate(iced::Vector::new(screen_x, screen_y));
+                        frame.rotate(-x_layout.angle);
+                        frame.fill_text(canvas::Text {
+                            position: Point::ORIGIN,
+                            align_x: iced::alignment::Horizontal::Right.into(),
+                            align_y: iced::alignment::Vertical::Center,
+                            ..text
+                        });
+                    });
+                }
+            }
+        }
+
+        // ---- Y tick labels ----
+        if self.show_y && y_span.abs() > f64::EPSILON {
+            for (tick, label) in y_ticks.iter().zip(y_labels) {
+                if *tick < y_range[0] || *tick > y_range[1] {
                     continue;
                 }
-                let x_norm = (tick - self.x_range[0]) / x_span;
-                let screen_x = self.padding + x_norm * plot_width;
-                let screen_y = self.padding + plot_height + 6.0;
+                let y_norm = ((tick - y_range[0]) / y_span) as f32;
+                let screen_y = padding.top + (1.0 - y_norm) * plot_height;
+                let screen_x = padding.left - 6.0;
 
                 frame.fill_text(canvas::Text {
                     content: label.clone(),
-                    size: iced::Pixels(self.x_label_size),
+                    size: iced::Pixels(self.y_label_size),
                     position: Point::new(screen_x, screen_y),
-                    color: self.x_label_color,
+                    color: self.y_label_color,
+                    align_x: iced::alignment::Horizontal::Right.into(),
+                    align_y: iced::alignment::Vertical::Center,
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        // ---- X axis title ----
+        if layout.show_titles
+            && let Some(ref title) = self.x_title
+        {
+            let center_x = padding.left + plot_width / 2.0;
+            // Place below tick labels: padding + plot_height + tick_label_space
+            let y = padding.top + plot_height + 6.0 + x_layout.height + 8.0;
+            frame.fill_text(canvas::Text {
+                content: title.clone(),
+                size: iced::Pixels(self.x_title_size),
+                position: Point::new(center_x, y),
+                color: self.x_title_color,
+                align_x: iced::alignment::Horizontal::Center.into(),
+                align_y: iced::alignment::Vertical::Top,
+                font: Font::DEFAULT,
+                ..canvas::Text::default()
+            });
+        }
+
+        // ---- Y axis title (rotated 90° counter-clockwise) ----
+        if layout.show_titles
+            && let Some(ref title) = self.y_title
+        {
+            let center_y = padding.top + plot_height / 2.0;
+            // Place to the left of tick labels, inside any extra left axes
+            let x = padding.left - self.base_padding + 4.0;
+            frame.with_save(|frame| {
+                // Move to the desired position, rotate, then draw centered at origin
+                frame.translate(iced::Vector::new(x, center_y));
+                frame.rotate(-std::f32::consts::FRAC_PI_2);
+                frame.fill_text(canvas::Text {
+                    content: title.clone(),
+                    size: iced::Pixels(self.y_title_size),
+                    position: Point::new(0.0, 0.0),
+                    color: self.y_title_color,
                     align_x: iced::alignment::Horizontal::Center.into(),
                     align_y: iced::alignment::Vertical::Top,
-                    font: Font::MONOSPACE,
+                    font: Font::DEFAULT,
+                    ..canvas::Text::default()
+                });
+            });
+        }
+
+        // ---- Extra Y axes (stacked outward from the plot area) ----
+        let mut left_edge = padding.left - self.base_padding;
+        let mut right_edge = padding.left + plot_width;
+        for axis in &self.extra_y_axes {
+            // Band [start, start + width] reserved for this axis
+            let (start, line_x) = match axis.side {
+                AxisSide::Left => {
+                    left_edge -= axis.width;
+                    (left_edge, left_edge + axis.width)
+                }
+                AxisSide::Right => {
+                    right_edge += axis.width;
+                    (right_edge - axis.width, right_edge - axis.width)
+                }
+            };
+            let span = axis.range[1] - axis.range[0];
+            if !axis.config.show || span.abs() <= f64::EPSILON {
+                continue;
+            }
+
+            frame.stroke(
+                &canvas::Path::line(
+                    Point::new(line_x, padding.top),
+                    Point::new(line_x, padding.top + plot_height),
+                ),
+                canvas::Stroke::default()
+                    .with_color(axis.config.color)
+                    .with_width(axis.config.line_width),
+            );
+
+            let (label_x, align_x) = match axis.side {
+                AxisSide::Left => (line_x - 6.0, iced::alignment::Horizontal::Right),
+                AxisSide::Right => (line_x + 6.0, iced::alignment::Horizontal::Left),
+            };
+            for (tick, label) in axis.ticks.iter().zip(&axis.labels) {
+                if *tick < axis.range[0] || *tick > axis.range[1] {
+                    continue;
+                }
+                let y_norm = ((tick - axis.range[0]) / span) as f32;
+                frame.fill_text(canvas::Text {
+                    content: label.clone(),
+                    size: iced::Pixels(axis.config.label_size),
+                    position: Point::new(label_x, padding.top + (1.0 - y_norm) * plot_height),
+                    color: axis.config.label_color,
+                    align_x: align_x.into(),
+                    align_y: iced::alignment::Vertical::Center,
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                });
+            }
+
+            // Title along the outer edge of the band, rotated like the primary one
+            if layout.show_titles
+                && let Some(ref title) = axis.config.title
+            {
+                let (x, align_y) = match axis.side {
+                    AxisSide::Left => (start + 4.0, iced::alignment::Vertical::Top),
+                    AxisSide::Right => {
+                        (start + axis.width - 4.0, iced::alignment::Vertical::Bottom)
+                    }
+                };
+                frame.with_save(|frame| {
+                    frame.translate(iced::Vector::new(x, padding.top + plot_height / 2.0));
+                    frame.rotate(-std::f32::consts::FRAC_PI_2);
+                    frame.fill_text(canvas::Text {
+                        content: title.clone(),
+                        size: iced::Pixels(axis.config.title_size),
+                        position: Point::new(0.0, 0.0),
+                        color: axis.config.title_color,
+                        align_x: iced::alignment::Horizontal::Center.into(),
+                        align_y,
+                        font: Font::DEFAULT,
+                        ..canvas::Text::default()
+                    });
+                });
+            }
+        }
+
+        // ---- Colorbar (outside the extra right axes) ----
+        if let Some(ref colorbar) = self.colorbar {
+            let config = &colorbar.config;
+            let x = right_edge + 8.0;
+            let step = plot_height / colorbar.colors.len() as f32;
+            for (i, color) in colorbar.colors.iter().enumerate() {
+                // Overlap by a pixel so no seams show between steps
+                let top = padding.top + plot_height - (i + 1) as f32 * step;
+                frame.fill_rectangle(
+                    Point::new(x, top),
+                    iced::Size::new(config.strip_width, step + 1.0),
+                    *color,
+                );
+            }
+            frame.stroke(
+                &canvas::Path::rectangle(
+                    Point::new(x, padding.top),
+                    iced::Size::new(config.strip_width, plot_height),
+                ),
+                canvas::Stroke::default()
+                    .with_color(config.label_color)
+                    .with_width(1.0),
+            );
+
+            let span = colorbar.range[1] - colorbar.range[0];
+            let label_x = x + config.strip_width + 4.0;
+            for (tick, label) in colorbar.ticks.iter().zip(&colorbar.labels) {
+                let y =
+                    padding.top + (1.0 - ((tick - colorbar.range[0]) / span) as f32) * plot_height;
+                frame.stroke(
+                    &canvas::Path::line(
+                        Point::new(x + config.strip_width - 3.0, y),
+                        Point::new(x + config.strip_width, y),
+                    ),
+                    canvas::Stroke::default()
+                        .with_color(config.label_color)
+                        .with_width(1.0),
+                );
+                frame.fill_text(canvas::Text {
+                    content: label.clone(),
+                    size: iced::Pixels(config.label_size),
+                    position: Point::new(label_x, y),
+                    color: config.label_color,
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Center,
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                });
+            }
+
+            if let Some(ref title) = config.title {
+                frame.fill_text(canvas::Text {
+                    content: title.clone(),
+                    size: iced::Pixels(config.label_size),
+                    position: Point::new(x, padding.top - 6.0),
+                    color: config.label_color,
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Bottom,
+                    font: Font::DEFAULT,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        // ---- Caption / footnote (stacked upwards from the widget bottom) ----
+        let mut block_bottom = bounds.height - TEXT_BLOCK_GAP;
+        for block in [&self.footnote, &self.caption].into_iter().flatten() {
+            let x = match block.align {
+                iced::alignment::Horizontal::Left => padding.left,
+                iced::alignment::Horizontal::Center => padding.left + plot_width / 2.0,
+                iced::alignment::Horizontal::Right => padding.left + plot_width,
+            };
+            frame.fill_text(canvas::Text {
+                content: block.text.clone(),
+                size: iced::Pixels(block.size),
+                position: Point::new(x, block_bottom),
+                color: block.color,
+                align_x: block.align.into(),
+                align_y: iced::alignment::Vertical::Bottom,
+                font: Font::DEFAULT,
+                ..canvas::Text::default()
+            });
+            block_bottom -= block.size + TEXT_BLOCK_GAP;
+        }
+
+        // ---- Annotation labels ----
+        let x_span = x_range[1] - x_range[0];
+        let y_span = y_range[1] - y_range[0];
+        #[cfg(feature = "annotations")]
+        if x_span > 0.0 && y_span > 0.0 {
+            let to_x = |x: f64| padding.left + ((x - x_range[0]) / x_span) as f32 * plot_width;
+            let to_y =
+                |y: f64| padding.top + (1.0 - ((y - y_range[0]) / y_span) as f32) * plot_height;
+            let (left, top) = (padding.left, padding.top);
+            let (right, bottom) = (left + plot_width, top + plot_height);
+            for annotation in &self.annotations {
+                let Some(ref label) = annotation.label else {
+                    continue;
+                };
+                // Anchor point and horizontal alignment, or None when out of view
+                let anchor = match annotation.kind {
+                    AnnotationKind::HLine { y } => {
+                        let y = to_y(y);
+                        (top..=bottom).contains(&y).then_some((
+                            right - 4.0,
+                            y - 2.0,
+                            iced::alignment::Horizontal::Right,
+                            iced::alignment::Vertical::Bottom,
+                        ))
+                    }
+                    AnnotationKind::VLine { x } => {
+                        let x = to_x(x);
+                        (left..=right).contains(&x).then_some((
+                            x + 4.0,
+                            top + 4.0,
+                            iced::alignment::Horizontal::Left,
+                            iced::alignment::Vertical::Top,
+                        ))
+                    }
+                    AnnotationKind::HBand { y: (y0, y1) } => {
+                        let y = to_y(y0).min(to_y(y1)).max(top);
+                        (y < bottom && to_y(y0).max(to_y(y1)) > top).then_some((
+                            left + 4.0,
+                            y + 4.0,
+                            iced::alignment::Horizontal::Left,
+                            iced::alignment::Vertical::Top,
+                        ))
+                    }
+                    AnnotationKind::VBand { x: (x0, x1) } => {
+                        let x = to_x(x0).min(to_x(x1)).max(left);
+                        (x < right && to_x(x0).max(to_x(x1)) > left).then_some((
+                            x + 4.0,
+                            top + 4.0,
+                            iced::alignment::Horizontal::Left,
+                            iced::alignment::Vertical::Top,
+                        ))
+                    }
+                    AnnotationKind::Text {
+                        x,
+                        y,
+                        anchor: (align_x, align_y),
+                    } => {
+                        let (x, y) = (to_x(x), to_y(y));
+                        ((left..=right).contains(&x) && (top..=bottom).contains(&y))
+                            .then_some((x, y, align_x, align_y))
+                    }
+                    AnnotationKind::Arrow { from, to } => {
+                        // At the tail, on the side facing away from the head
+                        let (x, y) = (to_x(from.0), to_y(from.1));
+                        let (dx, dy) = (to_x(to.0) - x, to_y(to.1) - y);
+                        let placement = if dx.abs() >= dy.abs() {
+                            let (offset, align) = if dx > 0.0 {
+                                (-4.0, iced::alignment::Horizontal::Right)
+                            } else {
+                                (4.0, iced::alignment::Horizontal::Left)
+                            };
+                            (x + offset, y, align, iced::alignment::Vertical::Center)
+                        } else {
+                            let (offset, align) = if dy > 0.0 {
+                                (-4.0, iced::alignment::Vertical::Bottom)
+                            } else {
+                                (4.0, iced::alignment::Vertical::Top)
+                            };
+                            (x, y + offset, iced::alignment::Horizontal::Center, align)
+                        };
+                        ((left..=right).contains(&x) && (top..=bottom).contains(&y))
+                            .then_some(placement)
+                    }
+                };
+                let Some((x, y, align_x, align_y)) = anchor else {
+                    continue;
+                };
+                // Free text is drawn in its own color, labels stay readable
+                // over faint lines and bands
+                let color = match annotation.kind {
+                    AnnotationKind::Text { .. } => annotation.color,
+                    _ => iced::Color {
+                        a: annotation.color.a.max(0.8),
+                        ..annotation.color
+                    },
+                };
+                frame.fill_text(canvas::Text {
+                    content: label.clone(),
+                    size: iced::Pixels(annotation.label_size),
+                    position: Point::new(x, y),
+                    color,
+                    align_x: align_x.into(),
+                    align_y,
+                    font: Font::DEFAULT,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        // ---- Regions of interest ----
+        if let Some(ref state) = self.rois {
+            let selected = *state.selected.borrow();
+            let sx = |x: f64| {
+                (padding.left + ((x - x_range[0]) / x_span) as f32 * plot_width)
+                    .clamp(padding.left, padding.left + plot_width)
+            };
+            let sy = |y: f64| {
+                (padding.top + (1.0 - ((y - y_range[0]) / y_span) as f32) * plot_height)
+                    .clamp(padding.top, padding.top + plot_height)
+            };
+            let overlaps = |(lo, hi): (f64, f64), range: [f64; 2]| lo <= range[1] && hi >= range[0];
+            for (i, roi) in state.rois.borrow().iter().enumerate() {
+                if !overlaps(roi.x_range, x_range)
+                    || roi.y_range.is_some_and(|range| !overlaps(range, y_range))
+                {
+                    continue;
+                }
+                let (left, right) = (sx(roi.x_range.0), sx(roi.x_range.1));
+                let (top, bottom) = match roi.y_range {
+                    Some((lo, hi)) => (sy(hi), sy(lo)),
+                    None => (padding.top, padding.top + plot_height),
+                };
+                let rect = canvas::Path::rectangle(
+                    Point::new(left, top),
+                    iced::Size::new(right - left, bottom - top),
+                );
+                frame.fill(
+                    &rect,
+                    iced::Color {
+                        a: roi.color.a * 0.15,
+                        ..roi.color
+                    },
+                );
+                let is_selected = selected == Some(i);
+                frame.stroke(
+                    &rect,
+                    canvas::Stroke::default()
+                        .with_color(roi.color)
+                        .with_width(if is_selected { 2.0 } else { 1.0 }),
+                );
+                if is_selected {
+                    let handles = match roi.y_range {
+                        Some(_) => vec![
+                            Point::new(left, top),
+                            Point::new(right, top),
+                            Point::new(left, bottom),
+                            Point::new(right, bottom),
+                        ],
+                        None => {
+                            let middle = (top + bottom) / 2.0;
+                            vec![Point::new(left, middle), Point::new(right, middle)]
+                        }
+                    };
+                    for handle in handles {
+                        frame.fill_rectangle(
+                            Point::new(handle.x - 3.0, handle.y - 3.0),
+                            iced::Size::new(6.0, 6.0),
+                            roi.color,
+                        );
+                    }
+                }
+                if let Some(ref label) = roi.label {
+                    frame.fill_text(canvas::Text {
+                        content: label.clone(),
+                        size: iced::Pixels(11.0),
+                        position: Point::new(left + 4.0, top + 3.0),
+                        color: roi.color,
+                        align_x: iced::alignment::Horizontal::Left.into(),
+                        align_y: iced::alignment::Vertical::Top,
+                        font: Font::DEFAULT,
+                        ..canvas::Text::default()
+                    });
+                }
+            }
+        }
+
+        // ---- Cursor lines ----
+        for line in &self.cursor_lines {
+            let (start, end, label_at) = match line.axis {
+                CursorAxis::X if (x_range[0]..=x_range[1]).contains(&line.value) => {
+                    let sx =
+                        padding.left + ((line.value - x_range[0]) / x_span) as f32 * plot_width;
+                    (
+                        Point::new(sx, padding.top),
+                        Point::new(sx, padding.top + plot_height),
+                        Point::new(sx + 4.0, padding.top + 3.0),
+                    )
+                }
+                CursorAxis::Y if (y_range[0]..=y_range[1]).contains(&line.value) => {
+                    let sy = padding.top
+                        + (1.0 - ((line.value - y_range[0]) / y_span) as f32) * plot_height;
+                    (
+                        Point::new(padding.left, sy),
+                        Point::new(padding.left + plot_width, sy),
+                        Point::new(padding.left + 4.0, sy - 14.0),
+                    )
+                }
+                _ => continue,
+            };
+            frame.stroke(
+                &canvas::Path::line(start, end),
+                canvas::Stroke::default()
+                    .with_color(line.color)
+                    .with_width(line.width),
+            );
+            if let Some(ref label) = line.label {
+                frame.fill_text(canvas::Text {
+                    content: label.clone(),
+                    size: iced::Pixels(11.0),
+                    position: label_at,
+                    color: line.color,
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Top,
+                    font: Font::DEFAULT,
                     ..canvas::Text::default()
                 });
             }
         }
 
-        // ---- Y tick labels ----
-        if self.show_y && y_span.abs() > f32::EPSILON {
-            for (tick, label) in self.y_ticks.iter().zip(&self.y_labels) {
-                if *tick < self.y_range[0] || *tick > self.y_range[1] {
-                    continue;
+        // ---- Cursor line deltas ----
+        if let Some(ref config) = self.cursor_delta {
+            let [dx, dy] = CursorLine::deltas(&self.cursor_lines);
+            let mut lines = Vec::new();
+            if let Some(dx) = dx {
+                lines.push(format!("Δx: {}", (config.format)(dx)));
+                if dx != 0.0 {
+                    lines.push(format!("1/Δx: {}", (config.format)(1.0 / dx)));
+                }
+            }
+            if let Some(dy) = dy {
+                lines.push(format!("Δy: {}", (config.format)(dy)));
+            }
+            if !lines.is_empty() {
+                let char_width = config.text_size * 0.6;
+                let line_height = config.text_size * 1.25;
+                let max_chars = lines.iter().map(|t| t.chars().count()).max();
+                let box_width = max_chars.unwrap_or(0) as f32 * char_width + config.padding * 2.0;
+                let box_height = config.text_size
+                    + (lines.len() - 1) as f32 * line_height
+                    + config.padding * 2.0;
+
+                let left = padding.left + config.margin;
+                let right = padding.left + plot_width - config.margin - box_width;
+                let top = padding.top + config.margin;
+                let bottom = padding.top + plot_height - config.margin - box_height;
+                let (box_x, box_y) = match config.corner {
+                    Corner::TopLeft => (left, top),
+                    Corner::TopRight => (right, top),
+                    Corner::BottomLeft => (left, bottom),
+                    Corner::BottomRight => (right, bottom),
+                };
+                frame.fill_rectangle(
+                    Point::new(box_x, box_y),
+                    iced::Size::new(box_width, box_height),
+                    config.background_color,
+                );
+                for (i, text) in lines.into_iter().enumerate() {
+                    frame.fill_text(canvas::Text {
+                        content: text,
+                        size: iced::Pixels(config.text_size),
+                        position: Point::new(
+                            box_x + config.padding,
+                            box_y
+                                + config.padding
+                                + config.text_size / 2.0
+                                + i as f32 * line_height,
+                        ),
+                        color: config.text_color,
+                        align_x: iced::alignment::Horizontal::Left.into(),
+                        align_y: iced::alignment::Vertical::Center,
+                        font: Font::MONOSPACE,
+                        ..canvas::Text::default()
+                    });
                 }
-                let y_norm = (tick - self.y_range[0]) / y_span;
-                let screen_y = self.padding + (1.0 - y_norm) * plot_height;
-                let screen_x = self.padding - 6.0;
-
-                frame.fill_text(canvas::Text {
-                    content: label.clone(),
-                    size: iced::Pixels(self.y_label_size),
-                    position: Point::new(screen_x, screen_y),
-                    color: self.y_label_color,
-                    align_x: iced::alignment::Horizontal::Right.into(),
-                    align_y: iced::alignment::Vertical::Center,
-                    font: Font::MONOSPACE,
-                    ..canvas::Text::default()
-                });
             }
         }
 
-        // ---- X axis title ----
-        if let Some(ref title) = self.x_title {
-            let center_x = self.padding + plot_width / 2.0;
-            // Place below tick labels: padding + plot_height + tick_label_space
-            let y = self.padding + plot_height + 6.0 + self.x_label_size + 8.0;
-            frame.fill_text(canvas::Text {
-                content: title.clone(),
-                size: iced::Pixels(self.x_title_size),
-                position: Point::new(center_x, y),
-                color: self.x_title_color,
-                align_x: iced::alignment::Horizontal::Center.into(),
-                align_y: iced::alignment::Vertical::Top,
-                font: Font::DEFAULT,
-                ..canvas::Text::default()
-            });
-        }
+        // ---- Measurement ----
+        // A pending one follows the cursor over the plot area
+        let measurement = self.measure.as_ref().and_then(|state| {
+            let pending = *state.pending.borrow();
+            match pending {
+                Some(from) => {
+                    let pos = cursor.position_in(bounds).filter(|pos| {
+                        pos.x >= padding.left
+                            && pos.x <= padding.left + plot_width
+                            && pos.y >= padding.top
+                            && pos.y <= padding.top + plot_height
+                    })?;
+                    let to = (
+                        x_range[0] + f64::from((pos.x - padding.left) / plot_width) * x_span,
+                        y_range[0] + f64::from(1.0 - (pos.y - padding.top) / plot_height) * y_span,
+                    );
+                    Some(Measurement { from, to })
+                }
+                None => *state.measurement.borrow(),
+            }
+        });
+        if let Some(measurement) = measurement {
+            let screen = |(x, y): (f64, f64)| {
+                Point::new(
+                    padding.left + ((x - x_range[0]) / x_span) as f32 * plot_width,
+                    padding.top + (1.0 - ((y - y_range[0]) / y_span) as f32) * plot_height,
+                )
+            };
+            let (from, to) = (screen(measurement.from), screen(measurement.to));
+            let color = iced::Color::from_rgba(1.0, 1.0, 1.0, 0.9);
+            frame.stroke(
+                &canvas::Path::line(from, to),
+                canvas::Stroke::default().with_color(color).with_width(1.5),
+            );
+            for end in [from, to] {
+                frame.fill(&canvas::Path::circle(end, 3.0), color);
+            }
 
-        // ---- Y axis title (rotated 90° counter-clockwise) ----
-        if let Some(ref title) = self.y_title {
-            let center_y = self.padding + plot_height / 2.0;
-            // Place to the left of tick labels
-            let x = 4.0;
-            frame.with_save(|frame| {
-                // Move to the desired position, rotate, then draw centered at origin
-                frame.translate(iced::Vector::new(x, center_y));
-                frame.rotate(-std::f32::consts::FRAC_PI_2);
+            let lines = [
+                format!("Δx: {:.4}", measurement.dx()),
+                format!("Δy: {:.4}", measurement.dy()),
+                format!("slope: {:.4}", measurement.slope()),
+                format!("dist: {:.4}", measurement.distance()),
+            ];
+            let (text_size, box_padding) = (12.0, 4.0);
+            let line_height = text_size * 1.25;
+            let max_chars = lines.iter().map(|t| t.chars().count()).max();
+            let box_width = max_chars.unwrap_or(0) as f32 * text_size * 0.6 + box_padding * 2.0;
+            let box_height = text_size + 3.0 * line_height + box_padding * 2.0;
+            // Beside the end point, kept inside the plot area
+            let box_x = (to.x + 10.0)
+                .min(padding.left + plot_width - box_width)
+                .max(padding.left);
+            let box_y = (to.y + 10.0)
+                .min(padding.top + plot_height - box_height)
+                .max(padding.top);
+            frame.fill_rectangle(
+                Point::new(box_x, box_y),
+                iced::Size::new(box_width, box_height),
+                iced::Color::from_rgba(0.1, 0.1, 0.1, 0.8),
+            );
+            for (i, text) in lines.into_iter().enumerate() {
                 frame.fill_text(canvas::Text {
-                    content: title.clone(),
-                    size: iced::Pixels(self.y_title_size),
-                    position: Point::new(0.0, 0.0),
-                    color: self.y_title_color,
-                    align_x: iced::alignment::Horizontal::Center.into(),
-                    align_y: iced::alignment::Vertical::Top,
-                    font: Font::DEFAULT,
+                    content: text,
+                    size: iced::Pixels(text_size),
+                    position: Point::new(
+                        box_x + box_padding,
+                        box_y + box_padding + text_size / 2.0 + i as f32 * line_height,
+                    ),
+                    color,
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Center,
+                    font: Font::MONOSPACE,
                     ..canvas::Text::default()
                 });
-            });
+            }
         }
 
         // ---- Legend ----
-        if let Some(ref config) = self.legend_config {
+        #[cfg(feature = "legend")]
+        if let Some(ref config) = self.legend_config
+            && layout.show_legend
+        {
             let hidden = self.hidden_series.borrow();
             let mut toggle_rects: Vec<LegendToggleRect> = Vec::new();
             let mut legend_bg_rect: Option<iced::Rectangle> = None;
 
-            let row_height = config.toggle_size.max(config.text_size) + 4.0;
             let num_entries = self.legend_entries.len();
             if num_entries > 0 {
-                // Estimate legend box dimensions
-                // Each row: [toggle_square] [gap] [label] [gap] [value]
+                // Each entry: [toggle_square] [gap] [label] [gap] [value]
                 let gap = 6.0;
-                let value_format = &config.value_format;
-                let mut max_text_width: f32 = 0.0;
-                for entry in &self.legend_entries {
-                    // Rough character width estimate: text_size * 0.6 per char (monospace)
-                    let char_width = config.text_size * 0.6;
-                    let label_width = entry.label.len() as f32 * char_width;
-                    let value_width = if config.show_value {
-                        if let Some(v) = entry.latest_value {
-                            let formatted = (value_format)(v);
-                            (formatted.len() as f32 + 1.0) * char_width // +1 for space
-                        } else {
-                            0.0
-                        }
-                    } else {
-                        0.0
-                    };
-                    max_text_width = max_text_width.max(label_width + value_width);
-                }
-
-                let legend_width = config.padding * 2.0 + config.toggle_size + gap + max_text_width;
-                let legend_height = config.padding * 2.0 + num_entries as f32 * row_height - 4.0;
+                let without_legend = self.padding_without_legend;
+                let grid = LegendGrid::new(
+                    config,
+                    &self.legend_entries,
+                    iced::Size::new(
+                        bounds.width - without_legend.x(),
+                        bounds.height - without_legend.y(),
+                    ),
+                );
+                let row_height = grid.row_height;
+                let (legend_width, legend_height) = grid.size(config, num_entries);
 
                 // Position based on legend position
-                let (legend_x, legend_y) = match config.position {
+                let base = self.base_padding;
+                let position = match self.legend_offset {
+                    Some(offset) if !config.position.is_outside() => LegendPosition::Custom(offset),
+                    _ => config.position,
+                };
+                let (legend_x, legend_y) = match position {
                     LegendPosition::TopRight => (
-                        self.padding + plot_width - config.margin - legend_width,
-                        self.padding + config.margin,
+                        padding.left + plot_width - config.margin - legend_width,
+                        padding.top + config.margin,
                     ),
                     LegendPosition::TopLeft => {
-                        (self.padding + config.margin, self.padding + config.margin)
+                        (padding.left + config.margin, padding.top + config.margin)
                     }
                     LegendPosition::BottomRight => (
-                        self.padding + plot_width - config.margin - legend_width,
-                        self.padding + plot_height - config.margin - legend_height,
+                        padding.left + plot_width - config.margin - legend_width,
+                        padding.top + plot_height - config.margin - legend_height,
                     ),
                     LegendPosition::BottomLeft => (
-                        self.padding + config.margin,
-                        self.padding + plot_height - config.margin - legend_height,
+                        padding.left + config.margin,
+                        padding.top + plot_height - config.margin - legend_height,
+                    ),
+                    // Outside the plot area and any extra right axes, in the
+                    // space reserved by `plot_padding`
+                    LegendPosition::Right => (right_edge + config.margin, padding.top),
+                    LegendPosition::Bottom => (
+                        padding.left + (plot_width - legend_width) / 2.0,
+                        padding.top + plot_height + base,
                     ),
+                    LegendPosition::Custom(offset) => {
+                        let offset = clamp_legend_offset(
+                            offset,
+                            iced::Size::new(legend_width, legend_height),
+                            iced::Size::new(plot_width, plot_height),
+                        );
+                        (padding.left + offset.x, padding.top + offset.y)
+                    }
                 };
 
                 // Draw background
@@ -1311,11 +7044,12 @@ impl<Message> canvas::Program<Message> for AxisOverlay {
 
                 // Draw entries
                 for (i, entry) in self.legend_entries.iter().enumerate() {
-                    let is_hidden = hidden.contains(&i);
-                    let entry_y = legend_y + config.padding + i as f32 * row_height;
+                    let is_hidden = entry.hidden || hidden.contains(&i);
+                    let cell = grid.cell_offset(i);
+                    let entry_y = legend_y + config.padding + cell.y;
 
                     // Toggle square (rounded rect)
-                    let toggle_x = legend_x + config.padding;
+                    let toggle_x = legend_x + config.padding + cell.x;
                     let toggle_y = entry_y + (row_height - 4.0 - config.toggle_size) / 2.0;
                     let toggle_rect = iced::Rectangle::new(
                         Point::new(toggle_x, toggle_y),
@@ -1326,6 +7060,10 @@ impl<Message> canvas::Program<Message> for AxisOverlay {
                     toggle_rects.push(LegendToggleRect {
                         series_index: i,
                         rect: toggle_rect,
+                        entry: iced::Rectangle::new(
+                            Point::new(toggle_x, entry_y),
+                            iced::Size::new(grid.cell_width, row_height),
+                        ),
                     });
 
                     let toggle_color = if is_hidden {
@@ -1391,9 +7129,10 @@ impl<Message> canvas::Program<Message> for AxisOverlay {
 
                     let mut display_text = entry.label.clone();
                     if config.show_value
-                        && let Some(v) = entry.latest_value
+                        && let Some(value) = &entry.value
                     {
-                        display_text.push_str(&format!(" {}", (value_format)(v)));
+                        display_text.push(' ');
+                        display_text.push_str(value);
                     }
 
                     frame.fill_text(canvas::Text {
@@ -1414,73 +7153,275 @@ impl<Message> canvas::Program<Message> for AxisOverlay {
                 bounds: legend_bg_rect,
                 toggles: toggle_rects,
             };
+        } else if self.legend_config.is_some() {
+            // Legend hidden at this size: nothing to hit test
+            *self.legend_layout.borrow_mut() = LegendLayout::default();
+        }
+
+        // Cursor over the plot area, else the X another plot's cursor is at
+        let plot_cursor = cursor.position_in(bounds).filter(|pos| {
+            pos.x >= padding.left
+                && pos.x <= padding.left + plot_width
+                && pos.y >= padding.top
+                && pos.y <= padding.top + plot_height
+        });
+        let shared_x = match self.shared_cursor {
+            Some(ref shared) if plot_cursor.is_none() => *shared.x.borrow(),
+            _ => None,
+        };
+
+        // ---- Crosshair ----
+        let fallback;
+        let crosshair = match (&self.crosshair, shared_x) {
+            (Some(config), _) => Some(config),
+            (None, Some(_)) => {
+                fallback = CrosshairConfig::default();
+                Some(&fallback)
+            }
+            (None, None) => None,
+        };
+        // The local cursor draws both lines, a shared one just the vertical
+        let cross = match self.cursor_state.position.borrow().as_ref() {
+            Some(p) if self.crosshair.is_some() => (y_range[0]..=y_range[1])
+                .contains(&p.y)
+                .then_some((p.x, Some(p.y))),
+            _ => shared_x.map(|x| (x, None)),
+        };
+        if let Some(config) = crosshair
+            && let Some((px, py)) = cross
+            && (x_range[0]..=x_range[1]).contains(&px)
+        {
+            let sx = padding.left + ((px - x_range[0]) / x_span) as f32 * plot_width;
+            let stroke = canvas::Stroke::default()
+                .with_color(config.line_color)
+                .with_width(config.line_width);
+            frame.stroke(
+                &canvas::Path::line(
+                    Point::new(sx, padding.top),
+                    Point::new(sx, padding.top + plot_height),
+                ),
+                stroke,
+            );
+
+            // Value boxes over the tick labels: X centered below the plot
+            // area, Y right-aligned against its left edge
+            let char_width = config.text_size * 0.6;
+            let box_height = config.text_size + config.padding * 2.0;
+            let x_text = (config.format_x)(px);
+            let x_width = x_text.chars().count() as f32 * char_width + config.padding * 2.0;
+            let mut boxes = vec![(
+                x_text,
+                Point::new(
+                    (sx - x_width / 2.0).clamp(padding.left, padding.left + plot_width - x_width),
+                    padding.top + plot_height + 2.0,
+                ),
+                x_width,
+            )];
+            if let Some(py) = py {
+                let sy = padding.top + (1.0 - ((py - y_range[0]) / y_span) as f32) * plot_height;
+                frame.stroke(
+                    &canvas::Path::line(
+                        Point::new(padding.left, sy),
+                        Point::new(padding.left + plot_width, sy),
+                    ),
+                    stroke,
+                );
+                let y_text = (config.format_y)(py);
+                let y_width = y_text.chars().count() as f32 * char_width + config.padding * 2.0;
+                boxes.push((
+                    y_text,
+                    Point::new(
+                        padding.left - y_width - 2.0,
+                        (sy - box_height / 2.0)
+                            .clamp(padding.top, padding.top + plot_height - box_height),
+                    ),
+                    y_width,
+                ));
+            }
+            for (text, top_left, width) in boxes {
+                frame.fill_rectangle(
+                    top_left,
+                    iced::Size::new(width, box_height),
+                    config.background_color,
+                );
+                frame.fill_text(canvas::Text {
+                    content: text,
+                    size: iced::Pixels(config.text_size),
+                    position: Point::new(
+                        top_left.x + config.padding,
+                        top_left.y + box_height / 2.0,
+                    ),
+                    color: config.text_color,
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Center,
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                });
+            }
         }
 
         // ---- Tooltip ----
+        #[cfg(feature = "tooltip")]
         if let Some(ref config) = self.tooltip_config {
-            let hovered = self.tooltip_state.hovered.borrow();
-            if let Some(ref hp) = *hovered {
-                let format_x = &config.format_x;
-                let format_y = &config.format_y;
+            let label = |hp: &HoveredPoint| {
                 let text = format!(
                     "{}: ({}, {})",
                     hp.series_label,
-                    (format_x)(hp.x),
-                    (format_y)(hp.y)
+                    (config.format_x)(hp.x),
+                    (config.format_y)(hp.y)
                 );
-
-                // Estimate text dimensions
-                let char_width = config.text_size * 0.6;
-                let text_width = text.len() as f32 * char_width;
-                let text_height = config.text_size;
-
-                let box_width = text_width + config.padding * 2.0;
-                let box_height = text_height + config.padding * 2.0;
-
-                // Position tooltip above and to the right of the point, with clamping
-                let offset_x = 12.0;
-                let offset_y = -12.0;
-
-                let mut tooltip_x = hp.screen_pos.x + offset_x;
-                let mut tooltip_y = hp.screen_pos.y + offset_y - box_height;
-
-                // Clamp to widget bounds
-                if tooltip_x + box_width > bounds.width {
-                    tooltip_x = hp.screen_pos.x - offset_x - box_width;
+                match hp.metadata {
+                    Some(ref metadata) => format!("{text}  {metadata}"),
+                    None => text,
                 }
-                if tooltip_x < 0.0 {
-                    tooltip_x = 0.0;
-                }
-                if tooltip_y < 0.0 {
-                    tooltip_y = hp.screen_pos.y + offset_x; // flip below
+            };
+
+            // Pinned tooltips follow their points, while those are in view
+            let pinned = self.tooltip_state.pinned.borrow();
+            let mut close_buttons = Vec::new();
+            let ring = canvas::Stroke::default()
+                .with_color(config.highlight_color)
+                .with_width(config.highlight_width);
+            for (index, tooltip) in pinned.iter().enumerate() {
+                #[cfg(feature = "legend")]
+                if self
+                    .hidden_series
+                    .borrow()
+                    .contains(&tooltip.point.series_index)
+                {
+                    continue;
                 }
-                if tooltip_y + box_height > bounds.height {
-                    tooltip_y = bounds.height - box_height;
+                let (x, y) = tooltip.anchor;
+                let at = Point::new(
+                    padding.left + ((x - x_range[0]) / x_span) as f32 * plot_width,
+                    padding.top + (1.0 - ((y - y_range[0]) / y_span) as f32) * plot_height,
+                );
+                let in_plot = (padding.left..=padding.left + plot_width).contains(&at.x)
+                    && (padding.top..=padding.top + plot_height).contains(&at.y);
+                if !in_plot {
+                    continue;
                 }
-
-                // Draw background
-                frame.fill_rectangle(
-                    Point::new(tooltip_x, tooltip_y),
-                    iced::Size::new(box_width, box_height),
-                    config.background_color,
+                frame.stroke(&canvas::Path::circle(at, config.highlight_radius), ring);
+                let close = draw_tooltip_box(
+                    &mut frame,
+                    config,
+                    label(&tooltip.point),
+                    at,
+                    bounds.size(),
+                    true,
                 );
+                close_buttons.extend(close.map(|rect| (index, rect)));
+            }
+            *self.tooltip_state.close_buttons.borrow_mut() = close_buttons;
 
-                // Draw border
-                frame.stroke_rectangle(
-                    Point::new(tooltip_x, tooltip_y),
-                    iced::Size::new(box_width, box_height),
-                    canvas::Stroke::default()
-                        .with_color(iced::Color::from_rgba(1.0, 1.0, 1.0, 0.3))
-                        .with_width(1.0),
+            let hovered = self.tooltip_state.hovered.borrow();
+            if let Some(ref hp) = *hovered
+                && !pinned.iter().any(|p| p.point.is_same_point(hp))
+            {
+                draw_tooltip_box(
+                    &mut frame,
+                    config,
+                    label(hp),
+                    hp.screen_pos,
+                    bounds.size(),
+                    false,
                 );
+            }
+        }
+
+        // ---- Cursor readout ----
+        let readout_at = match plot_cursor {
+            Some(pos) => Some((
+                x_range[0] + f64::from((pos.x - padding.left) / plot_width) * x_span,
+                Some(y_range[0] + f64::from(1.0 - (pos.y - padding.top) / plot_height) * y_span),
+            )),
+            None => shared_x
+                .filter(|x| (x_range[0]..=x_range[1]).contains(x))
+                .map(|x| (x, None)),
+        };
+        if let Some(ref config) = self.cursor_readout
+            && let Some((x, y)) = readout_at
+        {
+            let lines = self.readout_lines(config, x, y);
+
+            // Estimate text dimensions
+            let char_width = config.text_size * 0.6;
+            let line_height = config.text_size * 1.25;
+            let max_chars = lines.iter().map(|(t, _)| t.chars().count()).max();
+            let box_width = max_chars.unwrap_or(0) as f32 * char_width + config.padding * 2.0;
+            let box_height =
+                config.text_size + (lines.len() - 1) as f32 * line_height + config.padding * 2.0;
+
+            let left = padding.left + config.margin;
+            let right = padding.left + plot_width - config.margin - box_width;
+            let top = padding.top + config.margin;
+            let bottom = padding.top + plot_height - config.margin - box_height;
+            let (box_x, box_y) = match config.corner {
+                Corner::TopLeft => (left, top),
+                Corner::TopRight => (right, top),
+                Corner::BottomLeft => (left, bottom),
+                Corner::BottomRight => (right, bottom),
+            };
 
-                // Draw text
+            frame.fill_rectangle(
+                Point::new(box_x, box_y),
+                iced::Size::new(box_width, box_height),
+                config.background_color,
+            );
+            for (i, (text, color)) in lines.into_iter().enumerate() {
                 frame.fill_text(canvas::Text {
                     content: text,
                     size: iced::Pixels(config.text_size),
                     position: Point::new(
-                        tooltip_x + config.padding,
-                        tooltip_y + config.padding + text_height / 2.0,
+                        box_x + config.padding,
+                        box_y + config.padding + config.text_size / 2.0 + i as f32 * line_height,
+                    ),
+                    color,
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Center,
+                    font: Font::MONOSPACE,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        // ---- Context menu (on top of everything) ----
+        if let Some(ref config) = self.context_menu
+            && let Some(ref menu) = *self.context_menu_state.open.borrow()
+        {
+            let menu_bounds = menu.bounds(config, bounds.size());
+            let row_height = OpenContextMenu::row_height(config);
+            let hovered = cursor
+                .position_in(bounds)
+                .and_then(|pos| menu.entry_at(config, bounds.size(), pos));
+
+            frame.fill_rectangle(
+                menu_bounds.position(),
+                menu_bounds.size(),
+                config.background_color,
+            );
+            frame.stroke_rectangle(
+                menu_bounds.position(),
+                menu_bounds.size(),
+                canvas::Stroke::default()
+                    .with_color(iced::Color::from_rgba(1.0, 1.0, 1.0, 0.3))
+                    .with_width(1.0),
+            );
+            for (i, entry) in menu.entries.iter().enumerate() {
+                let row_y = menu_bounds.y + i as f32 * row_height;
+                if hovered == Some(i) {
+                    frame.fill_rectangle(
+                        Point::new(menu_bounds.x, row_y),
+                        iced::Size::new(menu_bounds.width, row_height),
+                        config.highlight_color,
+                    );
+                }
+                frame.fill_text(canvas::Text {
+                    content: entry.label.clone(),
+                    size: iced::Pixels(config.text_size),
+                    position: Point::new(
+                        menu_bounds.x + config.padding * 2.0,
+                        row_y + row_height / 2.0,
                     ),
                     color: config.text_color,
                     align_x: iced::alignment::Horizontal::Left.into(),
@@ -1494,3 +7435,760 @@ impl<Message> canvas::Program<Message> for AxisOverlay {
         vec![frame.into_geometry()]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+    use iced::{Event, Point, keyboard, mouse};
+
+    #[test]
+    fn test_cursor_readout_uses_custom_formatters() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let points = vec![(0.0, 0.0).into(), (10.0, 5.0).into()];
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("volts", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            cursor_readout: Some(
+                CursorReadoutConfig::default()
+                    .with_format_x(|v| format!("{v} s"))
+                    .with_format_y(|v| format!("{v} V"))
+                    .with_series_values(true),
+            ),
+            ..PlotterOptions::default()
+        });
+
+        // The overlay draws from its own copy of the options
+        let overlay = plotter.overlay();
+        let config = overlay.cursor_readout.as_ref().expect("readout configured");
+        let lines: Vec<String> = overlay
+            .readout_lines(config, 4.0, Some(1.5))
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect();
+        assert_eq!(lines, ["x: 4 s  y: 1.5 V", "volts: 2 V"]);
+    }
+
+    #[cfg(all(feature = "legend", feature = "tooltip"))]
+    #[test]
+    fn test_overlay_keeps_legend_and_tooltip_formatters() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &view).with_options(PlotterOptions {
+                legend: Some(LegendConfig::default().with_value_format(|v| format!("{v} V"))),
+                tooltip: Some(
+                    TooltipConfig::default()
+                        .with_format_x(|v| format!("{v} s"))
+                        .with_format_y(|v| format!("{v} V")),
+                ),
+                ..PlotterOptions::default()
+            });
+
+        let overlay = plotter.overlay();
+        let legend = overlay.legend_config.as_ref().expect("legend configured");
+        assert_eq!((legend.value_format)(1.5), "1.5 V");
+        let tooltip = overlay.tooltip_config.as_ref().expect("tooltip configured");
+        assert_eq!((tooltip.format_x)(2.0), "2 s");
+        assert_eq!((tooltip.format_y)(1.5), "1.5 V");
+    }
+    #[test]
+    fn test_interpolate_y_breaks_at_gaps() {
+        let points: Vec<PlotPoint> = [(0.0, 0.0), (1.0, 2.0), (2.0, f64::NAN), (3.0, 4.0)]
+            .map(PlotPoint::from)
+            .into();
+        let linear = LineInterpolation::Linear;
+        assert_eq!(interpolate_y(&points, 0.5, linear), Some(1.0));
+        assert_eq!(interpolate_y(&points, 1.5, linear), None);
+        assert_eq!(interpolate_y(&points, 2.0, linear), None);
+        assert_eq!(interpolate_y(&points, 2.5, linear), None);
+        assert_eq!(interpolate_y(&points, 3.0, linear), Some(4.0));
+    }
+
+    #[cfg(feature = "legend")]
+    #[test]
+    fn test_legend_values_skip_gaps() {
+        let points: Vec<PlotPoint> = [(0.0, 1.0), (1.0, f64::NAN), (2.0, 3.0), (3.0, f64::NAN)]
+            .map(PlotPoint::from)
+            .into();
+        let all = [f64::NEG_INFINITY, f64::INFINITY];
+        assert_eq!(LegendValue::Last.aggregate(&points, all), Some(3.0));
+        assert_eq!(LegendValue::Mean.aggregate(&points, all), Some(2.0));
+        assert_eq!(LegendValue::Min.aggregate(&points, all), Some(1.0));
+        assert_eq!(LegendValue::Max.aggregate(&points, all), Some(3.0));
+        assert_eq!(LegendValue::Mean.aggregate(&points, [0.5, 1.5]), None);
+    }
+
+    #[test]
+    fn test_x_labels_thin_out_unless_rotated() {
+        // Ten 6 px characters per label, ticks 30 px apart
+        let labels = vec!["12:00:00.5".to_string(); 5];
+        let xs = || (0..5).map(|i| i as f32 * 30.0);
+
+        // Level labels are wider than the spacing: keep every 3rd
+        let level = XLabelLayout::new(&labels, 10.0, 0.0);
+        assert_eq!(level.footprint, 66.0);
+        assert_eq!(level.stride(xs()), 3);
+        assert_eq!(level.height, 10.0);
+
+        // Tilted labels sit a line height apart and fit at every tick,
+        // hanging further below the axis
+        for degrees in [45.0, 90.0] {
+            let tilted = XLabelLayout::new(&labels, 10.0, degrees);
+            assert!(tilted.footprint <= 30.0);
+            assert_eq!(tilted.stride(xs()), 1);
+            assert!(tilted.height > 40.0);
+        }
+
+        // A lone label, or none, is always drawn
+        assert_eq!(level.stride(std::iter::once(0.0)), 1);
+        assert_eq!(level.stride(std::iter::empty()), 1);
+    }
+
+    /// Three stored points.
+    fn line() -> PlotPoints<'static> {
+        PlotPoints::owned(vec![
+            (0.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (2.0, 0.0).into(),
+        ])
+    }
+
+    /// An adaptively sampled generator.
+    fn adaptive() -> PlotPoints<'static> {
+        PlotPoints::generator(f64::sin, (0.0, 1.0), 10)
+            .with_adaptive_sampling(AdaptiveSampling::default())
+    }
+
+    /// What `validate` reports for `series` over an auto-fit view.
+    fn series_error(series: PlotSeries<'_>) -> Option<PlotterError> {
+        let view = ViewState::auto_fit();
+        Plotter::<'_, ()>::new(vec![series], &view).validate().err()
+    }
+
+    #[test]
+    fn test_validate_color_values_length() {
+        let color = ColorMode::value_gradient_values(
+            iced::Color::BLACK,
+            iced::Color::WHITE,
+            vec![0.0, 1.0],
+        );
+        let series = PlotSeries::new("s", line()).with_style(SeriesStyle::new(color));
+        assert_eq!(
+            series_error(series),
+            Some(PlotterError::ColorValuesLength {
+                series: "s".into(),
+                expected: 3,
+                found: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_color_values_with_adaptive_sampling() {
+        let color =
+            ColorMode::value_gradient_values(iced::Color::BLACK, iced::Color::WHITE, vec![0.0; 10]);
+        let series = PlotSeries::new("s", adaptive()).with_style(SeriesStyle::new(color));
+        assert_eq!(
+            series_error(series),
+            Some(PlotterError::ColorValuesWithAdaptiveSampling { series: "s".into() })
+        );
+    }
+
+    #[test]
+    fn test_validate_empty_generator() {
+        let explicit = PlotPoints::generator(f64::sin, (0.0, 1.0), 0);
+        let parametric = PlotPoints::parametric(f64::cos, f64::sin, (0.0, 1.0), 0);
+        for points in [explicit, parametric] {
+            assert_eq!(
+                series_error(PlotSeries::new("s", points)),
+                Some(PlotterError::EmptyGenerator { series: "s".into() })
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_invalid_generator_range() {
+        let points = PlotPoints::generator(f64::sin, (0.0, f64::INFINITY), 10);
+        assert_eq!(
+            series_error(PlotSeries::new("s", points)),
+            Some(PlotterError::InvalidGeneratorRange {
+                series: "s".into(),
+                range: (0.0, f64::INFINITY),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_view_range() {
+        let view = ViewState::with_ranges((1.0, 0.0), (0.0, 1.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view);
+        assert_eq!(
+            plotter.validate(),
+            Err(PlotterError::InvalidViewRange {
+                axis: "X",
+                range: (1.0, 0.0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_bounds_set_by_builder() {
+        let view = ViewState::auto_fit();
+        let plotter = || {
+            Plotter::<'_, ()>::new(Vec::new(), &view).with_interaction(InteractionConfig {
+                y_bounds: Some((5.0, 5.0)),
+                ..InteractionConfig::default()
+            })
+        };
+        assert_eq!(
+            plotter().validate(),
+            Err(PlotterError::InvalidBounds {
+                axis: "Y",
+                range: (5.0, 5.0),
+            })
+        );
+        // Checked once the builder is done
+        assert!(matches!(
+            plotter().try_draw(),
+            Err(PlotterError::InvalidBounds { axis: "Y", .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_degenerate_polygon() {
+        let view = ViewState::auto_fit();
+        // Closed by repeating the first vertex, so only two distinct ones
+        let triangle = vec![(0.0, 0.0).into(), (1.0, 0.0).into(), (0.0, 1.0).into()];
+        let closed = vec![(0.0, 0.0).into(), (1.0, 0.0).into(), (0.0, 0.0).into()];
+        let polygon = PolygonSeries::new(triangle, iced::Color::WHITE).with_loop(closed);
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view).with_polygon(polygon);
+        assert_eq!(
+            plotter.validate(),
+            Err(PlotterError::DegeneratePolygon {
+                polygon: 0,
+                loop_index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_y_axis() {
+        assert_eq!(
+            series_error(PlotSeries::new("s", line()).on_secondary_y()),
+            Some(PlotterError::InvalidYAxis {
+                series: "s".into(),
+                axis: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_y_axis_range() {
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &view).with_options(PlotterOptions {
+                extra_y_axes: vec![ExtraYAxis::new(AxisConfig::default()).with_range((2.0, 1.0))],
+                ..PlotterOptions::default()
+            });
+        assert_eq!(
+            plotter.validate(),
+            Err(PlotterError::InvalidYAxisRange {
+                axis: 1,
+                range: (2.0, 1.0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_bars() {
+        let generated = PlotPoints::generator(f64::sin, (0.0, 1.0), 10);
+        for series in [
+            PlotSeries::new("s", generated).bars(1.0, 0.0),
+            PlotSeries::new("s", line()).bars(0.0, 0.0),
+            PlotSeries::new("s", line()).bars(1.0, f64::NAN),
+        ] {
+            assert_eq!(
+                series_error(series),
+                Some(PlotterError::InvalidBars { series: "s".into() })
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_invalid_stack() {
+        let generated = PlotPoints::generator(f64::sin, (0.0, 1.0), 10);
+        for series in [
+            PlotSeries::new("s", generated).with_stack("total"),
+            PlotSeries::new("s", line())
+                .bars(1.0, 0.0)
+                .with_stack("total"),
+        ] {
+            assert_eq!(
+                series_error(series),
+                Some(PlotterError::InvalidStack { series: "s".into() })
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_point_array_length() {
+        let style = SeriesStyle::default().with_marker_sizes(vec![1.0; 4]);
+        assert_eq!(
+            series_error(PlotSeries::new("s", line()).with_style(style)),
+            Some(PlotterError::PointArrayLength {
+                series: "s".into(),
+                array: "marker_sizes",
+                expected: 3,
+                found: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_point_array_with_adaptive_sampling() {
+        let style = SeriesStyle::default().with_point_colors(vec![iced::Color::WHITE; 10]);
+        assert_eq!(
+            series_error(PlotSeries::new("s", adaptive()).with_style(style)),
+            Some(PlotterError::PointArrayWithAdaptiveSampling {
+                series: "s".into(),
+                array: "point_colors",
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_image_size() {
+        let view = ViewState::auto_fit();
+        let image = PlotImage::new(2, 2, vec![255; 12], (0.0, 1.0), (0.0, 1.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view).with_image(image);
+        assert_eq!(
+            plotter.validate(),
+            Err(PlotterError::ImageSize {
+                image: 0,
+                expected: 16,
+                found: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_image_range() {
+        let view = ViewState::auto_fit();
+        let image = PlotImage::new(1, 1, vec![255; 4], (0.0, 1.0), (1.0, f64::NAN));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view).with_image(image);
+        assert!(matches!(
+            plotter.validate(),
+            Err(PlotterError::InvalidImageRange {
+                image: 0,
+                axis: "Y",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_extra_y_axis_fills_primary_range() {
+        let view = ViewState::auto_fit();
+        let series = |label: &str, ys: [f64; 2]| {
+            let points = vec![(0.0, ys[0]).into(), (1.0, ys[1]).into()];
+            PlotSeries::new(label, PlotPoints::owned(points))
+        };
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![
+                series("volts", [0.0, 10.0]),
+                series("rpm", [1000.0, 3000.0]).with_y_axis(1),
+            ],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            autofit_padding: 0.0,
+            extra_y_axes: vec![ExtraYAxis::new(AxisConfig::default())],
+            ..PlotterOptions::default()
+        });
+        assert_eq!(plotter.validate(), Ok(()));
+        assert_eq!(plotter.compute_data_ranges().1, [0.0, 10.0]);
+
+        // Both series span the full height, relative to the view center
+        let ys: Vec<f32> = Harness::new(&plotter)
+            .primitive()
+            .points()
+            .iter()
+            .map(|p| p.position[1])
+            .collect();
+        assert_eq!(ys, [-5.0, 5.0, -5.0, 5.0]);
+    }
+
+    #[test]
+    fn test_named_y_axes_fit_their_series() {
+        let view = ViewState::default();
+        let channels = [
+            ("volts", 10.0),
+            ("rpm", 3000.0),
+            ("bar", 2.0),
+            ("degC", 90.0),
+        ];
+        let options = PlotterOptions {
+            autofit_padding: 0.0,
+            extra_y_axes: channels[1..]
+                .iter()
+                .enumerate()
+                .map(|(i, (name, _))| {
+                    ExtraYAxis::new(AxisConfig::default())
+                        .with_name(*name)
+                        .with_side(if i % 2 == 0 {
+                            AxisSide::Right
+                        } else {
+                            AxisSide::Left
+                        })
+                        .with_color(iced::Color::from_rgb(1.0, 0.0, 0.0))
+                })
+                .collect(),
+            ..PlotterOptions::default()
+        };
+        assert_eq!(options.y_axis_index("bar"), Some(2));
+        assert_eq!(options.y_axis_index("missing"), None);
+        assert_eq!(options.extra_y_axes[0].config.label_color.r, 1.0);
+
+        let series = channels
+            .iter()
+            .map(|(name, high)| {
+                let points = vec![(0.0, 0.0).into(), (1.0, *high).into()];
+                PlotSeries::new(*name, PlotPoints::owned(points))
+                    .with_y_axis(options.y_axis_index(name).unwrap_or(0))
+            })
+            .collect();
+        let plotter: Plotter<'_, ()> = Plotter::new(series, &view).with_options(options);
+        assert_eq!(plotter.validate(), Ok(()));
+
+        // Every channel spans the full height on its own axis
+        let ys: Vec<f32> = Harness::new(&plotter)
+            .primitive()
+            .points()
+            .iter()
+            .map(|p| p.position[1])
+            .collect();
+        assert_eq!(ys, [-5.0, 5.0].repeat(4));
+    }
+
+    #[test]
+    fn test_stacked_bars_extent() {
+        let view = ViewState::auto_fit();
+        let bars = |label: &str, ys: [f64; 2]| {
+            let points = vec![(0.0, ys[0]).into(), (1.0, ys[1]).into()];
+            PlotSeries::new(label, PlotPoints::owned(points)).bars(0.8, 0.0)
+        };
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(vec![bars("a", [2.0, 3.0]), bars("b", [1.0, -1.0])], &view).with_options(
+                PlotterOptions {
+                    bar_layout: BarLayout::Stacked,
+                    ..PlotterOptions::default()
+                },
+            );
+        assert_eq!(plotter.validate(), Ok(()));
+
+        // "b" stacks on "a" at x = 0 and hangs below the baseline at x = 1
+        assert_eq!(plotter.compute_data_ranges(), ([-0.4, 1.4], [-1.0, 3.0]));
+
+        // Four quads, no markers or lines
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.line_vertices().len(), 4 * 6);
+    }
+
+    #[cfg(feature = "annotations")]
+    #[test]
+    fn test_events_draw_bands_beneath_annotations() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let red = iced::Color::from_rgb(1.0, 0.0, 0.0);
+        let events = EventSeries::new(vec![
+            PlotEvent::new(2.0, 4.0, red).with_label("alarm"),
+            PlotEvent::new(6.0, 6.0, red).with_label("restart"),
+        ]);
+        let points = vec![(0.0, 0.0).into(), (10.0, 10.0).into()];
+        let series = vec![PlotSeries::new("line", PlotPoints::owned(points))];
+        let plotter: Plotter<'_, ()> = Plotter::new(series, &view)
+            .with_events(events.clone().with_edges(1.0))
+            .with_options(PlotterOptions {
+                annotations: vec![Annotation::hline(5.0)],
+                ..PlotterOptions::default()
+            });
+
+        // A band with its edges, then a line for the zero-length event, and
+        // the options' annotations last
+        let annotations = plotter.annotations();
+        let kinds: Vec<_> = annotations.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                AnnotationKind::VBand { x: (2.0, 4.0) },
+                AnnotationKind::VLine { x: 2.0 },
+                AnnotationKind::VLine { x: 4.0 },
+                AnnotationKind::VLine { x: 6.0 },
+                AnnotationKind::HLine { y: 5.0 },
+            ]
+        );
+        assert_eq!(annotations[0].color.a, 0.25);
+        assert_eq!(annotations[0].label.as_deref(), Some("alarm"));
+        assert_eq!(annotations[3].label.as_deref(), Some("restart"));
+
+        // The band is filled in the event color at a quarter of its alpha
+        let grid = Harness::new(&plotter).primitive().grid_vertices();
+        let fill: Vec<_> = grid
+            .iter()
+            .filter(|v| v.color == [1.0, 0.0, 0.0, 0.25])
+            .collect();
+        assert_eq!(fill.len(), 6);
+        let unlabeled = EventSeries::new(events.events).with_labels(false);
+        assert!(unlabeled.annotations().all(|a| a.label.is_none()));
+    }
+
+    #[cfg(feature = "legend")]
+    #[test]
+    fn test_outside_legend_wraps_into_columns() {
+        let view = ViewState::with_ranges((0.0, 1.0), (0.0, 1.0));
+        let series = (0..24)
+            .map(|i| {
+                let points = vec![(0.0, 0.0).into(), (1.0, f64::from(i)).into()];
+                PlotSeries::new(format!("s{i:02}"), PlotPoints::owned(points))
+            })
+            .collect();
+        let legend = |position| PlotterOptions {
+            legend: Some(LegendConfig {
+                position,
+                ..LegendConfig::default()
+            }),
+            ..PlotterOptions::default()
+        };
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(series, &view).with_options(legend(LegendPosition::Right));
+
+        // Half the height needs twice the columns
+        let tall = plotter.plot_padding(iced::Size::new(800.0, 600.0));
+        let short = plotter.plot_padding(iced::Size::new(800.0, 300.0));
+        assert!(short.right > tall.right);
+        assert_eq!(short.bottom, tall.bottom);
+
+        // Below the plot, a narrower widget needs more rows
+        let plotter = plotter.with_options(legend(LegendPosition::Bottom));
+        let wide = plotter.plot_padding(iced::Size::new(1600.0, 600.0));
+        let narrow = plotter.plot_padding(iced::Size::new(400.0, 600.0));
+        assert!(narrow.bottom > wide.bottom);
+        assert_eq!(narrow.right, wide.right);
+    }
+
+    #[test]
+    fn test_follow_x_keeps_window_width() {
+        let mut stream = crate::streaming::StreamingSeries::new(50);
+        stream.extend((0..=100).map(|i| (f64::from(i), 0.0)));
+        let view = ViewState::auto_fit().with_x_range(Some((0.0, 10.0)));
+        let plotter: Plotter<'_, ViewState> =
+            Plotter::new(vec![PlotSeries::new("live", stream.points())], &view)
+                .with_interaction(InteractionConfig::streaming())
+                .on_view_change(|view| view);
+        assert_eq!(plotter.resolve_view_ranges(true).0, [90.0, 100.0]);
+
+        // Zooming changes the width, the right edge stays on the newest point
+        let mut harness = Harness::new(&plotter);
+        harness.move_to(Point::new(400.0, 300.0));
+        let zoomed = harness.scroll(1.0).expect("zoom publishes a view");
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(vec![PlotSeries::new("live", stream.points())], &zoomed)
+                .with_interaction(InteractionConfig::streaming());
+        let [lo, hi] = plotter.resolve_view_ranges(true).0;
+        assert_eq!(hi, 100.0);
+        assert!(hi - lo < 10.0);
+    }
+
+    #[test]
+    fn test_colorbar_range_and_padding() {
+        let points = || PlotPoints::owned(vec![(0.0, 2.0).into(), (1.0, -3.0).into()]);
+        let blue_red = || {
+            SeriesStyle::new(ColorMode::value_gradient(
+                iced::Color::from_rgb(0.0, 0.0, 1.0),
+                iced::Color::from_rgb(1.0, 0.0, 0.0),
+            ))
+        };
+        let series = vec![
+            PlotSeries::new("plain", points()),
+            PlotSeries::new("by y", points()).with_style(blue_red()),
+            PlotSeries::new("by value", points()).with_style(SeriesStyle::new(
+                ColorMode::value_gradient_values(
+                    iced::Color::BLACK,
+                    iced::Color::WHITE,
+                    vec![10.0, 40.0],
+                ),
+            )),
+        ];
+        let view = ViewState::auto_fit();
+        let options = PlotterOptions {
+            colorbar: Some(ColorbarConfig::default()),
+            ..Default::default()
+        };
+        let plotter: Plotter<'_, ()> = Plotter::new(series, &view).with_options(options);
+
+        // Y gradients span the visible data, value arrays their own range
+        assert_eq!(plotter.color_value_range(1, [-3.0, 2.0]), Some((-3.0, 2.0)));
+        assert_eq!(
+            plotter.color_value_range(2, [-3.0, 2.0]),
+            Some((10.0, 40.0))
+        );
+
+        // The bar takes its width from the right padding
+        let size = iced::Size::new(400.0, 300.0);
+        let padding = plotter.plot_padding(size);
+        assert_eq!(padding.right, plotter.options.padding + 64.0);
+    }
+
+    #[test]
+    fn test_view_history_steps_back_and_forward() {
+        let history = ViewHistory::default();
+        let start = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        fn plotter<'a>(view: &'a ViewState, history: &ViewHistory) -> Plotter<'a, ViewState> {
+            Plotter::new(vec![], view)
+                .with_active_tool(ActiveTool::ZoomSelect)
+                .with_interaction(InteractionConfig {
+                    keyboard: true,
+                    ..InteractionConfig::default()
+                })
+                .with_view_history(history.clone())
+                .on_view_change(|view| view)
+        }
+
+        // A zoom rectangle records the view it replaced
+        let first = plotter(&start, &history);
+        let zoomed = Harness::new(&first)
+            .drag(Point::new(200.0, 200.0), Point::new(400.0, 400.0))
+            .pop()
+            .expect("zoom publishes a view");
+        assert_eq!(
+            history.back.borrow().as_slice(),
+            std::slice::from_ref(&start)
+        );
+        assert!(!history.can_go_forward());
+
+        // Backspace steps back, the mouse's forward button steps forward
+        let second = plotter(&zoomed, &history);
+        let mut harness = Harness::new(&second);
+        harness.move_to(Point::new(400.0, 300.0));
+        assert_eq!(
+            harness.key_press(keyboard::Key::Named(keyboard::key::Named::Backspace)),
+            Some(start.clone())
+        );
+        assert!(!history.can_go_back() && history.can_go_forward());
+        let third = plotter(&start, &history);
+        let mut harness = Harness::new(&third);
+        harness.move_to(Point::new(400.0, 300.0));
+        assert_eq!(
+            harness.event(Event::Mouse(mouse::Event::ButtonPressed(
+                mouse::Button::Forward
+            ))),
+            Some(zoomed.clone())
+        );
+        assert_eq!(
+            harness.key_press(keyboard::Key::Named(keyboard::key::Named::Backspace)),
+            Some(start.clone())
+        );
+
+        // A new navigation drops the views stepped back from
+        history.push(ViewState::auto_fit());
+        assert!(!history.can_go_forward());
+    }
+
+    #[test]
+    fn test_fit_helpers_match_auto_fit() {
+        let view = ViewState::default();
+        let points = |ys: [f64; 3]| {
+            PlotPoints::owned(
+                ys.iter()
+                    .enumerate()
+                    .map(|(i, y)| (i as f64, *y).into())
+                    .collect(),
+            )
+        };
+        let spike = PlotSeries::new("spike", points([1.0, 9.0, 1.0]));
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("base", points([-10.0, 0.0, 10.0])), spike],
+            &view,
+        );
+
+        let (view_x, view_y, _, _) = plotter.resolve_view_ranges(false);
+        let fit = plotter.fit_all();
+        assert_eq!(fit.x_range, Some((view_x[0], view_x[1])));
+        assert_eq!(fit.y_range, Some((view_y[0], view_y[1])));
+
+        let fit = ViewState::fit_series(&plotter.series[1], 0.5);
+        assert_eq!(fit.x_range, Some((-1.0, 3.0)));
+        assert_eq!(fit.y_range, Some((-3.0, 13.0)));
+
+        let zoomed = ViewState::default().zoom_to((1.0, 2.0), (3.0, 4.0));
+        assert_eq!(zoomed, ViewState::with_ranges((1.0, 2.0), (3.0, 4.0)));
+    }
+
+    #[test]
+    fn test_export_visible_points() {
+        let view = ViewState::with_ranges((2.0, 4.0), (0.0, 10.0));
+        let line = |scale: f64| {
+            PlotPoints::owned(
+                (0..10)
+                    .map(|i| (f64::from(i), f64::from(i) * scale).into())
+                    .collect(),
+            )
+        };
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![
+                PlotSeries::new("a, \"b\"", line(1.0)),
+                // Only x = 2 fits under the Y range
+                PlotSeries::new("steep", line(4.0)),
+                PlotSeries::new("off", line(1.0)).hidden(true),
+            ],
+            &view,
+        );
+
+        assert_eq!(
+            plotter.export_visible_csv(),
+            "series,x,y\n\
+             \"a, \"\"b\"\"\",2,2\n\
+             \"a, \"\"b\"\"\",3,3\n\
+             \"a, \"\"b\"\"\",4,4\n\
+             steep,2,8\n"
+        );
+        assert_eq!(
+            plotter.export_visible_json(),
+            r#"[{"series":"a, \"b\"","points":[[2,2],[3,3],[4,4]]},{"series":"steep","points":[[2,8]]}]"#
+        );
+    }
+
+    #[test]
+    fn test_equal_aspect_widens_and_zooms_both_axes() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter: Plotter<'_, ViewState> = Plotter::new(vec![], &view)
+            .with_options(PlotterOptions {
+                aspect: AspectMode::EqualData,
+                ..Default::default()
+            })
+            .with_interaction(InteractionConfig {
+                zoom_y: false,
+                ..Default::default()
+            })
+            .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+
+        // The wider plot area shows more of X, one unit per as many pixels
+        let uniforms = *harness.primitive().uniforms();
+        let per_px = |range: [f32; 2], px: f32| (range[1] - range[0]) / px;
+        let (x_per_px, y_per_px) = (
+            per_px(uniforms.x_range, uniforms.plot_size[0]),
+            per_px(uniforms.y_range, uniforms.plot_size[1]),
+        );
+        assert!((x_per_px - y_per_px).abs() < 1e-6);
+        assert!(uniforms.x_range[1] - uniforms.x_range[0] > 10.0);
+        assert_eq!(uniforms.y_range[1] - uniforms.y_range[0], 10.0);
+
+        // Zooming X alone would be undone by widening, so Y zooms along
+        harness.move_to(Point::new(400.0, 300.0));
+        let zoomed = harness.scroll(1.0).expect("zoom publishes a view");
+        let (lo, hi) = zoomed.y_range.expect("Y zoomed too");
+        assert!(hi - lo < 10.0);
+    }
+}
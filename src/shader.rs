@@ -1,17 +1,43 @@
 //! Shader-based rendering for the plotter using iced's wgpu backend.
 
-use crate::gpu_types::{RawPoint, Uniforms};
+#[cfg(feature = "tooltip")]
+use crate::core::PointGrid;
+use crate::core::{
+    self, PixelGrid, apply_elastic_resistance, clamp_range_to_bounds, is_out_of_bounds, lerp_range,
+};
+use crate::gpu_types::{CompactPoint, ImageVertex, LineVertex, RawPoint, Uniforms, marker_radius};
 use crate::pipeline::Pipeline;
+#[cfg(feature = "legend")]
+use crate::plotter::clamp_legend_offset;
 use crate::plotter::{
-    ColorMode, HoveredPoint, PlotPoints, PlotSeries, Plotter, PlotterOptions, ViewState,
+    ActiveTool, AxisConfig, AxisReference, BandSeries, BarRect, BoxPlotSeries, ColorMode,
+    ColorScale, ContextAction, ContextMenuEntry, CursorAxis, CursorLine, DEFAULT_HOVER_DISTANCE,
+    ExplicitGenerator, ExtraYAxis, LineCap, LineGlow, LineInterpolation, LineJoin, LinePattern,
+    LinkMode, MarkerBlend, MarkerShape, Measurement, Normalization, OpenContextMenu, PlotImage,
+    PlotPoint, PlotPoints, PlotSeries, Plotter, PlotterOptions, PolygonSeries, QuiverScale,
+    QuiverSeries, RenderBackend, ResponsiveConfig, Roi, RoiChange, RoiKind, SelectionMode,
+    SeriesKind, SeriesStyle, SizeUnit, ViewChangeKind, ViewState, WheelAction, YAxisMap,
+    ZoomAnchor, bar_rects, stack_ranges,
 };
-use crate::ticks::compute_ticks;
+#[cfg(feature = "annotations")]
+use crate::plotter::{Annotation, AnnotationKind, EventSeries};
+#[cfg(feature = "tooltip")]
+use crate::plotter::{HoveredPoint, PinnedTooltip};
+use crate::spectrogram::Spectrogram;
+use crate::ticks::{TickConfig, compute_ticks_from};
+use crate::triangulate::triangulate;
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use iced::keyboard;
 use iced::mouse::Cursor;
 use iced::wgpu;
 use iced::widget::shader::{self, Viewport};
-use iced::{mouse, Event, Point, Rectangle};
+use iced::{Event, Point, Rectangle, mouse};
 
 // ================================================================================
 // Interaction State
@@ -25,23 +51,86 @@ pub enum InteractionMode {
     Panning,
     /// Ctrl+drag rectangle zoom selection.
     ZoomSelecting,
+    /// Rectangle record selection for linked brushing.
+    Brushing,
+    /// Moving the legend; `grab` is the cursor's offset from its top-left corner.
+    LegendDragging {
+        grab: iced::Vector,
+    },
+    /// Drawing, moving or resizing the region of interest at `index`;
+    /// `start` is where the drag started in data coordinates.
+    RoiEditing {
+        index: usize,
+        grab: RoiGrab,
+        start: (f64, f64),
+        /// Whether the drag draws a new ROI.
+        added: bool,
+    },
+    /// Dragging cursor line `index`; `offset` is from the cursor to the line
+    /// in data units.
+    CursorLineDragging {
+        index: usize,
+        offset: f64,
+    },
+    /// Dragging point `index` of series `series`, which was at `origin`;
+    /// `offset` is from the cursor to the point on the primary Y axis.
+    PointDragging {
+        series: usize,
+        index: usize,
+        origin: (f64, f64),
+        offset: (f64, f64),
+    },
+}
+
+/// The part of a region of interest a drag holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoiGrab {
+    /// The inside, to move it.
+    Body,
+    /// The edges to resize: left, right, bottom, top.
+    Edges([bool; 4]),
+}
+
+/// Distance in pixels within which ROI edges and cursor lines can be grabbed.
+const GRAB_DISTANCE: f32 = 5.0;
+
+/// An axis's tick-label strip outside the plot area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisRegion {
+    /// Below the plot area.
+    X,
+    /// Left or right of the plot area.
+    Y,
 }
 
 /// State for elastic spring-back animation.
 #[derive(Debug, Clone)]
 pub struct ElasticState {
     /// The over-scrolled view at the start of the animation.
-    pub from_x: Option<(f32, f32)>,
-    pub from_y: Option<(f32, f32)>,
+    pub from_x: Option<(f64, f64)>,
+    pub from_y: Option<(f64, f64)>,
     /// The clamped target view to animate towards.
-    pub to_x: Option<(f32, f32)>,
-    pub to_y: Option<(f32, f32)>,
+    pub to_x: Option<(f64, f64)>,
+    pub to_y: Option<(f64, f64)>,
     /// When the animation started.
     pub start_time: std::time::Instant,
     /// Duration of the animation in milliseconds.
     pub duration_ms: u64,
 }
 
+/// Kinetic panning after a fling.
+#[derive(Debug, Clone)]
+pub struct InertiaState {
+    /// View ranges as if there were no bounds; the shown view adds elastic
+    /// resistance on top.
+    pub x_range: (f64, f64),
+    pub y_range: (f64, f64),
+    /// Data units per second along each axis.
+    pub velocity: [f64; 2],
+    /// When the view was last moved.
+    pub last_tick: std::time::Instant,
+}
+
 /// State for the shader program (persists across frames via iced's widget tree).
 #[derive(Default)]
 pub struct PlotterState {
@@ -51,16 +140,613 @@ pub struct PlotterState {
     pub drag_start: Option<Point>,
     /// View state snapshot at the start of a drag (for computing deltas).
     pub drag_start_view: Option<ViewState>,
+    /// Axes the current pan moves; just one when it started over an axis.
+    pub pan_axes: [bool; 2],
+    /// View when the current pan started, recorded in the view history
+    /// once the pan moved it.
+    pub pan_origin_view: Option<ViewState>,
     /// Last known cursor position (absolute screen coords).
     pub last_cursor: Option<Point>,
     /// Timestamp of last click for double-click detection.
     pub last_click_time: Option<std::time::Instant>,
     /// Current keyboard modifiers (for Ctrl detection).
     pub modifiers: keyboard::Modifiers,
-    /// Current position during zoom selection (relative to widget bounds).
+    /// Where the left button was last pressed (relative to widget bounds),
+    /// for telling clicks from drags.
+    pub press_position: Option<Point>,
+    /// Current position during zoom selection or brushing (relative to widget bounds).
     pub zoom_select_current: Option<Point>,
+    /// The region of interest being edited, as it was when the drag started.
+    pub roi_origin: Option<Roi>,
     /// Active elastic animation (spring-back after over-scroll).
     pub elastic_animation: Option<ElasticState>,
+    /// Last pan offset from the drag start (data units) and when it was
+    /// reached, for measuring fling velocity.
+    pub pan_sample: Option<(std::time::Instant, [f64; 2])>,
+    /// Smoothed pan velocity in data units per second.
+    pub pan_velocity: [f64; 2],
+    /// Active kinetic pan after a fling.
+    pub inertia_animation: Option<InertiaState>,
+    /// When the last interactive view change was published, for throttling.
+    pub last_view_change: Option<std::time::Instant>,
+    /// The latest interactive view change of the current gesture, published
+    /// or held back by the throttle, for the final change.
+    pub gesture_view: Option<ViewState>,
+    /// X this plot last wrote to its [`SharedCursor`], so it only clears
+    /// its own.
+    pub shared_cursor_x: Option<f64>,
+    /// Primitive from the last `draw`, reused while its key is unchanged.
+    pub cached_primitive: RefCell<Option<PlotterPrimitive>>,
+    /// Samples of generated series, shared by drawing and hit testing.
+    pub generator_samples: RefCell<SampleCache>,
+    /// X ordering of the series for hover hit testing.
+    #[cfg(feature = "tooltip")]
+    pub hover_index: HoverIndex,
+}
+
+/// Stored series with at least this many points are hit tested through a
+/// screen-space [`PointGrid`] rather than their X ordering.
+#[cfg(feature = "tooltip")]
+const HOVER_GRID_POINTS: usize = 10_000;
+
+/// Points of each series ordered by X, so hover hit testing only looks at
+/// the points within snapping distance of the cursor's X instead of all of
+/// them. Rebuilt when the series data changes, keyed like the primitive cache.
+///
+/// Large series can be dense within that distance (a long zoomed-out trace
+/// has thousands of points per pixel column), so they are also bucketed by
+/// screen position, once per view, and only the cells around the cursor are
+/// visited. This covers every stored point, including the ones decimation
+/// leaves out of the drawn line.
+#[cfg(feature = "tooltip")]
+#[derive(Debug, Default)]
+pub struct HoverIndex {
+    key: Option<u64>,
+    /// Per series: `None` when the points are already sorted by X or not
+    /// stored in a slice, else the indices of the non-NaN points sorted by X.
+    order: Vec<Option<Vec<u32>>>,
+    /// View and layout the grids were built for.
+    view_key: Option<u64>,
+    /// Per series: screen-space grid, built on first use in a view.
+    grids: Vec<Option<PointGrid>>,
+}
+
+#[cfg(feature = "tooltip")]
+impl HoverIndex {
+    fn refresh(&mut self, series: &[PlotSeries<'_>]) {
+        let mut hasher = DefaultHasher::new();
+        for s in series {
+            s.generation.hash(&mut hasher);
+            hash_points(&s.points, &mut hasher);
+        }
+        let key = hasher.finish();
+        if self.key == Some(key) {
+            return;
+        }
+        self.key = Some(key);
+        self.view_key = None;
+        self.order = series
+            .iter()
+            .map(|s| {
+                let points = s.points.as_slice()?;
+                if points.windows(2).all(|w| w[0].x <= w[1].x) {
+                    return None;
+                }
+                let mut order: Vec<u32> = (0..points.len() as u32)
+                    .filter(|&i| !points[i as usize].x.is_nan())
+                    .collect();
+                order.sort_by(|&a, &b| points[a as usize].x.total_cmp(&points[b as usize].x));
+                Some(order)
+            })
+            .collect();
+    }
+
+    /// Drop the grids when the view or layout they were built for changed.
+    fn set_view(&mut self, view_key: u64, series: usize) {
+        if self.view_key != Some(view_key) {
+            self.view_key = Some(view_key);
+            self.grids = vec![None; series];
+        }
+    }
+
+    /// Grid of series `index` for the current view, built by `build` if
+    /// there is none yet.
+    fn grid(&mut self, index: usize, build: impl FnOnce() -> PointGrid) -> &PointGrid {
+        self.grids[index].get_or_insert_with(build)
+    }
+
+    /// Indices of the points of series `index` with X in `range`.
+    fn window<'p>(
+        &'p self,
+        index: usize,
+        points: &'p [PlotPoint],
+        (lo, hi): (f64, f64),
+    ) -> Box<dyn Iterator<Item = usize> + 'p> {
+        match self.order.get(index) {
+            Some(Some(order)) => {
+                let x = |i: &u32| points[*i as usize].x;
+                let start = order.partition_point(|i| x(i) < lo);
+                let end = order.partition_point(|i| x(i) <= hi);
+                Box::new(order[start..end.max(start)].iter().map(|&i| i as usize))
+            }
+            _ => {
+                let start = points.partition_point(|p| p.x < lo);
+                let end = points.partition_point(|p| p.x <= hi);
+                Box::new(start..end.max(start))
+            }
+        }
+    }
+}
+
+/// Generated (x, y) samples, shared by the cache and its readers.
+type Samples = Rc<[(f64, f64)]>;
+
+/// Samples of each generated series, reused while the series is unchanged
+/// and, for adaptive sampling, the view and plot size are too. Hovering a
+/// plot hit tests on every cursor move, and each test would otherwise run
+/// the refinement again.
+#[derive(Debug, Default)]
+pub struct SampleCache {
+    /// Per series: key of the samples, and the samples
+    entries: Vec<Option<(u64, Samples)>>,
+}
+
+impl SampleCache {
+    /// Samples of `generator`, the points of series `index`, see
+    /// [`ExplicitGenerator::sample`].
+    fn sample(
+        &mut self,
+        index: usize,
+        series: &PlotSeries<'_>,
+        generator: &ExplicitGenerator<'_>,
+        visible_x: (f64, f64),
+        px_per_unit: (f64, f64),
+    ) -> Samples {
+        let mut hasher = DefaultHasher::new();
+        series.generation.hash(&mut hasher);
+        hash_points(&series.points, &mut hasher);
+        if generator.adaptive.is_some() {
+            (visible_x, px_per_unit).bits().hash(&mut hasher);
+        }
+        let key = hasher.finish();
+
+        if self.entries.len() <= index {
+            self.entries.resize(index + 1, None);
+        }
+        match &self.entries[index] {
+            Some((cached, samples)) if *cached == key => samples.clone(),
+            _ => {
+                let samples: Samples = generator.sample(visible_x, px_per_unit).into();
+                self.entries[index] = Some((key, samples.clone()));
+                samples
+            }
+        }
+    }
+}
+
+/// Hash what identifies a series' data for caching: every stored point, or
+/// the sampling setup of generators.
+fn hash_points<H: Hasher>(points: &PlotPoints<'_>, hasher: &mut H) {
+    match points {
+        PlotPoints::Owned(_) | PlotPoints::Borrowed(_) => {
+            let points = points.as_slice().unwrap_or_default();
+            points.len().hash(hasher);
+            fold_words(points.iter().flat_map(|p| [p.x.to_bits(), p.y.to_bits()])).hash(hasher);
+        }
+        PlotPoints::Generator(generator) => {
+            [generator.x_range.0, generator.x_range.1]
+                .map(f64::to_bits)
+                .hash(hasher);
+            generator.points.hash(hasher);
+            generator
+                .adaptive
+                .map(|a| (a.max_points, a.tolerance.to_bits()))
+                .hash(hasher);
+        }
+        PlotPoints::Parametric(generator) => {
+            [generator.t_range.0, generator.t_range.1]
+                .map(f64::to_bits)
+                .hash(hasher);
+            generator.points.hash(hasher);
+        }
+    }
+}
+
+// ================================================================================
+// Content Hashing
+// ================================================================================
+
+/// Fold 64-bit words into one, such that changing any single word changes
+/// the result. Feeding millions of points to `DefaultHasher` one by one
+/// would cost more than the rest of a cached frame.
+fn fold_words(words: impl IntoIterator<Item = u64>) -> u64 {
+    words.into_iter().fold(0, |h, w| {
+        (h.rotate_left(5) ^ w).wrapping_mul(0x517c_c1b7_2722_0a95)
+    })
+}
+
+/// Length and folded bits of a value array.
+fn fold_values(values: &[f32]) -> (usize, u64) {
+    let words = values.iter().map(|v| u64::from(v.to_bits()));
+    (values.len(), fold_words(words))
+}
+
+/// Length and folded bits of a color array.
+fn fold_colors(colors: &[iced::Color]) -> (usize, u64) {
+    let words = colors.iter().flat_map(|c| {
+        let [r, g, b, a] = c.bits().map(u64::from);
+        [r << 32 | g, b << 32 | a]
+    });
+    (colors.len(), fold_words(words))
+}
+
+/// The bits of floats and of types made of them, for the `Hash` impls below.
+trait Bits {
+    type Bits: Hash;
+    fn bits(&self) -> Self::Bits;
+}
+
+impl Bits for f32 {
+    type Bits = u32;
+    fn bits(&self) -> u32 {
+        self.to_bits()
+    }
+}
+
+impl Bits for f64 {
+    type Bits = u64;
+    fn bits(&self) -> u64 {
+        self.to_bits()
+    }
+}
+
+impl Bits for iced::Color {
+    type Bits = [u32; 4];
+    fn bits(&self) -> [u32; 4] {
+        [self.r, self.g, self.b, self.a].map(f32::to_bits)
+    }
+}
+
+impl Bits for iced::Vector {
+    type Bits = [u32; 2];
+    fn bits(&self) -> [u32; 2] {
+        [self.x, self.y].map(f32::to_bits)
+    }
+}
+
+impl<T: Bits> Bits for &T {
+    type Bits = T::Bits;
+    fn bits(&self) -> T::Bits {
+        T::bits(self)
+    }
+}
+
+impl<T: Bits> Bits for Option<T> {
+    type Bits = Option<T::Bits>;
+    fn bits(&self) -> Self::Bits {
+        self.as_ref().map(T::bits)
+    }
+}
+
+impl<A: Bits, B: Bits> Bits for (A, B) {
+    type Bits = (A::Bits, B::Bits);
+    fn bits(&self) -> Self::Bits {
+        (self.0.bits(), self.1.bits())
+    }
+}
+
+/// Everything drawn of the series. Stored points are hashed in full, with
+/// the values a [`ColorMode::ValueFn`] gives them; generators by their
+/// sampling setup.
+impl Hash for PlotSeries<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.generation.hash(state);
+        self.label.hash(state);
+        self.kind.hash(state);
+        self.y_axis.hash(state);
+        self.opacity.bits().hash(state);
+        self.z_order.hash(state);
+        self.stack_id.hash(state);
+        self.decimate.hash(state);
+        hash_points(&self.points, state);
+        self.style.hash(state);
+        if let (ColorMode::ValueFn { function, .. }, Some(points)) =
+            (&self.style.color, self.points.as_slice())
+        {
+            let values = points.iter().enumerate();
+            fold_words(values.map(|(i, p)| u64::from(function(i, p.x, p.y).to_bits()))).hash(state);
+        }
+    }
+}
+
+impl Hash for SeriesKind {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let SeriesKind::Bars { width, baseline } = self {
+            (width, baseline).bits().hash(state);
+        }
+    }
+}
+
+impl Hash for SeriesStyle<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.color.hash(state);
+        self.marker_shape.hash(state);
+        self.marker_size.bits().hash(state);
+        self.marker_size_unit.hash(state);
+        self.line_pattern.hash(state);
+        self.interpolation.hash(state);
+        self.line_width.bits().hash(state);
+        self.line_width_unit.hash(state);
+        self.glow.hash(state);
+        self.line_join.hash(state);
+        self.line_cap.hash(state);
+        self.marker_sizes.as_deref().map(fold_values).hash(state);
+        self.point_colors.as_deref().map(fold_colors).hash(state);
+    }
+}
+
+/// The function of a [`ColorMode::ValueFn`] is left out: it is hashed by
+/// its values, with the series' points.
+impl Hash for ColorMode<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ColorMode::Solid(color) => color.bits().hash(state),
+            ColorMode::ValueGradient {
+                low,
+                high,
+                values,
+                scale,
+            } => {
+                (low, high).bits().hash(state);
+                values.as_deref().map(fold_values).hash(state);
+                scale.hash(state);
+            }
+            ColorMode::ValueFn {
+                low, high, scale, ..
+            } => {
+                (low, high).bits().hash(state);
+                scale.hash(state);
+            }
+            ColorMode::IndexGradient { start, end } => (start, end).bits().hash(state),
+            #[cfg(feature = "colormap")]
+            ColorMode::Colormap {
+                name,
+                values,
+                scale,
+            } => {
+                name.hash(state);
+                values.as_deref().map(fold_values).hash(state);
+                scale.hash(state);
+            }
+        }
+    }
+}
+
+impl Hash for ColorScale {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.range.bits().hash(state);
+        self.normalization.hash(state);
+    }
+}
+
+impl Hash for Normalization {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let Normalization::Power(exponent) = self {
+            exponent.bits().hash(state);
+        }
+    }
+}
+
+#[cfg(feature = "colormap")]
+impl Hash for crate::colormap::ColormapName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        use crate::colormap::ColormapName;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ColormapName::Custom(stops) => {
+                stops.iter().map(Bits::bits).collect::<Vec<_>>().hash(state)
+            }
+            ColormapName::Reversed(base) => base.hash(state),
+            ColormapName::Discrete { base, levels } => (base, levels).hash(state),
+            _ => {}
+        }
+    }
+}
+
+impl Hash for LineGlow {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.color.bits(), self.width.bits(), self.alpha.bits()).hash(state);
+        self.offset.bits().hash(state);
+    }
+}
+
+impl Hash for PolygonSeries {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for points in &self.loops {
+            let words = points.iter().flat_map(|p| [p.x.to_bits(), p.y.to_bits()]);
+            (points.len(), fold_words(words)).hash(state);
+        }
+        (self.fill_color, self.outline_color).bits().hash(state);
+        self.outline_width.bits().hash(state);
+    }
+}
+
+impl Hash for BandSeries {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let words = self
+            .samples
+            .iter()
+            .flat_map(|s| [s.x, s.low, s.high, s.center].map(f64::to_bits));
+        (self.samples.len(), fold_words(words)).hash(state);
+        (self.fill_color, self.center_color).bits().hash(state);
+        self.center_width.bits().hash(state);
+    }
+}
+
+#[cfg(feature = "annotations")]
+impl Hash for EventSeries {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for event in &self.events {
+            (event.start, event.end).bits().hash(state);
+            (&event.label, event.color.bits()).hash(state);
+        }
+        (self.fill_alpha, self.edge_width).bits().hash(state);
+        (self.show_labels, self.label_size.bits()).hash(state);
+    }
+}
+
+impl Hash for BoxPlotSeries {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for summary in &self.boxes {
+            summary.label.hash(state);
+            [
+                summary.min,
+                summary.q1,
+                summary.median,
+                summary.q3,
+                summary.max,
+            ]
+            .map(f64::to_bits)
+            .hash(state);
+            let outliers = summary.outliers.iter().map(|v| v.to_bits());
+            (summary.outliers.len(), fold_words(outliers)).hash(state);
+        }
+        self.box_width.bits().hash(state);
+        (self.fill_color, self.line_color).bits().hash(state);
+        (self.line_width, self.outlier_size).bits().hash(state);
+        self.outlier_shape.hash(state);
+    }
+}
+
+impl Hash for QuiverSeries {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let words = self
+            .arrows
+            .iter()
+            .flat_map(|a| [a.x, a.y, a.u, a.v].map(f64::to_bits));
+        (self.arrows.len(), fold_words(words)).hash(state);
+        match self.scale {
+            QuiverScale::Data(factor) => (0u8, factor.to_bits()).hash(state),
+            QuiverScale::Pixels(length) => (1u8, length.to_bits()).hash(state),
+        }
+        self.color.hash(state);
+        (self.line_width, self.head_size).bits().hash(state);
+    }
+}
+
+/// The pixels can't change after construction, so the image's id stands
+/// in for them.
+impl Hash for PlotImage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.id(), self.size()).hash(state);
+        (self.x_range.bits(), self.y_range.bits()).hash(state);
+        (self.opacity.bits(), self.smooth).hash(state);
+    }
+}
+
+#[cfg(feature = "annotations")]
+impl Hash for Annotation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(&self.kind).hash(state);
+        match self.kind {
+            AnnotationKind::HLine { y: v } | AnnotationKind::VLine { x: v } => v.bits().hash(state),
+            AnnotationKind::HBand { y: range } | AnnotationKind::VBand { x: range } => {
+                range.bits().hash(state)
+            }
+            AnnotationKind::Text { x, y, anchor } => ((x, y).bits(), anchor).hash(state),
+            AnnotationKind::Arrow { from, to } => (from.bits(), to.bits()).hash(state),
+        }
+        (self.color.bits(), self.width.bits(), self.pattern).hash(state);
+        (&self.label, self.label_size.bits()).hash(state);
+    }
+}
+
+/// The options the shader draws from. The legend, tooltip, readouts, tick
+/// labels and titles are drawn by the overlay; the room they take reaches
+/// the primitive as its padding.
+impl Hash for PlotterOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.padding.bits().hash(state);
+        (
+            self.grid.show,
+            self.grid.color.bits(),
+            self.grid.line_width.bits(),
+        )
+            .hash(state);
+        (&self.x_axis, &self.y_axis, &self.extra_y_axes).hash(state);
+        self.responsive.hash(state);
+        self.marker_bleed.bits().hash(state);
+        self.compact_markers_threshold.hash(state);
+        (self.marker_blend, self.multisample, self.backend).hash(state);
+        self.performance
+            .as_ref()
+            .map(|p| p.min_pixels_per_marker.bits())
+            .hash(state);
+        (self.bar_layout, self.stack_mode).hash(state);
+        self.background_color.bits().hash(state);
+        #[cfg(feature = "annotations")]
+        self.annotations.hash(state);
+    }
+}
+
+/// The axis line and ticks; labels and title are drawn by the overlay.
+impl Hash for AxisConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.show, self.color.bits(), self.line_width.bits()).hash(state);
+        (&self.ticks, self.kind).hash(state);
+        match self.reference {
+            AxisReference::Absolute => 0u8.hash(state),
+            AxisReference::FirstSample => 1u8.hash(state),
+            AxisReference::LastSample => 2u8.hash(state),
+            AxisReference::Value(v) => (3u8, v.to_bits()).hash(state),
+        }
+    }
+}
+
+impl Hash for ExtraYAxis {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&self.config, self.side).hash(state);
+        (self.range.bits(), self.width.bits()).hash(state);
+    }
+}
+
+impl Hash for TickConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            TickConfig::Auto {
+                min_ticks,
+                max_ticks,
+            } => (min_ticks, max_ticks).hash(state),
+            TickConfig::Fixed(ticks) => {
+                ticks.iter().map(Bits::bits).collect::<Vec<_>>().hash(state)
+            }
+            TickConfig::Labeled(ticks) => ticks
+                .iter()
+                .map(|(position, label)| (position.to_bits(), label))
+                .collect::<Vec<_>>()
+                .hash(state),
+        }
+    }
+}
+
+impl Hash for ResponsiveConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        [
+            self.hide_legend_width,
+            self.hide_legend_height,
+            self.hide_titles_width,
+            self.hide_titles_height,
+            self.compact_x_ticks_width,
+            self.compact_y_ticks_height,
+        ]
+        .map(f32::to_bits)
+        .hash(state);
+        self.compact_ticks.hash(state);
+    }
 }
 
 // ================================================================================
@@ -72,176 +758,929 @@ pub struct PlotterState {
 pub struct RenderConfig {
     pub show_markers: bool,
     pub show_lines: bool,
+    /// Margin in pixels around the plot area in which markers are not clipped.
+    pub marker_bleed: f32,
+    /// Blend markers additively, see [`MarkerBlend::Additive`].
+    pub additive_markers: bool,
+    /// Draw markers and lines multisampled, see [`PlotterOptions::multisample`].
+    pub multisample: bool,
+    /// Leave everything to the canvas, see [`RenderBackend::Software`].
+    pub software: bool,
+}
+
+/// Distance in pixels the cursor may move between press and release for a click.
+#[cfg(feature = "tooltip")]
+const CLICK_SLOP: f32 = 3.0;
+
+/// Longest miter, in half line widths, before a corner is beveled instead
+/// (the SVG default).
+const MITER_LIMIT: f32 = 4.0;
+
+/// A pan released longer than this after the last move doesn't fling.
+const FLING_MAX_IDLE: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Speed in view widths (or heights) per second below which kinetic
+/// panning stops; flings need ten times this.
+const INERTIA_STOP_SPEED: f64 = 0.05;
+
+/// How much faster a fling slows down once past the bounds.
+const INERTIA_OVERSCROLL_FRICTION: f32 = 8.0;
+
+/// Alpha multiplier for records outside a linked selection in [`LinkMode::Highlight`].
+const BRUSH_DIM_ALPHA: f32 = 0.2;
+
+/// Alpha multiplier for stacked area fills, so grid lines show through.
+const STACK_FILL_ALPHA: f32 = 0.6;
+
+/// Line vertices reserved per point in performance mode: one quad per
+/// segment, joins aside.
+const LINE_VERTICES_PER_POINT: usize = 6;
+
+/// Per-series line rendering parameters.
+#[derive(Debug, Clone, Copy)]
+struct SeriesLine {
+    width: f32,
+    width_unit: SizeUnit,
+    pattern: LinePattern,
+    interpolation: LineInterpolation,
+    glow: Option<LineGlow>,
+    join: LineJoin,
+    cap: LineCap,
+}
+
+/// RGBA texels of an [`ImageLayer`].
+#[derive(Clone, Debug)]
+enum ImageTexels {
+    /// A [`PlotImage`]'s pixels
+    Fixed(Arc<[u8]>),
+    /// A [`Spectrogram`]'s ring of columns, which it keeps writing to
+    Shared(Arc<Mutex<Vec<u8>>>),
+}
+
+/// Texture drawn as a quad in the plot area: a [`PlotImage`] or a
+/// [`Spectrogram`].
+#[derive(Clone, Debug)]
+struct ImageLayer {
+    texels: ImageTexels,
+    size: [u32; 2],
+    smooth: bool,
+    /// Texels id and columns written as of building the primitive, see
+    /// [`Pipeline::update_image`]
+    key: (u64, u64),
+    /// Quad clipped to the plot area, in screen space
+    vertices: [ImageVertex; 6],
 }
 
 #[derive(Debug, Clone)]
 pub struct TickInfo {
-    pub x_ticks: Vec<f32>,
-    pub y_ticks: Vec<f32>,
+    pub x_ticks: Vec<f64>,
+    pub y_ticks: Vec<f64>,
+}
+
+/// An axis-aligned rectangle of the grid pass, in widget-local logical pixels.
+#[derive(Clone, Copy, Debug)]
+struct GridQuad {
+    x: [f32; 2],
+    y: [f32; 2],
+    color: [f32; 4],
+}
+
+/// The plot background, grid lines and axes, kept as rectangles until
+/// prepare time, when the scale factor is known and they can be snapped to
+/// physical pixels.
+#[derive(Clone, Debug, Default)]
+struct GridSpec {
+    /// Background and grid lines, beneath the annotations
+    below: Vec<GridQuad>,
+    /// Annotation triangles, drawn as they are
+    annotations: Vec<LineVertex>,
+    /// Axis lines, above the annotations
+    above: Vec<GridQuad>,
+}
+
+impl GridSpec {
+    fn vertex_count(&self) -> usize {
+        (self.below.len() + self.above.len()) * 6 + self.annotations.len()
+    }
+}
+
+/// The lines and markers of the series sharing one
+/// [`PlotSeries::z_order`]. Each layer's lines are drawn, then its markers,
+/// before the next layer.
+///
+/// Layer 0 also holds the polygons, bands, box plots and quivers, beneath
+/// its series.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawLayer {
+    /// Range of the line vertices
+    pub lines: std::ops::Range<u32>,
+    /// Range of the marker instances, in whichever format is uploaded
+    pub markers: std::ops::Range<u32>,
 }
 
 /// The primitive that holds all data to be rendered on the GPU.
-#[derive(Debug)]
+///
+/// Vertex data is reference-counted so an unchanged primitive can be reused
+/// across frames cheaply.
+#[derive(Clone, Debug)]
 pub struct PlotterPrimitive {
     /// Points to render as markers
-    points: Vec<RawPoint>,
+    points: Arc<[RawPoint]>,
+    /// Markers in the compact format; when non-empty, used instead of `points`
+    compact_points: Arc<[CompactPoint]>,
     /// Pre-computed line vertices (triangles for thick lines)
-    line_vertices: Vec<RawPoint>,
+    line_vertices: Arc<[LineVertex]>,
+    /// Lines and markers of each z-order, in drawing order
+    layers: Arc<[DrawLayer]>,
     /// Uniform data for coordinate transformation
     uniforms: Uniforms,
     /// Config for what to render
     config: RenderConfig,
-    /// Pre-computed grid line vertices
-    grid_vertices: Vec<RawPoint>,
+    /// Background, grid, annotations and axes, snapped to pixels when
+    /// uploaded
+    grid: Arc<GridSpec>,
     /// Selection rectangle overlay vertices (if zoom-selecting)
-    selection_vertices: Vec<RawPoint>,
+    selection_vertices: Arc<[LineVertex]>,
     /// Highlight ring vertices (for tooltip hover indicator)
-    highlight_vertices: Vec<RawPoint>,
+    highlight_vertices: Arc<[LineVertex]>,
+    /// Images and the spectrogram, drawn above the grid and beneath the series
+    images: Arc<[ImageLayer]>,
     /// Series boundaries to prevent line connections between series
     #[allow(dead_code)]
-    series_boundaries: Vec<usize>,
+    series_boundaries: Arc<[usize]>,
     pub tick_info: TickInfo,
+    /// Data-space origin of the local frame, see [`Self::origin`].
+    origin: [f64; 2],
+    /// Fingerprint of everything the primitive was built from. The pipeline
+    /// skips GPU uploads when it already holds data for the same key.
+    key: u64,
+    /// Fingerprint of the data and view, leaving out the selection and
+    /// highlight overlays. Primitives with the same data key share markers,
+    /// lines and grid.
+    data_key: u64,
 }
 
 impl PlotterPrimitive {
+    /// Marker points, one per data point, relative to [`Uniforms::origin`].
+    ///
+    /// Empty when the markers use the compact format, see [`Self::compact_points`].
+    pub fn points(&self) -> &[RawPoint] {
+        &self.points
+    }
+
+    /// Marker points in the compact format, relative to the view.
+    pub fn compact_points(&self) -> &[CompactPoint] {
+        &self.compact_points
+    }
+
+    /// Line and polygon fill triangles.
+    pub fn line_vertices(&self) -> &[LineVertex] {
+        &self.line_vertices
+    }
+
+    /// Ranges of [`Self::line_vertices`] and the markers drawn for each
+    /// z-order, lowest first.
+    pub fn layers(&self) -> &[DrawLayer] {
+        &self.layers
+    }
+
+    /// Grid line triangles, snapped to whole logical pixels.
+    pub fn grid_vertices(&self) -> Vec<LineVertex> {
+        self.grid_vertices_at(PixelGrid::default())
+    }
+
+    /// Grid line triangles, snapped to the physical pixels of `pixels`.
+    pub fn grid_vertices_at(&self, pixels: PixelGrid) -> Vec<LineVertex> {
+        Self::generate_grid_vertices(&self.grid, pixels)
+    }
+
+    /// Zoom-selection / brush rectangle triangles.
+    pub fn selection_vertices(&self) -> &[LineVertex] {
+        &self.selection_vertices
+    }
+
+    /// Hover highlight ring triangles.
+    pub fn highlight_vertices(&self) -> &[LineVertex] {
+        &self.highlight_vertices
+    }
+
+    /// Image and spectrogram quad triangles, clipped to the plot area; six
+    /// vertices per visible layer.
+    pub fn image_vertices(&self) -> Vec<ImageVertex> {
+        self.images
+            .iter()
+            .flat_map(|image| image.vertices)
+            .collect()
+    }
+
+    /// The quad covering the widget that blends the multisampled markers and
+    /// lines onto the frame, drawn after the image layers.
+    fn composite_vertices(&self) -> [ImageVertex; 6] {
+        let [width, height] = self.uniforms.viewport_size;
+        let vertex = |x: f32, y: f32| ImageVertex {
+            position: [x * width, y * height],
+            uv: [x, y],
+            opacity: 1.0,
+        };
+        [
+            vertex(0.0, 0.0),
+            vertex(1.0, 0.0),
+            vertex(0.0, 1.0),
+            vertex(1.0, 0.0),
+            vertex(1.0, 1.0),
+            vertex(0.0, 1.0),
+        ]
+    }
+
+    /// Uniforms the shaders receive.
+    pub fn uniforms(&self) -> &Uniforms {
+        &self.uniforms
+    }
+
+    /// Data-space origin of the local frame at full precision;
+    /// [`Uniforms::origin`] only holds it rounded to `f32`.
+    pub fn origin(&self) -> [f64; 2] {
+        self.origin
+    }
+
+    /// Render configuration.
+    pub fn config(&self) -> RenderConfig {
+        self.config
+    }
+
+    /// Fingerprint of everything the primitive was built from.
+    pub(crate) fn key(&self) -> u64 {
+        self.key
+    }
+
     /// Create a new primitive from plotter data.
     ///
     /// `view_x_range` and `view_y_range` are the resolved visible ranges
     /// (already accounting for ViewState auto-fit).
     /// `selection_rect` is an optional screen-space rectangle for zoom selection overlay.
+    /// `y_maps` holds each series' map onto the primary Y axis (see
+    /// [`Plotter::y_axis_maps`](crate::plotter::Plotter::y_axis_maps)); missing
+    /// entries mean the primary axis.
+    /// `emphasis` is a series drawn wider by its scale while the others'
+    /// alpha is multiplied by the dim factor, see
+    /// [`LegendConfig::hover_emphasis`](crate::plotter::LegendConfig::hover_emphasis).
+    #[allow(clippy::too_many_arguments)]
     pub fn new<'a>(
         series: &'a [PlotSeries<'a>],
+        polygons: &[PolygonSeries],
+        bands: &[BandSeries],
+        box_plots: &[BoxPlotSeries],
+        quivers: &[QuiverSeries],
+        images: &[PlotImage],
+        spectrogram: Option<&Spectrogram>,
         bounds: Rectangle,
         options: &PlotterOptions,
-        view_x_range: [f32; 2],
-        view_y_range: [f32; 2],
+        view_x_range: [f64; 2],
+        view_y_range: [f64; 2],
+        padding: iced::Padding,
         selection_rect: Option<(Point, Point)>,
         hidden_series: &std::collections::HashSet<usize>,
         highlight: Option<(Point, [f32; 4], f32, f32)>, // (screen_pos, color, radius, width)
+        brushed: Option<(&std::collections::HashSet<usize>, LinkMode)>,
+        tick_offsets: [f64; 2],
+        y_maps: &[Option<YAxisMap>],
+        emphasis: Option<(usize, f32, f32)>, // (series, dim alpha, width scale)
+        samples: &mut SampleCache,
     ) -> Self {
-        let config = RenderConfig {
+        // An empty linked selection means nothing is brushed
+        let brushed = brushed.filter(|(selected, _)| !selected.is_empty());
+        let is_filtered = |i: usize| {
+            brushed.is_some_and(|(sel, mode)| mode == LinkMode::Filter && !sel.contains(&i))
+        };
+
+        let mut config = RenderConfig {
             show_markers: true,
             show_lines: true,
+            marker_bleed: 0.0,
+            additive_markers: options.marker_blend == MarkerBlend::Additive,
+            multisample: options.multisample,
+            software: options.backend == RenderBackend::Software,
         };
 
-        // Collect all points with color info, tracking series boundaries
-        let mut all_points_with_colors: Vec<(f32, f32, ColorMode<'a>)> = Vec::new();
+        // Collect all points, tracking series boundaries and colors
+        let mut data_points: Vec<(f64, f64)> = Vec::new();
         let mut series_boundaries: Vec<usize> = Vec::new();
+        // Indices where a series' line restarts after a non-finite point
+        let mut line_breaks: Vec<usize> = Vec::new();
+        let mut series_colors: Vec<&ColorMode<'a>> = Vec::new();
+        let mut series_y_maps: Vec<Option<YAxisMap>> = Vec::new();
+        // Marker shape, signed radius (see `RawPoint::size`), opacity and
+        // size scale per series
+        let mut series_markers: Vec<(u32, f32, f32, f32)> = Vec::new();
+        // Per-point marker radii, whether they are in data units, and
+        // per-point colors, per series
+        let mut series_point_styles = Vec::new();
+        // Record index (point index within its series) of each collected point
+        let mut record_indices: Vec<usize> = Vec::new();
+        let bars = bar_rects(series, hidden_series, options.bar_layout);
+        let mut series_bars: Vec<&[BarRect]> = Vec::new();
+        let stacks = stack_ranges(series, hidden_series, options.stack_mode);
+        let mut series_stacks: Vec<&[[f64; 2]]> = Vec::new();
 
         // We still need data-space min/max for color gradient normalization
-        let mut data_y_min = f32::INFINITY;
-        let mut data_y_max = f32::NEG_INFINITY;
-
-        for (idx, s) in series.iter().enumerate() {
+        let mut data_y_min = f64::INFINITY;
+        let mut data_y_max = f64::NEG_INFINITY;
+
+        let mut series_lines: Vec<SeriesLine> = Vec::new();
+        // Records decimation kept for each series' line, when its markers
+        // all stay
+        let mut series_line_kept: Vec<Option<Vec<usize>>> = Vec::new();
+        // Whether each series' points were decimated, markers and all
+        let mut series_decimated: Vec<bool> = Vec::new();
+        // Points inside the view per series, for the marker density
+        let mut series_visible: Vec<usize> = Vec::new();
+
+        // Series are collected lowest z-order first, and each z-order is a
+        // draw layer starting at the recorded position in the arrays above
+        let mut order: Vec<usize> = (0..series.len()).collect();
+        order.sort_by_key(|&i| series[i].z_order);
+        let mut layer_starts: Vec<(i32, usize)> = Vec::new();
+
+        for (idx, s) in order.into_iter().map(|i| (i, &series[i])) {
             // Skip hidden series
             if hidden_series.contains(&idx) {
                 continue;
             }
+            if layer_starts.last().is_none_or(|&(z, _)| z != s.z_order) {
+                layer_starts.push((s.z_order, series_boundaries.len()));
+            }
+
+            // Bar series keep their points for colors and hit testing, but
+            // draw neither markers nor lines
+            let is_bars = matches!(s.kind, SeriesKind::Bars { .. });
+            let (alpha_scale, width_scale) = match emphasis {
+                Some((series, _, width_scale)) if series == idx => (1.0, width_scale),
+                Some((_, dim_alpha, _)) => (dim_alpha, 1.0),
+                None => (1.0, 1.0),
+            };
+            series_boundaries.push(data_points.len());
+            series_colors.push(&s.style.color);
+            series_bars.push(&bars[idx]);
+            series_stacks.push(&stacks[idx]);
+            series_markers.push((
+                if is_bars {
+                    MarkerShape::None
+                } else {
+                    s.style.marker_shape
+                }
+                .as_u32(),
+                marker_radius(
+                    s.style.marker_size,
+                    s.style.marker_size_unit == SizeUnit::Data,
+                ),
+                s.opacity * alpha_scale,
+                width_scale,
+            ));
+            series_point_styles.push((
+                s.style.marker_sizes.as_deref(),
+                s.style.marker_size_unit == SizeUnit::Data,
+                s.style.point_colors.as_deref(),
+            ));
+            let y_map = y_maps.get(idx).copied().flatten();
+            series_y_maps.push(y_map);
+            let to_primary = |y: f64| y_map.map_or(y, |m| m.apply(y));
+
+            // Bars are drawn one per point and stacks need every point to
+            // line up, so neither is decimated, and neither is a series
+            // without a line
+            let slice = s.points.as_slice();
+            let kept = slice
+                .filter(|_| {
+                    s.decimate
+                        && !is_bars
+                        && stacks[idx].is_empty()
+                        && s.style.line_pattern != LinePattern::None
+                })
+                .and_then(|points| {
+                    core::m4_indices(
+                        |i| points[i].x,
+                        |i| points[i].y,
+                        points.len(),
+                        view_x_range,
+                        (bounds.width - padding.x()).ceil() as usize,
+                    )
+                });
+            // Only the line is decimated, so every marker stays, unless the
+            // series draws none or performance mode would skip them as too
+            // dense anyway
+            let (kept, line_kept) =
+                if s.style.marker_shape == MarkerShape::None || options.performance.is_some() {
+                    (kept, None)
+                } else {
+                    (None, kept)
+                };
+            series_decimated.push(kept.is_some());
+            series_line_kept.push(line_kept);
+            // Performance mode sizes the point arrays up front instead of
+            // growing them through millions of points
+            if options.performance.is_some() {
+                let count = kept
+                    .as_ref()
+                    .map_or(slice.map_or(0, <[PlotPoint]>::len), Vec::len);
+                data_points.reserve(count);
+                record_indices.reserve(count);
+            }
 
-            series_boundaries.push(all_points_with_colors.len());
+            // Non-finite points (e.g. sensor dropouts) are left out and break
+            // the line
+            let series_start = data_points.len();
+            let mut after_gap = false;
+            let mut visible = 0;
+            let mut push_point = |x: f64, y: f64, record: usize| {
+                if !(x.is_finite() && y.is_finite()) {
+                    after_gap = true;
+                    return;
+                }
+                let in_view = |v: f64, range: [f64; 2]| range[0] <= v && v <= range[1];
+                if in_view(x, view_x_range) && in_view(to_primary(y), view_y_range) {
+                    visible += 1;
+                }
+                if after_gap && data_points.len() > series_start {
+                    line_breaks.push(data_points.len());
+                }
+                after_gap = false;
+                data_points.push((x, y));
+                record_indices.push(record);
+                data_y_min = data_y_min.min(to_primary(y));
+                data_y_max = data_y_max.max(to_primary(y));
+            };
+            series_lines.push(SeriesLine {
+                width: s.style.line_width * width_scale,
+                width_unit: s.style.line_width_unit,
+                pattern: if is_bars {
+                    LinePattern::None
+                } else {
+                    s.style.line_pattern
+                },
+                interpolation: s.style.interpolation,
+                glow: s.style.glow.filter(|_| !is_bars),
+                join: s.style.line_join,
+                cap: s.style.line_cap,
+            });
             match &s.points {
-                PlotPoints::Owned(points) => {
-                    for p in points {
-                        all_points_with_colors.push((p.x, p.y, s.style.color.clone()));
-                        data_y_min = data_y_min.min(p.y);
-                        data_y_max = data_y_max.max(p.y);
+                PlotPoints::Owned(_) | PlotPoints::Borrowed(_) => {
+                    let points = slice.unwrap_or_default();
+                    let indices: Box<dyn Iterator<Item = usize>> = match kept {
+                        Some(kept) => Box::new(kept.into_iter()),
+                        None => Box::new(0..points.len()),
+                    };
+                    for i in indices {
+                        let p = &points[i];
+                        if is_filtered(i) {
+                            continue;
+                        }
+                        // Stacked series are drawn along their stack's top
+                        let y = stacks[idx].get(i).map_or(p.y, |range| range[1]);
+                        push_point(p.x, y, i);
                     }
                 }
-                PlotPoints::Borrowed(points) => {
-                    for p in *points {
-                        all_points_with_colors.push((p.x, p.y, s.style.color.clone()));
-                        data_y_min = data_y_min.min(p.y);
-                        data_y_max = data_y_max.max(p.y);
+                PlotPoints::Parametric(generator) => {
+                    for (i, (x, y)) in generator.sample().enumerate() {
+                        push_point(x, y, i);
                     }
                 }
                 PlotPoints::Generator(generator) => {
-                    let (x_min_range, x_max_range) = generator.x_range;
-                    let x_span = x_max_range - x_min_range;
-                    for i in 0..generator.points {
-                        let t = i as f32 / (generator.points - 1).max(1) as f32;
-                        let x = x_min_range + t * x_span;
-                        let y = (generator.function)(x);
-                        all_points_with_colors.push((x, y, s.style.color.clone()));
-                        data_y_min = data_y_min.min(y);
-                        data_y_max = data_y_max.max(y);
+                    let px_per_unit = (
+                        f64::from(bounds.width - padding.x()) / (view_x_range[1] - view_x_range[0]),
+                        f64::from(bounds.height - padding.y())
+                            / (view_y_range[1] - view_y_range[0])
+                            * y_map.map_or(1.0, YAxisMap::scale),
+                    );
+                    let samples = samples.sample(
+                        idx,
+                        s,
+                        generator,
+                        (view_x_range[0], view_x_range[1]),
+                        px_per_unit,
+                    );
+                    for (i, &(x, y)) in samples.iter().enumerate() {
+                        push_point(x, y, i);
                     }
                 }
             }
+            series_visible.push(visible);
         }
 
+        // Series whose markers are too dense to tell apart from their line
+        let plot_area = (bounds.width - padding.x()) * (bounds.height - padding.y());
+        let skip_markers: Vec<bool> = series_visible
+            .iter()
+            .zip(&series_lines)
+            .zip(&series_decimated)
+            .map(|((&visible, line), &decimated)| {
+                options.performance.as_ref().is_some_and(|performance| {
+                    line.pattern != LinePattern::None
+                        && (decimated
+                            || plot_area < performance.min_pixels_per_marker * visible as f32)
+                })
+            })
+            .collect();
+
         // Handle empty data
-        if all_points_with_colors.is_empty() {
+        if data_points.is_empty() {
             data_y_min = 0.0;
             data_y_max = 1.0;
-        } else if (data_y_max - data_y_min).abs() < f32::EPSILON {
+        } else if (data_y_max - data_y_min).abs() < f64::EPSILON {
             data_y_min -= 0.5;
             data_y_max += 0.5;
         }
 
-        let padding = options.padding;
-        let marker_radius = series.first().map(|s| s.style.marker_size).unwrap_or(4.0);
         let line_width = series.first().map(|s| s.style.line_width).unwrap_or(2.0);
 
+        // Render in a local frame centered on the view. Data is subtracted
+        // from the origin in f64 and only the small difference is narrowed
+        // to f32, so data far from zero (epoch timestamps, projected
+        // coordinates) keeps full precision however deep the zoom.
+        let origin = [
+            0.5 * (view_x_range[0] + view_x_range[1]),
+            0.5 * (view_y_range[0] + view_y_range[1]),
+        ];
+
         // Use the view ranges (not data ranges) for rendering
         let uniforms = Uniforms {
             viewport_size: [bounds.width, bounds.height],
-            x_range: view_x_range,
-            y_range: view_y_range,
-            padding: [padding, padding],
-            marker_radius,
+            x_range: view_x_range.map(|x| (x - origin[0]) as f32),
+            y_range: view_y_range.map(|y| (y - origin[1]) as f32),
+            padding: [padding.left, padding.top],
+            plot_size: [bounds.width - padding.x(), bounds.height - padding.y()],
             line_width,
+            scale_factor: 1.0,
+            origin: origin.map(|o| o as f32),
+            // Compact markers use one unit per half span, so f16 precision
+            // follows the zoom level
+            compact_scale: [
+                ((0.5 * (view_x_range[1] - view_x_range[0])) as f32).max(f32::MIN_POSITIVE),
+                ((0.5 * (view_y_range[1] - view_y_range[0])) as f32).max(f32::MIN_POSITIVE),
+            ],
         };
 
         // Apply color mode using *data* y range for gradient normalization
-        let all_points = Self::apply_color_mode(
-            &all_points_with_colors,
-            view_x_range[0],
-            view_x_range[1],
+        let mut all_points = Self::apply_color_mode(
+            &data_points,
+            &record_indices,
+            &series_boundaries,
+            &series_colors,
+            &series_y_maps,
             data_y_min,
             data_y_max,
+            origin,
         );
 
-        let line_vertices = if config.show_lines {
-            Self::generate_line_vertices(&all_points, &series_boundaries, &uniforms)
-        } else {
-            Vec::new()
-        };
-
-        let grid_vertices = Self::generate_grid_vertices(options, &uniforms);
+        for (s, &(shape, size, opacity, scale)) in series_markers.iter().enumerate() {
+            let end = series_boundaries
+                .get(s + 1)
+                .copied()
+                .unwrap_or(all_points.len());
+            let range = series_boundaries[s]..end;
+            let (sizes, data_units, colors) = series_point_styles[s];
+            for (point, &record) in all_points[range.clone()]
+                .iter_mut()
+                .zip(&record_indices[range])
+            {
+                point.shape = if skip_markers[s] {
+                    MarkerShape::None.as_u32()
+                } else {
+                    shape
+                };
+                point.size = sizes
+                    .and_then(|sizes| sizes.get(record))
+                    .filter(|r| r.is_finite())
+                    .map_or(size, |&r| marker_radius(r, data_units))
+                    * scale;
+                if let Some(c) = colors.and_then(|colors| colors.get(record)) {
+                    point.color = [c.r, c.g, c.b, c.a];
+                }
+                point.color[3] *= opacity;
+            }
+        }
 
-        // Generate selection rectangle overlay
-        let selection_vertices = if let Some((start, end)) = selection_rect {
-            Self::generate_selection_rect(start, end)
-        } else {
-            Vec::new()
-        };
+        // Bleed by the largest visible marker, in pixels
+        config.marker_bleed = options.marker_bleed.unwrap_or_else(|| {
+            let [plot_width, plot_height] = uniforms.plot_size;
+            let [x_span, y_span] = [uniforms.x_range, uniforms.y_range].map(|r| r[1] - r[0]);
+            let (x_scale, y_scale) = (plot_width / x_span, plot_height / y_span);
+            all_points
+                .iter()
+                .filter(|point| point.shape != MarkerShape::None.as_u32())
+                .map(|point| {
+                    if point.size < 0.0 {
+                        -point.size * x_scale.max(y_scale)
+                    } else {
+                        point.size
+                    }
+                })
+                .fold(0.0, f32::max)
+        });
 
-        let x_ticks = compute_ticks(view_x_range[0], view_x_range[1], &options.x_axis.ticks);
-        let y_ticks = compute_ticks(view_y_range[0], view_y_range[1], &options.y_axis.ticks);
-        let tick_info = TickInfo { x_ticks, y_ticks };
+        // Dim records outside the linked selection
+        if let Some((selected, LinkMode::Highlight)) = brushed {
+            for (point, record) in all_points.iter_mut().zip(&record_indices) {
+                if !selected.contains(record) {
+                    point.color[3] *= BRUSH_DIM_ALPHA;
+                }
+            }
+        }
 
-        let highlight_vertices = if let Some((screen_pos, color, radius, width)) = highlight {
-            Self::generate_highlight_ring(screen_pos, color, radius, width)
-        } else {
-            Vec::new()
-        };
+        // Polygons, bands, box plots and quivers belong to layer 0, beneath
+        // its series, and box plot outliers are drawn after its markers
+        let mut fills = Self::generate_polygon_vertices(polygons, origin, &uniforms);
+        fills.extend(Self::generate_band_vertices(bands, origin, &uniforms));
+        let (box_vertices, mut outliers) =
+            Self::generate_box_vertices(box_plots, origin, &uniforms);
+        fills.extend(box_vertices);
+        fills.extend(Self::generate_quiver_vertices(quivers, origin, &uniforms));
+
+        // Positions in the per-series arrays of each layer's series
+        let mut layer_series: Vec<(i32, std::ops::Range<usize>)> = layer_starts
+            .iter()
+            .enumerate()
+            .map(|(i, &(z, start))| {
+                let end = layer_starts
+                    .get(i + 1)
+                    .map_or(series_boundaries.len(), |&(_, end)| end);
+                (z, start..end)
+            })
+            .collect();
+        if !layer_series.iter().any(|(z, _)| *z == 0) {
+            let at = layer_series.partition_point(|(z, _)| *z < 0);
+            let start = layer_series
+                .get(at)
+                .map_or(series_boundaries.len(), |(_, range)| range.start);
+            layer_series.insert(at, (0, start..start));
+        }
+
+        let mut line_vertices = Vec::new();
+        if options.performance.is_some() {
+            let line_points: usize = (0..series_lines.len())
+                .filter(|&s| series_lines[s].pattern != LinePattern::None)
+                .map(|s| {
+                    let end = series_boundaries.get(s + 1).copied();
+                    end.unwrap_or(all_points.len()) - series_boundaries[s]
+                })
+                .sum();
+            line_vertices.reserve(fills.len() + LINE_VERTICES_PER_POINT * line_points);
+        }
+        // Decimated lines run through their kept points only
+        let decimated;
+        let (line_points, line_boundaries, line_gaps) =
+            if series_line_kept.iter().all(Option::is_none) {
+                (&all_points, &series_boundaries, &line_breaks)
+            } else {
+                decimated = Self::decimated_lines(
+                    &all_points,
+                    &record_indices,
+                    &series_boundaries,
+                    &line_breaks,
+                    &series_line_kept,
+                );
+                (&decimated.0, &decimated.1, &decimated.2)
+            };
+
+        let mut markers = Vec::with_capacity(all_points.len() + outliers.len());
+        let mut layers = Vec::with_capacity(layer_series.len());
+        for (z, series_range) in layer_series {
+            let point_start = |s: usize| {
+                series_boundaries
+                    .get(s)
+                    .copied()
+                    .unwrap_or(all_points.len())
+            };
+            let lines_start = line_vertices.len();
+            let markers_start = markers.len();
+            if z == 0 {
+                line_vertices.append(&mut fills);
+            }
+
+            // The series' boundaries stay indices into all points, so the
+            // point arrays are cut at the layer's end rather than sliced
+            let end = point_start(series_range.end);
+            let boundaries = &series_boundaries[series_range.clone()];
+            line_vertices.extend(Self::generate_bar_vertices(
+                &all_points[..end],
+                &record_indices[..end],
+                boundaries,
+                &series_bars[series_range.clone()],
+                &series_y_maps[series_range.clone()],
+                origin,
+                &uniforms,
+            ));
+            line_vertices.extend(Self::generate_stack_vertices(
+                &data_points[..end],
+                &all_points[..end],
+                &record_indices[..end],
+                boundaries,
+                &line_breaks,
+                &series_stacks[series_range.clone()],
+                &series_y_maps[series_range.clone()],
+                origin,
+                &uniforms,
+            ));
+            if config.show_lines {
+                let line_end = line_boundaries
+                    .get(series_range.end)
+                    .copied()
+                    .unwrap_or(line_points.len());
+                line_vertices.extend(Self::generate_line_vertices(
+                    &line_points[..line_end],
+                    &line_boundaries[series_range.clone()],
+                    line_gaps,
+                    &series_lines[series_range.clone()],
+                    &uniforms,
+                ));
+            }
+
+            for s in series_range.filter(|&s| !skip_markers[s]) {
+                markers.extend_from_slice(&all_points[point_start(s)..point_start(s + 1)]);
+            }
+            if z == 0 {
+                markers.append(&mut outliers);
+            }
+            layers.push(DrawLayer {
+                lines: lines_start as u32..line_vertices.len() as u32,
+                markers: markers_start as u32..markers.len() as u32,
+            });
+        }
+
+        let (x_tick_config, y_tick_config) = options.tick_configs(bounds.size());
+        let [x_offset, y_offset] = tick_offsets;
+        let x_ticks = compute_ticks_from(
+            options.x_axis.kind,
+            view_x_range[0],
+            view_x_range[1],
+            x_offset,
+            x_tick_config,
+        );
+        let y_ticks = compute_ticks_from(
+            options.y_axis.kind,
+            view_y_range[0],
+            view_y_range[1],
+            y_offset,
+            y_tick_config,
+        );
+        let tick_info = TickInfo { x_ticks, y_ticks };
+        let grid = Self::generate_grid_spec(options, origin, &uniforms, &tick_info);
+
+        // Large scatter plots: upload markers in the compact format
+        let (markers, compact_points) = if options
+            .compact_markers_threshold
+            .is_some_and(|threshold| markers.len() > threshold)
+        {
+            let compact = markers
+                .iter()
+                .map(|p| CompactPoint::from_raw(p, uniforms.compact_scale))
+                .collect();
+            (Vec::new(), compact)
+        } else {
+            (markers, Vec::new())
+        };
 
         Self {
-            points: all_points,
-            line_vertices,
+            points: markers.into(),
+            compact_points: compact_points.into(),
+            line_vertices: line_vertices.into(),
+            layers: layers.into(),
             uniforms,
+            origin,
             config,
-            grid_vertices,
-            selection_vertices,
-            highlight_vertices,
-            series_boundaries,
+            grid: Arc::new(grid),
+            selection_vertices: Arc::new([]),
+            highlight_vertices: Arc::new([]),
+            images: images
+                .iter()
+                .filter_map(|image| Self::image_layer(image, origin, &uniforms))
+                .chain(spectrogram.and_then(|s| Self::spectrogram_layer(s, origin, &uniforms)))
+                .collect(),
+            series_boundaries: series_boundaries.into(),
             tick_info,
+            key: 0,
+            data_key: 0,
+        }
+        .with_overlays(selection_rect, highlight)
+    }
+
+    /// An image's texture and quad, or `None` if it is invalid or out of view.
+    fn image_layer(image: &PlotImage, origin: [f64; 2], uniforms: &Uniforms) -> Option<ImageLayer> {
+        if image.pixels().len() != image.expected_len() {
+            return None;
+        }
+        let (x, y) = (image.x_range, image.y_range);
+        Some(ImageLayer {
+            texels: ImageTexels::Fixed(image.pixels.clone()),
+            size: image.size(),
+            smooth: image.smooth,
+            key: (image.id(), 0),
+            vertices: Self::image_quad(
+                [x.0, x.1],
+                [y.0, y.1],
+                [0.0, 1.0],
+                image.opacity,
+                origin,
+                uniforms,
+            )?,
+        })
+    }
+
+    /// The spectrogram's texture and quad, or `None` if it is empty or out
+    /// of view.
+    ///
+    /// Once the ring is full, U starts at the oldest column's slot and runs
+    /// one texture width further, wrapping around.
+    fn spectrogram_layer(
+        spectrogram: &Spectrogram,
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Option<ImageLayer> {
+        let width = spectrogram.columns() as u64;
+        let pushed = spectrogram.pushed();
+        let u = if pushed < width {
+            [0.0, pushed as f32 / width as f32]
+        } else {
+            let u0 = (pushed % width) as f32 / width as f32;
+            [u0, u0 + 1.0]
+        };
+        Some(ImageLayer {
+            texels: ImageTexels::Shared(spectrogram.texels()),
+            size: [spectrogram.columns(), spectrogram.bins()].map(|n| n as u32),
+            smooth: false,
+            key: (spectrogram.id(), pushed),
+            vertices: Self::image_quad(
+                spectrogram.time_range()?,
+                spectrogram.frequency_range(),
+                u,
+                1.0,
+                origin,
+                uniforms,
+            )?,
+        })
+    }
+
+    /// A quad over the data rectangle `x` by `y` showing texture columns
+    /// `u` (top to bottom in V), clipped to the plot area with the texture
+    /// coordinates following. `None` when nothing is visible.
+    fn image_quad(
+        x: [f64; 2],
+        y: [f64; 2],
+        [u0, u1]: [f32; 2],
+        opacity: f32,
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Option<[ImageVertex; 6]> {
+        let (x0, y0) = Self::data_to_plot_screen(x[0], y[1], origin, uniforms);
+        let (x1, y1) = Self::data_to_plot_screen(x[1], y[0], origin, uniforms);
+
+        // Clip [a, b] to [lo, hi], moving the texture coordinates along
+        let clip = |a: f32, b: f32, lo: f32, hi: f32, ta: f32, tb: f32| {
+            let at = |x: f32| ta + (tb - ta) * (x - a) / (b - a);
+            let (ca, cb) = (a.max(lo), b.min(hi));
+            (ca < cb).then(|| (ca, cb, at(ca), at(cb)))
+        };
+        let [left, top] = uniforms.padding;
+        let [plot_width, plot_height] = uniforms.plot_size;
+        let (x0, x1, u0, u1) = clip(x0, x1, left, left + plot_width, u0, u1)?;
+        let (y0, y1, v0, v1) = clip(y0, y1, top, top + plot_height, 0.0, 1.0)?;
+
+        let corner = |x: f32, y: f32, u: f32, v: f32| ImageVertex {
+            position: [x, y],
+            uv: [u, v],
+            opacity,
+        };
+        let (a, b) = (corner(x0, y0, u0, v0), corner(x1, y0, u1, v0));
+        let (c, d) = (corner(x1, y1, u1, v1), corner(x0, y1, u0, v1));
+        Some([a, b, c, a, c, d])
+    }
+
+    /// A copy of this primitive with the selection rectangle and highlight
+    /// ring replaced, sharing everything else.
+    fn with_overlays(
+        &self,
+        selection_rect: Option<(Point, Point)>,
+        highlight: Option<(Point, [f32; 4], f32, f32)>,
+    ) -> Self {
+        let selection_vertices = match selection_rect {
+            Some((start, end)) => Self::generate_selection_rect(start, end),
+            None => Vec::new(),
+        };
+        let highlight_vertices = match highlight {
+            Some((screen_pos, color, radius, width)) => {
+                Self::generate_highlight_ring(screen_pos, color, radius, width)
+            }
+            None => Vec::new(),
+        };
+        Self {
+            selection_vertices: selection_vertices.into(),
+            highlight_vertices: highlight_vertices.into(),
+            ..self.clone()
         }
     }
 
     /// Generate the selection rectangle as screen-space quads.
     /// Renders a semi-transparent fill with a solid border.
-    fn generate_selection_rect(start: Point, end: Point) -> Vec<RawPoint> {
+    fn generate_selection_rect(start: Point, end: Point) -> Vec<LineVertex> {
         let mut vertices = Vec::new();
 
         let x0 = start.x.min(end.x);
@@ -252,18 +1691,18 @@ impl PlotterPrimitive {
         // Semi-transparent fill
         let fill_color = [0.3, 0.5, 0.8, 0.15];
         // Two triangles for the fill quad
-        vertices.push(RawPoint::new(x0, y0, fill_color));
-        vertices.push(RawPoint::new(x1, y0, fill_color));
-        vertices.push(RawPoint::new(x0, y1, fill_color));
-        vertices.push(RawPoint::new(x1, y0, fill_color));
-        vertices.push(RawPoint::new(x1, y1, fill_color));
-        vertices.push(RawPoint::new(x0, y1, fill_color));
+        vertices.push(LineVertex::solid(x0, y0, fill_color));
+        vertices.push(LineVertex::solid(x1, y0, fill_color));
+        vertices.push(LineVertex::solid(x0, y1, fill_color));
+        vertices.push(LineVertex::solid(x1, y0, fill_color));
+        vertices.push(LineVertex::solid(x1, y1, fill_color));
+        vertices.push(LineVertex::solid(x0, y1, fill_color));
 
         // Border lines (1.5px thick)
         let border_color = [0.4, 0.6, 0.9, 0.8];
         let half = 1.0;
 
-        let push_border_line = |verts: &mut Vec<RawPoint>, ax: f32, ay: f32, bx: f32, by: f32| {
+        let push_border_line = |verts: &mut Vec<LineVertex>, ax: f32, ay: f32, bx: f32, by: f32| {
             let dx = bx - ax;
             let dy = by - ay;
             let len = (dx * dx + dy * dy).sqrt();
@@ -273,10 +1712,10 @@ impl PlotterPrimitive {
             let nx = -dy / len * half;
             let ny = dx / len * half;
 
-            let v0 = RawPoint::new(ax + nx, ay + ny, border_color);
-            let v1 = RawPoint::new(ax - nx, ay - ny, border_color);
-            let v2 = RawPoint::new(bx + nx, by + ny, border_color);
-            let v3 = RawPoint::new(bx - nx, by - ny, border_color);
+            let v0 = LineVertex::solid(ax + nx, ay + ny, border_color);
+            let v1 = LineVertex::solid(ax - nx, ay - ny, border_color);
+            let v2 = LineVertex::solid(bx + nx, by + ny, border_color);
+            let v3 = LineVertex::solid(bx - nx, by - ny, border_color);
 
             verts.push(v0);
             verts.push(v1);
@@ -301,113 +1740,127 @@ impl PlotterPrimitive {
         color: [f32; 4],
         radius: f32,
         width: f32,
-    ) -> Vec<RawPoint> {
+    ) -> Vec<LineVertex> {
         let segments = 32;
         let mut vertices = Vec::with_capacity(segments * 6);
-        let half_width = width / 2.0;
 
         for i in 0..segments {
             let angle0 = (i as f32 / segments as f32) * std::f32::consts::TAU;
             let angle1 = ((i + 1) as f32 / segments as f32) * std::f32::consts::TAU;
 
-            let ax = center.x + radius * angle0.cos();
-            let ay = center.y + radius * angle0.sin();
-            let bx = center.x + radius * angle1.cos();
-            let by = center.y + radius * angle1.sin();
-
-            // Build a quad for this line segment
-            let dx = bx - ax;
-            let dy = by - ay;
-            let len = (dx * dx + dy * dy).sqrt();
-            if len < 0.001 {
-                continue;
-            }
-            let nx = -dy / len * half_width;
-            let ny = dx / len * half_width;
-
-            let v0 = RawPoint::new(ax + nx, ay + ny, color);
-            let v1 = RawPoint::new(ax - nx, ay - ny, color);
-            let v2 = RawPoint::new(bx + nx, by + ny, color);
-            let v3 = RawPoint::new(bx - nx, by - ny, color);
-
-            vertices.push(v0);
-            vertices.push(v1);
-            vertices.push(v2);
-            vertices.push(v1);
-            vertices.push(v3);
-            vertices.push(v2);
+            // Anti-aliased like series lines, the ring is all diagonals
+            Self::push_line_segment(
+                &mut vertices,
+                (
+                    center.x + radius * angle0.cos(),
+                    center.y + radius * angle0.sin(),
+                ),
+                (
+                    center.x + radius * angle1.cos(),
+                    center.y + radius * angle1.sin(),
+                ),
+                width / 2.0,
+                (color, color),
+                (LinePattern::Solid.as_u32(), [0.0; 2]),
+            );
         }
 
         vertices
     }
 
     /// Apply color modes to raw point data, computing final RGBA colors.
+    ///
+    /// `series_colors` and `y_maps` are parallel to `series_boundaries`;
+    /// `record_indices` holds each point's index within its series. Gradients
+    /// are normalized per series: over the `values` array or computed values
+    /// when present, otherwise over the data Y range. Output positions are on
+    /// the primary Y axis, relative to `origin`.
+    #[allow(clippy::too_many_arguments)]
     fn apply_color_mode(
-        points_with_colors: &[(f32, f32, ColorMode<'_>)],
-        _x_min: f32,
-        _x_max: f32,
-        y_min: f32,
-        y_max: f32,
+        points: &[(f64, f64)],
+        record_indices: &[usize],
+        series_boundaries: &[usize],
+        series_colors: &[&ColorMode<'_>],
+        y_maps: &[Option<YAxisMap>],
+        y_min: f64,
+        y_max: f64,
+        origin: [f64; 2],
     ) -> Vec<RawPoint> {
-        let mut result = Vec::with_capacity(points_with_colors.len());
-
-        for (idx, (x, y, color_mode)) in points_with_colors.iter().enumerate() {
-            let color = match color_mode {
-                ColorMode::Solid(c) => *c,
-                ColorMode::ValueGradient { low, high, values } => {
-                    let value = values.as_ref().map(|v| v[idx]).unwrap_or(*y);
-                    let value_min = if let Some(v) = values {
-                        v.iter().fold(f32::INFINITY, |a, &b| a.min(b))
-                    } else {
-                        y_min
-                    };
-                    let value_max = if let Some(v) = values {
-                        v.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b))
-                    } else {
-                        y_max
-                    };
-
-                    let t = if (value_max - value_min).abs() < f32::EPSILON {
-                        0.5
-                    } else {
-                        (value - value_min) / (value_max - value_min)
-                    };
-
-                    Self::lerp_color(*low, *high, t)
-                }
-                ColorMode::IndexGradient { start, end } => {
-                    let total = points_with_colors.len() as f32;
-                    let t = if total > 1.0 {
-                        idx as f32 / (total - 1.0)
-                    } else {
-                        0.5
-                    };
-                    Self::lerp_color(*start, *end, t)
-                }
-                ColorMode::Colormap { name, values } => {
-                    let value = values.as_ref().map(|v| v[idx]).unwrap_or(*y);
-                    let value_min = if let Some(v) = values {
-                        v.iter().fold(f32::INFINITY, |a, &b| a.min(b))
-                    } else {
-                        y_min
-                    };
-                    let value_max = if let Some(v) = values {
-                        v.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b))
-                    } else {
-                        y_max
-                    };
-
-                    let t = if (value_max - value_min).abs() < f32::EPSILON {
-                        0.5
-                    } else {
-                        (value - value_min) / (value_max - value_min)
-                    };
-
-                    name.sample(t)
+        let mut result = Vec::with_capacity(points.len());
+
+        for (s, color_mode) in series_colors.iter().enumerate() {
+            let start = series_boundaries[s];
+            let end = series_boundaries
+                .get(s + 1)
+                .copied()
+                .unwrap_or(points.len());
+            let points = &points[start..end];
+            let records = &record_indices[start..end];
+            let to_primary = |y: f64| y_maps[s].map_or(y, |m| m.apply(y));
+
+            let lookup = |v: &[f32]| -> (Vec<f64>, (f64, f64)) {
+                let values = records
+                    .iter()
+                    .map(|&i| v.get(i).copied().map_or(f64::NAN, f64::from))
+                    .collect();
+                let (lo, hi) = value_range(v);
+                (values, (f64::from(lo), f64::from(hi)))
+            };
+            let (values, auto_range) = match color_mode {
+                ColorMode::Solid(_) => (Vec::new(), (0.0, 1.0)),
+                ColorMode::ValueGradient {
+                    values: Some(v), ..
+                } => lookup(v),
+                #[cfg(feature = "colormap")]
+                ColorMode::Colormap {
+                    values: Some(v), ..
+                } => lookup(v),
+                ColorMode::ValueFn { function, .. } => {
+                    let values: Vec<f32> = points
+                        .iter()
+                        .zip(records)
+                        .map(|(&(x, y), &i)| function(i, x, y))
+                        .collect();
+                    let (lo, hi) = value_range(&values);
+                    (
+                        values.into_iter().map(f64::from).collect(),
+                        (f64::from(lo), f64::from(hi)),
+                    )
                 }
+                ColorMode::IndexGradient { .. } => (
+                    (0..points.len()).map(|i| i as f64).collect(),
+                    (0.0, points.len().saturating_sub(1) as f64),
+                ),
+                _ => (
+                    points.iter().map(|&(_, y)| to_primary(y)).collect(),
+                    (y_min, y_max),
+                ),
             };
+            // A fixed range keeps colors stable as data changes
+            let scale = color_mode.scale().unwrap_or_default();
+            let range = scale
+                .range
+                .map_or(auto_range, |(lo, hi)| (f64::from(lo), f64::from(hi)));
+
+            for (i, &(x, y)) in points.iter().enumerate() {
+                let t = values.get(i).map_or(0.5, |&value| {
+                    scale.normalization.apply(value, range).unwrap_or(0.5)
+                });
+                let color = match color_mode {
+                    ColorMode::Solid(c) => *c,
+                    ColorMode::ValueGradient { low, high, .. }
+                    | ColorMode::ValueFn { low, high, .. } => Self::lerp_color(*low, *high, t),
+                    ColorMode::IndexGradient { start, end } => Self::lerp_color(*start, *end, t),
+                    #[cfg(feature = "colormap")]
+                    ColorMode::Colormap { name, .. } => name.sample(t.clamp(0.0, 1.0)),
+                };
 
-            result.push(RawPoint::new(*x, *y, [color.r, color.g, color.b, color.a]));
+                result.push(RawPoint::new(
+                    (x - origin[0]) as f32,
+                    (to_primary(y) - origin[1]) as f32,
+                    [color.r, color.g, color.b, color.a],
+                ));
+            }
         }
 
         result
@@ -424,22 +1877,67 @@ impl PlotterPrimitive {
     }
 
     /// Generate line vertices as quads for thick lines, respecting series boundaries.
+    ///
+    /// `series_lines` holds the per-series line parameters, parallel to
+    /// `series_boundaries`. Glow passes are emitted before the series' own
+    /// line so they render underneath it.
+    /// The points, series boundaries and line breaks to draw lines through
+    /// when `kept` lists, for some series, the records decimation kept for
+    /// its line (see [`core::m4_indices`]).
+    fn decimated_lines(
+        points: &[RawPoint],
+        records: &[usize],
+        series_boundaries: &[usize],
+        line_breaks: &[usize],
+        kept: &[Option<Vec<usize>>],
+    ) -> (Vec<RawPoint>, Vec<usize>, Vec<usize>) {
+        let mut line_points = Vec::new();
+        let mut boundaries = Vec::with_capacity(series_boundaries.len());
+        let mut breaks = Vec::new();
+        for (s, kept) in kept.iter().enumerate() {
+            let start = series_boundaries[s];
+            let end = series_boundaries
+                .get(s + 1)
+                .copied()
+                .unwrap_or(points.len());
+            boundaries.push(line_points.len());
+            // Both the kept records and the series' records are ascending
+            let mut kept = kept.as_deref().map(|kept| kept.iter().peekable());
+            let mut after_gap = false;
+            for i in start..end {
+                after_gap |= line_breaks.binary_search(&i).is_ok();
+                if let Some(kept) = kept.as_mut() {
+                    while kept.next_if(|&&record| record < records[i]).is_some() {}
+                    if kept.peek() != Some(&&records[i]) {
+                        continue;
+                    }
+                }
+                if after_gap && line_points.len() > boundaries[s] {
+                    breaks.push(line_points.len());
+                }
+                after_gap = false;
+                line_points.push(points[i]);
+            }
+        }
+        (line_points, boundaries, breaks)
+    }
+
     fn generate_line_vertices(
         points: &[RawPoint],
         series_boundaries: &[usize],
+        line_breaks: &[usize],
+        series_lines: &[SeriesLine],
         uniforms: &Uniforms,
-    ) -> Vec<RawPoint> {
+    ) -> Vec<LineVertex> {
         if points.len() < 2 {
             return Vec::new();
         }
 
         let mut vertices = Vec::with_capacity((points.len() - 1) * 6);
 
-        let plot_width = uniforms.viewport_size[0] - 2.0 * uniforms.padding[0];
-        let plot_height = uniforms.viewport_size[1] - 2.0 * uniforms.padding[1];
+        let [plot_width, plot_height] = uniforms.plot_size;
         let x_range = uniforms.x_range;
         let y_range = uniforms.y_range;
-        let half_width = uniforms.line_width / 2.0;
 
         let to_screen = |x: f32, y: f32| -> (f32, f32) {
             let x_norm = (x - x_range[0]) / (x_range[1] - x_range[0]);
@@ -449,1145 +1947,5655 @@ impl PlotterPrimitive {
             (screen_x, screen_y)
         };
 
+        // Each series' line, broken where non-finite points were dropped
+        let mut runs = Vec::with_capacity(series_boundaries.len() + line_breaks.len());
         for series_idx in 0..series_boundaries.len() {
-            let start_idx = series_boundaries[series_idx];
-            let end_idx = if series_idx + 1 < series_boundaries.len() {
-                series_boundaries[series_idx + 1]
-            } else {
-                points.len()
-            };
+            let start = series_boundaries[series_idx];
+            let end = series_boundaries
+                .get(series_idx + 1)
+                .copied()
+                .unwrap_or(points.len());
+            let mut run_start = start;
+            for &run_end in line_breaks.iter().filter(|&&b| b > start && b < end) {
+                runs.push((series_idx, run_start, run_end));
+                run_start = run_end;
+            }
+            runs.push((series_idx, run_start, end));
+        }
 
+        for (series_idx, start_idx, end_idx) in runs {
             if end_idx <= start_idx + 1 {
                 continue;
             }
 
-            for window_idx in start_idx..end_idx - 1 {
-                let p0 = &points[window_idx];
-                let p1 = &points[window_idx + 1];
-                let x0 = p0.position[0];
-                let y0 = p0.position[1];
-                let x1 = p1.position[0];
-                let y1 = p1.position[1];
-                let color = p0.color;
-
-                let (sx0, sy0) = to_screen(x0, y0);
-                let (sx1, sy1) = to_screen(x1, y1);
-
-                let dx = sx1 - sx0;
-                let dy = sy1 - sy0;
-                let len = (dx * dx + dy * dy).sqrt();
-
-                if len < 0.001 {
-                    continue;
+            let line = series_lines[series_idx];
+            if line.pattern == LinePattern::None {
+                continue;
+            }
+            let width_px = match line.width_unit {
+                SizeUnit::Pixels => line.width,
+                SizeUnit::Data => line.width * plot_height / (y_range[1] - y_range[0]),
+            };
+            let half_width = width_px / 2.0;
+            let pattern = line.pattern.as_u32();
+
+            // Screen positions, with the corners of steps inserted, and the
+            // distance along the line at each of them, in line widths, so
+            // patterns continue across segments
+            let (screen, colors) = Self::interpolate_path(
+                line.interpolation,
+                points[start_idx..end_idx]
+                    .iter()
+                    .map(|p| (to_screen(p.position[0], p.position[1]), p.color)),
+            );
+            let mut distances = Vec::with_capacity(screen.len());
+            let mut distance = 0.0;
+            for (i, &(x, y)) in screen.iter().enumerate() {
+                if i > 0 {
+                    let (px, py) = screen[i - 1];
+                    distance += ((x - px).powi(2) + (y - py).powi(2)).sqrt() / width_px.max(1.0);
                 }
+                distances.push(distance);
+            }
 
-                // Extend the quad by 1px on each side for anti-aliased edges.
-                // edge_distance is interpolated across the quad:
-                //   0.0  = line centre
-                //   1.0  = original half-width (start of fade)
-                //  >1.0  = extension zone (fades to transparent)
-                // We use signed values (+/- edge_outer) so interpolation crosses 0 at the centre.
-                let aa_extend: f32 = 1.0; // extra pixels for AA fringe
-                let extended_half = half_width + aa_extend;
+            // Glow: concentric passes of decreasing width, so the accumulated
+            // alpha falls off towards the outside like a blurred halo.
+            if let Some(glow) = line.glow {
+                const GLOW_PASSES: usize = 3;
+                for pass in (1..=GLOW_PASSES).rev() {
+                    let pass_half = half_width + glow.width * pass as f32 / GLOW_PASSES as f32;
+                    let shifted: Vec<(f32, f32)> = screen
+                        .iter()
+                        .map(|&(x, y)| (x + glow.offset.x, y + glow.offset.y))
+                        .collect();
+                    let faded: Vec<[f32; 4]> = colors
+                        .iter()
+                        .map(|&color| {
+                            let [r, g, b, a] = glow.color.map_or(color, |c| [c.r, c.g, c.b, c.a]);
+                            [r, g, b, a * glow.alpha / GLOW_PASSES as f32]
+                        })
+                        .collect();
+                    Self::push_polyline(
+                        &mut vertices,
+                        &shifted,
+                        &faded,
+                        &distances,
+                        pass_half,
+                        pattern,
+                        (line.join, line.cap),
+                    );
+                }
+            }
 
-                let nx = -dy / len * extended_half;
-                let ny = dx / len * extended_half;
+            Self::push_polyline(
+                &mut vertices,
+                &screen,
+                &colors,
+                &distances,
+                half_width,
+                pattern,
+                (line.join, line.cap),
+            );
+        }
 
-                let edge_outer = extended_half / half_width.max(0.5);
+        vertices
+    }
 
-                // +nx side gets +edge_outer, -nx side gets -edge_outer
-                let v0 = RawPoint::with_edge_distance(sx0 + nx, sy0 + ny, color, edge_outer);
-                let v1 = RawPoint::with_edge_distance(sx0 - nx, sy0 - ny, color, -edge_outer);
-                let v2 = RawPoint::with_edge_distance(sx1 + nx, sy1 + ny, color, edge_outer);
-                let v3 = RawPoint::with_edge_distance(sx1 - nx, sy1 - ny, color, -edge_outer);
+    /// Screen positions and colors along a series' line, with the corners of
+    /// step interpolation inserted between points. Horizontal runs keep the
+    /// color of the value they hold.
+    fn interpolate_path(
+        interpolation: LineInterpolation,
+        points: impl Iterator<Item = ((f32, f32), [f32; 4])>,
+    ) -> (Vec<(f32, f32)>, Vec<[f32; 4]>) {
+        let mut screen: Vec<(f32, f32)> = Vec::new();
+        let mut colors: Vec<[f32; 4]> = Vec::new();
+        for ((x, y), color) in points {
+            if let (Some(&(px, py)), Some(&prev)) = (screen.last(), colors.last()) {
+                match interpolation {
+                    LineInterpolation::Linear => {}
+                    LineInterpolation::StepBefore => {
+                        screen.push((px, y));
+                        colors.push(color);
+                    }
+                    LineInterpolation::StepAfter => {
+                        screen.push((x, py));
+                        colors.push(prev);
+                    }
+                    LineInterpolation::StepMid => {
+                        let mid = 0.5 * (px + x);
+                        screen.extend([(mid, py), (mid, y)]);
+                        colors.extend([prev, color]);
+                    }
+                }
+            }
+            screen.push((x, y));
+            colors.push(color);
+        }
+        (screen, colors)
+    }
 
-                vertices.push(v0);
-                vertices.push(v1);
-                vertices.push(v2);
+    /// Convert absolute data coordinates to widget-local screen coordinates.
+    fn data_to_plot_screen(x: f64, y: f64, origin: [f64; 2], uniforms: &Uniforms) -> (f32, f32) {
+        let [plot_width, plot_height] = uniforms.plot_size;
+        let local =
+            |v: f64, o: f64, range: [f32; 2]| core::normalize(v - o, range.map(f64::from)) as f32;
+        let x_norm = local(x, origin[0], uniforms.x_range);
+        let y_norm = local(y, origin[1], uniforms.y_range);
+        (
+            uniforms.padding[0] + x_norm * plot_width,
+            uniforms.padding[1] + (1.0 - y_norm) * plot_height,
+        )
+    }
 
-                vertices.push(v1);
-                vertices.push(v3);
-                vertices.push(v2);
+    /// Generate filled quads for bar series, in screen space.
+    ///
+    /// Each bar takes the color of its point in `points`, so color modes and
+    /// brushing apply to bars like they do to markers.
+    fn generate_bar_vertices(
+        points: &[RawPoint],
+        record_indices: &[usize],
+        series_boundaries: &[usize],
+        series_bars: &[&[BarRect]],
+        series_y_maps: &[Option<YAxisMap>],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
+        for (s, bars) in series_bars.iter().enumerate() {
+            if bars.is_empty() {
+                continue;
+            }
+            let to_primary = |y: f64| series_y_maps[s].map_or(y, |m| m.apply(y));
+            let end = series_boundaries
+                .get(s + 1)
+                .copied()
+                .unwrap_or(points.len());
+            for j in series_boundaries[s]..end {
+                let Some(bar) = bars.get(record_indices[j]) else {
+                    continue;
+                };
+                let color = points[j].color;
+                let corner =
+                    |x: f64, y: f64| Self::data_to_plot_screen(x, to_primary(y), origin, uniforms);
+                let (x0, y0) = corner(bar.x[0], bar.y[0]);
+                let (x1, y1) = corner(bar.x[1], bar.y[1]);
+                for (x, y) in [(x0, y0), (x1, y0), (x0, y1), (x1, y0), (x1, y1), (x0, y1)] {
+                    vertices.push(LineVertex::solid(x, y, color));
+                }
             }
         }
+        vertices
+    }
 
+    /// Generate filled areas between the bottom and top of stacked series,
+    /// in screen space.
+    ///
+    /// Each area segment takes the faded color of its left point, and
+    /// segments are left out across gaps in the line.
+    #[allow(clippy::too_many_arguments)]
+    fn generate_stack_vertices(
+        data_points: &[(f64, f64)],
+        points: &[RawPoint],
+        record_indices: &[usize],
+        series_boundaries: &[usize],
+        line_breaks: &[usize],
+        series_stacks: &[&[[f64; 2]]],
+        series_y_maps: &[Option<YAxisMap>],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
+        for (s, stacks) in series_stacks.iter().enumerate() {
+            if stacks.is_empty() {
+                continue;
+            }
+            let to_primary = |y: f64| series_y_maps[s].map_or(y, |m| m.apply(y));
+            let end = series_boundaries
+                .get(s + 1)
+                .copied()
+                .unwrap_or(points.len());
+            // Bottom and top of each collected point, in screen space
+            let edge = |j: usize| {
+                let [bottom, top] = stacks[record_indices[j]];
+                let x = data_points[j].0;
+                (
+                    Self::data_to_plot_screen(x, to_primary(bottom), origin, uniforms),
+                    Self::data_to_plot_screen(x, to_primary(top), origin, uniforms),
+                )
+            };
+            let start = series_boundaries[s];
+            for (j, point) in points
+                .iter()
+                .enumerate()
+                .take(end.saturating_sub(1))
+                .skip(start)
+            {
+                if line_breaks.binary_search(&(j + 1)).is_ok() {
+                    continue;
+                }
+                let [r, g, b, a] = point.color;
+                let color = [r, g, b, a * STACK_FILL_ALPHA];
+                let (bottom0, top0) = edge(j);
+                let (bottom1, top1) = edge(j + 1);
+                for (x, y) in [bottom0, top0, bottom1, top0, top1, bottom1] {
+                    vertices.push(LineVertex::solid(x, y, color));
+                }
+            }
+        }
         vertices
     }
 
-    fn generate_grid_vertices(options: &PlotterOptions, uniforms: &Uniforms) -> Vec<RawPoint> {
+    /// Generate filled triangles and outlines for polygon series, in screen space.
+    fn generate_polygon_vertices(
+        polygons: &[PolygonSeries],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Vec<LineVertex> {
         let mut vertices = Vec::new();
 
-        let padding_x = uniforms.padding[0];
-        let padding_y = uniforms.padding[1];
-        let plot_width = uniforms.viewport_size[0] - 2.0 * padding_x;
-        let plot_height = uniforms.viewport_size[1] - 2.0 * padding_y;
-        let x_range = uniforms.x_range;
-        let y_range = uniforms.y_range;
+        for polygon in polygons {
+            let fill = [
+                polygon.fill_color.r,
+                polygon.fill_color.g,
+                polygon.fill_color.b,
+                polygon.fill_color.a,
+            ];
+            for points in &polygon.loops {
+                let screen: Vec<(f32, f32)> = points
+                    .iter()
+                    .map(|p| Self::data_to_plot_screen(p.x, p.y, origin, uniforms))
+                    .collect();
+
+                // edge_distance 0.0 renders fully opaque in fs_line
+                for [a, b, c] in triangulate(&screen) {
+                    for i in [a, b, c] {
+                        let (x, y) = screen[i];
+                        vertices.push(LineVertex::solid(x, y, fill));
+                    }
+                }
 
-        // Plot area background quad (rendered first, behind everything else)
-        if let Some(bg) = options.background_color {
-            let color = [bg.r, bg.g, bg.b, bg.a];
-            let x0 = padding_x;
-            let y0 = padding_y;
-            let x1 = padding_x + plot_width;
-            let y1 = padding_y + plot_height;
-
-            vertices.push(RawPoint::new(x0, y0, color));
-            vertices.push(RawPoint::new(x1, y0, color));
-            vertices.push(RawPoint::new(x0, y1, color));
-
-            vertices.push(RawPoint::new(x1, y0, color));
-            vertices.push(RawPoint::new(x1, y1, color));
-            vertices.push(RawPoint::new(x0, y1, color));
-        }
-
-        let push_line_quad = |vertices: &mut Vec<RawPoint>,
-                              x0: f32,
-                              y0: f32,
-                              x1: f32,
-                              y1: f32,
-                              half_width: f32,
-                              color: [f32; 4]| {
-            let dx = x1 - x0;
-            let dy = y1 - y0;
-            let len = (dx * dx + dy * dy).sqrt();
-            if len < 0.001 {
-                return;
+                if let Some(outline) = polygon.outline_color {
+                    let color = [outline.r, outline.g, outline.b, outline.a];
+                    for (i, &p0) in screen.iter().enumerate() {
+                        let p1 = screen[(i + 1) % screen.len()];
+                        Self::push_line_segment(
+                            &mut vertices,
+                            p0,
+                            p1,
+                            polygon.outline_width / 2.0,
+                            (color, color),
+                            (LinePattern::Solid.as_u32(), [0.0; 2]),
+                        );
+                    }
+                }
             }
-            let nx = -dy / len * half_width;
-            let ny = dx / len * half_width;
-
-            let v0 = RawPoint::new(x0 + nx, y0 + ny, color);
-            let v1 = RawPoint::new(x0 - nx, y0 - ny, color);
-            let v2 = RawPoint::new(x1 + nx, y1 + ny, color);
-            let v3 = RawPoint::new(x1 - nx, y1 - ny, color);
-
-            vertices.push(v0);
-            vertices.push(v1);
-            vertices.push(v2);
+        }
 
-            vertices.push(v1);
-            vertices.push(v3);
-            vertices.push(v2);
-        };
+        vertices
+    }
 
-        if options.grid.show {
-            let grid_color = [
-                options.grid.color.r,
-                options.grid.color.g,
-                options.grid.color.b,
-                options.grid.color.a,
-            ];
-            let grid_half = options.grid.line_width / 2.0;
+    /// Generate arrows for vector fields, in screen space: an anti-aliased
+    /// shaft and a filled head per arrow.
+    fn generate_quiver_vertices(
+        quivers: &[QuiverSeries],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
+        // Pixels per data unit, for pointing pixel-scaled arrows
+        let [plot_width, plot_height] = uniforms.plot_size.map(f64::from);
+        let [x_span, y_span] = [uniforms.x_range, uniforms.y_range].map(|r| f64::from(r[1] - r[0]));
+        let (x_scale, y_scale) = (plot_width / x_span, plot_height / y_span);
 
-            let x_ticks = compute_ticks(x_range[0], x_range[1], &options.x_axis.ticks);
-            for &v in &x_ticks {
-                if v < x_range[0] || v > x_range[1] {
+        for quiver in quivers {
+            let Some(range) = quiver.magnitude_range() else {
+                continue;
+            };
+            for arrow in quiver.drawn() {
+                let magnitude = arrow.magnitude();
+                let base = Self::data_to_plot_screen(arrow.x, arrow.y, origin, uniforms);
+                let tip = match quiver.scale {
+                    QuiverScale::Data(k) => Self::data_to_plot_screen(
+                        arrow.x + k * arrow.u,
+                        arrow.y + k * arrow.v,
+                        origin,
+                        uniforms,
+                    ),
+                    QuiverScale::Pixels(longest) => {
+                        let (dx, dy) = (arrow.u * x_scale, -arrow.v * y_scale);
+                        let length = f64::from(longest) * magnitude / range.1 / dx.hypot(dy);
+                        (base.0 + (dx * length) as f32, base.1 + (dy * length) as f32)
+                    }
+                };
+                let (dx, dy) = (tip.0 - base.0, tip.1 - base.1);
+                let length = dx.hypot(dy);
+                // Zero vectors have no direction, and sub-pixel arrows no shape
+                if length.is_nan() || length < 0.5 {
                     continue;
                 }
-                let x_norm = (v - x_range[0]) / (x_range[1] - x_range[0]);
-                let screen_x = padding_x + x_norm * plot_width;
-                push_line_quad(
+                let c = quiver.arrow_color(magnitude, range);
+                let color = [c.r, c.g, c.b, c.a];
+
+                let (ux, uy) = (dx / length, dy / length);
+                let head = quiver.head_size.min(length / 2.0);
+                let neck = (tip.0 - ux * head, tip.1 - uy * head);
+                Self::push_polyline(
                     &mut vertices,
-                    screen_x,
-                    padding_y,
-                    screen_x,
-                    padding_y + plot_height,
-                    grid_half,
-                    grid_color,
+                    &[base, neck],
+                    &[color; 2],
+                    &[0.0; 2],
+                    quiver.line_width / 2.0,
+                    LinePattern::Solid.as_u32(),
+                    (LineJoin::Miter, LineCap::Butt),
                 );
+                let wing = (head * 0.4).max(quiver.line_width);
+                let (px, py) = (-uy * wing, ux * wing);
+                for (x, y) in [tip, (neck.0 + px, neck.1 + py), (neck.0 - px, neck.1 - py)] {
+                    vertices.push(LineVertex::solid(x, y, color));
+                }
             }
+        }
 
-            let y_ticks = compute_ticks(y_range[0], y_range[1], &options.y_axis.ticks);
-            for &v in &y_ticks {
-                if v < y_range[0] || v > y_range[1] {
-                    continue;
-                }
-                let y_norm = (v - y_range[0]) / (y_range[1] - y_range[0]);
-                let screen_y = padding_y + (1.0 - y_norm) * plot_height;
-                push_line_quad(
-                    &mut vertices,
-                    padding_x,
-                    screen_y,
-                    padding_x + plot_width,
-                    screen_y,
-                    grid_half,
-                    grid_color,
+        vertices
+    }
+
+    /// Generate boxes, medians and whiskers for box plots, in screen space,
+    /// and their outliers as markers in the local frame.
+    fn generate_box_vertices(
+        box_plots: &[BoxPlotSeries],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> (Vec<LineVertex>, Vec<RawPoint>) {
+        let mut vertices = Vec::new();
+        let mut outliers = Vec::new();
+
+        for box_plot in box_plots {
+            let c = box_plot.fill_color;
+            let fill = [c.r, c.g, c.b, c.a];
+            let c = box_plot.line_color;
+            let line = [c.r, c.g, c.b, c.a];
+            let half_width = box_plot.line_width / 2.0;
+            let segment = |vertices: &mut Vec<LineVertex>, p0, p1| {
+                Self::push_line_segment(
+                    vertices,
+                    p0,
+                    p1,
+                    half_width,
+                    (line, line),
+                    (LinePattern::Solid.as_u32(), [0.0; 2]),
                 );
+            };
+
+            for (i, summary) in box_plot.boxes.iter().enumerate() {
+                let x = i as f64;
+                let half = 0.5 * box_plot.box_width;
+                let at = |x: f64, y: f64| Self::data_to_plot_screen(x, y, origin, uniforms);
+
+                if [summary.q1, summary.median, summary.q3]
+                    .iter()
+                    .all(|v| v.is_finite())
+                {
+                    let (x0, y0) = at(x - half, summary.q1);
+                    let (x1, y1) = at(x + half, summary.q3);
+                    // edge_distance 0.0 renders fully opaque in fs_line
+                    for (x, y) in [(x0, y0), (x1, y0), (x0, y1), (x1, y0), (x1, y1), (x0, y1)] {
+                        vertices.push(LineVertex::solid(x, y, fill));
+                    }
+                    let corners = [(x0, y0), (x1, y0), (x1, y1), (x0, y1)];
+                    for (k, &p0) in corners.iter().enumerate() {
+                        segment(&mut vertices, p0, corners[(k + 1) % 4]);
+                    }
+                    segment(
+                        &mut vertices,
+                        at(x - half, summary.median),
+                        at(x + half, summary.median),
+                    );
+                }
+
+                // Whiskers from the box to each end, with a cap half the box wide
+                for (end, edge) in [(summary.min, summary.q1), (summary.max, summary.q3)] {
+                    if !(end.is_finite() && edge.is_finite()) {
+                        continue;
+                    }
+                    segment(&mut vertices, at(x, edge), at(x, end));
+                    segment(
+                        &mut vertices,
+                        at(x - 0.5 * half, end),
+                        at(x + 0.5 * half, end),
+                    );
+                }
+
+                for &y in summary.outliers.iter().filter(|y| y.is_finite()) {
+                    outliers.push(RawPoint {
+                        position: [(x - origin[0]) as f32, (y - origin[1]) as f32],
+                        color: line,
+                        shape: box_plot.outlier_shape.as_u32(),
+                        size: marker_radius(box_plot.outlier_size, false),
+                    });
+                }
             }
         }
 
-        if options.x_axis.show {
-            let color = [
-                options.x_axis.color.r,
-                options.x_axis.color.g,
-                options.x_axis.color.b,
-                options.x_axis.color.a,
-            ];
-            let half = options.x_axis.line_width / 2.0;
-            let screen_y = padding_y + plot_height;
-            push_line_quad(
-                &mut vertices,
-                padding_x,
-                screen_y,
-                padding_x + plot_width,
-                screen_y,
-                half,
-                color,
-            );
-        }
+        (vertices, outliers)
+    }
 
-        if options.y_axis.show {
-            let color = [
-                options.y_axis.color.r,
-                options.y_axis.color.g,
-                options.y_axis.color.b,
-                options.y_axis.color.a,
+    /// Generate filled triangles and center lines for band series, in screen space.
+    fn generate_band_vertices(
+        bands: &[BandSeries],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
+
+        for band in bands {
+            let fill = [
+                band.fill_color.r,
+                band.fill_color.g,
+                band.fill_color.b,
+                band.fill_color.a,
             ];
-            let half = options.y_axis.line_width / 2.0;
-            let screen_x = padding_x;
-            push_line_quad(
-                &mut vertices,
-                screen_x,
-                padding_y,
-                screen_x,
-                padding_y + plot_height,
-                half,
-                color,
-            );
+            let center_color = band.center_color.map(|c| [c.r, c.g, c.b, c.a]);
+
+            // Non-finite samples split the band into runs
+            for run in band
+                .samples
+                .split(|s| !(s.x.is_finite() && s.low.is_finite() && s.high.is_finite()))
+            {
+                let edges: Vec<((f32, f32), (f32, f32))> = run
+                    .iter()
+                    .map(|s| {
+                        (
+                            Self::data_to_plot_screen(s.x, s.low, origin, uniforms),
+                            Self::data_to_plot_screen(s.x, s.high, origin, uniforms),
+                        )
+                    })
+                    .collect();
+                // edge_distance 0.0 renders fully opaque in fs_line
+                for pair in edges.windows(2) {
+                    let [(low0, high0), (low1, high1)] = [pair[0], pair[1]];
+                    for (x, y) in [low0, high0, low1, high0, high1, low1] {
+                        vertices.push(LineVertex::solid(x, y, fill));
+                    }
+                }
+
+                if let Some(color) = center_color {
+                    let screen: Vec<(f32, f32)> = run
+                        .iter()
+                        .filter(|s| s.center.is_finite())
+                        .map(|s| Self::data_to_plot_screen(s.x, s.center, origin, uniforms))
+                        .collect();
+                    Self::push_polyline(
+                        &mut vertices,
+                        &screen,
+                        &vec![color; screen.len()],
+                        &vec![0.0; screen.len()],
+                        band.center_width / 2.0,
+                        LinePattern::Solid.as_u32(),
+                        (LineJoin::Round, LineCap::Butt),
+                    );
+                }
+            }
         }
 
         vertices
     }
-}
-
-// ================================================================================
-// Coordinate conversion helpers
-// ================================================================================
 
-/// Convert screen coordinates (relative to widget bounds) to data coordinates.
-fn screen_to_data(
-    screen: Point,
-    bounds: Rectangle,
-    view_x: [f32; 2],
-    view_y: [f32; 2],
-    padding: f32,
-) -> (f32, f32) {
-    let plot_width = bounds.width - 2.0 * padding;
-    let plot_height = bounds.height - 2.0 * padding;
-    let x_norm = (screen.x - bounds.x - padding) / plot_width;
-    let y_norm = 1.0 - (screen.y - bounds.y - padding) / plot_height;
-    let x = view_x[0] + x_norm * (view_x[1] - view_x[0]);
-    let y = view_y[0] + y_norm * (view_y[1] - view_y[0]);
-    (x, y)
-}
+    /// Push an anti-aliased polyline in screen space, with `join` geometry
+    /// at its corners and `cap` geometry at its ends.
+    ///
+    /// Mitered corners move the shared quad corners to the miter point, so
+    /// they add no vertices. Bevel and round joins, and sharp miters, leave
+    /// the quads square and fill the outer gap with a triangle fan around
+    /// the corner; the inner side overlaps.
+    #[allow(clippy::too_many_arguments)]
+    fn push_polyline(
+        vertices: &mut Vec<LineVertex>,
+        screen: &[(f32, f32)],
+        colors: &[[f32; 4]],
+        distances: &[f32],
+        half_width: f32,
+        pattern: u32,
+        (join, cap): (LineJoin, LineCap),
+    ) {
+        // Drop repeated points, which have no direction
+        let mut kept: Vec<usize> = Vec::with_capacity(screen.len());
+        for (i, &(x, y)) in screen.iter().enumerate() {
+            if let Some(&last) = kept.last() {
+                let (px, py): (f32, f32) = screen[last];
+                if (x - px).hypot(y - py) < 0.001 {
+                    continue;
+                }
+            }
+            kept.push(i);
+        }
+        if kept.len() < 2 {
+            return;
+        }
 
-/// Convert data coordinates to screen coordinates (relative to widget bounds).
-fn data_to_screen(
-    data_x: f32,
-    data_y: f32,
-    bounds: Rectangle,
-    view_x: [f32; 2],
-    view_y: [f32; 2],
-    padding: f32,
-) -> Point {
-    let plot_width = bounds.width - 2.0 * padding;
-    let plot_height = bounds.height - 2.0 * padding;
-    let x_norm = (data_x - view_x[0]) / (view_x[1] - view_x[0]);
-    let y_norm = (data_y - view_y[0]) / (view_y[1] - view_y[0]);
-    Point::new(
-        padding + x_norm * plot_width,
-        padding + (1.0 - y_norm) * plot_height,
-    )
-}
-
-/// Clamp a view range to bounds, keeping the range size the same (shift rather than squash).
-/// If the view range exceeds bounds+padding, clamp it to the bounds size.
-pub fn clamp_range_to_bounds(
-    range: (f32, f32),
-    bounds: Option<(f32, f32)>,
-    padding_frac: f32,
-) -> (f32, f32) {
-    let (mut lo, mut hi) = range;
-    if let Some((b_lo, b_hi)) = bounds {
-        let pad = (b_hi - b_lo) * padding_frac;
-        let min_bound = b_lo - pad;
-        let max_bound = b_hi + pad;
-        let bounds_size = max_bound - min_bound;
-        let range_size = hi - lo;
-
-        // If the view is wider than bounds+padding, clamp to bounds size and center
-        if range_size > bounds_size {
-            let center = (min_bound + max_bound) / 2.0;
-            lo = center - bounds_size / 2.0;
-            hi = center + bounds_size / 2.0;
-        } else {
-            // Shift to stay within bounds
-            if lo < min_bound {
-                lo = min_bound;
-                hi = lo + range_size;
-            }
-            if hi > max_bound {
-                hi = max_bound;
-                lo = hi - range_size;
-            }
-        }
-    }
-    (lo, hi)
-}
-
-/// Apply elastic resistance when dragging past bounds.
-/// Returns the elastically-damped range (allows slight over-scroll).
-fn apply_elastic_resistance(
-    range: (f32, f32),
-    bounds: Option<(f32, f32)>,
-    padding_frac: f32,
-    elastic_limit: f32,
-) -> (f32, f32) {
-    let (lo, hi) = range;
-    if let Some((b_lo, b_hi)) = bounds {
-        let pad = (b_hi - b_lo) * padding_frac;
-        let min_bound = b_lo - pad;
-        let max_bound = b_hi + pad;
-        let range_size = hi - lo;
-        let max_overscroll = range_size * elastic_limit;
-
-        let mut new_lo = lo;
-        let mut new_hi = hi;
-
-        // Apply damping when past bounds (exponential decay)
-        if lo < min_bound {
-            let over = min_bound - lo;
-            let damped = max_overscroll * (1.0 - (-over / max_overscroll).exp());
-            new_lo = min_bound - damped;
-            new_hi = new_lo + range_size;
-        } else if hi > max_bound {
-            let over = hi - max_bound;
-            let damped = max_overscroll * (1.0 - (-over / max_overscroll).exp());
-            new_hi = max_bound + damped;
-            new_lo = new_hi - range_size;
-        }
-
-        (new_lo, new_hi)
-    } else {
-        (lo, hi)
-    }
-}
-
-/// Check if a range is outside its bounds (needs spring-back).
-fn is_out_of_bounds(range: (f32, f32), bounds: Option<(f32, f32)>, padding_frac: f32) -> bool {
-    if let Some((b_lo, b_hi)) = bounds {
-        let pad = (b_hi - b_lo) * padding_frac;
-        let min_bound = b_lo - pad;
-        let max_bound = b_hi + pad;
-        range.0 < min_bound - 0.001 || range.1 > max_bound + 0.001
-    } else {
-        false
-    }
-}
+        let (extended_half, edge_outer, hairline) = Self::line_aa(half_width);
+        let fade = |[r, g, b, a]: [f32; 4]| [r, g, b, a * hairline];
+        let point = |k: usize| screen[kept[k]];
+        let color = |k: usize| fade(colors[kept[k]]);
+        let distance = |k: usize| distances[kept[k]];
+
+        // Unit direction and left normal of each segment
+        let directions: Vec<(f32, f32)> = kept
+            .windows(2)
+            .map(|w| {
+                let ((x0, y0), (x1, y1)) = (screen[w[0]], screen[w[1]]);
+                let len = (x1 - x0).hypot(y1 - y0);
+                ((x1 - x0) / len, (y1 - y0) / len)
+            })
+            .collect();
+        let normal = |(dx, dy): (f32, f32)| (-dy * extended_half, dx * extended_half);
+
+        // Offset of the quad sides at the start and end of each segment
+        let mut offsets: Vec<[(f32, f32); 2]> =
+            directions.iter().map(|&d| [normal(d); 2]).collect();
+
+        // Circular fan of triangles around `center` from `from` to `to`
+        // (offsets of length extended_half), turning through `angle`
+        let push_fan = |vertices: &mut Vec<LineVertex>,
+                        center: (f32, f32),
+                        from: (f32, f32),
+                        angle: f32,
+                        steps: usize,
+                        color: [f32; 4],
+                        distance: f32| {
+            let (cx, cy) = center;
+            let start = from.1.atan2(from.0);
+            let rim = |i: usize| {
+                let a = start + angle * i as f32 / steps as f32;
+                LineVertex::new(
+                    cx + extended_half * a.cos(),
+                    cy + extended_half * a.sin(),
+                    color,
+                    distance,
+                    pattern,
+                )
+                .with_edge_distance(edge_outer)
+            };
+            for i in 0..steps {
+                vertices.push(LineVertex::new(cx, cy, color, distance, pattern));
+                vertices.push(rim(i));
+                vertices.push(rim(i + 1));
+            }
+        };
+        let round_steps =
+            |angle: f32| ((angle.abs() / (std::f32::consts::PI / 8.0)).ceil() as usize).max(1);
+
+        // Corners
+        for k in 1..kept.len() - 1 {
+            let (d0, d1) = (directions[k - 1], directions[k]);
+            let cross = d0.0 * d1.1 - d0.1 * d1.0;
+            let dot = d0.0 * d1.0 + d0.1 * d1.1;
+            if cross.abs() < 1e-4 && dot > 0.0 {
+                continue; // Straight on
+            }
+            let (n0, n1) = (normal(d0), normal(d1));
+
+            // Miter: the sides meet along the bisector of the normals, at
+            // 1 / cos(half the turn) times the half width
+            let bisector = (n0.0 + n1.0, n0.1 + n1.1);
+            let bisector_len = bisector.0.hypot(bisector.1);
+            if join == LineJoin::Miter && bisector_len > 1e-6 {
+                let cos_half = bisector_len / (2.0 * extended_half);
+                if 1.0 / cos_half <= MITER_LIMIT {
+                    let scale = extended_half / (cos_half * bisector_len);
+                    let miter = (bisector.0 * scale, bisector.1 * scale);
+                    offsets[k - 1][1] = miter;
+                    offsets[k][0] = miter;
+                    continue;
+                }
+            }
 
-/// Ease-out cubic: decelerating to zero velocity.
-fn ease_out_cubic(t: f32) -> f32 {
-    let t = t.clamp(0.0, 1.0);
-    1.0 - (1.0 - t).powi(3)
-}
+            // Fill the outer side: the normals' side when turning right
+            let (outer0, outer1) = if cross < 0.0 {
+                (n0, n1)
+            } else {
+                ((-n0.0, -n0.1), (-n1.0, -n1.1))
+            };
+            let turn = cross.atan2(dot);
+            let (center, color, distance) = (point(k), color(k), distance(k));
+            if join == LineJoin::Round {
+                // Around the outside, opposite to the turn
+                push_fan(
+                    vertices,
+                    center,
+                    outer0,
+                    turn,
+                    round_steps(turn),
+                    color,
+                    distance,
+                );
+            } else {
+                let corner = |(ox, oy): (f32, f32)| {
+                    LineVertex::new(center.0 + ox, center.1 + oy, color, distance, pattern)
+                        .with_edge_distance(edge_outer)
+                };
+                vertices.push(LineVertex::new(
+                    center.0, center.1, color, distance, pattern,
+                ));
+                vertices.push(corner(outer0));
+                vertices.push(corner(outer1));
+            }
+        }
 
-/// Interpolate between two ranges using an easing function.
-fn lerp_range(from: (f32, f32), to: (f32, f32), t: f32) -> (f32, f32) {
-    let t = ease_out_cubic(t);
-    (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
-}
+        // Ends
+        let last = kept.len() - 1;
+        let mut ends = [point(0), point(last)];
+        match cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                let (d0, d1) = (directions[0], directions[last - 1]);
+                ends[0].0 -= d0.0 * half_width;
+                ends[0].1 -= d0.1 * half_width;
+                ends[1].0 += d1.0 * half_width;
+                ends[1].1 += d1.1 * half_width;
+            }
+            LineCap::Round => {
+                let pi = std::f32::consts::PI;
+                let start_normal = normal(directions[0]);
+                let end_normal = normal(directions[last - 1]);
+                let steps = round_steps(pi);
+                push_fan(
+                    vertices,
+                    point(0),
+                    start_normal,
+                    pi,
+                    steps,
+                    color(0),
+                    distance(0),
+                );
+                push_fan(
+                    vertices,
+                    point(last),
+                    (-end_normal.0, -end_normal.1),
+                    pi,
+                    steps,
+                    color(last),
+                    distance(last),
+                );
+            }
+        }
 
-// ================================================================================
-// shader::Primitive implementation
-// ================================================================================
+        for (k, [offset0, offset1]) in offsets.into_iter().enumerate() {
+            let p0 = if k == 0 { ends[0] } else { point(k) };
+            let p1 = if k + 1 == last { ends[1] } else { point(k + 1) };
+            Self::push_offset_quad(
+                vertices,
+                (p0, p1),
+                (offset0, offset1),
+                edge_outer,
+                (color(k), color(k + 1)),
+                (pattern, [distance(k), distance(k + 1)]),
+            );
+        }
+    }
 
-impl shader::Primitive for PlotterPrimitive {
-    type Pipeline = Pipeline;
+    /// Quad half width including the anti-aliasing fringe, the matching
+    /// `edge_distance` at the quad sides, and the alpha factor for lines
+    /// thinner than a pixel.
+    fn line_aa(half_width: f32) -> (f32, f32, f32) {
+        // Extend the quad by 1px on each side for anti-aliased edges.
+        // edge_distance is interpolated across the quad:
+        //   0.0  = line centre
+        //   1.0  = original half-width (start of fade)
+        //  >1.0  = extension zone (fades to transparent)
+        let aa_extend: f32 = 1.0; // extra pixels for AA fringe
+        let extended_half = half_width + aa_extend;
+
+        // Lines thinner than a pixel are drawn one pixel wide and faded to
+        // keep their visual weight
+        let edge_outer = extended_half / half_width.max(0.5);
+        let hairline = (2.0 * half_width).min(1.0);
+        (extended_half, edge_outer, hairline)
+    }
 
-    fn prepare(
-        &self,
-        pipeline: &mut Self::Pipeline,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        bounds: &Rectangle,
-        viewport: &Viewport,
+    /// Push a quad from `start` to `end` whose sides are at `±offset0` from
+    /// the start and `±offset1` from the end. The `+` side gets
+    /// `+edge_outer` and the `-` side `-edge_outer`, so interpolation
+    /// crosses 0 at the centre.
+    fn push_offset_quad(
+        vertices: &mut Vec<LineVertex>,
+        ((sx0, sy0), (sx1, sy1)): ((f32, f32), (f32, f32)),
+        ((nx0, ny0), (nx1, ny1)): ((f32, f32), (f32, f32)),
+        edge_outer: f32,
+        (color0, color1): ([f32; 4], [f32; 4]),
+        (pattern, [d0, d1]): (u32, [f32; 2]),
     ) {
-        // Combine grid + selection + highlight vertices for the grid render pass
-        let has_overlay =
-            !self.selection_vertices.is_empty() || !self.highlight_vertices.is_empty();
-        if !has_overlay {
-            pipeline.update(
-                device,
-                queue,
-                &self.uniforms,
-                &self.points,
-                &self.line_vertices,
-                &self.grid_vertices,
-            );
-        } else {
-            let mut combined = self.grid_vertices.clone();
-            combined.extend_from_slice(&self.selection_vertices);
-            combined.extend_from_slice(&self.highlight_vertices);
-            pipeline.update(
-                device,
-                queue,
-                &self.uniforms,
-                &self.points,
-                &self.line_vertices,
-                &combined,
-            );
+        let v0 = LineVertex::new(sx0 + nx0, sy0 + ny0, color0, d0, pattern)
+            .with_edge_distance(edge_outer);
+        let v1 = LineVertex::new(sx0 - nx0, sy0 - ny0, color0, d0, pattern)
+            .with_edge_distance(-edge_outer);
+        let v2 = LineVertex::new(sx1 + nx1, sy1 + ny1, color1, d1, pattern)
+            .with_edge_distance(edge_outer);
+        let v3 = LineVertex::new(sx1 - nx1, sy1 - ny1, color1, d1, pattern)
+            .with_edge_distance(-edge_outer);
+
+        vertices.push(v0);
+        vertices.push(v1);
+        vertices.push(v2);
+
+        vertices.push(v1);
+        vertices.push(v3);
+        vertices.push(v2);
+    }
+
+    /// Push one anti-aliased line segment (two triangles) in screen space.
+    ///
+    /// Colors are given per endpoint and interpolated along the segment, so
+    /// gradient and colormap-colored lines stay continuous. `dash` is the
+    /// [`LinePattern`] and the distance along the line at each endpoint.
+    fn push_line_segment(
+        vertices: &mut Vec<LineVertex>,
+        (sx0, sy0): (f32, f32),
+        (sx1, sy1): (f32, f32),
+        half_width: f32,
+        (color0, color1): ([f32; 4], [f32; 4]),
+        (pattern, distances): (u32, [f32; 2]),
+    ) {
+        let dx = sx1 - sx0;
+        let dy = sy1 - sy0;
+        let len = (dx * dx + dy * dy).sqrt();
+
+        if len < 0.001 {
+            return;
         }
 
-        // Compute scissor rects in absolute physical pixel coordinates.
-        // iced sets the viewport to the widget's bounds before calling draw,
-        // but set_scissor_rect always operates in absolute framebuffer coords.
-        let scale = viewport.scale_factor() as f32;
-        let pad_x = self.uniforms.padding[0];
-        let pad_y = self.uniforms.padding[1];
+        let (extended_half, edge_outer, hairline) = Self::line_aa(half_width);
+        let normal = (-dy / len * extended_half, dx / len * extended_half);
+        let fade = |[r, g, b, a]: [f32; 4]| [r, g, b, a * hairline];
+        Self::push_offset_quad(
+            vertices,
+            ((sx0, sy0), (sx1, sy1)),
+            (normal, normal),
+            edge_outer,
+            (fade(color0), fade(color1)),
+            (pattern, distances),
+        );
+    }
 
-        // Widget bounds in physical pixels
-        let wx = (bounds.x * scale) as u32;
-        let wy = (bounds.y * scale) as u32;
-        let ww = (bounds.width * scale) as u32;
-        let wh = (bounds.height * scale) as u32;
-        pipeline.widget_scissor = [wx, wy, ww.max(1), wh.max(1)];
+    /// Draw `annotations` with the grid, above the grid lines and beneath
+    /// the axes.
+    #[cfg(feature = "annotations")]
+    fn with_annotations(mut self, annotations: &[Annotation]) -> Self {
+        Arc::make_mut(&mut self.grid).annotations =
+            Self::generate_annotation_vertices(annotations, self.origin, &self.uniforms);
+        self
+    }
 
-        // Plot area (inside padding) in physical pixels
-        let px = (bounds.x + pad_x) * scale;
-        let py = (bounds.y + pad_y) * scale;
-        let pw = (bounds.width - 2.0 * pad_x) * scale;
-        let ph = (bounds.height - 2.0 * pad_y) * scale;
-        pipeline.plot_scissor = [
-            px as u32,
-            py as u32,
-            (pw as u32).max(1),
-            (ph as u32).max(1),
-        ];
+    /// Reference lines, bands and arrows from [`PlotterOptions::annotations`]
+    /// and the event bands, in screen space and clipped to the plot area. Text is left to the overlay.
+    #[cfg(feature = "annotations")]
+    fn generate_annotation_vertices(
+        annotations: &[Annotation],
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+    ) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
+        let [left, top] = uniforms.padding;
+        let [plot_width, plot_height] = uniforms.plot_size;
+        let (right, bottom) = (left + plot_width, top + plot_height);
+        let plot_rect = [left, top, right, bottom];
+        let screen = |(x, y): (f64, f64)| Self::data_to_plot_screen(x, y, origin, uniforms);
+        let screen_x = |x: f64| screen((x, origin[1])).0;
+        let screen_y = |y: f64| screen((origin[0], y)).1;
+
+        for annotation in annotations {
+            let c = annotation.color;
+            let color = [c.r, c.g, c.b, c.a];
+            // Unclipped line segments, the band rectangle and the arrow tip
+            let mut band = None;
+            let mut tip = None;
+            let lines = match annotation.kind {
+                AnnotationKind::HLine { y } => vec![((left, screen_y(y)), (right, screen_y(y)))],
+                AnnotationKind::VLine { x } => vec![((screen_x(x), top), (screen_x(x), bottom))],
+                AnnotationKind::HBand { y: (y0, y1) } => {
+                    let (s0, s1) = (screen_y(y0), screen_y(y1));
+                    let (lo, hi) = (s0.min(s1).max(top), s0.max(s1).min(bottom));
+                    band = (lo < hi).then_some([left, lo, right, hi]);
+                    vec![((left, s0), (right, s0)), ((left, s1), (right, s1))]
+                }
+                AnnotationKind::VBand { x: (x0, x1) } => {
+                    let (s0, s1) = (screen_x(x0), screen_x(x1));
+                    let (lo, hi) = (s0.min(s1).max(left), s0.max(s1).min(right));
+                    band = (lo < hi).then_some([lo, top, hi, bottom]);
+                    vec![((s0, top), (s0, bottom)), ((s1, top), (s1, bottom))]
+                }
+                AnnotationKind::Text { .. } => Vec::new(),
+                AnnotationKind::Arrow { from, to } => {
+                    let (from, to) = (screen(from), screen(to));
+                    tip = Some((from, to));
+                    vec![(from, to)]
+                }
+            };
+
+            if let Some([x0, y0, x1, y1]) = band {
+                for (x, y) in [(x0, y0), (x1, y0), (x0, y1), (x1, y0), (x1, y1), (x0, y1)] {
+                    vertices.push(LineVertex::solid(x, y, color));
+                }
+            }
+            if annotation.width <= 0.0 {
+                continue;
+            }
+
+            // Solid head, only when the tip itself is visible
+            let mut head_length = 0.0;
+            if let Some(((x0, y0), (x1, y1))) = tip {
+                let (dx, dy) = (x1 - x0, y1 - y0);
+                let length = (dx * dx + dy * dy).sqrt();
+                head_length = (annotation.width * 3.0 + 6.0).min(length);
+                let inside = (left..=right).contains(&x1) && (top..=bottom).contains(&y1);
+                if inside && length > 0.001 {
+                    let (ux, uy) = (dx / length, dy / length);
+                    let (bx, by) = (x1 - ux * head_length, y1 - uy * head_length);
+                    let half = head_length * 0.5;
+                    for (x, y) in [
+                        (x1, y1),
+                        (bx - uy * half, by + ux * half),
+                        (bx + uy * half, by - ux * half),
+                    ] {
+                        vertices.push(LineVertex::solid(x, y, color));
+                    }
+                }
+            }
+            if annotation.pattern == LinePattern::None {
+                continue;
+            }
+
+            for ((x0, y0), (x1, y1)) in lines {
+                let (dx, dy) = (x1 - x0, y1 - y0);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length < 0.001 {
+                    continue;
+                }
+                // Arrow shafts stop at the base of the head
+                let end = 1.0 - head_length / length;
+                let (x1, y1) = (x0 + dx * end, y0 + dy * end);
+                let Some([t0, t1]) = core::clip_segment([x0, y0], [x1, y1], plot_rect) else {
+                    continue;
+                };
+                let at = |t: f32| (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+                // Pattern distance from the unclipped start, so dashes don't
+                // shift when the start scrolls out of view
+                let per_width = length * end / annotation.width.max(1.0);
+                Self::push_line_segment(
+                    &mut vertices,
+                    at(t0),
+                    at(t1),
+                    annotation.width / 2.0,
+                    (color, color),
+                    (
+                        annotation.pattern.as_u32(),
+                        [t0 * per_width, t1 * per_width],
+                    ),
+                );
+            }
+        }
+        vertices
     }
 
-    fn draw(&self, pipeline: &Self::Pipeline, render_pass: &mut wgpu::RenderPass<'_>) -> bool {
-        let total_grid = self.grid_vertices.len()
-            + self.selection_vertices.len()
-            + self.highlight_vertices.len();
-        if total_grid > 0 {
-            pipeline.render_grid(render_pass, total_grid as u32);
+    fn generate_grid_spec(
+        options: &PlotterOptions,
+        origin: [f64; 2],
+        uniforms: &Uniforms,
+        ticks: &TickInfo,
+    ) -> GridSpec {
+        let mut grid = GridSpec::default();
+
+        let padding_x = uniforms.padding[0];
+        let padding_y = uniforms.padding[1];
+        let [plot_width, plot_height] = uniforms.plot_size;
+        let x_range = uniforms.x_range;
+        let y_range = uniforms.y_range;
+        let (x0, x1) = (padding_x, padding_x + plot_width);
+        let (y0, y1) = (padding_y, padding_y + plot_height);
+        let rgba = |c: iced::Color| [c.r, c.g, c.b, c.a];
+
+        // Plot area background quad (rendered first, behind everything else)
+        if let Some(bg) = options.background_color {
+            grid.below.push(GridQuad {
+                x: [x0, x1],
+                y: [y0, y1],
+                color: rgba(bg),
+            });
         }
 
-        // Set scissor rect to clip markers and lines to the plot area (inside padding).
-        // These are absolute physical-pixel coordinates computed during prepare().
-        let [sx, sy, sw, sh] = pipeline.plot_scissor;
-        render_pass.set_scissor_rect(sx, sy, sw, sh);
+        // Lines are thin quads along the plot's edges, skipped when the plot
+        // is too small to hold them
+        let vertical = |x: f32, half_width: f32, color: [f32; 4]| {
+            (plot_height >= 0.001).then_some(GridQuad {
+                x: [x - half_width, x + half_width],
+                y: [y0, y1],
+                color,
+            })
+        };
+        let horizontal = |y: f32, half_width: f32, color: [f32; 4]| {
+            (plot_width >= 0.001).then_some(GridQuad {
+                x: [x0, x1],
+                y: [y - half_width, y + half_width],
+                color,
+            })
+        };
+
+        if options.grid.show {
+            let grid_color = rgba(options.grid.color);
+            let grid_half = options.grid.line_width / 2.0;
 
-        if self.config.show_lines {
-            pipeline.render_lines(render_pass, self.line_vertices.len() as u32);
+            // Ticks sit at round data values; move them into the local frame
+            let [ox, oy] = origin;
+            for v in ticks.x_ticks.iter().map(|v| (v - ox) as f32) {
+                if v < x_range[0] || v > x_range[1] {
+                    continue;
+                }
+                let x_norm = (v - x_range[0]) / (x_range[1] - x_range[0]);
+                let screen_x = padding_x + x_norm * plot_width;
+                grid.below.extend(vertical(screen_x, grid_half, grid_color));
+            }
+
+            for v in ticks.y_ticks.iter().map(|v| (v - oy) as f32) {
+                if v < y_range[0] || v > y_range[1] {
+                    continue;
+                }
+                let y_norm = (v - y_range[0]) / (y_range[1] - y_range[0]);
+                let screen_y = padding_y + (1.0 - y_norm) * plot_height;
+                grid.below
+                    .extend(horizontal(screen_y, grid_half, grid_color));
+            }
         }
 
-        if self.config.show_markers {
-            pipeline.render_markers(render_pass, self.points.len() as u32);
+        if options.x_axis.show {
+            let half = options.x_axis.line_width / 2.0;
+            grid.above
+                .extend(horizontal(y1, half, rgba(options.x_axis.color)));
         }
 
-        // Restore scissor rect to full widget bounds so iced's subsequent rendering is correct.
-        let [wx, wy, ww, wh] = pipeline.widget_scissor;
-        render_pass.set_scissor_rect(wx, wy, ww, wh);
+        if options.y_axis.show {
+            let half = options.y_axis.line_width / 2.0;
+            grid.above
+                .extend(vertical(x0, half, rgba(options.y_axis.color)));
+        }
 
-        true
+        grid
     }
-}
 
-impl shader::Pipeline for Pipeline {
-    fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
-        Pipeline::new(device, queue, format)
+    /// Triangles for `grid`, with the background, grid and axis quads
+    /// snapped to the physical pixels described by `pixels` so hairlines
+    /// don't straddle two pixels and blur.
+    fn generate_grid_vertices(grid: &GridSpec, pixels: PixelGrid) -> Vec<LineVertex> {
+        let mut vertices = Vec::with_capacity(grid.vertex_count());
+        let push_quads = |vertices: &mut Vec<LineVertex>, quads: &[GridQuad]| {
+            for quad in quads {
+                let [x0, x1] = pixels.snap(0, quad.x[0], quad.x[1]);
+                let [y0, y1] = pixels.snap(1, quad.y[0], quad.y[1]);
+                let color = quad.color;
+
+                vertices.push(LineVertex::solid(x0, y0, color));
+                vertices.push(LineVertex::solid(x1, y0, color));
+                vertices.push(LineVertex::solid(x0, y1, color));
+
+                vertices.push(LineVertex::solid(x1, y0, color));
+                vertices.push(LineVertex::solid(x1, y1, color));
+                vertices.push(LineVertex::solid(x0, y1, color));
+            }
+        };
+
+        push_quads(&mut vertices, &grid.below);
+        vertices.extend_from_slice(&grid.annotations);
+        push_quads(&mut vertices, &grid.above);
+        vertices
     }
 }
 
+/// Min and max of the finite entries in `values`.
+fn value_range(values: &[f32]) -> (f32, f32) {
+    values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        })
+}
+
 // ================================================================================
-// shader::Program implementation (event handling + drawing)
+// Coordinate conversion helpers
 // ================================================================================
 
-impl<Message: Clone> shader::Program<Message> for Plotter<'_, Message> {
-    type State = PlotterState;
-    type Primitive = PlotterPrimitive;
+/// Convert screen coordinates (relative to widget bounds) to data coordinates.
+fn screen_to_data(
+    screen: Point,
+    bounds: Rectangle,
+    view_x: [f64; 2],
+    view_y: [f64; 2],
+    padding: iced::Padding,
+) -> (f64, f64) {
+    let [x, y] = core::screen_to_data(
+        [
+            screen.x - bounds.x - padding.left,
+            screen.y - bounds.y - padding.top,
+        ],
+        [bounds.width - padding.x(), bounds.height - padding.y()],
+        view_x,
+        view_y,
+    );
+    (x, y)
+}
 
-    fn update(
-        &self,
-        state: &mut Self::State,
-        event: &Event,
-        bounds: Rectangle,
-        cursor: Cursor,
-    ) -> Option<shader::Action<Message>> {
-        let interaction = &self.interaction;
+/// Convert data coordinates to screen coordinates (relative to widget bounds).
+fn data_to_screen(
+    data_x: f64,
+    data_y: f64,
+    bounds: Rectangle,
+    view_x: [f64; 2],
+    view_y: [f64; 2],
+    padding: iced::Padding,
+) -> Point {
+    let [x, y] = core::data_to_screen(
+        [data_x, data_y],
+        [bounds.width - padding.x(), bounds.height - padding.y()],
+        view_x,
+        view_y,
+    );
+    Point::new(padding.left + x, padding.top + y)
+}
 
-        // Check if any interaction is enabled at all
-        let has_any_interaction = interaction.pan_x
-            || interaction.pan_y
-            || interaction.zoom_x
-            || interaction.zoom_y
-            || interaction.double_click_to_fit
-            || interaction.zoom_select;
-        let has_tooltip = self.options.tooltip.is_some();
+impl<Message> Plotter<'_, Message> {
+    /// Current time from the configured clock.
+    fn now(&self) -> std::time::Instant {
+        self.clock
+            .as_ref()
+            .map_or_else(std::time::Instant::now, |clock| clock())
+    }
 
-        if !has_any_interaction && !has_tooltip {
-            return None;
+    /// Carry an explicit secondary Y range along with a change of the
+    /// primary one, so both Y axes pan and zoom together.
+    fn follow_secondary_y(&self, mut view: ViewState) -> ViewState {
+        if view.y_range == self.view_state.y_range {
+            return view;
         }
+        if let (Some((lo, hi)), Some((new_lo, new_hi))) = (view.secondary_y_range, view.y_range) {
+            let [old_lo, old_hi] = self.resolve_view_ranges(false).1;
+            let scale = (hi - lo) / (old_hi - old_lo);
+            view.secondary_y_range = Some((
+                lo + (new_lo - old_lo) * scale,
+                lo + (new_hi - old_lo) * scale,
+            ));
+        }
+        view
+    }
 
-        let (view_x, view_y, data_x, data_y) = self.resolve_view_ranges(false);
-        let padding = self.options.padding;
-
-        // When elastic is enabled but no explicit bounds are set, use the data
-        // extent as automatic bounds. Without bounds the elastic/clamping logic
-        // has nothing to enforce, which silently disables the feature — a
-        // confusing API pitfall.
-        let effective_x_bounds = interaction.x_bounds.or_else(|| {
-            if interaction.elastic && interaction.pan_x {
-                Some((data_x[0], data_x[1]))
-            } else {
+    /// The view with every pannable or zoomable axis back on auto-fit.
+    fn fit_view(&self) -> ViewState {
+        let interaction = &self.interaction;
+        let fit_x = interaction.pan_x || interaction.zoom_x;
+        let fit_y = interaction.pan_y || interaction.zoom_y;
+        ViewState {
+            x_range: if fit_x { None } else { self.view_state.x_range },
+            y_range: if fit_y { None } else { self.view_state.y_range },
+            secondary_y_range: if fit_y {
                 None
-            }
-        });
-        let effective_y_bounds = interaction.y_bounds.or_else(|| {
-            if interaction.elastic && interaction.pan_y {
-                Some((data_y[0], data_y[1]))
             } else {
-                None
-            }
-        });
-
-        // ---------- Elastic spring-back animation ----------
-        // Tick the animation on every event while it's active.
-        // Each tick publishes the interpolated view and requests the next redraw.
-        if let Some(ref anim) = state.elastic_animation.clone() {
-            let elapsed = anim.start_time.elapsed().as_millis() as u64;
+                self.view_state.secondary_y_range
+            },
+        }
+    }
 
-            if elapsed >= anim.duration_ms {
-                // Animation complete: snap to target
-                let mut new_view = self.view_state.clone();
-                if let (Some(_from), Some(to)) = (anim.from_x, anim.to_x) {
-                    new_view.x_range = Some(to);
-                }
-                if let (Some(_from), Some(to)) = (anim.from_y, anim.to_y) {
-                    new_view.y_range = Some(to);
-                }
-                state.elastic_animation = None;
+    /// The view after a navigation key press, or `None` if the key does
+    /// nothing here.
+    fn key_view(
+        &self,
+        key: &keyboard::Key,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        x_bounds: Option<(f64, f64)>,
+        y_bounds: Option<(f64, f64)>,
+    ) -> Option<ViewState> {
+        use keyboard::key::Named;
 
-                if let Some(ref on_change) = self.on_view_change {
-                    return Some(shader::Action::publish((on_change)(new_view)));
-                }
-                return None;
+        let interaction = &self.interaction;
+        let pan_step = f64::from(interaction.keyboard_pan_step);
+        let zoom_step = f64::from(interaction.keyboard_zoom_step).clamp(0.0, 0.9);
+
+        // Pan by fractions of the view width/height, or zoom about the center
+        let (pan, zoom) = match key.as_ref() {
+            keyboard::Key::Named(Named::ArrowLeft) => ([-pan_step, 0.0], 1.0),
+            keyboard::Key::Named(Named::ArrowRight) => ([pan_step, 0.0], 1.0),
+            keyboard::Key::Named(Named::ArrowUp) => ([0.0, pan_step], 1.0),
+            keyboard::Key::Named(Named::ArrowDown) => ([0.0, -pan_step], 1.0),
+            keyboard::Key::Named(Named::PageUp) => ([-1.0, 0.0], 1.0),
+            keyboard::Key::Named(Named::PageDown) => ([1.0, 0.0], 1.0),
+            keyboard::Key::Character("+" | "=") => ([0.0; 2], 1.0 - zoom_step),
+            keyboard::Key::Character("-" | "_") => ([0.0; 2], 1.0 / (1.0 - zoom_step)),
+            keyboard::Key::Named(Named::Home) => {
+                return (interaction.pan_x
+                    || interaction.pan_y
+                    || interaction.zoom_x
+                    || interaction.zoom_y)
+                    .then(|| self.fit_view());
             }
+            _ => return None,
+        };
 
-            // Still animating: interpolate and request next frame
-            let t = elapsed as f32 / anim.duration_ms as f32;
-            let mut new_view = self.view_state.clone();
-            if let (Some(from), Some(to)) = (anim.from_x, anim.to_x) {
-                new_view.x_range = Some(lerp_range(from, to, t));
-            }
-            if let (Some(from), Some(to)) = (anim.from_y, anim.to_y) {
-                new_view.y_range = Some(lerp_range(from, to, t));
-            }
+        let step = |[lo, hi]: [f64; 2], pan: f64, enabled_pan: bool, enabled_zoom: bool| {
+            let (shift, scale) = (
+                if enabled_pan { pan * (hi - lo) } else { 0.0 },
+                if enabled_zoom { zoom } else { 1.0 },
+            );
+            let center = (lo + hi) / 2.0 + shift;
+            let half = (hi - lo) / 2.0 * scale;
+            (shift != 0.0 || scale != 1.0).then_some((center - half, center + half))
+        };
+        let (zoom_x, zoom_y) = self.zoom_axes(interaction.zoom_x, interaction.zoom_y);
+        let new_x = step(view_x, pan[0], interaction.pan_x, zoom_x);
+        let new_y = step(view_y, pan[1], interaction.pan_y, zoom_y);
+        if new_x.is_none() && new_y.is_none() {
+            return None;
+        }
 
-            if let Some(ref on_change) = self.on_view_change {
-                // Publish triggers a redraw, which triggers another update cycle
-                return Some(shader::Action::publish((on_change)(new_view)));
-            }
-            return Some(shader::Action::request_redraw());
+        let mut view = self.view_state.clone();
+        if let Some(range) = new_x {
+            view.x_range = Some(clamp_range_to_bounds(
+                range,
+                x_bounds,
+                interaction.boundary_padding,
+            ));
+        }
+        if let Some(range) = new_y {
+            view.y_range = Some(clamp_range_to_bounds(
+                range,
+                y_bounds,
+                interaction.boundary_padding,
+            ));
         }
+        Some(self.follow_secondary_y(view))
+    }
 
-        match event {
-            // ---- Track keyboard modifiers ----
-            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
-                state.modifiers = *modifiers;
-                None
-            }
-
-            // ---- Mouse button press ----
-            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
-                if let Some(pos) = cursor.position_in(bounds) {
-                    // Check legend interactions first — block all clicks within legend bounds
-                    if self.options.legend.is_some() {
-                        let layout = self.legend_state.layout.borrow();
-                        // Check toggle button clicks
-                        for toggle in layout.toggles.iter() {
-                            if toggle.rect.contains(pos) {
-                                let mut hidden = self.legend_state.hidden_series.borrow_mut();
-                                if hidden.contains(&toggle.series_index) {
-                                    hidden.remove(&toggle.series_index);
-                                } else {
-                                    hidden.insert(toggle.series_index);
-                                }
-                                return Some(shader::Action::request_redraw().and_capture());
-                            }
-                        }
-                        // Block clicks anywhere on the legend background
-                        if let Some(legend_bounds) = layout.bounds {
-                            if legend_bounds.contains(pos) {
-                                return Some(shader::Action::capture());
-                            }
-                        }
-                    }
-                    // Double-click detection
-                    if interaction.double_click_to_fit {
-                        let now = std::time::Instant::now();
-                        if let Some(last) = state.last_click_time
-                            && now.duration_since(last).as_millis() < 300 {
-                                // Double-click: reset to auto-fit
-                                state.last_click_time = None;
-                                state.interaction_mode = InteractionMode::Idle;
-                                state.elastic_animation = None;
-
-                                if let Some(ref on_change) = self.on_view_change {
-                                    let new_view = ViewState {
-                                        x_range: if interaction.pan_x || interaction.zoom_x {
-                                            None
-                                        } else {
-                                            self.view_state.x_range
-                                        },
-                                        y_range: if interaction.pan_y || interaction.zoom_y {
-                                            None
-                                        } else {
-                                            self.view_state.y_range
-                                        },
-                                    };
-                                    return Some(
-                                        shader::Action::publish((on_change)(new_view))
-                                            .and_capture(),
-                                    );
-                                }
-                                return Some(shader::Action::capture());
-                            }
-                        state.last_click_time = Some(now);
-                    }
+    /// Axes a zoom of `zoom_x`/`zoom_y` acts on: both when the aspect is
+    /// locked, so widening doesn't undo the zoom of one.
+    fn zoom_axes(&self, zoom_x: bool, zoom_y: bool) -> (bool, bool) {
+        if self.aspect_locked() && (zoom_x || zoom_y) {
+            (true, true)
+        } else {
+            (zoom_x, zoom_y)
+        }
+    }
 
-                    // Ctrl+click = zoom select
-                    if interaction.zoom_select && state.modifiers.control() {
-                        // Clear tooltip when starting interaction
-                        *self.tooltip_state.hovered.borrow_mut() = None;
-                        state.interaction_mode = InteractionMode::ZoomSelecting;
-                        state.drag_start = Some(pos);
-                        state.zoom_select_current = Some(pos);
-                        return Some(shader::Action::capture());
-                    }
+    /// Remember the current view in the view history before a zoom, pan or
+    /// fit replaces it.
+    fn record_view(&self) {
+        if let Some(history) = &self.view_history {
+            history.push(self.view_state.clone());
+        }
+    }
 
-                    // Start panning
-                    if interaction.pan_x || interaction.pan_y {
-                        // Clear tooltip when starting interaction
-                        *self.tooltip_state.hovered.borrow_mut() = None;
-                        state.elastic_animation = None; // Cancel any ongoing animation
-                        state.interaction_mode = InteractionMode::Panning;
-                        state.drag_start = Some(pos);
-                        state.drag_start_view = Some(ViewState {
-                            x_range: Some((view_x[0], view_x[1])),
-                            y_range: Some((view_y[0], view_y[1])),
-                        });
-                        return Some(shader::Action::capture());
-                    }
-                }
-                None
+    /// Carry out a context menu action opened at `data`, returning the
+    /// message to publish.
+    fn context_action(
+        &self,
+        state: &mut PlotterState,
+        action: ContextAction,
+        data: (f64, f64),
+    ) -> Option<Message> {
+        match action {
+            ContextAction::ResetView => {
+                let on_change = self.on_view_change.as_ref()?;
+                state.elastic_animation = None;
+                self.record_view();
+                Some(on_change(self.fit_view(), ViewChangeKind::Final))
             }
+            _ => self.on_context_action.as_ref().map(|f| f(action, data)),
+        }
+    }
 
-            // ---- Mouse button release ----
-            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
-                match state.interaction_mode {
-                    InteractionMode::Panning => {
-                        state.interaction_mode = InteractionMode::Idle;
-                        state.drag_start = None;
-                        state.drag_start_view = None;
+    /// Publish the view one step back (or forward) in the view history.
+    fn step_history(
+        &self,
+        state: &mut PlotterState,
+        forward: bool,
+    ) -> Option<shader::Action<Message>> {
+        let history = self.view_history.as_ref()?;
+        let on_change = self.on_view_change.as_ref()?;
+        let view = if forward {
+            history.forward(self.view_state)
+        } else {
+            history.back(self.view_state)
+        }?;
+        state.elastic_animation = None;
+        state.inertia_animation = None;
+        Some(shader::Action::publish(on_change(view, ViewChangeKind::Final)).and_capture())
+    }
 
-                        // Check if we need to spring back from over-scroll
-                        if interaction.elastic {
-                            let current_x =
-                                self.view_state.x_range.unwrap_or((view_x[0], view_x[1]));
-                            let current_y =
-                                self.view_state.y_range.unwrap_or((view_y[0], view_y[1]));
+    /// Publish a step of a gesture or animation, at most
+    /// [`InteractionConfig::view_change_rate`] times per second. The view is
+    /// kept in `state.gesture_view` for the final change; while held back,
+    /// the redraw keeps animations ticking. `None` without a callback.
+    fn publish_interactive_view(
+        &self,
+        state: &mut PlotterState,
+        view: ViewState,
+    ) -> Option<shader::Action<Message>> {
+        let on_change = self.on_view_change.as_ref()?;
+        state.gesture_view = Some(view.clone());
+        let now = self.now();
+        let interval = self
+            .interaction
+            .view_change_rate
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| std::time::Duration::from_secs_f32(1.0 / rate));
+        if let (Some(interval), Some(last)) = (interval, state.last_view_change)
+            && now.duration_since(last) < interval
+        {
+            return Some(shader::Action::request_redraw());
+        }
+        state.last_view_change = Some(now);
+        Some(shader::Action::publish(on_change(
+            view,
+            ViewChangeKind::Interactive,
+        )))
+    }
 
-                            let x_out = interaction.pan_x
-                                && is_out_of_bounds(
-                                    current_x,
-                                    effective_x_bounds,
-                                    interaction.boundary_padding,
-                                );
-                            let y_out = interaction.pan_y
-                                && is_out_of_bounds(
-                                    current_y,
-                                    effective_y_bounds,
-                                    interaction.boundary_padding,
-                                );
+    /// Fingerprint of the series, polygons, bands, events, box plots,
+    /// quivers, images, spectrogram, options and shared state.
+    fn content_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.series.hash(&mut hasher);
+        self.polygons.hash(&mut hasher);
+        self.bands.hash(&mut hasher);
+        #[cfg(feature = "annotations")]
+        self.events.hash(&mut hasher);
+        self.box_plots.hash(&mut hasher);
+        self.quivers.hash(&mut hasher);
+        self.images.hash(&mut hasher);
+        self.options.hash(&mut hasher);
+        if let Some(spectrogram) = self.spectrogram {
+            (spectrogram.id(), spectrogram.pushed()).hash(&mut hasher);
+            spectrogram
+                .time_range()
+                .map(|range| range.map(f64::to_bits))
+                .hash(&mut hasher);
+        }
 
-                            if x_out || y_out {
-                                let target_x = if x_out {
-                                    Some(clamp_range_to_bounds(
-                                        current_x,
-                                        effective_x_bounds,
-                                        interaction.boundary_padding,
-                                    ))
-                                } else {
-                                    None
-                                };
-                                let target_y = if y_out {
-                                    Some(clamp_range_to_bounds(
-                                        current_y,
-                                        effective_y_bounds,
-                                        interaction.boundary_padding,
-                                    ))
-                                } else {
-                                    None
-                                };
+        let mut hidden: Vec<usize> = self.hidden_series().into_iter().collect();
+        hidden.sort_unstable();
+        hidden.hash(&mut hasher);
+        if let Some((link, mode)) = &self.selection_link {
+            let mut selected: Vec<usize> = link.selected.borrow().iter().copied().collect();
+            selected.sort_unstable();
+            selected.hash(&mut hasher);
+            (*mode as u8).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
 
-                                state.elastic_animation = Some(ElasticState {
-                                    from_x: if x_out { Some(current_x) } else { None },
-                                    from_y: if y_out { Some(current_y) } else { None },
-                                    to_x: target_x,
-                                    to_y: target_y,
-                                    start_time: std::time::Instant::now(),
-                                    duration_ms: interaction.elastic_duration_ms,
-                                });
+    /// Whether a legend is configured (always `false` without the `legend` feature).
+    fn legend_enabled(&self) -> bool {
+        #[cfg(feature = "legend")]
+        return self.options.legend.is_some();
+        #[cfg(not(feature = "legend"))]
+        false
+    }
 
-                                return Some(shader::Action::request_redraw().and_capture());
-                            }
-                        }
+    /// The series emphasized because its legend entry is hovered, with the
+    /// dim alpha and width scale (always `None` without the `legend` feature).
+    fn legend_emphasis(&self) -> Option<(usize, f32, f32)> {
+        #[cfg(feature = "legend")]
+        {
+            let emphasis = self.options.legend.as_ref()?.hover_emphasis?;
+            let series = (*self.legend_state.hovered.borrow())?;
+            let hidden = self.legend_state.hidden_series.borrow().contains(&series)
+                || self.series.get(series).is_none_or(|s| s.hidden);
+            (!hidden).then_some((series, emphasis.dim_alpha, emphasis.width_scale))
+        }
+        #[cfg(not(feature = "legend"))]
+        None
+    }
 
-                        Some(shader::Action::capture())
-                    }
-                    InteractionMode::ZoomSelecting => {
-                        // Complete the zoom selection
-                        if let (Some(start), Some(current)) =
-                            (state.drag_start, state.zoom_select_current)
-                        {
-                            // Convert screen coords to data coords
-                            let (x0, y0) = screen_to_data(
-                                Point::new(start.x + bounds.x, start.y + bounds.y),
-                                bounds,
-                                view_x,
-                                view_y,
-                                padding,
-                            );
-                            let (x1, y1) = screen_to_data(
-                                Point::new(current.x + bounds.x, current.y + bounds.y),
-                                bounds,
-                                view_x,
-                                view_y,
-                                padding,
-                            );
+    /// Track the legend entry under the cursor for
+    /// [`LegendConfig::hover_emphasis`](crate::plotter::LegendConfig::hover_emphasis).
+    /// Returns whether it changed.
+    #[cfg_attr(not(feature = "legend"), allow(unused_variables))]
+    fn update_legend_hover(&self, cursor: Cursor, bounds: Rectangle) -> bool {
+        #[cfg(feature = "legend")]
+        if self
+            .options
+            .legend
+            .as_ref()
+            .is_some_and(|config| config.hover_emphasis.is_some())
+        {
+            let hovered = cursor.position_in(bounds).and_then(|pos| {
+                let layout = self.legend_state.layout.borrow();
+                let toggle = layout.toggles.iter().find(|t| t.entry.contains(pos))?;
+                Some(toggle.series_index)
+            });
+            return self.legend_state.hovered.replace(hovered) != hovered;
+        }
+        false
+    }
 
-                            // Only zoom if the rectangle is big enough (>5px in both directions)
-                            let dx = (current.x - start.x).abs();
-                            let dy = (current.y - start.y).abs();
+    /// Whether the legend can be dragged (always `false` without the `legend` feature).
+    fn legend_draggable(&self) -> bool {
+        #[cfg(feature = "legend")]
+        return self
+            .options
+            .legend
+            .as_ref()
+            .is_some_and(|config| config.draggable && !config.position.is_outside());
+        #[cfg(not(feature = "legend"))]
+        false
+    }
 
-                            if dx > 5.0 || dy > 5.0 {
-                                let mut new_view = self.view_state.clone();
+    /// Whether hover hit testing is needed, for tooltips or point callbacks
+    /// (always `false` without the `tooltip` feature).
+    fn hover_enabled(&self) -> bool {
+        #[cfg(feature = "tooltip")]
+        return self.options.tooltip.is_some()
+            || self.on_point_hover.is_some()
+            || self.on_point_click.is_some();
+        #[cfg(not(feature = "tooltip"))]
+        false
+    }
 
-                                if interaction.zoom_x && dx > 5.0 {
-                                    let lo = x0.min(x1);
-                                    let hi = x0.max(x1);
-                                    new_view.x_range = Some((lo, hi));
-                                }
+    /// Clear the hovered point, if any.
+    fn clear_hover(&self) {
+        #[cfg(feature = "tooltip")]
+        {
+            *self.tooltip_state.hovered.borrow_mut() = None;
+        }
+    }
 
-                                if interaction.zoom_y && dy > 5.0 {
-                                    let lo = y0.min(y1);
-                                    let hi = y0.max(y1);
-                                    new_view.y_range = Some((lo, hi));
-                                }
+    /// Nearest hoverable point (or bar) within snapping distance of a
+    /// widget-local cursor position.
+    #[cfg(feature = "tooltip")]
+    #[allow(clippy::too_many_arguments)]
+    fn hit_test(
+        &self,
+        index: &mut HoverIndex,
+        samples: &RefCell<SampleCache>,
+        cursor_pos: Point,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> Option<HoveredPoint> {
+        index.refresh(&self.series);
+        // Data X of the cursor and data units per pixel along X
+        let plot_width = bounds.width - padding.x();
+        let x_per_px = (view_x[1] - view_x[0]) / f64::from(plot_width);
+        let cursor_x = view_x[0] + f64::from(cursor_pos.x - padding.left) * x_per_px;
+        let max_distance = self
+            .options
+            .tooltip
+            .as_ref()
+            .map_or(DEFAULT_HOVER_DISTANCE, |t| t.max_distance);
+        let mut best_dist_sq = f32::INFINITY;
+        let mut best: Option<HoveredPoint> = None;
+
+        let hidden = self.hidden_series();
+        let y_maps = self.y_axis_maps(view_y);
+        let bars = bar_rects(&self.series, &hidden, self.options.bar_layout);
+        let stacks = stack_ranges(&self.series, &hidden, self.options.stack_mode);
+
+        // Grids hold screen positions, so they last as long as the view,
+        // plot area and whatever moves points on screen
+        let plot_size = [bounds.width - padding.x(), bounds.height - padding.y()];
+        let mut hasher = DefaultHasher::new();
+        [view_x, view_y]
+            .map(|r| r.map(f64::to_bits))
+            .hash(&mut hasher);
+        plot_size.map(f32::to_bits).hash(&mut hasher);
+        for map in &y_maps {
+            map.map(|m| [m.from, m.to].map(|r| r.map(f64::to_bits)))
+                .hash(&mut hasher);
+        }
+        let mut hidden_sorted: Vec<usize> = hidden.iter().copied().collect();
+        hidden_sorted.sort_unstable();
+        hidden_sorted.hash(&mut hasher);
+        self.options.stack_mode.hash(&mut hasher);
+        index.set_view(hasher.finish(), self.series.len());
+        let cursor_in_plot = [cursor_pos.x - padding.left, cursor_pos.y - padding.top];
+
+        for (series_idx, series) in self.series.iter().enumerate() {
+            if hidden.contains(&series_idx) || !series.hoverable {
+                continue;
+            }
+            let y_map = y_maps[series_idx];
+            let max_dist = series.hover_distance.unwrap_or(max_distance);
+            let max_dist_sq = max_dist * max_dist;
+
+            // Bars snap when the cursor is over them or near their tip;
+            // the last bar drawn wins where bars overlap
+            if matches!(series.kind, SeriesKind::Bars { .. }) {
+                let points = series.points.as_slice().unwrap_or_default();
+                for (i, (bar, p)) in bars[series_idx].iter().zip(points).enumerate() {
+                    let to_screen = |x: f64, y: f64| {
+                        let y = y_map.map_or(y, |m| m.apply(y));
+                        data_to_screen(x, y, bounds, view_x, view_y, padding)
+                    };
+                    let a = to_screen(bar.x[0], bar.y[0]);
+                    let b = to_screen(bar.x[1], bar.y[1]);
+                    let inside = (a.x.min(b.x)..=a.x.max(b.x)).contains(&cursor_pos.x)
+                        && (a.y.min(b.y)..=a.y.max(b.y)).contains(&cursor_pos.y);
+                    let (tip_x, tip_y) = bar.tip();
+                    let tip = to_screen(tip_x, tip_y);
+                    let dist_sq = if inside {
+                        0.0
+                    } else {
+                        (tip.x - cursor_pos.x).powi(2) + (tip.y - cursor_pos.y).powi(2)
+                    };
+                    if dist_sq < max_dist_sq && (inside || dist_sq < best_dist_sq) {
+                        best_dist_sq = dist_sq;
+                        best = Some(HoveredPoint {
+                            series_index: series_idx,
+                            series_label: series.label.clone(),
+                            x: p.x,
+                            y: p.y,
+                            screen_pos: tip,
+                            index: Some(i),
+                            metadata: None,
+                        });
+                    }
+                }
+                continue;
+            }
 
-                                state.interaction_mode = InteractionMode::Idle;
-                                state.drag_start = None;
-                                state.zoom_select_current = None;
+            // (x, y, plotted y, index): stacked points are drawn at their
+            // stack's top but report their own value
+            type Candidate = (f64, f64, f64, Option<usize>);
+            let iter: Box<dyn Iterator<Item = Candidate> + '_> = match &series.points {
+                PlotPoints::Owned(_) | PlotPoints::Borrowed(_) => {
+                    let points = series.points.as_slice().unwrap_or_default();
+                    let stack = &stacks[series_idx];
+                    let plotted = move |i: usize| {
+                        let p = &points[i];
+                        (
+                            p.x,
+                            p.y,
+                            stack.get(i).map_or(p.y, |range| range[1]),
+                            Some(i),
+                        )
+                    };
+                    let candidates: Box<dyn Iterator<Item = usize> + '_> =
+                        if points.len() >= HOVER_GRID_POINTS {
+                            let grid = index.grid(series_idx, || {
+                                let position = |i: usize| {
+                                    let (x, _, y, _) = plotted(i);
+                                    let y = y_map.map_or(y, |m| m.apply(y));
+                                    core::data_to_screen([x, y], plot_size, view_x, view_y)
+                                };
+                                PointGrid::new(position, points.len(), plot_size, max_dist)
+                            });
+                            Box::new(grid.near(cursor_in_plot, max_dist))
+                        } else {
+                            // Only points within snapping distance along X can
+                            // match (with a little slack for rounding)
+                            let reach = f64::from(max_dist) * x_per_px.abs() * 1.01;
+                            let range = (cursor_x - reach, cursor_x + reach);
+                            index.window(series_idx, points, range)
+                        };
+                    Box::new(candidates.map(plotted))
+                }
+                PlotPoints::Generator(generator) => {
+                    let px_per_unit = (
+                        f64::from(bounds.width - padding.x()) / (view_x[1] - view_x[0]),
+                        f64::from(bounds.height - padding.y()) / (view_y[1] - view_y[0])
+                            * y_map.map_or(1.0, YAxisMap::scale),
+                    );
+                    let samples = samples.borrow_mut().sample(
+                        series_idx,
+                        series,
+                        generator,
+                        (view_x[0], view_x[1]),
+                        px_per_unit,
+                    );
+                    Box::new((0..samples.len()).map(move |i| {
+                        let (x, y) = samples[i];
+                        (x, y, y, None)
+                    }))
+                }
+                PlotPoints::Parametric(generator) => {
+                    Box::new(generator.sample().map(|(x, y)| (x, y, y, None)))
+                }
+            };
 
-                                if let Some(ref on_change) = self.on_view_change {
-                                    return Some(
-                                        shader::Action::publish((on_change)(new_view))
-                                            .and_capture(),
-                                    );
-                                }
-                            }
-                        }
+            for (dx, dy, plot_y, point_index) in iter {
+                let sy = y_map.map_or(plot_y, |m| m.apply(plot_y));
+                let screen = data_to_screen(dx, sy, bounds, view_x, view_y, padding);
+                let ddx = screen.x - cursor_pos.x;
+                let ddy = screen.y - cursor_pos.y;
+                let dist_sq = ddx * ddx + ddy * ddy;
+                if dist_sq < max_dist_sq && dist_sq < best_dist_sq {
+                    best_dist_sq = dist_sq;
+                    best = Some(HoveredPoint {
+                        series_index: series_idx,
+                        series_label: series.label.clone(),
+                        x: dx,
+                        y: dy,
+                        screen_pos: screen,
+                        index: point_index,
+                        metadata: None,
+                    });
+                }
+            }
+        }
+        // Only the winner's text is looked up
+        if let Some(ref mut point) = best
+            && let Some(index) = point.index
+        {
+            point.metadata = self.series[point.series_index]
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(index).cloned());
+        }
+        best
+    }
 
-                        state.interaction_mode = InteractionMode::Idle;
-                        state.drag_start = None;
-                        state.zoom_select_current = None;
-                        Some(shader::Action::capture())
+    /// Update the hovered point for the tooltip after the cursor moved.
+    ///
+    /// Returns an action when the hover state changed and a redraw is needed,
+    /// or the [`Plotter::on_point_hover`] message when the cursor reached a
+    /// different point.
+    #[cfg(feature = "tooltip")]
+    #[allow(clippy::too_many_arguments)]
+    fn update_hover(
+        &self,
+        index: &mut HoverIndex,
+        samples: &RefCell<SampleCache>,
+        cursor: Cursor,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> Option<shader::Action<Message>> {
+        let cursor_pos = cursor.position_in(bounds);
+        if let Some(cursor_pos) = cursor_pos {
+            // Skip tooltip when cursor is over the legend
+            if self.legend_enabled() {
+                let layout = self.legend_state.layout.borrow();
+                if let Some(legend_bounds) = layout.bounds
+                    && legend_bounds.contains(cursor_pos)
+                {
+                    if self.tooltip_state.hovered.borrow().is_some() {
+                        *self.tooltip_state.hovered.borrow_mut() = None;
+                        return Some(shader::Action::request_redraw());
                     }
-                    InteractionMode::Idle => None,
+                    return None;
                 }
             }
 
-            // ---- Cursor left widget ----
-            Event::Mouse(mouse::Event::CursorLeft) => {
-                // Clear tooltip when cursor leaves the widget
-                if self.options.tooltip.is_some() {
+            // Check cursor is within the plot area (inside padding)
+            let in_plot = !self.in_exclusion_zone(cursor_pos)
+                && cursor_pos.x >= padding.left
+                && cursor_pos.x <= bounds.width - padding.right
+                && cursor_pos.y >= padding.top
+                && cursor_pos.y <= bounds.height - padding.bottom;
+
+            if in_plot {
+                let best =
+                    self.hit_test(index, samples, cursor_pos, bounds, view_x, view_y, padding);
+                let prev = self.tooltip_state.hovered.replace(best.clone());
+
+                if let (Some(on_hover), Some(point)) = (&self.on_point_hover, best.as_ref())
+                    && prev.as_ref().is_none_or(|p| !p.is_same_point(point))
+                {
+                    return Some(shader::Action::publish((on_hover)(point.clone())));
+                }
+
+                // Request redraw if tooltip state changed
+                if prev.is_some() || best.is_some() {
+                    return Some(shader::Action::request_redraw());
+                }
+            } else {
+                // Cursor outside plot area, clear tooltip
+                if self.tooltip_state.hovered.borrow().is_some() {
                     *self.tooltip_state.hovered.borrow_mut() = None;
                     return Some(shader::Action::request_redraw());
                 }
-                None
             }
+        } else {
+            // Cursor not in widget bounds
+            if self.tooltip_state.hovered.borrow().is_some() {
+                *self.tooltip_state.hovered.borrow_mut() = None;
+                return Some(shader::Action::request_redraw());
+            }
+        }
+        None
+    }
 
-            // ---- Mouse move (drag) ----
-            Event::Mouse(mouse::Event::CursorMoved { position }) => {
-                state.last_cursor = Some(*position);
-
-                match state.interaction_mode {
-                    InteractionMode::Panning => {
-                        if let (Some(start), Some(start_view)) =
-                            (state.drag_start, &state.drag_start_view)
-                        {
-                            let start_view_x = start_view.x_range.unwrap();
-                            let start_view_y = start_view.y_range.unwrap();
+    /// Maximum number of pinned tooltips, `0` when pinning is off.
+    #[cfg(feature = "tooltip")]
+    fn max_pinned(&self) -> usize {
+        self.options.tooltip.as_ref().map_or(0, |t| t.max_pinned)
+    }
 
-                            let plot_width = bounds.width - 2.0 * padding;
-                            let plot_height = bounds.height - 2.0 * padding;
+    /// The point under a left click (a press and release without dragging),
+    /// if anything handles clicks on points.
+    #[cfg(feature = "tooltip")]
+    #[allow(clippy::too_many_arguments)]
+    fn point_click(
+        &self,
+        index: &mut HoverIndex,
+        samples: &RefCell<SampleCache>,
+        press: Option<Point>,
+        cursor: Cursor,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> Option<HoveredPoint> {
+        if self.on_point_click.is_none() && self.max_pinned() == 0 {
+            return None;
+        }
+        let pos = cursor.position_in(bounds)?;
+        let press = press?;
+        if (pos.x - press.x).abs() > CLICK_SLOP || (pos.y - press.y).abs() > CLICK_SLOP {
+            return None;
+        }
+        self.hit_test(index, samples, pos, bounds, view_x, view_y, padding)
+    }
 
-                            // position is absolute screen coords; drag_start is relative to bounds
+    /// Pin the tooltip of a clicked point. Returns whether it was pinned.
+    #[cfg(feature = "tooltip")]
+    fn pin_tooltip(
+        &self,
+        point: &HoveredPoint,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> bool {
+        let anchor = screen_to_data(
+            Point::new(point.screen_pos.x + bounds.x, point.screen_pos.y + bounds.y),
+            bounds,
+            view_x,
+            view_y,
+            padding,
+        );
+        let tooltip = PinnedTooltip {
+            point: point.clone(),
+            anchor,
+        };
+        self.tooltip_state.pin(tooltip, self.max_pinned())
+    }
+
+    /// Close the pinned tooltip whose close button is at `pos`. Returns
+    /// whether one was closed.
+    #[cfg(feature = "tooltip")]
+    fn close_pinned_at(&self, pos: Point) -> bool {
+        let hit = self
+            .tooltip_state
+            .close_buttons
+            .borrow()
+            .iter()
+            .find(|(_, rect)| rect.contains(pos))
+            .map(|&(index, _)| index);
+        let Some(index) = hit else {
+            return false;
+        };
+        let mut pinned = self.tooltip_state.pinned.borrow_mut();
+        if index >= pinned.len() {
+            return false;
+        }
+        pinned.remove(index);
+        // Indices past the closed one are stale until the next draw
+        self.tooltip_state.close_buttons.borrow_mut().clear();
+        true
+    }
+
+    /// Store the data coordinates under the cursor for the crosshair and
+    /// the shared cursor, or clear them when the cursor is outside the plot
+    /// area.
+    fn update_crosshair(
+        &self,
+        state: &mut PlotterState,
+        position: Point,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) {
+        let local = Point::new(position.x - bounds.x, position.y - bounds.y);
+        let in_plot = !self.in_exclusion_zone(local)
+            && local.x >= padding.left
+            && local.x <= bounds.width - padding.right
+            && local.y >= padding.top
+            && local.y <= bounds.height - padding.bottom;
+        let point = in_plot.then(|| {
+            let (x, y) = screen_to_data(position, bounds, view_x, view_y, padding);
+            PlotPoint { x, y }
+        });
+        self.set_shared_cursor(state, point.as_ref().map(|p| p.x));
+        if self.options.crosshair.is_some() {
+            *self.cursor_state.position.borrow_mut() = point;
+        }
+    }
+
+    /// Publish `x` to the shared cursor, or clear it if this plot set it.
+    fn set_shared_cursor(&self, state: &mut PlotterState, x: Option<f64>) {
+        let Some(ref shared) = self.shared_cursor else {
+            return;
+        };
+        let mut current = shared.x.borrow_mut();
+        if x.is_some() || (state.shared_cursor_x.is_some() && *current == state.shared_cursor_x) {
+            *current = x;
+        }
+        state.shared_cursor_x = x;
+    }
+
+    /// Whether a widget-local position lies inside a registered exclusion zone.
+    fn in_exclusion_zone(&self, pos: Point) -> bool {
+        self.exclusion_zones.iter().any(|zone| zone.contains(pos))
+    }
+
+    /// The region of interest at `pos` (relative to the widget) and the part
+    /// of it there, topmost first. Edges count from a few pixels away.
+    fn roi_at(
+        &self,
+        pos: Point,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> Option<(usize, RoiGrab)> {
+        let rois = self.roi_state.as_ref()?.rois.borrow();
+        let screen = |x, y| data_to_screen(x, y, bounds, view_x, view_y, padding);
+        rois.iter().enumerate().rev().find_map(|(i, roi)| {
+            let (left, right) = (screen(roi.x_range.0, 0.0).x, screen(roi.x_range.1, 0.0).x);
+            let (top, bottom) = match roi.y_range {
+                Some((lo, hi)) => (screen(0.0, hi).y, screen(0.0, lo).y),
+                None => (padding.top, bounds.height - padding.bottom),
+            };
+            let d = GRAB_DISTANCE;
+            if pos.x < left - d || pos.x > right + d || pos.y < top - d || pos.y > bottom + d {
+                return None;
+            }
+            // The nearer edge of each pair, for ROIs only a few pixels wide
+            let near = |v: f32, lo: f32, hi: f32| {
+                let (to_lo, to_hi) = ((v - lo).abs(), (v - hi).abs());
+                [to_lo <= d && to_lo <= to_hi, to_hi <= d && to_hi < to_lo]
+            };
+            let [at_left, at_right] = near(pos.x, left, right);
+            let [at_top, at_bottom] = match roi.y_range {
+                Some(_) => near(pos.y, top, bottom),
+                None => [false, false],
+            };
+            let edges = [at_left, at_right, at_bottom, at_top];
+            if edges.contains(&true) {
+                Some((i, RoiGrab::Edges(edges)))
+            } else if pos.x >= left && pos.x <= right && pos.y >= top && pos.y <= bottom {
+                Some((i, RoiGrab::Body))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Index of the cursor line nearest to `pos` (relative to the widget)
+    /// within grabbing distance, over the plot area.
+    fn cursor_line_at(
+        &self,
+        pos: Point,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> Option<usize> {
+        if Self::axis_region(pos, bounds.size(), padding).is_some() {
+            return None;
+        }
+        let distance = |line: &CursorLine| {
+            let screen = data_to_screen(line.value, line.value, bounds, view_x, view_y, padding);
+            match line.axis {
+                CursorAxis::X => (screen.x - pos.x).abs(),
+                CursorAxis::Y => (screen.y - pos.y).abs(),
+            }
+        };
+        self.cursor_lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (i, distance(line)))
+            .filter(|&(_, d)| d <= GRAB_DISTANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// The series and index of the [`PlotSeries::editable`] point nearest to
+    /// `pos` (relative to the widget), within its hover distance.
+    fn editable_point_at(
+        &self,
+        pos: Point,
+        bounds: Rectangle,
+        view_x: [f64; 2],
+        view_y: [f64; 2],
+        padding: iced::Padding,
+    ) -> Option<(usize, usize)> {
+        let hidden = self.hidden_series();
+        let y_maps = self.y_axis_maps(view_y);
+        let mut best = None;
+        let mut best_dist_sq = f32::INFINITY;
+        for (series_idx, series) in self.series.iter().enumerate() {
+            if !series.editable || hidden.contains(&series_idx) {
+                continue;
+            }
+            let max_dist = series.hover_distance.unwrap_or(DEFAULT_HOVER_DISTANCE);
+            let y_map = y_maps[series_idx];
+            let points = series.points.as_slice().unwrap_or_default();
+            for (i, p) in points.iter().enumerate() {
+                let y = y_map.map_or(p.y, |m| m.apply(p.y));
+                let screen = data_to_screen(p.x, y, bounds, view_x, view_y, padding);
+                let dist_sq = (screen.x - pos.x).powi(2) + (screen.y - pos.y).powi(2);
+                if dist_sq <= max_dist * max_dist && dist_sq < best_dist_sq {
+                    best_dist_sq = dist_sq;
+                    best = Some((series_idx, i));
+                }
+            }
+        }
+        best
+    }
+
+    /// The axis strip `pos` (relative to the widget) is over, if any.
+    fn axis_region(pos: Point, size: iced::Size, padding: iced::Padding) -> Option<AxisRegion> {
+        let in_x = pos.x >= padding.left && pos.x <= size.width - padding.right;
+        let in_y = pos.y >= padding.top && pos.y <= size.height - padding.bottom;
+        if in_x && pos.y > size.height - padding.bottom {
+            Some(AxisRegion::X)
+        } else if in_y && (pos.x < padding.left || pos.x > size.width - padding.right) {
+            Some(AxisRegion::Y)
+        } else {
+            None
+        }
+    }
+
+    /// The primitive for `bounds`, reusing the cached one where possible.
+    fn primitive(&self, state: &PlotterState, bounds: Rectangle) -> PlotterPrimitive {
+        // Enforce bounds when idle — but not during drag or elastic animation
+        // so that elastic over-scroll remains visible.
+        let enforce = state.interaction_mode == InteractionMode::Idle
+            && state.elastic_animation.is_none()
+            && state.inertia_animation.is_none();
+        let (view_x, view_y, data_x, data_y) = self.resolve_view_ranges(enforce);
+        let padding = self.plot_padding(bounds.size());
+        let plot_size = iced::Size::new(bounds.width - padding.x(), bounds.height - padding.y());
+        let (view_x, view_y) = self.aspect_ranges(view_x, view_y, plot_size);
+
+        // Build selection rectangle from state if zoom-selecting or brushing
+        let selection_rect = if matches!(
+            state.interaction_mode,
+            InteractionMode::ZoomSelecting | InteractionMode::Brushing
+        ) {
+            if let (Some(start), Some(current)) = (state.drag_start, state.zoom_select_current) {
+                Some((start, current))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        // Build highlight ring from tooltip state
+        #[cfg(not(feature = "tooltip"))]
+        let highlight: Option<(Point, [f32; 4], f32, f32)> = None;
+        #[cfg(feature = "tooltip")]
+        let highlight = if let Some(ref tooltip_config) = self.options.tooltip {
+            let hovered = self.tooltip_state.hovered.borrow();
+            hovered.as_ref().map(|hp| {
+                let color = [
+                    tooltip_config.highlight_color.r,
+                    tooltip_config.highlight_color.g,
+                    tooltip_config.highlight_color.b,
+                    tooltip_config.highlight_color.a,
+                ];
+                (
+                    hp.screen_pos,
+                    color,
+                    tooltip_config.highlight_radius,
+                    tooltip_config.highlight_width,
+                )
+            })
+        } else {
+            None
+        };
+
+        let emphasis = self.legend_emphasis();
+
+        // Reuse the last primitive when nothing that affects rendering changed,
+        // or its markers, lines and grid when only the overlays did
+        let mut hasher = DefaultHasher::new();
+        self.content_key().hash(&mut hasher);
+        emphasis
+            .map(|(series, dim, width)| (series, dim.to_bits(), width.to_bits()))
+            .hash(&mut hasher);
+        for v in [bounds.x, bounds.y, bounds.width, bounds.height]
+            .into_iter()
+            .chain([padding.top, padding.right, padding.bottom, padding.left])
+        {
+            v.to_bits().hash(&mut hasher);
+        }
+        [view_x, view_y]
+            .map(|range| range.map(f64::to_bits))
+            .hash(&mut hasher);
+        self.view_state
+            .secondary_y_range
+            .map(|(lo, hi)| [lo, hi].map(f64::to_bits))
+            .hash(&mut hasher);
+        let data_key = hasher.finish();
+        selection_rect
+            .map(|(a, b)| [a.x, a.y, b.x, b.y].map(f32::to_bits))
+            .hash(&mut hasher);
+        highlight
+            .map(|(p, _, _, _)| [p.x, p.y].map(f32::to_bits))
+            .hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(cached) = state.cached_primitive.borrow_mut().as_mut() {
+            if cached.key == key {
+                return cached.clone();
+            }
+            if cached.data_key == data_key {
+                *cached = PlotterPrimitive {
+                    key,
+                    ..cached.with_overlays(selection_rect, highlight)
+                };
+                return cached.clone();
+            }
+        }
+
+        let hidden = self.hidden_series();
+        let brushed = self
+            .selection_link
+            .as_ref()
+            .map(|(link, mode)| (link.selected.borrow(), *mode));
+        let mut primitive = PlotterPrimitive::new(
+            &self.series,
+            &self.polygons,
+            &self.bands,
+            &self.box_plots,
+            &self.quivers,
+            &self.images,
+            self.spectrogram,
+            bounds,
+            &self.options,
+            view_x,
+            view_y,
+            padding,
+            selection_rect,
+            &hidden,
+            highlight,
+            brushed
+                .as_ref()
+                .map(|(selected, mode)| (&**selected, *mode)),
+            self.tick_offsets(data_x, data_y),
+            &self.y_axis_maps(view_y),
+            emphasis,
+            &mut state.generator_samples.borrow_mut(),
+        );
+        #[cfg(feature = "annotations")]
+        {
+            primitive = primitive.with_annotations(&self.annotations());
+        }
+        primitive.key = key;
+        primitive.data_key = data_key;
+        *state.cached_primitive.borrow_mut() = Some(primitive.clone());
+        primitive
+    }
+}
+
+// ================================================================================
+// shader::Primitive implementation
+// ================================================================================
+
+impl shader::Primitive for PlotterPrimitive {
+    type Pipeline = Pipeline;
+
+    fn prepare(
+        &self,
+        pipeline: &mut Self::Pipeline,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        bounds: &Rectangle,
+        viewport: &Viewport,
+    ) {
+        // The canvas draws this primitive instead
+        if self.config.software {
+            return;
+        }
+
+        // Skip uploads entirely when the buffers already hold this primitive,
+        // and keep markers and lines when only the overlays changed. The grid
+        // is snapped to the device's pixels, so it also follows the widget
+        // moving or the scale factor changing.
+        let pixels = PixelGrid {
+            scale: viewport.scale_factor(),
+            offset: [bounds.x, bounds.y],
+        };
+        if pipeline.uploaded_key != Some(self.key) || pipeline.uploaded_pixels != Some(pixels) {
+            let uniforms = Uniforms {
+                scale_factor: pixels.scale,
+                ..self.uniforms
+            };
+            if pipeline.uploaded_data_key != Some(self.data_key) {
+                pipeline.update(device, queue, &uniforms, &self.points, &self.line_vertices);
+                pipeline.update_compact_points(device, queue, &self.compact_points);
+                let mut image_vertices = self.image_vertices();
+                if self.config.multisample {
+                    image_vertices.extend(self.composite_vertices());
+                }
+                pipeline.update_image_vertices(device, queue, &image_vertices);
+                pipeline.uploaded_data_key = Some(self.data_key);
+            } else if pipeline.uploaded_pixels.map(|p| p.scale) != Some(pixels.scale) {
+                pipeline.update_uniforms(queue, &uniforms);
+            }
+
+            // Combine grid + selection + highlight vertices for the grid render pass
+            let mut combined = self.grid_vertices_at(pixels);
+            combined.extend_from_slice(&self.selection_vertices);
+            combined.extend_from_slice(&self.highlight_vertices);
+            pipeline.update_grid(device, queue, &combined);
+            pipeline.uploaded_key = Some(self.key);
+            pipeline.uploaded_pixels = Some(pixels);
+        }
+
+        // Write the image texels that changed since the last upload
+        for (slot, image) in self.images.iter().enumerate() {
+            let (size, smooth, key) = (image.size, image.smooth, image.key);
+            match &image.texels {
+                ImageTexels::Fixed(pixels) => {
+                    pipeline.update_image(device, queue, slot, size, smooth, key, pixels);
+                }
+                ImageTexels::Shared(texels) => {
+                    let texels = texels.lock().expect("texels lock");
+                    pipeline.update_image(device, queue, slot, size, smooth, key, &texels);
+                }
+            }
+        }
+        pipeline.truncate_images(self.images.len());
+
+        // Compute scissor rects in absolute physical pixel coordinates.
+        // iced sets the viewport to the widget's bounds before calling draw,
+        // but set_scissor_rect always operates in absolute framebuffer coords.
+        let scale = viewport.scale_factor();
+        let pad_x = self.uniforms.padding[0];
+        let pad_y = self.uniforms.padding[1];
+
+        // Widget bounds in physical pixels
+        let wx = (bounds.x * scale) as u32;
+        let wy = (bounds.y * scale) as u32;
+        let ww = (bounds.width * scale) as u32;
+        let wh = (bounds.height * scale) as u32;
+        pipeline.widget_scissor = [wx, wy, ww.max(1), wh.max(1)];
+        pipeline.multisampling =
+            self.config.multisample && pipeline.prepare_multisample(device, [ww, wh]);
+
+        // Plot area (inside padding) in physical pixels
+        let px = (bounds.x + pad_x) * scale;
+        let py = (bounds.y + pad_y) * scale;
+        let pw = self.uniforms.plot_size[0] * scale;
+        let ph = self.uniforms.plot_size[1] * scale;
+        pipeline.plot_scissor = [px as u32, py as u32, (pw as u32).max(1), (ph as u32).max(1)];
+
+        // Marker area: plot area grown by the bleed margin, clamped to the widget
+        let bleed = self.config.marker_bleed.max(0.0) * scale;
+        let mx = (px - bleed).max(wx as f32);
+        let my = (py - bleed).max(wy as f32);
+        let mw = (px + pw + bleed).min((wx + ww) as f32) - mx;
+        let mh = (py + ph + bleed).min((wy + wh) as f32) - my;
+        pipeline.marker_scissor = [
+            mx as u32,
+            my as u32,
+            (mw.max(0.0) as u32).max(1),
+            (mh.max(0.0) as u32).max(1),
+        ];
+    }
+
+    fn draw(&self, pipeline: &Self::Pipeline, render_pass: &mut wgpu::RenderPass<'_>) -> bool {
+        if self.config.software {
+            return true;
+        }
+        // Multisampled series need passes of their own, see `render`
+        if pipeline.multisampling {
+            return false;
+        }
+
+        self.draw_background(pipeline, render_pass);
+        self.draw_series(pipeline, render_pass, None);
+
+        // Restore scissor rect to full widget bounds so iced's subsequent rendering is correct.
+        let [wx, wy, ww, wh] = pipeline.widget_scissor;
+        render_pass.set_scissor_rect(wx, wy, ww, wh);
+
+        true
+    }
+
+    fn render(
+        &self,
+        pipeline: &Self::Pipeline,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        clip_bounds: &Rectangle<u32>,
+    ) {
+        if clip_bounds.width == 0 || clip_bounds.height == 0 {
+            return;
+        }
+        // Passes on the frame get the viewport and clip rect iced would set
+        let [wx, wy, ww, wh] = pipeline.widget_scissor;
+        let frame_pass = |encoder: &mut wgpu::CommandEncoder, label: &'static str| {
+            let mut pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some(label),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: target,
+                        depth_slice: None,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            pass.set_viewport(wx as f32, wy as f32, ww as f32, wh as f32, 0.0, 1.0);
+            pass.set_scissor_rect(
+                clip_bounds.x,
+                clip_bounds.y,
+                clip_bounds.width,
+                clip_bounds.height,
+            );
+            pass
+        };
+
+        // Grid and images straight onto the frame, then the series into the
+        // multisampled target, blended on top once resolved
+        self.draw_background(pipeline, &mut frame_pass(encoder, "plot_background_pass"));
+        if let Some(mut pass) = pipeline.begin_multisampled_pass(encoder) {
+            self.draw_series(pipeline, &mut pass, Some([wx, wy, ww, wh]));
+        }
+        pipeline.render_composite(
+            &mut frame_pass(encoder, "plot_composite_pass"),
+            self.images.len(),
+        );
+    }
+}
+
+impl PlotterPrimitive {
+    /// Draw the grid pass and the image layers.
+    fn draw_background(&self, pipeline: &Pipeline, render_pass: &mut wgpu::RenderPass<'_>) {
+        let total_grid = self.grid.vertex_count()
+            + self.selection_vertices.len()
+            + self.highlight_vertices.len();
+        if total_grid > 0 {
+            pipeline.render_grid(render_pass, total_grid as u32);
+        }
+
+        let [sx, sy, sw, sh] = pipeline.plot_scissor;
+        render_pass.set_scissor_rect(sx, sy, sw, sh);
+
+        for slot in 0..self.images.len() {
+            pipeline.render_image(render_pass, slot);
+        }
+    }
+
+    /// Draw lines and markers. `target` is the widget's rect in physical
+    /// pixels when drawing into the multisampled target, which covers just
+    /// the widget, and `None` when drawing onto the frame.
+    fn draw_series(
+        &self,
+        pipeline: &Pipeline,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        target: Option<[u32; 4]>,
+    ) {
+        // Set scissor rect to clip lines to the plot area (inside padding); markers
+        // get a slightly larger rect so points on the boundary render fully.
+        // These are absolute physical-pixel coordinates computed during prepare().
+        let set_scissor = |render_pass: &mut wgpu::RenderPass<'_>, [x, y, w, h]: [u32; 4]| {
+            let Some([wx, wy, ww, wh]) = target else {
+                render_pass.set_scissor_rect(x, y, w, h);
+                return;
+            };
+            let (x, y) = (x.saturating_sub(wx).min(ww), y.saturating_sub(wy).min(wh));
+            render_pass.set_scissor_rect(x, y, w.min(ww - x), h.min(wh - y));
+        };
+        let multisampled = target.is_some();
+
+        for layer in self.layers.iter() {
+            // Line pass also carries polygon fills, so draw it whenever it has vertices
+            if !layer.lines.is_empty() {
+                set_scissor(render_pass, pipeline.plot_scissor);
+                pipeline.render_lines(render_pass, layer.lines.clone(), multisampled);
+            }
+
+            if self.config.show_markers && !layer.markers.is_empty() {
+                set_scissor(render_pass, pipeline.marker_scissor);
+                let additive = self.config.additive_markers;
+                if self.compact_points.is_empty() {
+                    pipeline.render_markers(
+                        render_pass,
+                        layer.markers.clone(),
+                        additive,
+                        multisampled,
+                    );
+                } else {
+                    pipeline.render_compact_markers(
+                        render_pass,
+                        layer.markers.clone(),
+                        additive,
+                        multisampled,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl shader::Pipeline for Pipeline {
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
+        Pipeline::new(device, queue, format)
+    }
+}
+
+// ================================================================================
+// shader::Program implementation (event handling + drawing)
+// ================================================================================
+
+impl<Message: Clone> shader::Program<Message> for Plotter<'_, Message> {
+    type State = PlotterState;
+    type Primitive = PlotterPrimitive;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Option<shader::Action<Message>> {
+        let interaction = &self.interaction;
+
+        // Check if any interaction is enabled at all
+        let has_any_interaction = interaction.pan_x
+            || interaction.pan_y
+            || interaction.zoom_x
+            || interaction.zoom_y
+            || interaction.double_click_to_fit
+            || interaction.zoom_select
+            || self.on_select.is_some()
+            || self.roi_state.is_some()
+            || self.on_point_moved.is_some()
+            || self.on_cursor_line_move.is_some()
+            || self.measure_state.is_some();
+        let has_tooltip = self.hover_enabled();
+        let has_readout = self.options.cursor_readout.is_some();
+        let has_crosshair = self.options.crosshair.is_some() || self.shared_cursor.is_some();
+
+        if !has_any_interaction
+            && !has_tooltip
+            && !has_readout
+            && !has_crosshair
+            && !self.legend_enabled()
+            && self.options.context_menu.is_none()
+        {
+            return None;
+        }
+
+        let (view_x, view_y, data_x, data_y) = self.resolve_view_ranges(false);
+        let padding = self.plot_padding(bounds.size());
+        let plot_size = iced::Size::new(bounds.width - padding.x(), bounds.height - padding.y());
+        let (view_x, view_y) = self.aspect_ranges(view_x, view_y, plot_size);
+
+        // When elastic is enabled but no explicit bounds are set, use the data
+        // extent as automatic bounds. Without bounds the elastic/clamping logic
+        // has nothing to enforce, which silently disables the feature — a
+        // confusing API pitfall.
+        let effective_x_bounds = interaction.x_bounds.or_else(|| {
+            if interaction.elastic && interaction.pan_x {
+                Some((data_x[0], data_x[1]))
+            } else {
+                None
+            }
+        });
+        let effective_y_bounds = interaction.y_bounds.or_else(|| {
+            if interaction.elastic && interaction.pan_y {
+                Some((data_y[0], data_y[1]))
+            } else {
+                None
+            }
+        });
+
+        // ---------- Kinetic panning ----------
+        // Grabbing or zooming catches the view; any other event moves it on.
+        if matches!(
+            event,
+            Event::Mouse(mouse::Event::ButtonPressed(_) | mouse::Event::WheelScrolled { .. })
+                | Event::Keyboard(keyboard::Event::KeyPressed { .. })
+        ) {
+            state.inertia_animation = None;
+        }
+        if let Some(inertia) = state.inertia_animation.as_mut() {
+            let now = self.now();
+            let dt = now.duration_since(inertia.last_tick).as_secs_f64();
+            inertia.last_tick = now;
+
+            let axes = [
+                (interaction.pan_x, &mut inertia.x_range, effective_x_bounds),
+                (interaction.pan_y, &mut inertia.y_range, effective_y_bounds),
+            ];
+            let mut shown = [None, None];
+            let mut moving = false;
+            let mut out = [false, false];
+            for (i, (enabled, range, axis_bounds)) in axes.into_iter().enumerate() {
+                if !enabled {
+                    continue;
+                }
+                let over = is_out_of_bounds(*range, axis_bounds, interaction.boundary_padding);
+                let friction = interaction.inertia_friction
+                    * if over {
+                        INERTIA_OVERSCROLL_FRICTION
+                    } else {
+                        1.0
+                    };
+                let velocity = &mut inertia.velocity[i];
+                *velocity *= (-f64::from(friction) * dt).exp();
+                *range = (range.0 + *velocity * dt, range.1 + *velocity * dt);
+                let view = if interaction.elastic {
+                    apply_elastic_resistance(
+                        *range,
+                        axis_bounds,
+                        interaction.boundary_padding,
+                        interaction.elastic_limit,
+                    )
+                } else {
+                    let clamped =
+                        clamp_range_to_bounds(*range, axis_bounds, interaction.boundary_padding);
+                    if clamped != *range {
+                        // Hard stop at the bounds
+                        *range = clamped;
+                        *velocity = 0.0;
+                    }
+                    clamped
+                };
+                moving |= velocity.abs() > INERTIA_STOP_SPEED * (range.1 - range.0);
+                out[i] = is_out_of_bounds(view, axis_bounds, interaction.boundary_padding);
+                shown[i] = Some(view);
+            }
+
+            let mut new_view = self.view_state.clone();
+            if let Some(x) = shown[0] {
+                new_view.x_range = Some(x);
+            }
+            if let Some(y) = shown[1] {
+                new_view.y_range = Some(y);
+            }
+            if !moving {
+                state.inertia_animation = None;
+                // Settle past the bounds by springing back, as after a drag
+                if interaction.elastic && (out[0] || out[1]) {
+                    let target = |range: Option<(f64, f64)>, axis_bounds| {
+                        range.map(|r| {
+                            clamp_range_to_bounds(r, axis_bounds, interaction.boundary_padding)
+                        })
+                    };
+                    let (from_x, from_y) =
+                        (shown[0].filter(|_| out[0]), shown[1].filter(|_| out[1]));
+                    state.elastic_animation = Some(ElasticState {
+                        from_x,
+                        from_y,
+                        to_x: target(from_x, effective_x_bounds),
+                        to_y: target(from_y, effective_y_bounds),
+                        start_time: now,
+                        duration_ms: interaction.elastic_duration_ms,
+                    });
+                }
+            }
+            let new_view = self.follow_secondary_y(new_view);
+            // The fling ends here unless it springs back
+            if !moving
+                && state.elastic_animation.is_none()
+                && let Some(ref on_change) = self.on_view_change
+            {
+                state.gesture_view = None;
+                return Some(shader::Action::publish((on_change)(
+                    new_view,
+                    ViewChangeKind::Final,
+                )));
+            }
+            return self
+                .publish_interactive_view(state, new_view)
+                .or(Some(shader::Action::request_redraw()));
+        }
+
+        // ---------- Elastic spring-back animation ----------
+        // Tick the animation on every event while it's active.
+        // Each tick publishes the interpolated view and requests the next redraw.
+        if let Some(ref anim) = state.elastic_animation.clone() {
+            let elapsed = self.now().duration_since(anim.start_time).as_millis() as u64;
+
+            if elapsed >= anim.duration_ms {
+                // Animation complete: snap to target
+                let mut new_view = self.view_state.clone();
+                if let (Some(_from), Some(to)) = (anim.from_x, anim.to_x) {
+                    new_view.x_range = Some(to);
+                }
+                if let (Some(_from), Some(to)) = (anim.from_y, anim.to_y) {
+                    new_view.y_range = Some(to);
+                }
+                state.elastic_animation = None;
+
+                if let Some(ref on_change) = self.on_view_change {
+                    state.gesture_view = None;
+                    return Some(shader::Action::publish((on_change)(
+                        self.follow_secondary_y(new_view),
+                        ViewChangeKind::Final,
+                    )));
+                }
+                return None;
+            }
+
+            // Still animating: interpolate and request next frame
+            let t = elapsed as f32 / anim.duration_ms as f32;
+            let mut new_view = self.view_state.clone();
+            if let (Some(from), Some(to)) = (anim.from_x, anim.to_x) {
+                new_view.x_range = Some(lerp_range(from, to, t));
+            }
+            if let (Some(from), Some(to)) = (anim.from_y, anim.to_y) {
+                new_view.y_range = Some(lerp_range(from, to, t));
+            }
+
+            // Publish triggers a redraw, which triggers another update cycle
+            return self
+                .publish_interactive_view(state, self.follow_secondary_y(new_view))
+                .or(Some(shader::Action::request_redraw()));
+        }
+
+        // ---- Context menu ----
+        // While open, the menu takes the mouse; any click or Escape closes it
+        let open_menu = self.context_menu_state.open.borrow().clone();
+        if let Some(menu) = open_menu {
+            let close = || *self.context_menu_state.open.borrow_mut() = None;
+            match event {
+                Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    state.last_cursor = Some(*position);
+                    return Some(shader::Action::request_redraw().and_capture());
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                    close();
+                    let picked = self.options.context_menu.as_ref().and_then(|config| {
+                        menu.entry_at(config, bounds.size(), cursor.position_in(bounds)?)
+                    });
+                    let message = picked.and_then(|i| {
+                        self.context_action(state, menu.entries[i].action, menu.data)
+                    });
+                    return Some(match message {
+                        Some(message) => shader::Action::publish(message).and_capture(),
+                        None => shader::Action::request_redraw().and_capture(),
+                    });
+                }
+                // Reopened where clicked, below
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => close(),
+                Event::Mouse(
+                    mouse::Event::ButtonPressed(_) | mouse::Event::WheelScrolled { .. },
+                )
+                | Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                    ..
+                }) => {
+                    close();
+                    return Some(shader::Action::request_redraw().and_capture());
+                }
+                _ => {}
+            }
+        }
+
+        // ---- Legend dragging ----
+        #[cfg(feature = "legend")]
+        if let InteractionMode::LegendDragging { grab } = state.interaction_mode {
+            match event {
+                Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    state.last_cursor = Some(*position);
+                    let Some(legend_bounds) = self.legend_state.layout.borrow().bounds else {
+                        return Some(shader::Action::capture());
+                    };
+                    let plot_origin =
+                        iced::Vector::new(bounds.x + padding.left, bounds.y + padding.top);
+                    let plot_size =
+                        iced::Size::new(bounds.width - padding.x(), bounds.height - padding.y());
+                    *self.legend_state.offset.borrow_mut() = Some(clamp_legend_offset(
+                        *position - plot_origin - grab,
+                        legend_bounds.size(),
+                        plot_size,
+                    ));
+                    return Some(shader::Action::request_redraw().and_capture());
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    state.interaction_mode = InteractionMode::Idle;
+                    state.press_position = None;
+                    return Some(shader::Action::capture());
+                }
+                _ => {}
+            }
+        }
+
+        match event {
+            // ---- Track keyboard modifiers ----
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = *modifiers;
+                None
+            }
+
+            // ---- Clear the measurement ----
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                ..
+            }) if self.active_tool == ActiveTool::Measure && self.measure_state.is_some() => {
+                cursor.position_in(bounds)?;
+                let measure = self.measure_state.as_ref()?;
+                if measure.pending.borrow().is_none() && measure.measurement.borrow().is_none() {
+                    return None;
+                }
+                measure.clear();
+                Some(shader::Action::request_redraw().and_capture())
+            }
+
+            // ---- Delete the selected region of interest ----
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Delete),
+                ..
+            }) if matches!(self.active_tool, ActiveTool::Roi(_)) && self.roi_state.is_some() => {
+                cursor.position_in(bounds)?;
+                let rois = self.roi_state.as_ref()?;
+                let index = (*rois.selected.borrow())?;
+                let roi = rois.remove(index)?;
+                Some(match self.on_roi_change {
+                    Some(ref on_change) => {
+                        shader::Action::publish(on_change(RoiChange::Removed(index, roi)))
+                            .and_capture()
+                    }
+                    None => shader::Action::request_redraw().and_capture(),
+                })
+            }
+
+            // ---- Keyboard navigation ----
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                if interaction.keyboard =>
+            {
+                // Only the plot under the cursor, and leave shortcuts alone
+                cursor.position_in(bounds)?;
+                if modifiers.command() || modifiers.alt() {
+                    return None;
+                }
+                if let keyboard::Key::Named(keyboard::key::Named::Backspace) = key.as_ref() {
+                    return self.step_history(state, modifiers.shift());
+                }
+                let new_view =
+                    self.key_view(key, view_x, view_y, effective_x_bounds, effective_y_bounds)?;
+                state.elastic_animation = None;
+                let on_change = self.on_view_change.as_ref()?;
+                if let keyboard::Key::Named(keyboard::key::Named::Home) = key.as_ref() {
+                    self.record_view();
+                }
+                Some(
+                    shader::Action::publish(on_change(new_view, ViewChangeKind::Final))
+                        .and_capture(),
+                )
+            }
+
+            // ---- Open the context menu ----
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                let config = self.options.context_menu.as_ref()?;
+                let pos = cursor.position_in(bounds)?;
+                let in_plot = pos.x >= padding.left
+                    && pos.x <= bounds.width - padding.right
+                    && pos.y >= padding.top
+                    && pos.y <= bounds.height - padding.bottom;
+                if !in_plot
+                    || self.in_exclusion_zone(pos)
+                    || state.interaction_mode != InteractionMode::Idle
+                {
+                    return None;
+                }
+                let entries: Vec<ContextMenuEntry> = config
+                    .entries
+                    .iter()
+                    .filter(|entry| match entry.action {
+                        ContextAction::ResetView => self.on_view_change.is_some(),
+                        _ => self.on_context_action.is_some(),
+                    })
+                    .cloned()
+                    .collect();
+                if entries.is_empty() {
+                    return None;
+                }
+                let data = screen_to_data(
+                    Point::new(pos.x + bounds.x, pos.y + bounds.y),
+                    bounds,
+                    view_x,
+                    view_y,
+                    padding,
+                );
+                self.clear_hover();
+                *self.context_menu_state.open.borrow_mut() = Some(OpenContextMenu {
+                    anchor: pos,
+                    data,
+                    entries,
+                });
+                Some(shader::Action::request_redraw().and_capture())
+            }
+
+            // ---- View history with the mouse's back/forward buttons ----
+            Event::Mouse(mouse::Event::ButtonPressed(
+                button @ (mouse::Button::Back | mouse::Button::Forward),
+            )) => {
+                cursor.position_in(bounds)?;
+                self.step_history(state, *button == mouse::Button::Forward)
+            }
+
+            // ---- Mouse button press ----
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(pos) = cursor.position_in(bounds) {
+                    // Leave clicks inside exclusion zones to whatever is drawn there
+                    if self.in_exclusion_zone(pos) {
+                        return None;
+                    }
+
+                    // Close a pinned tooltip
+                    #[cfg(feature = "tooltip")]
+                    if self.close_pinned_at(pos) {
+                        return Some(shader::Action::request_redraw().and_capture());
+                    }
+
+                    // Check legend interactions first — block all clicks within legend bounds
+                    if self.legend_enabled() {
+                        let layout = self.legend_state.layout.borrow();
+                        // Check toggle button clicks
+                        for toggle in layout.toggles.iter() {
+                            if toggle.rect.contains(pos) {
+                                let index = toggle.series_index;
+                                let mut hidden = self.legend_state.hidden_series.borrow_mut();
+                                let visible = hidden.remove(&index);
+                                if !visible {
+                                    hidden.insert(index);
+                                }
+                                #[cfg(feature = "legend")]
+                                if let Some(ref on_toggle) = self.on_legend_toggle {
+                                    return Some(
+                                        shader::Action::publish(on_toggle(index, visible))
+                                            .and_capture(),
+                                    );
+                                }
+                                return Some(shader::Action::request_redraw().and_capture());
+                            }
+                        }
+                        // Block clicks anywhere on the legend background, and
+                        // start dragging it if allowed
+                        if let Some(legend_bounds) = layout.bounds
+                            && legend_bounds.contains(pos)
+                        {
+                            if self.legend_draggable() {
+                                state.interaction_mode = InteractionMode::LegendDragging {
+                                    grab: pos - legend_bounds.position(),
+                                };
+                            }
+                            return Some(shader::Action::capture());
+                        }
+                    }
+                    state.press_position = Some(pos);
+
+                    // Grab a cursor line, whichever the tool
+                    if self.on_cursor_line_move.is_some()
+                        && let Some(index) =
+                            self.cursor_line_at(pos, bounds, view_x, view_y, padding)
+                    {
+                        let (x, y) = screen_to_data(
+                            Point::new(pos.x + bounds.x, pos.y + bounds.y),
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        );
+                        let line = &self.cursor_lines[index];
+                        let at = match line.axis {
+                            CursorAxis::X => x,
+                            CursorAxis::Y => y,
+                        };
+                        self.clear_hover();
+                        state.interaction_mode = InteractionMode::CursorLineDragging {
+                            index,
+                            offset: line.value - at,
+                        };
+                        return Some(shader::Action::capture());
+                    }
+
+                    // Start or finish a measurement
+                    if self.active_tool == ActiveTool::Measure
+                        && let Some(measure) = &self.measure_state
+                        && Self::axis_region(pos, bounds.size(), padding).is_none()
+                    {
+                        #[cfg_attr(not(feature = "tooltip"), allow(unused_mut))]
+                        let mut point = screen_to_data(
+                            Point::new(pos.x + bounds.x, pos.y + bounds.y),
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        );
+                        // Snap to a data point on the primary Y axis
+                        #[cfg(feature = "tooltip")]
+                        if let Some(hit) = self.hit_test(
+                            &mut state.hover_index,
+                            &state.generator_samples,
+                            pos,
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        ) && self.series[hit.series_index].y_axis == 0
+                        {
+                            point = (hit.x, hit.y);
+                        }
+                        let from = measure.pending.borrow_mut().take();
+                        let Some(from) = from else {
+                            *measure.pending.borrow_mut() = Some(point);
+                            *measure.measurement.borrow_mut() = None;
+                            return Some(shader::Action::request_redraw().and_capture());
+                        };
+                        let measurement = Measurement { from, to: point };
+                        *measure.measurement.borrow_mut() = Some(measurement);
+                        return Some(match self.on_measure {
+                            Some(ref on_measure) => {
+                                shader::Action::publish(on_measure(measurement)).and_capture()
+                            }
+                            None => shader::Action::request_redraw().and_capture(),
+                        });
+                    }
+
+                    // Report where to place an annotation
+                    #[cfg(feature = "annotations")]
+                    if self.active_tool == ActiveTool::Annotate
+                        && let Some(ref on_annotate) = self.on_annotate
+                        && Rectangle::new(
+                            Point::new(padding.left, padding.top),
+                            iced::Size::new(
+                                bounds.width - padding.x(),
+                                bounds.height - padding.y(),
+                            ),
+                        )
+                        .contains(pos)
+                    {
+                        let (x, y) = screen_to_data(
+                            Point::new(pos.x + bounds.x, pos.y + bounds.y),
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        );
+                        return Some(shader::Action::publish(on_annotate(x, y)).and_capture());
+                    }
+
+                    // Draw, move or resize a region of interest
+                    if let ActiveTool::Roi(kind) = self.active_tool
+                        && let Some(rois) = &self.roi_state
+                        && Self::axis_region(pos, bounds.size(), padding).is_none()
+                    {
+                        let start = screen_to_data(
+                            Point::new(pos.x + bounds.x, pos.y + bounds.y),
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        );
+                        let (index, grab, added) =
+                            match self.roi_at(pos, bounds, view_x, view_y, padding) {
+                                Some((index, grab)) => (index, grab, false),
+                                // A new ROI, resized from its bottom-left corner
+                                None => {
+                                    let x_range = (start.0, start.0);
+                                    let roi = match kind {
+                                        RoiKind::Rect => Roi::rect(x_range, (start.1, start.1)),
+                                        RoiKind::XSpan => Roi::x_span(x_range),
+                                    };
+                                    let index = rois.push(roi);
+                                    (index, RoiGrab::Edges([false, true, false, true]), true)
+                                }
+                            };
+                        self.clear_hover();
+                        *rois.selected.borrow_mut() = Some(index);
+                        state.roi_origin = rois.get(index);
+                        state.drag_start = Some(pos);
+                        state.interaction_mode = InteractionMode::RoiEditing {
+                            index,
+                            grab,
+                            start,
+                            added,
+                        };
+                        return Some(shader::Action::request_redraw().and_capture());
+                    }
+
+                    // Grab a point to drag
+                    if self.active_tool == ActiveTool::EditPoints
+                        && self.on_point_moved.is_some()
+                        && let Some((series, index)) =
+                            self.editable_point_at(pos, bounds, view_x, view_y, padding)
+                    {
+                        let p = &self.series[series].points.as_slice().unwrap_or_default()[index];
+                        let y_map = self.y_axis_maps(view_y)[series];
+                        let (x, y) = screen_to_data(
+                            Point::new(pos.x + bounds.x, pos.y + bounds.y),
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        );
+                        self.clear_hover();
+                        state.interaction_mode = InteractionMode::PointDragging {
+                            series,
+                            index,
+                            origin: (p.x, p.y),
+                            offset: (p.x - x, y_map.map_or(p.y, |m| m.apply(p.y)) - y),
+                        };
+                        return Some(shader::Action::capture());
+                    }
+
+                    // Double-click detection
+                    if interaction.double_click_to_fit {
+                        let now = self.now();
+                        if let Some(last) = state.last_click_time
+                            && now.duration_since(last).as_millis() < 300
+                        {
+                            // Double-click: reset to auto-fit
+                            state.last_click_time = None;
+                            state.interaction_mode = InteractionMode::Idle;
+                            state.elastic_animation = None;
+
+                            if let Some(ref on_change) = self.on_view_change {
+                                self.record_view();
+                                return Some(
+                                    shader::Action::publish((on_change)(
+                                        self.fit_view(),
+                                        ViewChangeKind::Final,
+                                    ))
+                                    .and_capture(),
+                                );
+                            }
+                            return Some(shader::Action::capture());
+                        }
+                        state.last_click_time = Some(now);
+                    }
+
+                    // Zoom select: the ZoomSelect tool, or Ctrl+drag with the Pan tool
+                    let zoom_select = match self.active_tool {
+                        ActiveTool::Pan => interaction.zoom_select && state.modifiers.control(),
+                        ActiveTool::ZoomSelect => {
+                            interaction.zoom_x
+                                || interaction.zoom_y
+                                || interaction.selection_mode == SelectionMode::ReportOnly
+                        }
+                        ActiveTool::Select
+                        | ActiveTool::Measure
+                        | ActiveTool::Roi(_)
+                        | ActiveTool::EditPoints => false,
+                        #[cfg(feature = "annotations")]
+                        ActiveTool::Annotate => false,
+                    };
+                    if zoom_select {
+                        // Clear tooltip when starting interaction
+                        self.clear_hover();
+                        state.interaction_mode = InteractionMode::ZoomSelecting;
+                        state.drag_start = Some(pos);
+                        state.zoom_select_current = Some(pos);
+                        return Some(shader::Action::capture());
+                    }
+
+                    // Brush: the Select tool on a linked plot
+                    if self.active_tool == ActiveTool::Select && self.selection_link.is_some() {
+                        self.clear_hover();
+                        state.interaction_mode = InteractionMode::Brushing;
+                        state.drag_start = Some(pos);
+                        state.zoom_select_current = Some(pos);
+                        return Some(shader::Action::capture());
+                    }
+
+                    // Start panning, along one axis only when grabbed by its labels
+                    let region = Self::axis_region(pos, bounds.size(), padding)
+                        .filter(|_| interaction.axis_drag);
+                    let pan_axes = [
+                        interaction.pan_x && region != Some(AxisRegion::Y),
+                        interaction.pan_y && region != Some(AxisRegion::X),
+                    ];
+                    if self.active_tool == ActiveTool::Pan && pan_axes.contains(&true) {
+                        // Clear tooltip when starting interaction
+                        self.clear_hover();
+                        state.elastic_animation = None; // Cancel any ongoing animation
+                        state.interaction_mode = InteractionMode::Panning;
+                        state.pan_axes = pan_axes;
+                        state.pan_sample = Some((self.now(), [0.0, 0.0]));
+                        state.pan_velocity = [0.0, 0.0];
+                        state.pan_origin_view = Some(self.view_state.clone());
+                        state.gesture_view = None;
+                        state.drag_start = Some(pos);
+                        state.drag_start_view = Some(ViewState {
+                            x_range: Some((view_x[0], view_x[1])),
+                            y_range: Some((view_y[0], view_y[1])),
+                            secondary_y_range: self.view_state.secondary_y_range,
+                        });
+                        return Some(shader::Action::capture());
+                    }
+                }
+                None
+            }
+
+            // ---- Mouse button release ----
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                // A click on a point pins its tooltip, unless it ends a selection
+                #[cfg(feature = "tooltip")]
+                {
+                    let press = state.press_position.take();
+                    if matches!(
+                        state.interaction_mode,
+                        InteractionMode::Idle | InteractionMode::Panning
+                    ) && let Some(point) = self.point_click(
+                        &mut state.hover_index,
+                        &state.generator_samples,
+                        press,
+                        cursor,
+                        bounds,
+                        view_x,
+                        view_y,
+                        padding,
+                    ) {
+                        state.interaction_mode = InteractionMode::Idle;
+                        state.drag_start = None;
+                        state.drag_start_view = None;
+                        self.pin_tooltip(&point, bounds, view_x, view_y, padding);
+                        return Some(match self.on_point_click {
+                            Some(ref on_click) => {
+                                shader::Action::publish((on_click)(point)).and_capture()
+                            }
+                            None => shader::Action::request_redraw().and_capture(),
+                        });
+                    }
+                }
+
+                match state.interaction_mode {
+                    InteractionMode::Panning => {
+                        state.interaction_mode = InteractionMode::Idle;
+                        state.drag_start = None;
+                        let start_view = state.drag_start_view.take();
+                        let moved = state
+                            .pan_sample
+                            .is_some_and(|(_, offset)| offset != [0.0; 2]);
+                        // Where the pan left the view, published or not
+                        let shown = state
+                            .gesture_view
+                            .take()
+                            .unwrap_or_else(|| self.view_state.clone());
+                        // Record the view the pan left, unless it never moved
+                        if let Some(origin) = state.pan_origin_view.take()
+                            && let Some(history) = &self.view_history
+                            && moved
+                        {
+                            history.push(origin);
+                        }
+
+                        // Fling: keep moving if the drag was still going when released
+                        let now = self.now();
+                        if interaction.inertia
+                            && let Some((time, [dx, dy])) = state.pan_sample.take()
+                            && now.duration_since(time) <= FLING_MAX_IDLE
+                            && let Some(ViewState {
+                                x_range: Some(start_x),
+                                y_range: Some(start_y),
+                                ..
+                            }) = start_view
+                        {
+                            // Continue from where the drag left the view, before
+                            // elastic resistance
+                            let [pan_x, pan_y] = state.pan_axes;
+                            let dx = if pan_x { dx } else { 0.0 };
+                            let dy = if pan_y { dy } else { 0.0 };
+                            let x_range = (start_x.0 + dx, start_x.1 + dx);
+                            let y_range = (start_y.0 + dy, start_y.1 + dy);
+                            let velocity = [
+                                if pan_x { state.pan_velocity[0] } else { 0.0 },
+                                if pan_y { state.pan_velocity[1] } else { 0.0 },
+                            ];
+                            let fast = |v: f64, (lo, hi): (f64, f64)| {
+                                v.abs() > 10.0 * INERTIA_STOP_SPEED * (hi - lo)
+                            };
+                            if fast(velocity[0], x_range) || fast(velocity[1], y_range) {
+                                state.inertia_animation = Some(InertiaState {
+                                    x_range,
+                                    y_range,
+                                    velocity,
+                                    last_tick: now,
+                                });
+                                return Some(shader::Action::request_redraw().and_capture());
+                            }
+                        }
+
+                        // Check if we need to spring back from over-scroll
+                        if interaction.elastic {
+                            let current_x = shown.x_range.unwrap_or((view_x[0], view_x[1]));
+                            let current_y = shown.y_range.unwrap_or((view_y[0], view_y[1]));
+
+                            let x_out = interaction.pan_x
+                                && is_out_of_bounds(
+                                    current_x,
+                                    effective_x_bounds,
+                                    interaction.boundary_padding,
+                                );
+                            let y_out = interaction.pan_y
+                                && is_out_of_bounds(
+                                    current_y,
+                                    effective_y_bounds,
+                                    interaction.boundary_padding,
+                                );
+
+                            if x_out || y_out {
+                                let target_x = if x_out {
+                                    Some(clamp_range_to_bounds(
+                                        current_x,
+                                        effective_x_bounds,
+                                        interaction.boundary_padding,
+                                    ))
+                                } else {
+                                    None
+                                };
+                                let target_y = if y_out {
+                                    Some(clamp_range_to_bounds(
+                                        current_y,
+                                        effective_y_bounds,
+                                        interaction.boundary_padding,
+                                    ))
+                                } else {
+                                    None
+                                };
+
+                                state.elastic_animation = Some(ElasticState {
+                                    from_x: if x_out { Some(current_x) } else { None },
+                                    from_y: if y_out { Some(current_y) } else { None },
+                                    to_x: target_x,
+                                    to_y: target_y,
+                                    start_time: self.now(),
+                                    duration_ms: interaction.elastic_duration_ms,
+                                });
+
+                                return Some(shader::Action::request_redraw().and_capture());
+                            }
+                        }
+
+                        if moved && let Some(ref on_change) = self.on_view_change {
+                            return Some(
+                                shader::Action::publish((on_change)(shown, ViewChangeKind::Final))
+                                    .and_capture(),
+                            );
+                        }
+                        Some(shader::Action::capture())
+                    }
+                    InteractionMode::ZoomSelecting => {
+                        // Complete the zoom selection
+                        if let (Some(start), Some(current)) =
+                            (state.drag_start, state.zoom_select_current)
+                        {
+                            // Convert screen coords to data coords
+                            let (x0, y0) = screen_to_data(
+                                Point::new(start.x + bounds.x, start.y + bounds.y),
+                                bounds,
+                                view_x,
+                                view_y,
+                                padding,
+                            );
+                            let (x1, y1) = screen_to_data(
+                                Point::new(current.x + bounds.x, current.y + bounds.y),
+                                bounds,
+                                view_x,
+                                view_y,
+                                padding,
+                            );
+
+                            // Only zoom if the rectangle is big enough (>5px in both directions)
+                            let dx = (current.x - start.x).abs();
+                            let dy = (current.y - start.y).abs();
+
+                            if (dx > 5.0 || dy > 5.0)
+                                && interaction.selection_mode == SelectionMode::ReportOnly
+                            {
+                                state.interaction_mode = InteractionMode::Idle;
+                                state.drag_start = None;
+                                state.zoom_select_current = None;
+
+                                let x_range = (x0.min(x1), x0.max(x1));
+                                let y_range = (y0.min(y1), y0.max(y1));
+                                return Some(match self.on_select {
+                                    Some(ref on_select) => {
+                                        shader::Action::publish((on_select)(x_range, y_range))
+                                            .and_capture()
+                                    }
+                                    // Still clear the selection rectangle
+                                    None => shader::Action::request_redraw().and_capture(),
+                                });
+                            }
+
+                            if dx > 5.0 || dy > 5.0 {
+                                let mut new_view = self.view_state.clone();
+                                // A locked aspect widens the rectangle to fit
+                                let locked = self.aspect_locked();
+                                let (zoom_x, zoom_y) =
+                                    self.zoom_axes(interaction.zoom_x, interaction.zoom_y);
+
+                                if zoom_x && (dx > 5.0 || locked) {
+                                    let lo = x0.min(x1);
+                                    let hi = x0.max(x1);
+                                    new_view.x_range = Some((lo, hi));
+                                }
+
+                                if zoom_y && (dy > 5.0 || locked) {
+                                    let lo = y0.min(y1);
+                                    let hi = y0.max(y1);
+                                    new_view.y_range = Some((lo, hi));
+                                }
+
+                                state.interaction_mode = InteractionMode::Idle;
+                                state.drag_start = None;
+                                state.zoom_select_current = None;
+
+                                if let Some(ref on_change) = self.on_view_change {
+                                    self.record_view();
+                                    return Some(
+                                        shader::Action::publish((on_change)(
+                                            self.follow_secondary_y(new_view),
+                                            ViewChangeKind::Final,
+                                        ))
+                                        .and_capture(),
+                                    );
+                                }
+                            }
+                        }
+
+                        state.interaction_mode = InteractionMode::Idle;
+                        state.drag_start = None;
+                        state.zoom_select_current = None;
+                        Some(shader::Action::capture())
+                    }
+                    InteractionMode::PointDragging { .. }
+                    | InteractionMode::CursorLineDragging { .. } => {
+                        state.interaction_mode = InteractionMode::Idle;
+                        Some(shader::Action::capture())
+                    }
+                    InteractionMode::RoiEditing { index, added, .. } => {
+                        state.interaction_mode = InteractionMode::Idle;
+                        let start = state.drag_start.take();
+                        let origin = state.roi_origin.take();
+                        let Some(rois) = &self.roi_state else {
+                            return Some(shader::Action::capture());
+                        };
+                        // A click without a drag draws nothing
+                        let dragged =
+                            start.zip(cursor.position_in(bounds)).is_some_and(|(a, b)| {
+                                (b.x - a.x).abs() > 3.0 || (b.y - a.y).abs() > 3.0
+                            });
+                        if added && !dragged {
+                            rois.remove(index);
+                            return Some(shader::Action::request_redraw().and_capture());
+                        }
+                        let change = if added {
+                            RoiChange::Added(index)
+                        } else if rois.get(index) != origin {
+                            RoiChange::Changed(index)
+                        } else {
+                            return Some(shader::Action::request_redraw().and_capture());
+                        };
+                        Some(match self.on_roi_change {
+                            Some(ref on_change) => {
+                                shader::Action::publish(on_change(change)).and_capture()
+                            }
+                            None => shader::Action::request_redraw().and_capture(),
+                        })
+                    }
+                    InteractionMode::Brushing => {
+                        if let (Some(start), Some(current), Some((link, _))) = (
+                            state.drag_start,
+                            state.zoom_select_current,
+                            &self.selection_link,
+                        ) {
+                            let mut selected = link.selected.borrow_mut();
+                            selected.clear();
+
+                            // A click without a drag just clears the selection
+                            let dx = (current.x - start.x).abs();
+                            let dy = (current.y - start.y).abs();
+                            if dx > 5.0 || dy > 5.0 {
+                                let (x0, y0) = screen_to_data(
+                                    Point::new(start.x + bounds.x, start.y + bounds.y),
+                                    bounds,
+                                    view_x,
+                                    view_y,
+                                    padding,
+                                );
+                                let (x1, y1) = screen_to_data(
+                                    Point::new(current.x + bounds.x, current.y + bounds.y),
+                                    bounds,
+                                    view_x,
+                                    view_y,
+                                    padding,
+                                );
+                                let (x_lo, x_hi) = (x0.min(x1), x0.max(x1));
+                                let (y_lo, y_hi) = (y0.min(y1), y0.max(y1));
+
+                                let hidden = self.hidden_series();
+                                let y_maps = self.y_axis_maps(view_y);
+                                for (series_idx, series) in self.series.iter().enumerate() {
+                                    if hidden.contains(&series_idx) {
+                                        continue;
+                                    }
+                                    let to_primary =
+                                        |y: f64| y_maps[series_idx].map_or(y, |m| m.apply(y));
+                                    let points = series.points.as_slice().unwrap_or_default();
+                                    for (i, p) in points.iter().enumerate() {
+                                        let y = to_primary(p.y);
+                                        if p.x >= x_lo && p.x <= x_hi && y >= y_lo && y <= y_hi {
+                                            selected.insert(i);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        state.interaction_mode = InteractionMode::Idle;
+                        state.drag_start = None;
+                        state.zoom_select_current = None;
+                        Some(shader::Action::request_redraw().and_capture())
+                    }
+                    // Handled before the event match
+                    InteractionMode::Idle | InteractionMode::LegendDragging { .. } => None,
+                }
+            }
+
+            // ---- Cursor left widget ----
+            Event::Mouse(mouse::Event::CursorLeft) => {
+                let legend_hover_changed = self.update_legend_hover(Cursor::Unavailable, bounds);
+                if has_crosshair {
+                    *self.cursor_state.position.borrow_mut() = None;
+                    self.set_shared_cursor(state, None);
+                }
+                // Clear tooltip when cursor leaves the widget
+                if self.hover_enabled() {
+                    self.clear_hover();
+                    return Some(shader::Action::request_redraw());
+                }
+                // Hide the cursor readout and crosshair
+                (legend_hover_changed || has_readout || has_crosshair)
+                    .then(shader::Action::request_redraw)
+            }
+
+            // ---- Mouse move (drag) ----
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                state.last_cursor = Some(*position);
+                if has_crosshair {
+                    self.update_crosshair(state, *position, bounds, view_x, view_y, padding);
+                }
+
+                match state.interaction_mode {
+                    InteractionMode::Panning => {
+                        if let (Some(start), Some(start_view)) =
+                            (state.drag_start, &state.drag_start_view)
+                        {
+                            let start_view_x = start_view.x_range.unwrap();
+                            let start_view_y = start_view.y_range.unwrap();
+
+                            let plot_width = bounds.width - padding.x();
+                            let plot_height = bounds.height - padding.y();
+
+                            // position is absolute screen coords; drag_start is relative to bounds
                             let current = Point::new(position.x - bounds.x, position.y - bounds.y);
                             let dx_screen = current.x - start.x;
                             let dy_screen = current.y - start.y;
 
-                            // Convert screen delta to data delta
-                            let dx_data =
-                                -dx_screen / plot_width * (start_view_x.1 - start_view_x.0);
-                            let dy_data =
-                                dy_screen / plot_height * (start_view_y.1 - start_view_y.0);
+                            // Convert screen delta to data delta
+                            let dx_data = -f64::from(dx_screen / plot_width)
+                                * (start_view_x.1 - start_view_x.0);
+                            let dy_data = f64::from(dy_screen / plot_height)
+                                * (start_view_y.1 - start_view_y.0);
+
+                            // Fling velocity, smoothed over the last few moves
+                            let now = self.now();
+                            if let Some((last_time, last)) = state.pan_sample {
+                                let dt = now.duration_since(last_time).as_secs_f64();
+                                if dt > 0.0 {
+                                    let current =
+                                        [(dx_data - last[0]) / dt, (dy_data - last[1]) / dt];
+                                    state.pan_velocity = [0, 1]
+                                        .map(|i| 0.6 * current[i] + 0.4 * state.pan_velocity[i]);
+                                }
+                            }
+                            state.pan_sample = Some((now, [dx_data, dy_data]));
+
+                            let mut new_view = self.view_state.clone();
+                            let [pan_x, pan_y] = state.pan_axes;
+
+                            if pan_x {
+                                let raw = (start_view_x.0 + dx_data, start_view_x.1 + dx_data);
+                                let new_x = if interaction.elastic {
+                                    apply_elastic_resistance(
+                                        raw,
+                                        effective_x_bounds,
+                                        interaction.boundary_padding,
+                                        interaction.elastic_limit,
+                                    )
+                                } else {
+                                    clamp_range_to_bounds(
+                                        raw,
+                                        effective_x_bounds,
+                                        interaction.boundary_padding,
+                                    )
+                                };
+                                new_view.x_range = Some(new_x);
+                            }
+
+                            if pan_y {
+                                let raw = (start_view_y.0 + dy_data, start_view_y.1 + dy_data);
+                                let new_y = if interaction.elastic {
+                                    apply_elastic_resistance(
+                                        raw,
+                                        effective_y_bounds,
+                                        interaction.boundary_padding,
+                                        interaction.elastic_limit,
+                                    )
+                                } else {
+                                    clamp_range_to_bounds(
+                                        raw,
+                                        effective_y_bounds,
+                                        interaction.boundary_padding,
+                                    )
+                                };
+                                new_view.y_range = Some(new_y);
+                            }
+
+                            let new_view = self.follow_secondary_y(new_view);
+                            return Some(match self.publish_interactive_view(state, new_view) {
+                                Some(action) => action.and_capture(),
+                                None => shader::Action::capture(),
+                            });
+                        }
+                        None
+                    }
+                    InteractionMode::ZoomSelecting | InteractionMode::Brushing => {
+                        // Update the current selection corner
+                        let relative = Point::new(position.x - bounds.x, position.y - bounds.y);
+                        state.zoom_select_current = Some(relative);
+                        // Request redraw to update the selection rectangle
+                        Some(shader::Action::request_redraw().and_capture())
+                    }
+                    InteractionMode::RoiEditing {
+                        index, grab, start, ..
+                    } => {
+                        let (Some(rois), Some(origin)) = (&self.roi_state, &state.roi_origin)
+                        else {
+                            return Some(shader::Action::capture());
+                        };
+                        let (x, y) = screen_to_data(*position, bounds, view_x, view_y, padding);
+                        let (dx, dy) = (x - start.0, y - start.1);
+                        let moved =
+                            |(lo, hi): (f64, f64), d: f64, [lo_edge, hi_edge]: [bool; 2]| {
+                                let lo = lo + if lo_edge { d } else { 0.0 };
+                                let hi = hi + if hi_edge { d } else { 0.0 };
+                                (lo.min(hi), lo.max(hi))
+                            };
+                        let [left, right, bottom, top] = match grab {
+                            RoiGrab::Body => [true; 4],
+                            RoiGrab::Edges(edges) => edges,
+                        };
+                        if let Some(roi) = rois.rois.borrow_mut().get_mut(index) {
+                            roi.x_range = moved(origin.x_range, dx, [left, right]);
+                            roi.y_range =
+                                origin.y_range.map(|range| moved(range, dy, [bottom, top]));
+                        }
+                        Some(shader::Action::request_redraw().and_capture())
+                    }
+                    InteractionMode::CursorLineDragging { index, offset } => {
+                        let on_move = self.on_cursor_line_move.as_ref()?;
+                        let line = self.cursor_lines.get(index)?;
+                        let (x, y) = screen_to_data(*position, bounds, view_x, view_y, padding);
+                        let value = offset
+                            + match line.axis {
+                                CursorAxis::X => x,
+                                CursorAxis::Y => y,
+                            };
+                        if value == line.value {
+                            return Some(shader::Action::capture());
+                        }
+                        Some(shader::Action::publish(on_move(index, value)).and_capture())
+                    }
+                    InteractionMode::PointDragging {
+                        series,
+                        index,
+                        origin,
+                        offset,
+                    } => {
+                        let on_moved = self.on_point_moved.as_ref()?;
+                        let config = &interaction.point_drag;
+                        let (x, y) = screen_to_data(*position, bounds, view_x, view_y, padding);
+                        let (x, y) = (x + offset.0, y + offset.1);
+                        let y = self.y_axis_maps(view_y)[series].map_or(y, |m| m.invert(y));
+                        let snap = |v: f64, step: Option<f64>| match step {
+                            Some(step) if step > 0.0 => (v / step).round() * step,
+                            _ => v,
+                        };
+                        let x = if config.lock_x {
+                            origin.0
+                        } else {
+                            snap(x, config.snap_x)
+                        };
+                        let y = if config.lock_y {
+                            origin.1
+                        } else {
+                            snap(y, config.snap_y)
+                        };
+                        // Only publish moves the app hasn't applied yet
+                        let current = self.series[series]
+                            .points
+                            .as_slice()
+                            .and_then(|points| points.get(index));
+                        if current.is_some_and(|p| p.x == x && p.y == y) {
+                            return Some(shader::Action::capture());
+                        }
+                        Some(shader::Action::publish(on_moved(series, index, x, y)).and_capture())
+                    }
+                    // Handled before the event match
+                    InteractionMode::LegendDragging { .. } => None,
+                    InteractionMode::Idle => {
+                        // ---- Legend hover: emphasize the entry's series ----
+                        let legend_hover_changed = self.update_legend_hover(cursor, bounds);
+
+                        // ---- Tooltip: nearest-point detection ----
+                        #[cfg(feature = "tooltip")]
+                        if let Some(action) = self.update_hover(
+                            &mut state.hover_index,
+                            &state.generator_samples,
+                            cursor,
+                            bounds,
+                            view_x,
+                            view_y,
+                            padding,
+                        ) {
+                            return Some(action);
+                        }
+                        // Keep the cursor readout, crosshair and a pending
+                        // measurement following the cursor
+                        let measuring = self
+                            .measure_state
+                            .as_ref()
+                            .is_some_and(|m| m.pending.borrow().is_some());
+                        (legend_hover_changed || has_readout || has_crosshair || measuring)
+                            .then(shader::Action::request_redraw)
+                    }
+                }
+            }
+
+            // ---- Scroll wheel (zoom) ----
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let action = interaction.wheel_bindings.action(state.modifiers);
+                let (zoom_x, zoom_y, pan_x, pan_y) = match action {
+                    WheelAction::Zoom => (interaction.zoom_x, interaction.zoom_y, false, false),
+                    WheelAction::ZoomX => (interaction.zoom_x, false, false, false),
+                    WheelAction::ZoomY => (false, interaction.zoom_y, false, false),
+                    WheelAction::PanX => (false, false, interaction.pan_x, false),
+                    WheelAction::PanY => (false, false, false, interaction.pan_y),
+                    WheelAction::Ignore => (false, false, false, false),
+                };
+                if !(zoom_x || zoom_y || pan_x || pan_y) {
+                    return None;
+                }
+
+                // Only act if cursor is within bounds
+                let cursor_pos = cursor.position_in(bounds)?;
+                if self.in_exclusion_zone(cursor_pos) {
+                    return None;
+                }
+
+                // Block scroll over legend
+                if self.legend_enabled() {
+                    let layout = self.legend_state.layout.borrow();
+                    if let Some(legend_bounds) = layout.bounds
+                        && legend_bounds.contains(cursor_pos)
+                    {
+                        return Some(shader::Action::capture());
+                    }
+                }
+
+                // Over an axis's labels, act on that axis alone
+                let region = Self::axis_region(cursor_pos, bounds.size(), padding)
+                    .filter(|_| interaction.axis_scroll);
+                let on_x = region != Some(AxisRegion::Y);
+                let on_y = region != Some(AxisRegion::X);
+                let (zoom_x, zoom_y) = self.zoom_axes(zoom_x && on_x, zoom_y && on_y);
+                let (pan_x, pan_y) = (pan_x && on_x, pan_y && on_y);
+                if !(zoom_x || zoom_y || pan_x || pan_y) {
+                    return None;
+                }
+
+                let scroll_y = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => *y,
+                    mouse::ScrollDelta::Pixels { y, .. } => *y / 50.0,
+                };
+
+                if scroll_y.abs() < f32::EPSILON {
+                    return None;
+                }
+
+                // Cancel any elastic animation
+                state.elastic_animation = None;
+
+                if pan_x || pan_y {
+                    let step = f64::from(scroll_y * interaction.wheel_bindings.pan_step);
+                    let shift = |[lo, hi]: [f64; 2], by: f64, bounds| {
+                        let offset = by * (hi - lo);
+                        clamp_range_to_bounds(
+                            (lo + offset, hi + offset),
+                            bounds,
+                            interaction.boundary_padding,
+                        )
+                    };
+                    let mut new_view = self.view_state.clone();
+                    if pan_x {
+                        new_view.x_range = Some(shift(view_x, -step, effective_x_bounds));
+                    }
+                    if pan_y {
+                        new_view.y_range = Some(shift(view_y, step, effective_y_bounds));
+                    }
+                    let on_change = self.on_view_change.as_ref()?;
+                    return Some(
+                        shader::Action::publish(on_change(
+                            self.follow_secondary_y(new_view),
+                            ViewChangeKind::Final,
+                        ))
+                        .and_capture(),
+                    );
+                }
+
+                // Zoom factor: positive scroll = zoom in (shrink range)
+                let factor = 1.0 - scroll_y * interaction.zoom_speed;
+                let factor = f64::from(factor.clamp(0.1, 10.0)); // safety clamp
+
+                // Zoom center in data space
+                let (cursor_x, cursor_y) =
+                    screen_to_data(cursor_pos, bounds, view_x, view_y, padding);
+                let (cx, cy) = match interaction.zoom_anchor {
+                    ZoomAnchor::Cursor => (cursor_x, cursor_y),
+                    ZoomAnchor::Center => {
+                        ((view_x[0] + view_x[1]) / 2.0, (view_y[0] + view_y[1]) / 2.0)
+                    }
+                    ZoomAnchor::Fixed { x, y } => (x.unwrap_or(cursor_x), y.unwrap_or(cursor_y)),
+                };
+
+                let mut new_view = self.view_state.clone();
+
+                if zoom_x {
+                    let new_lo = cx - (cx - view_x[0]) * factor;
+                    let new_hi = cx + (view_x[1] - cx) * factor;
+                    let clamped = clamp_range_to_bounds(
+                        (new_lo, new_hi),
+                        effective_x_bounds,
+                        interaction.boundary_padding,
+                    );
+                    new_view.x_range = Some(clamped);
+                }
+
+                if zoom_y {
+                    let new_lo = cy - (cy - view_y[0]) * factor;
+                    let new_hi = cy + (view_y[1] - cy) * factor;
+                    let clamped = clamp_range_to_bounds(
+                        (new_lo, new_hi),
+                        effective_y_bounds,
+                        interaction.boundary_padding,
+                    );
+                    new_view.y_range = Some(clamped);
+                }
+
+                // For axes with auto-fit that are not being zoomed,
+                // keep them as None (auto-fit)
+                if !zoom_x && self.view_state.x_range.is_none() {
+                    new_view.x_range = None;
+                }
+                if !zoom_y && self.view_state.y_range.is_none() {
+                    new_view.y_range = None;
+                }
+
+                if let Some(ref on_change) = self.on_view_change {
+                    return Some(
+                        shader::Action::publish((on_change)(
+                            self.follow_secondary_y(new_view),
+                            ViewChangeKind::Final,
+                        ))
+                        .and_capture(),
+                    );
+                }
+                Some(shader::Action::capture())
+            }
+
+            _ => None,
+        }
+    }
+
+    fn draw(&self, state: &Self::State, _cursor: Cursor, bounds: Rectangle) -> Self::Primitive {
+        let primitive = self.primitive(state, bounds);
+        if let Some(ref frame) = self.software_frame {
+            *frame.borrow_mut() = Some(primitive.clone());
+        }
+        primitive
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> mouse::Interaction {
+        let has_any = self.interaction.pan_x
+            || self.interaction.pan_y
+            || self.interaction.zoom_x
+            || self.interaction.zoom_y
+            || self.interaction.zoom_select
+            || (matches!(self.active_tool, ActiveTool::Roi(_)) && self.roi_state.is_some())
+            || (self.active_tool == ActiveTool::EditPoints && self.on_point_moved.is_some())
+            || (!self.cursor_lines.is_empty() && self.on_cursor_line_move.is_some());
+
+        if !has_any && !self.legend_enabled() {
+            return mouse::Interaction::default();
+        }
+
+        match state.interaction_mode {
+            InteractionMode::Panning | InteractionMode::LegendDragging { .. } => {
+                mouse::Interaction::Grabbing
+            }
+            InteractionMode::ZoomSelecting | InteractionMode::Brushing => {
+                mouse::Interaction::Crosshair
+            }
+            InteractionMode::RoiEditing { grab, .. } => roi_interaction(grab, true),
+            InteractionMode::PointDragging { .. } => mouse::Interaction::Grabbing,
+            InteractionMode::CursorLineDragging { index, .. } => self
+                .cursor_lines
+                .get(index)
+                .map_or(mouse::Interaction::default(), cursor_line_interaction),
+            InteractionMode::Idle => {
+                if let Some(pos) = cursor.position_in(bounds) {
+                    if self.in_exclusion_zone(pos) {
+                        return mouse::Interaction::default();
+                    }
+                    // Check if cursor is over the legend area
+                    if self.legend_enabled() {
+                        let layout = self.legend_state.layout.borrow();
+                        // Check toggle buttons first — show pointer
+                        for toggle in layout.toggles.iter() {
+                            if toggle.rect.contains(pos) {
+                                return mouse::Interaction::Pointer;
+                            }
+                        }
+                        // Over legend background — show a grab cursor if it can be dragged
+                        if let Some(legend_bounds) = layout.bounds
+                            && legend_bounds.contains(pos)
+                        {
+                            return if self.legend_draggable() {
+                                mouse::Interaction::Grab
+                            } else {
+                                mouse::Interaction::default()
+                            };
+                        }
+                    }
+                    if !has_any {
+                        return mouse::Interaction::default();
+                    }
+                    let padding = self.plot_padding(bounds.size());
+                    let plot_size =
+                        iced::Size::new(bounds.width - padding.x(), bounds.height - padding.y());
+                    let (view_x, view_y, _, _) = self.resolve_view_ranges(false);
+                    let (view_x, view_y) = self.aspect_ranges(view_x, view_y, plot_size);
+                    if self.on_cursor_line_move.is_some()
+                        && let Some(index) =
+                            self.cursor_line_at(pos, bounds, view_x, view_y, padding)
+                    {
+                        return cursor_line_interaction(&self.cursor_lines[index]);
+                    }
+                    match self.active_tool {
+                        // Show crosshair when Ctrl is held (indicating zoom select is available)
+                        ActiveTool::Pan => {
+                            if self.interaction.zoom_select && state.modifiers.control() {
+                                return mouse::Interaction::Crosshair;
+                            }
+                            // Over an axis strip, show which way it pans
+                            let region = Self::axis_region(pos, bounds.size(), padding)
+                                .filter(|_| self.interaction.axis_drag);
+                            match region {
+                                Some(AxisRegion::X) if self.interaction.pan_x => {
+                                    mouse::Interaction::ResizingHorizontally
+                                }
+                                Some(AxisRegion::Y) if self.interaction.pan_y => {
+                                    mouse::Interaction::ResizingVertically
+                                }
+                                _ => mouse::Interaction::Grab,
+                            }
+                        }
+                        ActiveTool::Roi(_) | ActiveTool::EditPoints => {
+                            if self.active_tool == ActiveTool::EditPoints {
+                                return match self
+                                    .editable_point_at(pos, bounds, view_x, view_y, padding)
+                                {
+                                    Some(_) => mouse::Interaction::Grab,
+                                    None => mouse::Interaction::default(),
+                                };
+                            }
+                            match self.roi_at(pos, bounds, view_x, view_y, padding) {
+                                Some((_, grab)) => roi_interaction(grab, false),
+                                None => mouse::Interaction::Crosshair,
+                            }
+                        }
+                        ActiveTool::ZoomSelect | ActiveTool::Select | ActiveTool::Measure => {
+                            mouse::Interaction::Crosshair
+                        }
+                        #[cfg(feature = "annotations")]
+                        ActiveTool::Annotate => mouse::Interaction::Crosshair,
+                    }
+                } else {
+                    mouse::Interaction::default()
+                }
+            }
+        }
+    }
+}
+
+/// Cursor over, or while dragging, a cursor line.
+fn cursor_line_interaction(line: &CursorLine) -> mouse::Interaction {
+    match line.axis {
+        CursorAxis::X => mouse::Interaction::ResizingHorizontally,
+        CursorAxis::Y => mouse::Interaction::ResizingVertically,
+    }
+}
+
+/// Cursor over, or while dragging, the `grab`bed part of a region of interest.
+fn roi_interaction(grab: RoiGrab, dragging: bool) -> mouse::Interaction {
+    match grab {
+        RoiGrab::Body if dragging => mouse::Interaction::Grabbing,
+        RoiGrab::Body => mouse::Interaction::Grab,
+        RoiGrab::Edges([left, right, bottom, top]) => match (left || right, bottom || top) {
+            (true, false) => mouse::Interaction::ResizingHorizontally,
+            (false, true) => mouse::Interaction::ResizingVertically,
+            // Top-left and bottom-right corners resize along one diagonal
+            _ if left == top => mouse::Interaction::ResizingDiagonallyDown,
+            _ => mouse::Interaction::ResizingDiagonallyUp,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotter::{
+        BoxSummary, ContextMenuConfig, CrosshairConfig, CursorState, InteractionConfig,
+        PerformanceConfig, PlotterError, PointDragConfig, RoiState, SharedCursor, StackMode,
+        WheelBindings,
+    };
+    #[cfg(feature = "legend")]
+    use crate::plotter::{LegendConfig, LegendState, LegendToggleRect};
+    #[cfg(feature = "tooltip")]
+    use crate::plotter::{MeasureState, TooltipConfig, TooltipState};
+    use crate::testing::{DEFAULT_BOUNDS, Harness, ManualClock};
+    use std::time::Duration;
+
+    fn content_key(series: PlotSeries<'_>) -> u64 {
+        let view = ViewState::with_ranges((0.0, 9.0), (0.0, 9.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(vec![series], &view);
+        plotter.content_key()
+    }
+
+    #[test]
+    fn test_interior_edits_change_content_key() {
+        let mut points: Vec<PlotPoint> = (0..10).map(|i| (f64::from(i), 1.0).into()).collect();
+        let mut sizes = vec![4.0; 10];
+        let mut colors = vec![iced::Color::WHITE; 10];
+        let key = |points: &[PlotPoint], sizes: &[f32], colors: &[iced::Color]| {
+            let style = SeriesStyle::default()
+                .with_marker_sizes(sizes)
+                .with_point_colors(colors);
+            content_key(PlotSeries::new("s", PlotPoints::borrowed(points)).with_style(style))
+        };
+
+        let before = key(&points, &sizes, &colors);
+        assert_eq!(key(&points, &sizes, &colors), before);
+        points[4].y = 2.0;
+        let moved = key(&points, &sizes, &colors);
+        assert_ne!(moved, before);
+        sizes[5] = 8.0;
+        let resized = key(&points, &sizes, &colors);
+        assert_ne!(resized, moved);
+        colors[6] = iced::Color::BLACK;
+        assert_ne!(key(&points, &sizes, &colors), resized);
+    }
+
+    #[test]
+    fn test_value_fn_output_changes_content_key() {
+        let points: Vec<PlotPoint> = (0..10).map(|i| (f64::from(i), 1.0).into()).collect();
+        let key = |threshold: usize| {
+            let color =
+                ColorMode::value_fn(iced::Color::BLACK, iced::Color::WHITE, move |i, _, _| {
+                    if i < threshold { 0.0 } else { 1.0 }
+                });
+            content_key(
+                PlotSeries::new("s", PlotPoints::borrowed(&points))
+                    .with_style(SeriesStyle::new(color)),
+            )
+        };
+
+        assert_eq!(key(5), key(5));
+        assert_ne!(key(5), key(6));
+    }
+
+    #[cfg(feature = "annotations")]
+    #[test]
+    fn test_annotate_tool_reports_clicks_in_plot_area() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter = Plotter::new(Vec::new(), &view)
+            .with_active_tool(ActiveTool::Annotate)
+            .on_view_change(|_| (f64::NAN, f64::NAN))
+            .on_annotate(|x, y| (x, y));
+        let mut harness = Harness::new(&plotter);
+        let padding = plotter.plot_padding(harness.bounds.size());
+        let center = Point::new(
+            (padding.left + harness.bounds.width - padding.right) / 2.0,
+            (padding.top + harness.bounds.height - padding.bottom) / 2.0,
+        );
+
+        // A click in the middle of the plot area reports the view center,
+        // and dragging from it doesn't pan
+        harness.move_to(center);
+        assert_eq!(harness.press(), Some((5.0, 5.0)));
+        assert_eq!(harness.move_to(Point::new(center.x + 50.0, center.y)), None);
+        harness.release();
+
+        // Clicks on the axes don't
+        harness.move_to(Point::new(center.x, harness.bounds.height - 5.0));
+        assert_eq!(harness.press(), None);
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_hovering_reuses_adaptive_samples() {
+        use crate::plotter::{AdaptiveSampling, TooltipConfig};
+
+        let calls = std::cell::Cell::new(0);
+        let wave = |x: f64| {
+            calls.set(calls.get() + 1);
+            (x * 40.0).sin()
+        };
+        let points = PlotPoints::generator(wave, (0.0, 1.0), 50)
+            .with_adaptive_sampling(AdaptiveSampling::default());
+        let view = ViewState::with_ranges((0.0, 1.0), (-1.0, 1.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(vec![PlotSeries::new("wave", points)], &view)
+            .with_options(PlotterOptions {
+                tooltip: Some(TooltipConfig::default()),
+                ..PlotterOptions::default()
+            });
+
+        let mut harness = Harness::new(&plotter);
+        let before = calls.get();
+        harness.primitive();
+        assert!(calls.get() - before > 50);
+        // Each move only evaluates the uniform samples of the data range,
+        // and hit tests against the refined samples of the draw
+        for x in [100.0, 200.0, 300.0] {
+            let before = calls.get();
+            harness.move_to(Point::new(x, 150.0));
+            assert_eq!(calls.get() - before, 50);
+        }
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_hover_skips_opted_out_series_and_uses_their_distance() {
+        let view = ViewState::with_ranges((0.0, 100.0), (0.0, 100.0));
+        let hovered = |reference: PlotSeries<'static>, signal: PlotSeries<'static>| {
+            let plotter: Plotter<'_, String> = Plotter::new(vec![reference, signal], &view)
+                .on_point_hover(|point| point.series_label);
+            let padding = plotter.plot_padding(DEFAULT_BOUNDS.size());
+            let [width, height] = [
+                DEFAULT_BOUNDS.width - padding.x(),
+                DEFAULT_BOUNDS.height - padding.y(),
+            ];
+            // On the reference point, 10 units below the signal's
+            Harness::new(&plotter).move_to(Point::new(
+                padding.left + width / 2.0,
+                padding.top + height / 2.0,
+            ))
+        };
+        let reference =
+            || PlotSeries::new("reference", PlotPoints::owned(vec![(50.0, 50.0).into()]));
+        let signal = || PlotSeries::new("signal", PlotPoints::owned(vec![(50.0, 60.0).into()]));
+
+        assert_eq!(hovered(reference(), signal()), Some("reference".into()));
+        // Opted out, and the signal is beyond the default distance
+        assert_eq!(hovered(reference().with_hover(false), signal()), None);
+        // The signal reaches further
+        assert_eq!(
+            hovered(
+                reference().with_hover(false),
+                signal().with_hover_distance(1000.0)
+            ),
+            Some("signal".into())
+        );
+        // A smaller distance than the cursor's offset misses
+        assert_eq!(
+            hovered(reference().with_hover_distance(0.0), signal()),
+            None
+        );
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_hover_reports_point_metadata() {
+        let view = ViewState::with_ranges((0.0, 4.0), (0.0, 4.0));
+        let hovered = |series: PlotSeries<'static>, x: f64| {
+            let plotter: Plotter<'_, (Option<usize>, Option<String>)> =
+                Plotter::new(vec![series], &view).on_point_hover(|p| (p.index, p.metadata));
+            let padding = plotter.plot_padding(DEFAULT_BOUNDS.size());
+            let width = DEFAULT_BOUNDS.width - padding.x();
+            let height = DEFAULT_BOUNDS.height - padding.y();
+            Harness::new(&plotter)
+                .move_to(Point::new(
+                    padding.left + width * (x / 4.0) as f32,
+                    padding.top + height / 2.0,
+                ))
+                .expect("the cursor is on a point")
+        };
+        let points = || PlotPoints::owned((0..4).map(|i| (f64::from(i), 2.0).into()).collect());
+        let runs: Vec<String> = ["run-a", "run-b", "run-c"].map(String::from).into();
+
+        let scatter = || PlotSeries::new("runs", points()).with_metadata(runs.clone());
+        assert_eq!(hovered(scatter(), 1.0), (Some(1), Some("run-b".into())));
+        // Points past the end of the metadata have none
+        assert_eq!(hovered(scatter(), 3.0), (Some(3), None));
+        assert_eq!(
+            hovered(PlotSeries::new("runs", points()), 1.0),
+            (Some(1), None)
+        );
+        // Generated points have no index to look it up by
+        let generated = PlotSeries::new("flat", PlotPoints::generator(|_| 2.0, (0.0, 4.0), 5))
+            .with_metadata(runs.clone());
+        assert_eq!(hovered(generated, 1.0), (None, None));
+    }
+
+    #[test]
+    fn test_primitive_geometry_at_image_size() {
+        // What the wgpu pipelines draw for `render_to_image(210, 100)`
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let red = iced::Color::from_rgb(1.0, 0.0, 0.0);
+        let style = SeriesStyle::new(ColorMode::solid(red))
+            .with_line_width(6.0)
+            .with_marker_size(8.0);
+        let points = vec![(0.0, 5.0).into(), (10.0, 5.0).into()];
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points)).with_style(style)],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            padding: 0.0,
+            ..PlotterOptions::default()
+        });
+        let mut harness = Harness::new(&plotter);
+        harness.bounds = Rectangle::with_size(iced::Size::new(210.0, 100.0));
+        let primitive = harness.primitive();
+
+        let uniforms = primitive.uniforms();
+        assert_eq!(uniforms.viewport_size, [210.0, 100.0]);
+        assert_eq!(uniforms.padding, [0.0, 0.0]);
+        assert_eq!(uniforms.plot_size, [210.0, 100.0]);
+
+        // One marker at each end, in the local frame around the origin
+        let origin = primitive.origin();
+        let markers: Vec<_> = primitive
+            .points()
+            .iter()
+            .map(|p| {
+                let [x, y] = p.position;
+                (f64::from(x) + origin[0], f64::from(y) + origin[1], p.size)
+            })
+            .collect();
+        assert_eq!(markers, [(0.0, 5.0, 8.0), (10.0, 5.0, 8.0)]);
+        assert!(
+            primitive
+                .points()
+                .iter()
+                .all(|p| p.color == [1.0, 0.0, 0.0, 1.0])
+        );
+
+        // The line is whole triangles in screen space across the plot at
+        // half height, a little wider than 6 pixels for anti-aliasing
+        let lines = primitive.line_vertices();
+        assert!(!lines.is_empty() && lines.len().is_multiple_of(3));
+        let xs = lines.iter().map(|v| v.position[0]);
+        let ys = lines.iter().map(|v| v.position[1]);
+        let (left, right) = (
+            xs.clone().fold(f32::MAX, f32::min),
+            xs.fold(f32::MIN, f32::max),
+        );
+        let (top, bottom) = (
+            ys.clone().fold(f32::MAX, f32::min),
+            ys.fold(f32::MIN, f32::max),
+        );
+        assert!((-6.0..=0.0).contains(&left));
+        assert!((210.0..216.0).contains(&right));
+        assert!((44.0..=47.0).contains(&top));
+        assert!((53.0..56.0).contains(&bottom));
+        assert!(lines.iter().all(|v| v.color == [1.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_large_coordinates_use_local_frame() {
+        // Epoch seconds with sub-second samples: f32 can't even tell the
+        // samples apart, but they must stay exact relative to the view
+        let t0 = 1.7e9 + 0.25;
+        let view = ViewState::with_ranges((t0, t0 + 1.0), (0.0, 1.0));
+        let points: Vec<PlotPoint> = (0..=8)
+            .map(|i| (t0 + f64::from(i) * 0.125, 0.5).into())
+            .collect();
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("ts", PlotPoints::owned(points))],
+            &view,
+        );
+        let primitive = Harness::new(&plotter).primitive();
+
+        assert_eq!(primitive.origin()[0], t0 + 0.5);
+        assert_eq!(primitive.uniforms().x_range, [-0.5, 0.5]);
+        let xs: Vec<f32> = primitive.points().iter().map(|p| p.position[0]).collect();
+        let expected: Vec<f32> = (0..=8).map(|i| i as f32 * 0.125 - 0.5).collect();
+        assert_eq!(xs, expected);
+    }
+
+    #[test]
+    fn test_zoom_at_large_coordinates_keeps_cursor_anchor() {
+        // Adjacent f32 values are 128 apart at 1.7e9, so a one-second view
+        // only zooms around the cursor if the view math stays in f64
+        let t0 = 1.7e9;
+        let view = ViewState::with_ranges((t0, t0 + 1.0), (0.0, 1.0));
+        let points = vec![(t0, 0.0).into(), (t0 + 1.0, 1.0).into()];
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("ts", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_interaction(InteractionConfig {
+            elastic: false,
+            ..InteractionConfig::full()
+        })
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+        let uniforms = *harness.primitive().uniforms();
+        harness.move_to(Point::new(
+            uniforms.padding[0] + uniforms.plot_size[0] / 4.0,
+            uniforms.padding[1] + uniforms.plot_size[1] / 2.0,
+        ));
+        let zoomed = harness.scroll(1.0).expect("scrolling zooms");
+
+        let (lo, hi) = zoomed.x_range.unwrap();
+        assert!(hi - lo < 1.0);
+        assert!((lo - t0) > 0.0 && (hi - t0) < 1.0);
+        let anchor = lo + (hi - lo) / 4.0;
+        assert!((anchor - (t0 + 0.25)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_decimation_keeps_every_marker() {
+        let points: Vec<PlotPoint> = (0..100_000)
+            .map(|i| (f64::from(i) / 10_000.0, (f64::from(i) * 0.1).sin()).into())
+            .collect();
+        let view = ViewState::with_ranges((0.0, 10.0), (-1.0, 1.0));
+        let draw = |style: SeriesStyle<'static>, decimate: bool| {
+            let series = PlotSeries::new("series", PlotPoints::owned(points.clone()))
+                .with_style(style)
+                .with_decimation(decimate);
+            let plotter: Plotter<'_, ()> = Plotter::new(vec![series], &view);
+            let primitive = Harness::new(&plotter).primitive();
+            (primitive.points().len(), primitive.line_vertices().len())
+        };
+        let white = || SeriesStyle::new(ColorMode::solid(iced::Color::WHITE));
+
+        // A sorted scatter series has no line to decimate
+        let scatter = white().with_line_pattern(LinePattern::None);
+        assert_eq!(draw(scatter, true), (100_000, 0));
+
+        // A line keeps its markers, but is drawn through far fewer points
+        let (markers, decimated) = draw(white(), true);
+        let (_, full) = draw(white(), false);
+        assert_eq!(markers, 100_000);
+        assert!(decimated * 10 < full);
+    }
+
+    #[test]
+    fn test_performance_mode_skips_dense_markers() {
+        let points: Vec<PlotPoint> = (0..100_000)
+            .map(|i| (f64::from(i) / 10_000.0, 5.0).into())
+            .collect();
+        let scatter = SeriesStyle::new(ColorMode::solid(iced::Color::WHITE))
+            .with_line_pattern(LinePattern::None);
+        let series = || {
+            vec![
+                PlotSeries::new("line", PlotPoints::owned(points.clone())).with_decimation(false),
+                PlotSeries::new("scatter", PlotPoints::owned(points.clone()))
+                    .with_style(scatter.clone())
+                    .with_decimation(false),
+            ]
+        };
+        let markers = |x_range: (f64, f64), performance: Option<PerformanceConfig>| {
+            let view = ViewState::with_ranges(x_range, (0.0, 10.0));
+            let plotter: Plotter<'_, ()> =
+                Plotter::new(series(), &view).with_options(PlotterOptions {
+                    performance,
+                    ..PlotterOptions::default()
+                });
+            let primitive = Harness::new(&plotter).primitive();
+            assert_eq!(
+                primitive.layers()[0].markers.len(),
+                primitive.points().len()
+            );
+            primitive.points().len()
+        };
+
+        // 100k points in a 700 x 500 plot area get 3.5 pixels each, too few
+        // for the line's markers; the scatter series keeps its own
+        assert_eq!(markers((0.0, 10.0), None), 200_000);
+        let performance = Some(PerformanceConfig::default());
+        assert_eq!(markers((0.0, 10.0), performance.clone()), 100_000);
+        // Zoomed in, the 10k visible points have room again
+        assert_eq!(markers((0.0, 1.0), performance), 200_000);
+    }
+
+    #[test]
+    fn test_line_pattern_distance_spans_segments() {
+        let view = ViewState::with_ranges((0.0, 2.0), (0.0, 1.0));
+        let points = vec![(0.0, 0.5).into(), (1.0, 0.5).into(), (2.0, 0.5).into()];
+        let style = SeriesStyle::new(ColorMode::solid(iced::Color::WHITE))
+            .with_line_pattern(LinePattern::Dashed)
+            .with_line_width(2.0);
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("dashed", PlotPoints::owned(points)).with_style(style)],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            padding: 0.0,
+            ..PlotterOptions::default()
+        });
+        let primitive = Harness::new(&plotter).primitive();
+
+        // Two segments of 400px each, measured in 2px line widths
+        let vertices = primitive.line_vertices();
+        assert!(
+            vertices
+                .iter()
+                .all(|v| v.pattern == LinePattern::Dashed.as_u32())
+        );
+        let max = vertices.iter().map(|v| v.distance).fold(0.0, f32::max);
+        assert_eq!(max, 400.0);
+    }
+
+    #[test]
+    fn test_secondary_y_range_follows_zoom() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0))
+            .with_secondary_y_range(Some((100.0, 200.0)));
+        let points = vec![(5.0, 150.0).into()];
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("rpm", PlotPoints::owned(points)).on_secondary_y()],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            extra_y_axes: vec![ExtraYAxis::new(AxisConfig::default())],
+            ..PlotterOptions::default()
+        })
+        .with_interaction(InteractionConfig {
+            elastic: false,
+            ..InteractionConfig::full()
+        })
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+
+        // 150 sits halfway up the secondary range, so at the view center
+        let [x, y] = harness.primitive().points()[0].position;
+        assert!(x.abs() < 1e-6 && y.abs() < 1e-6);
+
+        let uniforms = *harness.primitive().uniforms();
+        harness.move_to(Point::new(
+            uniforms.padding[0] + uniforms.plot_size[0] / 2.0,
+            uniforms.padding[1] + uniforms.plot_size[1] / 2.0,
+        ));
+        let zoomed = harness.scroll(1.0).expect("scrolling zooms");
+
+        // The secondary range scales with the primary one around the cursor
+        let (lo, hi) = zoomed.y_range.unwrap();
+        let (s_lo, s_hi) = zoomed.secondary_y_range.unwrap();
+        assert!(hi - lo < 10.0);
+        assert!(((s_hi - s_lo) / 100.0 - (hi - lo) / 10.0).abs() < 1e-4);
+        assert!((s_lo + s_hi - 300.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_step_after_inserts_corners() {
+        let view = ViewState::with_ranges((0.0, 2.0), (0.0, 1.0));
+        let points = vec![(0.0, 0.0).into(), (1.0, 1.0).into(), (2.0, 0.0).into()];
+        let style = SeriesStyle::new(ColorMode::solid(iced::Color::WHITE))
+            .with_interpolation(LineInterpolation::StepAfter);
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("counter", PlotPoints::owned(points)).with_style(style)],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            padding: 0.0,
+            ..PlotterOptions::default()
+        });
+        let primitive = Harness::new(&plotter).primitive();
+
+        // Four axis-aligned segments: 400 + 600 + 400 + 600 px in 2px widths
+        let vertices = primitive.line_vertices();
+        assert_eq!(vertices.len(), 4 * 6);
+        let max = vertices.iter().map(|v| v.distance).fold(0.0, f32::max);
+        assert_eq!(max, 1000.0);
+    }
+
+    #[test]
+    fn test_crosshair_tracks_cursor() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let cursor_state = CursorState::default();
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view)
+            .with_options(PlotterOptions {
+                crosshair: Some(CrosshairConfig::default()),
+                ..PlotterOptions::default()
+            })
+            .with_cursor_state(cursor_state.clone());
+        let mut harness = Harness::new(&plotter);
+
+        // Center of the 700x500 plot area inside the default 50px padding
+        harness.move_to(Point::new(400.0, 300.0));
+        let position = cursor_state
+            .position
+            .borrow()
+            .clone()
+            .expect("over the plot");
+        assert_eq!((position.x, position.y), (5.0, 5.0));
+
+        harness.move_to(Point::new(10.0, 10.0));
+        assert!(cursor_state.position.borrow().is_none());
+    }
+
+    #[test]
+    fn test_shared_cursor_follows_hovered_plot() {
+        let cursor = SharedCursor::default();
+        let top_view = ViewState::with_ranges((0.0, 10.0), (0.0, 1.0));
+        let bottom_view = ViewState::with_ranges((0.0, 10.0), (-500.0, 500.0));
+        let top: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &top_view).with_shared_cursor(cursor.clone());
+        let bottom: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &bottom_view).with_shared_cursor(cursor.clone());
+        let mut top = Harness::new(&top);
+        let mut bottom = Harness::new(&bottom);
+
+        top.move_to(Point::new(400.0, 300.0));
+        assert_eq!(*cursor.x.borrow(), Some(5.0));
+
+        // Moving onto the other plot: leaving the first must not clear it
+        bottom.move_to(Point::new(225.0, 300.0));
+        top.move_to(Point::new(10.0, 10.0));
+        assert_eq!(*cursor.x.borrow(), Some(2.5));
+
+        bottom.move_to(Point::new(10.0, 10.0));
+        assert_eq!(*cursor.x.borrow(), None);
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_point_click_ignores_drags() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let points: Vec<PlotPoint> = (0..=10)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let runs: Vec<String> = (0..=10).map(|i| format!("run-{i}")).collect();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points)).with_metadata(runs)],
+            &view,
+        )
+        .with_interaction(InteractionConfig::full())
+        .on_point_click(|point| point);
+        let mut harness = Harness::new(&plotter);
+
+        // (5, 5) sits at the center of the plot area
+        let center = Point::new(400.0, 300.0);
+        harness.move_to(center);
+        harness.press();
+        let clicked = harness.release().expect("clicking a point publishes it");
+        assert_eq!((clicked.series_index, clicked.x, clicked.y), (0, 5.0, 5.0));
+        assert_eq!(clicked.index, Some(5));
+        assert_eq!(clicked.metadata.as_deref(), Some("run-5"));
+
+        let dragged = harness.drag(center, Point::new(420.0, 300.0));
+        assert!(dragged.is_empty());
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_click_pins_tooltips() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let points: Vec<PlotPoint> = (0..=10)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let tooltips = TooltipState::default();
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            tooltip: Some(TooltipConfig {
+                max_pinned: 2,
+                ..TooltipConfig::default()
+            }),
+            ..PlotterOptions::default()
+        })
+        .with_tooltip_state(tooltips.clone());
+        let mut harness = Harness::new(&plotter);
+        let pinned = || -> Vec<(f64, f64)> {
+            let pinned = tooltips.pinned.borrow();
+            pinned.iter().map(|p| (p.point.x, p.point.y)).collect()
+        };
+
+        // 70px per unit across, 50px per unit down from (5, 5) at the center;
+        // the oldest pin goes when a third is added
+        for (x, y) in [
+            (400.0, 300.0),
+            (470.0, 250.0),
+            (400.0, 300.0),
+            (540.0, 200.0),
+        ] {
+            harness.move_to(Point::new(x, y));
+            harness.press();
+            harness.release();
+        }
+        assert_eq!(pinned(), [(6.0, 6.0), (7.0, 7.0)]);
+        let (x, y) = tooltips.pinned.borrow()[0].anchor;
+        assert!((x - 6.0).abs() < 1e-6 && (y - 6.0).abs() < 1e-6);
+
+        // Clicking a close button (as laid out by the overlay) unpins
+        let close = Rectangle::new(Point::new(100.0, 100.0), iced::Size::new(16.0, 16.0));
+        *tooltips.close_buttons.borrow_mut() = vec![(1, close)];
+        harness.move_to(Point::new(108.0, 108.0));
+        harness.press();
+        harness.release();
+        assert_eq!(pinned(), [(6.0, 6.0)]);
+    }
+
+    #[test]
+    fn test_report_only_selection_keeps_view() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter = Plotter::new(Vec::new(), &view)
+            .with_interaction(InteractionConfig {
+                selection_mode: SelectionMode::ReportOnly,
+                ..InteractionConfig::none()
+            })
+            .with_active_tool(ActiveTool::ZoomSelect)
+            .on_view_change(|_| unreachable!("the view must not change"))
+            .on_select(|x, y| (x, y));
+        let mut harness = Harness::new(&plotter);
+
+        // 70px per unit across, 50px per unit down, from the plot center
+        let messages = harness.drag(Point::new(400.0, 300.0), Point::new(575.0, 175.0));
+        assert_eq!(messages, [((5.0, 7.5), (5.0, 7.5))]);
+    }
+
+    #[test]
+    fn test_roi_draw_move_resize_delete() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let rois = RoiState::default();
+        let plotter = Plotter::new(Vec::new(), &view)
+            .with_interaction(InteractionConfig::none())
+            .with_active_tool(ActiveTool::Roi(RoiKind::Rect))
+            .with_roi_state(rois.clone())
+            .on_roi_change(|change| change);
+        let mut harness = Harness::new(&plotter);
+        let ranges = || {
+            let roi = rois.get(0).expect("the ROI is stored");
+            let round = |(lo, hi): (f64, f64)| ((lo * 1e6).round() / 1e6, (hi * 1e6).round() / 1e6);
+            (round(roi.x_range), roi.y_range.map(round))
+        };
+
+        // 70px per unit across, 50px per unit down, from (5, 5) at the center
+        let drawn = harness.drag(Point::new(400.0, 300.0), Point::new(540.0, 200.0));
+        assert_eq!(drawn, [RoiChange::Added(0)]);
+        assert_eq!(ranges(), ((5.0, 7.0), Some((5.0, 7.0))));
+
+        // Dragging the inside moves it, dragging an edge resizes it
+        let moved = harness.drag(Point::new(470.0, 250.0), Point::new(400.0, 250.0));
+        assert_eq!(moved, [RoiChange::Changed(0)]);
+        assert_eq!(ranges(), ((4.0, 6.0), Some((5.0, 7.0))));
+        let resized = harness.drag(Point::new(471.0, 250.0), Point::new(611.0, 250.0));
+        assert_eq!(resized, [RoiChange::Changed(0)]);
+        assert_eq!(ranges(), ((4.0, 8.0), Some((5.0, 7.0))));
+
+        // A click elsewhere deselects without drawing, a click on it selects
+        assert!(
+            harness
+                .drag(Point::new(100.0, 100.0), Point::new(100.0, 100.0))
+                .is_empty()
+        );
+        assert_eq!(
+            (rois.rois.borrow().len(), *rois.selected.borrow()),
+            (1, None)
+        );
+        assert!(
+            harness
+                .drag(Point::new(450.0, 250.0), Point::new(450.0, 250.0))
+                .is_empty()
+        );
+        assert_eq!(*rois.selected.borrow(), Some(0));
+
+        let removed = harness.key_press(keyboard::Key::Named(keyboard::key::Named::Delete));
+        assert!(matches!(removed, Some(RoiChange::Removed(0, _))));
+        assert!(rois.rois.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_drag_editable_point() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let points = || {
+            let points = vec![(0.0, 0.0).into(), (5.0, 5.0).into(), (10.0, 10.0).into()];
+            PlotPoints::owned(points)
+        };
+        let drag = |point_drag| {
+            let series = vec![
+                PlotSeries::new("fixed", points()),
+                PlotSeries::new("envelope", points()).with_editable(true),
+            ];
+            let plotter = Plotter::new(series, &view)
+                .with_interaction(InteractionConfig {
+                    point_drag,
+                    ..InteractionConfig::none()
+                })
+                .with_active_tool(ActiveTool::EditPoints)
+                .on_point_moved(|series, index, x, y| (series, index, x, y));
+            // Grab (5, 5) at the center, 70px per unit across, 50px per unit down
+            Harness::new(&plotter).drag(Point::new(403.0, 300.0), Point::new(438.0, 270.0))
+        };
+        let snap = PointDragConfig {
+            snap_x: Some(0.5),
+            snap_y: Some(0.5),
+            ..PointDragConfig::default()
+        };
+        assert_eq!(drag(snap), [(1, 1, 5.5, 5.5)]);
+        let locked = PointDragConfig {
+            lock_x: true,
+            ..snap
+        };
+        assert_eq!(drag(locked), [(1, 1, 5.0, 5.5)]);
+    }
+
+    #[test]
+    fn test_drag_cursor_line() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter = Plotter::new(Vec::new(), &view)
+            .with_interaction(InteractionConfig {
+                pan_x: true,
+                ..InteractionConfig::none()
+            })
+            .with_cursor_line(CursorLine::vertical(2.0))
+            .with_cursor_line(CursorLine::vertical(5.0))
+            .with_cursor_line(CursorLine::horizontal(1.0))
+            .on_view_change(|_| unreachable!("grabbing a line must not pan"))
+            .on_cursor_line_move(|index, value| (index, value));
+        assert_eq!(CursorLine::deltas(&plotter.cursor_lines), [Some(3.0), None]);
+
+        // The line at x = 5 sits at the center, 70px per unit across; the
+        // grab offset is kept
+        let moved = Harness::new(&plotter).drag(Point::new(402.0, 300.0), Point::new(472.0, 300.0));
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].0, 1);
+        assert!((moved[0].1 - 6.0).abs() < 1e-6, "{moved:?}");
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_measure_snaps_to_points() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let points: Vec<PlotPoint> = (0..=10)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let measure = MeasureState::default();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_interaction(InteractionConfig::none())
+        .with_active_tool(ActiveTool::Measure)
+        .with_measure_state(measure.clone())
+        .on_measure(|measurement| measurement);
+        let mut harness = Harness::new(&plotter);
+
+        // Near (5, 5) and (7, 7), 70px per unit across, 50px per unit down
+        harness.move_to(Point::new(403.0, 302.0));
+        assert!(harness.press().is_none());
+        assert_eq!(*measure.pending.borrow(), Some((5.0, 5.0)));
+        harness.release();
+        harness.move_to(Point::new(538.0, 203.0));
+        let measurement = harness.press().expect("the second click measures");
+        assert_eq!(
+            measurement,
+            Measurement {
+                from: (5.0, 5.0),
+                to: (7.0, 7.0)
+            }
+        );
+        assert_eq!((measurement.dx(), measurement.slope()), (2.0, 1.0));
+        assert_eq!(measurement.distance(), 8.0_f64.sqrt());
+
+        harness.key_press(keyboard::Key::Named(keyboard::key::Named::Escape));
+        assert!(measure.measurement.borrow().is_none());
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_hover_reuses_line_geometry() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let points: Vec<PlotPoint> = (0..=10)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            tooltip: Some(TooltipConfig::default()),
+            ..PlotterOptions::default()
+        });
+        let mut harness = Harness::new(&plotter);
+        let before = harness.primitive();
+        assert!(before.highlight_vertices().is_empty());
+
+        // Only the highlight ring changes, so the line triangles are shared
+        harness.move_to(Point::new(400.0, 300.0));
+        let after = harness.primitive();
+        assert!(!after.highlight_vertices().is_empty());
+        assert_eq!(
+            before.line_vertices().as_ptr(),
+            after.line_vertices().as_ptr()
+        );
+    }
+
+    #[cfg(feature = "annotations")]
+    #[test]
+    fn test_annotations_clip_to_plot_area() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let series = || {
+            let points = vec![(0.0, 0.0).into(), (10.0, 10.0).into()];
+            vec![PlotSeries::new("line", PlotPoints::owned(points))]
+        };
+        let grid_vertices = |annotations: Vec<Annotation>| {
+            let plotter: Plotter<'_, ()> =
+                Plotter::new(series(), &view).with_options(PlotterOptions {
+                    annotations,
+                    ..PlotterOptions::default()
+                });
+            let primitive = Harness::new(&plotter).primitive();
+            let uniforms = *primitive.uniforms();
+            (primitive.grid_vertices().to_vec(), uniforms)
+        };
+        let (base, _) = grid_vertices(Vec::new());
+        let (line, _) = grid_vertices(vec![Annotation::hline(5.0)]);
+        assert!(line.len() > base.len());
+
+        // Out-of-view lines and arrows add nothing, text is left to the overlay
+        let (hidden, _) = grid_vertices(vec![
+            Annotation::hline(5.0),
+            Annotation::vline(20.0),
+            Annotation::arrow((20.0, 0.0), (30.0, 10.0)),
+            Annotation::text(5.0, 5.0, "peak"),
+        ]);
+        assert_eq!(hidden.len(), line.len());
+
+        // A band reaching past the view is cut at the plot edge
+        let (band, uniforms) = grid_vertices(vec![
+            Annotation::hband(5.0, 50.0).with_color(iced::Color::from_rgba(1.0, 0.0, 0.0, 0.2)),
+        ]);
+        assert_eq!(band.len(), base.len() + 6);
+        let fill: Vec<_> = band
+            .iter()
+            .filter(|v| v.color == [1.0, 0.0, 0.0, 0.2])
+            .collect();
+        assert_eq!(fill.len(), 6);
+        let top = uniforms.padding[1];
+        assert!(fill.iter().all(|v| v.position[1] >= top));
+        assert!(fill.iter().any(|v| v.position[1] == top));
+    }
+
+    #[cfg(feature = "legend")]
+    #[test]
+    fn test_legend_toggle_publishes_visibility() {
+        let view = ViewState::with_ranges((0.0, 1.0), (0.0, 1.0));
+        let points = vec![(0.0, 0.0).into(), (1.0, 1.0).into()];
+        let legend_state = LegendState::default();
+        // The overlay fills this in when it draws the legend
+        legend_state
+            .layout
+            .borrow_mut()
+            .toggles
+            .push(LegendToggleRect {
+                series_index: 0,
+                rect: Rectangle::new(Point::new(10.0, 10.0), iced::Size::new(12.0, 12.0)),
+                entry: Rectangle::new(Point::new(10.0, 10.0), iced::Size::new(60.0, 16.0)),
+            });
+        let plotter: Plotter<'_, (usize, bool)> = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            legend: Some(LegendConfig::default()),
+            ..PlotterOptions::default()
+        })
+        .with_legend_state(legend_state.clone())
+        .on_legend_toggle(|index, visible| (index, visible));
+        let mut harness = Harness::new(&plotter);
+
+        harness.move_to(Point::new(15.0, 15.0));
+        assert_eq!(harness.press(), Some((0, false)));
+        assert!(harness.primitive().points().is_empty());
+        assert_eq!(harness.press(), Some((0, true)));
+        assert!(legend_state.hidden_series.borrow().is_empty());
+    }
+
+    #[cfg(feature = "legend")]
+    #[test]
+    fn test_legend_hover_emphasizes_series() {
+        let view = ViewState::with_ranges((0.0, 1.0), (0.0, 1.0));
+        let series = |label| {
+            let points = vec![(0.0, 0.0).into(), (1.0, 1.0).into()];
+            PlotSeries::new(label, PlotPoints::owned(points))
+        };
+        let legend_state = LegendState::default();
+        // The overlay fills this in when it draws the legend
+        for i in 0..2 {
+            let y = 10.0 + 16.0 * i as f32;
+            legend_state
+                .layout
+                .borrow_mut()
+                .toggles
+                .push(LegendToggleRect {
+                    series_index: i,
+                    rect: Rectangle::new(Point::new(10.0, y), iced::Size::new(12.0, 12.0)),
+                    entry: Rectangle::new(Point::new(10.0, y), iced::Size::new(60.0, 16.0)),
+                });
+        }
+        let plotter: Plotter<'_, ()> = Plotter::new(vec![series("a"), series("b")], &view)
+            .with_options(PlotterOptions {
+                legend: Some(LegendConfig::default()),
+                ..PlotterOptions::default()
+            })
+            .with_legend_state(legend_state.clone());
+        let mut harness = Harness::new(&plotter);
+        let base = harness.primitive().points()[2].size;
+
+        // Over the second entry's label: the first series fades, the second widens
+        harness.move_to(Point::new(50.0, 34.0));
+        assert_eq!(*legend_state.hovered.borrow(), Some(1));
+        let primitive = harness.primitive();
+        assert!(primitive.points()[..2].iter().all(|p| p.color[3] == 0.25));
+        assert!(primitive.points()[2..].iter().all(|p| p.color[3] == 1.0));
+        assert_eq!(primitive.points()[2].size, base * 1.5);
+
+        harness.move_to(Point::new(400.0, 300.0));
+        assert_eq!(*legend_state.hovered.borrow(), None);
+        assert!(
+            harness
+                .primitive()
+                .points()
+                .iter()
+                .all(|p| p.color[3] == 1.0)
+        );
+    }
+
+    #[cfg(feature = "legend")]
+    #[test]
+    fn test_legend_drag_stores_offset() {
+        let view = ViewState::with_ranges((0.0, 1.0), (0.0, 1.0));
+        let points = vec![(0.0, 0.0).into(), (1.0, 1.0).into()];
+        let legend_state = LegendState::default();
+        legend_state.layout.borrow_mut().bounds = Some(Rectangle::new(
+            Point::new(10.0, 10.0),
+            iced::Size::new(80.0, 40.0),
+        ));
+        let plotter: Plotter<'_, ViewState> = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            legend: Some(LegendConfig::default()),
+            ..PlotterOptions::default()
+        })
+        .with_legend_state(legend_state.clone())
+        .with_interaction(InteractionConfig::full())
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+
+        // Grabbed 10px into the box; the view doesn't pan
+        let messages = harness.drag(Point::new(20.0, 20.0), Point::new(220.0, 120.0));
+        assert!(messages.is_empty());
+        let padding = plotter.plot_padding(DEFAULT_BOUNDS.size());
+        assert_eq!(
+            *legend_state.offset.borrow(),
+            Some(Point::new(210.0 - padding.left, 110.0 - padding.top))
+        );
+
+        // Kept inside the plot area
+        legend_state.layout.borrow_mut().bounds = Some(Rectangle::new(
+            Point::new(210.0, 110.0),
+            iced::Size::new(80.0, 40.0),
+        ));
+        harness.drag(Point::new(220.0, 120.0), Point::new(-100.0, 900.0));
+        let plot_height = DEFAULT_BOUNDS.height - padding.y();
+        assert_eq!(
+            *legend_state.offset.borrow(),
+            Some(Point::new(0.0, plot_height - 40.0))
+        );
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_hover_finds_points_in_unsorted_series() {
+        let view = ViewState::with_ranges((0.0, 1000.0), (0.0, 1000.0));
+        // Every tenth X in 0..1000 once, shuffled
+        let points: Vec<PlotPoint> = (0..100)
+            .map(|i| {
+                let x = f64::from(i * 37 % 100 * 10);
+                (x, x).into()
+            })
+            .collect();
+        let plotter: Plotter<'_, f64> = Plotter::new(
+            vec![PlotSeries::new("scatter", PlotPoints::owned(points))],
+            &view,
+        )
+        .on_point_hover(|point| point.x);
+        let mut harness = Harness::new(&plotter);
+        let padding = plotter.plot_padding(DEFAULT_BOUNDS.size());
+        let plot_width = DEFAULT_BOUNDS.width - padding.x();
+        let plot_height = DEFAULT_BOUNDS.height - padding.y();
+
+        for x in [0.0, 430.0, 990.0] {
+            let target = Point::new(
+                padding.left + (x / 1000.0) as f32 * plot_width + 1.0,
+                padding.top + (1.0 - x / 1000.0) as f32 * plot_height,
+            );
+            assert_eq!(harness.move_to(target), Some(x));
+        }
+    }
+
+    #[cfg(feature = "tooltip")]
+    #[test]
+    fn test_hover_in_dense_series() {
+        let view = ViewState::with_ranges((0.0, 1000.0), (0.0, 1000.0));
+        // A hundred points per X unit along the bottom, and one peak
+        let points: Vec<PlotPoint> = (0..100_000)
+            .map(|i| {
+                let x = f64::from(i) / 100.0;
+                let y = if i == 43_000 { 900.0 } else { f64::from(i % 7) };
+                (x, y).into()
+            })
+            .collect();
+        let plotter: Plotter<'_, (f64, f64)> = Plotter::new(
+            vec![PlotSeries::new("dense", PlotPoints::owned(points))],
+            &view,
+        )
+        .on_point_hover(|point| (point.x, point.y));
+        let mut harness = Harness::new(&plotter);
+        let padding = plotter.plot_padding(DEFAULT_BOUNDS.size());
+        let plot_width = DEFAULT_BOUNDS.width - padding.x();
+        let plot_height = DEFAULT_BOUNDS.height - padding.y();
+        let to_screen = |x: f32, y: f32| {
+            Point::new(
+                padding.left + x / 1000.0 * plot_width,
+                padding.top + (1.0 - y / 1000.0) * plot_height,
+            )
+        };
+
+        assert_eq!(
+            harness.move_to(to_screen(430.0, 895.0)),
+            Some((430.0, 900.0))
+        );
+        let (x, y) = harness
+            .move_to(to_screen(700.0, 0.0))
+            .expect("a point under the cursor");
+        assert!((x - 700.0).abs() < 5.0 && y < 7.0);
+        assert_eq!(harness.move_to(to_screen(500.0, 500.0)), None);
+    }
+
+    #[test]
+    fn test_fling_keeps_panning_then_stops() {
+        let view = ViewState::with_ranges((40.0, 50.0), (0.0, 100.0));
+        let clock = ManualClock::new();
+        let points: Vec<PlotPoint> = (0..=100)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_interaction(InteractionConfig {
+            inertia: true,
+            ..InteractionConfig::default()
+        })
+        .with_clock(clock.source())
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+        let x_min = |view: Option<ViewState>| view.and_then(|v| v.x_range).unwrap().0;
+
+        // Drag left quickly and let go while still moving
+        harness.move_to(Point::new(400.0, 300.0));
+        harness.press();
+        let mut released_at = 0.0;
+        for x in [350.0, 300.0, 250.0] {
+            clock.advance(Duration::from_millis(10));
+            released_at = x_min(harness.move_to(Point::new(x, 300.0)));
+        }
+        harness.release();
+
+        // Frames keep moving the view the same way, more slowly each time
+        let redraw = || Event::Window(iced::window::Event::RedrawRequested(clock.now()));
+        let mut last = released_at;
+        let mut last_step = f64::INFINITY;
+        for _ in 0..5 {
+            clock.advance(Duration::from_millis(16));
+            let x = x_min(harness.event(redraw()));
+            assert!(x > last && x - last < last_step);
+            (last_step, last) = (x - last, x);
+        }
+
+        // Until it settles
+        clock.advance(Duration::from_secs(5));
+        harness.event(redraw());
+        assert!(harness.state.inertia_animation.is_none());
+
+        // A slow release doesn't fling
+        harness.move_to(Point::new(400.0, 300.0));
+        harness.press();
+        clock.advance(Duration::from_millis(10));
+        harness.move_to(Point::new(300.0, 300.0));
+        clock.advance(Duration::from_millis(200));
+        harness.release();
+        assert!(harness.state.inertia_animation.is_none());
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        use keyboard::key::Named;
+
+        let view = ViewState::with_ranges((40.0, 50.0), (0.0, 100.0));
+        let points: Vec<PlotPoint> = (0..=100)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_interaction(InteractionConfig {
+            keyboard: true,
+            ..InteractionConfig::default()
+        })
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+        let x_range = |view: Option<ViewState>| {
+            let (lo, hi) = view.and_then(|v| v.x_range).unwrap();
+            // Steps are f32 fractions
+            [lo, hi].map(|v| (v * 1e4).round() / 1e4)
+        };
+
+        // Keys only reach the plot under the cursor
+        assert!(harness.key_press(Named::ArrowRight.into()).is_none());
+        harness.move_to(Point::new(400.0, 300.0));
+
+        assert_eq!(
+            x_range(harness.key_press(Named::ArrowRight.into())),
+            [41.0, 51.0]
+        );
+        assert_eq!(
+            x_range(harness.key_press(Named::PageUp.into())),
+            [30.0, 40.0]
+        );
+        assert_eq!(
+            x_range(harness.key_press(keyboard::Key::Character("+".into()))),
+            [41.0, 49.0]
+        );
+        // Y isn't pannable by default
+        assert!(harness.key_press(Named::ArrowUp.into()).is_none());
+        assert_eq!(
+            harness.key_press(Named::Home.into()).map(|v| v.x_range),
+            Some(None)
+        );
+    }
+
+    #[test]
+    fn test_view_changes_throttled_until_final() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let clock = ManualClock::new();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new(
+                "line",
+                PlotPoints::owned(vec![(0.0, 0.0).into(), (10.0, 10.0).into()]),
+            )],
+            &view,
+        )
+        .with_interaction(InteractionConfig {
+            elastic: false,
+            view_change_rate: Some(10.0),
+            ..InteractionConfig::full()
+        })
+        .with_clock(clock.source())
+        .on_view_change_with_kind(|view, kind| (view.x_range.unwrap().0.round(), kind));
+        let mut harness = Harness::new(&plotter);
+
+        // 70px per unit across: the first step is published, the next
+        // within 100ms held back until the release
+        harness.move_to(Point::new(400.0, 300.0));
+        harness.press();
+        let first = harness.move_to(Point::new(330.0, 300.0));
+        assert_eq!(first, Some((1.0, ViewChangeKind::Interactive)));
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(harness.move_to(Point::new(260.0, 300.0)), None);
+        assert_eq!(harness.release(), Some((2.0, ViewChangeKind::Final)));
+
+        // One-off changes are final right away
+        clock.advance(Duration::from_secs(1));
+        let (_, kind) = harness.scroll(1.0).expect("scrolling zooms");
+        assert_eq!(kind, ViewChangeKind::Final);
+    }
 
-                            let mut new_view = self.view_state.clone();
+    #[test]
+    fn test_axis_strips_move_one_axis() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let clock = ManualClock::new();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new(
+                "line",
+                PlotPoints::owned(vec![(0.0, 0.0).into(), (10.0, 10.0).into()]),
+            )],
+            &view,
+        )
+        .with_interaction(InteractionConfig {
+            elastic: false,
+            axis_drag: true,
+            axis_scroll: true,
+            ..InteractionConfig::full()
+        })
+        .with_clock(clock.source())
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+        let ranges = |view: Vec<ViewState>| {
+            let view = view.last().cloned().unwrap();
+            (view.x_range.unwrap(), view.y_range.unwrap())
+        };
 
-                            if interaction.pan_x {
-                                let raw = (start_view_x.0 + dx_data, start_view_x.1 + dx_data);
-                                let new_x = if interaction.elastic {
-                                    apply_elastic_resistance(
-                                        raw,
-                                        effective_x_bounds,
-                                        interaction.boundary_padding,
-                                        interaction.elastic_limit,
-                                    )
-                                } else {
-                                    clamp_range_to_bounds(
-                                        raw,
-                                        effective_x_bounds,
-                                        interaction.boundary_padding,
-                                    )
-                                };
-                                new_view.x_range = Some(new_x);
-                            }
+        // Dragging diagonally from the X labels only pans X
+        let (x, y) = ranges(harness.drag(Point::new(400.0, 595.0), Point::new(300.0, 500.0)));
+        assert!(x.0 > 0.0);
+        assert_eq!(y, (0.0, 10.0));
+
+        // And from the Y labels only Y
+        clock.advance(Duration::from_secs(1));
+        let (x, y) = ranges(harness.drag(Point::new(5.0, 300.0), Point::new(100.0, 350.0)));
+        assert_eq!(x, (0.0, 10.0));
+        assert!(y.0 > 0.0);
+
+        // Scrolling over the Y labels zooms Y alone
+        harness.move_to(Point::new(5.0, 300.0));
+        let (x, y) = ranges(harness.scroll(1.0).into_iter().collect());
+        assert_eq!(x, (0.0, 10.0));
+        assert!(y.1 - y.0 < 10.0);
+    }
 
-                            if interaction.pan_y {
-                                let raw = (start_view_y.0 + dy_data, start_view_y.1 + dy_data);
-                                let new_y = if interaction.elastic {
-                                    apply_elastic_resistance(
-                                        raw,
-                                        effective_y_bounds,
-                                        interaction.boundary_padding,
-                                        interaction.elastic_limit,
-                                    )
-                                } else {
-                                    clamp_range_to_bounds(
-                                        raw,
-                                        effective_y_bounds,
-                                        interaction.boundary_padding,
-                                    )
-                                };
-                                new_view.y_range = Some(new_y);
-                            }
+    #[test]
+    fn test_default_scroll_over_axis_strip_zooms_x() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter = Plotter::new(
+            vec![PlotSeries::new(
+                "line",
+                PlotPoints::owned(vec![(0.0, 0.0).into(), (10.0, 10.0).into()]),
+            )],
+            &view,
+        )
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+
+        // Without axis scrolling the Y labels are part of the plot, which
+        // zooms X only by default
+        harness.move_to(Point::new(5.0, 300.0));
+        let view = harness.scroll(1.0).expect("scrolling zooms");
+        let (lo, hi) = view.x_range.unwrap();
+        assert!(hi - lo < 10.0);
+        assert_eq!(view.y_range, Some((0.0, 10.0)));
+    }
 
-                            if let Some(ref on_change) = self.on_view_change {
-                                return Some(
-                                    shader::Action::publish((on_change)(new_view)).and_capture(),
-                                );
-                            }
-                            return Some(shader::Action::capture());
-                        }
-                        None
-                    }
-                    InteractionMode::ZoomSelecting => {
-                        // Update the current selection corner
-                        let relative = Point::new(position.x - bounds.x, position.y - bounds.y);
-                        state.zoom_select_current = Some(relative);
-                        // Request redraw to update the selection rectangle
-                        Some(shader::Action::request_redraw().and_capture())
-                    }
-                    InteractionMode::Idle => {
-                        // ---- Tooltip: nearest-point detection ----
-                        if let Some(ref tooltip_config) = self.options.tooltip {
-                            let cursor_pos = cursor.position_in(bounds);
-                            if let Some(cursor_pos) = cursor_pos {
-                                // Skip tooltip when cursor is over the legend
-                                if self.options.legend.is_some() {
-                                    let layout = self.legend_state.layout.borrow();
-                                    if let Some(legend_bounds) = layout.bounds {
-                                        if legend_bounds.contains(cursor_pos) {
-                                            if self.tooltip_state.hovered.borrow().is_some() {
-                                                *self.tooltip_state.hovered.borrow_mut() = None;
-                                                return Some(shader::Action::request_redraw());
-                                            }
-                                            return None;
-                                        }
-                                    }
-                                }
+    #[test]
+    fn test_wheel_bindings_by_modifier() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let plotter = Plotter::new(
+            vec![PlotSeries::new(
+                "line",
+                PlotPoints::owned(vec![(0.0, 0.0).into(), (100.0, 100.0).into()]),
+            )],
+            &view,
+        )
+        .with_interaction(InteractionConfig {
+            wheel_bindings: WheelBindings {
+                plain: WheelAction::ZoomX,
+                shift: WheelAction::ZoomY,
+                alt: WheelAction::PanX,
+                ..WheelBindings::default()
+            },
+            ..InteractionConfig::full()
+        })
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+        harness.move_to(Point::new(400.0, 300.0));
+        let mut scroll = |modifiers, lines| {
+            harness.set_modifiers(modifiers);
+            let view = harness.scroll(lines).expect("scroll publishes a view");
+            (view.x_range.unwrap(), view.y_range.unwrap())
+        };
 
-                                // Check cursor is within the plot area (inside padding)
-                                let in_plot = cursor_pos.x >= padding
-                                    && cursor_pos.x <= bounds.width - padding
-                                    && cursor_pos.y >= padding
-                                    && cursor_pos.y <= bounds.height - padding;
-
-                                if in_plot {
-                                    let max_dist_sq =
-                                        tooltip_config.max_distance * tooltip_config.max_distance;
-                                    let mut best_dist_sq = max_dist_sq;
-                                    let mut best: Option<HoveredPoint> = None;
-
-                                    let hidden = self.legend_state.hidden_series.borrow();
-                                    for (series_idx, series) in self.series.iter().enumerate() {
-                                        if hidden.contains(&series_idx) {
-                                            continue;
-                                        }
-                                        let iter: Box<dyn Iterator<Item = (f32, f32)> + '_> =
-                                            match &series.points {
-                                                PlotPoints::Owned(pts) => {
-                                                    Box::new(pts.iter().map(|p| (p.x, p.y)))
-                                                }
-                                                PlotPoints::Borrowed(pts) => {
-                                                    Box::new(pts.iter().map(|p| (p.x, p.y)))
-                                                }
-                                                PlotPoints::Generator(generator) => {
-                                                    let (x0, x1) = generator.x_range;
-                                                    let span = x1 - x0;
-                                                    let n = generator.points;
-                                                    Box::new((0..n).map(move |i| {
-                                                        let t =
-                                                            i as f32 / (n - 1).max(1) as f32;
-                                                        let x = x0 + t * span;
-                                                        let y = (generator.function)(x);
-                                                        (x, y)
-                                                    }))
-                                                }
-                                            };
-
-                                        for (dx, dy) in iter {
-                                            let screen = data_to_screen(
-                                                dx, dy, bounds, view_x, view_y, padding,
-                                            );
-                                            let ddx = screen.x - cursor_pos.x;
-                                            let ddy = screen.y - cursor_pos.y;
-                                            let dist_sq = ddx * ddx + ddy * ddy;
-                                            if dist_sq < best_dist_sq {
-                                                best_dist_sq = dist_sq;
-                                                best = Some(HoveredPoint {
-                                                    series_index: series_idx,
-                                                    series_label: series.label.clone(),
-                                                    x: dx,
-                                                    y: dy,
-                                                    screen_pos: screen,
-                                                });
-                                            }
-                                        }
-                                    }
+        let (x, y) = scroll(keyboard::Modifiers::empty(), 1.0);
+        assert!(x.1 - x.0 < 10.0);
+        assert_eq!(y, (0.0, 10.0));
 
-                                    let prev = self.tooltip_state.hovered.borrow().is_some();
-                                    *self.tooltip_state.hovered.borrow_mut() = best;
-                                    let now = self.tooltip_state.hovered.borrow().is_some();
+        let (x, y) = scroll(keyboard::Modifiers::SHIFT, 1.0);
+        assert_eq!(x, (0.0, 10.0));
+        assert!(y.1 - y.0 < 10.0);
 
-                                    // Request redraw if tooltip state changed
-                                    if prev || now {
-                                        return Some(shader::Action::request_redraw());
-                                    }
-                                } else {
-                                    // Cursor outside plot area, clear tooltip
-                                    if self.tooltip_state.hovered.borrow().is_some() {
-                                        *self.tooltip_state.hovered.borrow_mut() = None;
-                                        return Some(shader::Action::request_redraw());
-                                    }
-                                }
-                            } else {
-                                // Cursor not in widget bounds
-                                if self.tooltip_state.hovered.borrow().is_some() {
-                                    *self.tooltip_state.hovered.borrow_mut() = None;
-                                    return Some(shader::Action::request_redraw());
-                                }
-                            }
-                        }
-                        None
-                    }
-                }
-            }
+        // Scrolling down pans towards larger X
+        let (x, y) = scroll(keyboard::Modifiers::ALT, -1.0);
+        assert!(x.0 > 0.0 && (x.1 - x.0 - 10.0).abs() < 1e-9);
+        assert_eq!(y, (0.0, 10.0));
+    }
 
-            // ---- Scroll wheel (zoom) ----
-            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
-                if !interaction.zoom_x && !interaction.zoom_y {
-                    return None;
-                }
+    #[test]
+    fn test_hidden_and_faded_series() {
+        let view = ViewState::auto_fit();
+        let series = |label, y: f64| {
+            PlotSeries::new(
+                label,
+                PlotPoints::owned(vec![(0.0, y).into(), (1.0, y + 1.0).into()]),
+            )
+            .with_style(SeriesStyle::new(ColorMode::Solid(iced::Color::WHITE)))
+        };
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![
+                series("faded", 0.0).opacity(0.5),
+                series("hidden", 1000.0).hidden(true),
+            ],
+            &view,
+        );
 
-                // Only zoom if cursor is within bounds
-                let cursor_pos = cursor.position_in(bounds)?;
+        // The hidden series is left out of auto-fit and drawing
+        assert_eq!(plotter.compute_data_ranges().1, [0.0, 1.0]);
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.points().len(), 2);
+        assert!(primitive.points().iter().all(|p| p.color[3] == 0.5));
+    }
 
-                // Block scroll over legend
-                if self.options.legend.is_some() {
-                    let layout = self.legend_state.layout.borrow();
-                    if let Some(legend_bounds) = layout.bounds {
-                        if legend_bounds.contains(cursor_pos) {
-                            return Some(shader::Action::capture());
-                        }
-                    }
-                }
+    #[test]
+    fn test_hairlines_fade_instead_of_thinning() {
+        let view = ViewState::with_ranges((0.0, 1.0), (0.0, 1.0));
+        let line = |width| {
+            let style =
+                SeriesStyle::new(ColorMode::solid(iced::Color::WHITE)).with_line_width(width);
+            let plotter: Plotter<'_, ()> = Plotter::new(
+                vec![
+                    PlotSeries::new(
+                        "line",
+                        PlotPoints::owned(vec![(0.0, 0.0).into(), (1.0, 1.0).into()]),
+                    )
+                    .with_style(style),
+                ],
+                &view,
+            );
+            let vertices = Harness::new(&plotter).primitive().line_vertices().to_vec();
+            let half_span = vertices.iter().map(|v| v.edge_distance).fold(0.0, f32::max);
+            (vertices[0].color[3], half_span)
+        };
 
-                let scroll_y = match delta {
-                    mouse::ScrollDelta::Lines { y, .. } => *y,
-                    mouse::ScrollDelta::Pixels { y, .. } => *y / 50.0,
-                };
+        // The quad reaches one pixel past the edge for anti-aliasing
+        let (alpha, span) = line(2.0);
+        assert_eq!((alpha, span), (1.0, 2.0));
 
-                if scroll_y.abs() < f32::EPSILON {
-                    return None;
-                }
+        // A quarter-pixel line is drawn a pixel wide at a quarter opacity
+        let (alpha, span) = line(0.25);
+        assert_eq!((alpha, span), (0.25, (0.125 + 1.0) / 0.5));
+    }
 
-                // Cancel any elastic animation
-                state.elastic_animation = None;
+    #[test]
+    fn test_line_joins_and_caps() {
+        let view = ViewState::with_ranges((0.0, 2.0), (0.0, 2.0));
+        let vertices = |peak: f64, join, cap| {
+            let style = SeriesStyle::new(ColorMode::solid(iced::Color::WHITE))
+                .with_line_width(8.0)
+                .with_line_join(join)
+                .with_line_cap(cap);
+            let points = vec![(0.0, 0.0).into(), (1.0, peak).into(), (2.0, 0.0).into()];
+            let plotter: Plotter<'_, ()> = Plotter::new(
+                vec![PlotSeries::new("line", PlotPoints::owned(points)).with_style(style)],
+                &view,
+            );
+            Harness::new(&plotter).primitive().line_vertices().to_vec()
+        };
 
-                // Zoom factor: positive scroll = zoom in (shrink range)
-                let factor = 1.0 - scroll_y * interaction.zoom_speed;
-                let factor = factor.clamp(0.1, 10.0); // safety clamp
+        // Mitered segments share their corner vertices, with no gap to fill
+        let mitered = vertices(1.0, LineJoin::Miter, LineCap::Butt);
+        assert_eq!(mitered.len(), 2 * 6);
+        assert_eq!(mitered[2].position, mitered[6].position);
+        assert_eq!(mitered[4].position, mitered[7].position);
 
-                // Get cursor position in data space (zoom center)
-                let (cx, cy) = screen_to_data(cursor_pos, bounds, view_x, view_y, padding);
+        // Bevels add one triangle, round joins a fan
+        assert_eq!(
+            vertices(1.0, LineJoin::Bevel, LineCap::Butt).len(),
+            2 * 6 + 3
+        );
+        assert!(vertices(1.0, LineJoin::Round, LineCap::Butt).len() > 2 * 6 + 3);
 
-                let mut new_view = self.view_state.clone();
+        // A spike too sharp to miter is beveled
+        assert_eq!(
+            vertices(200.0, LineJoin::Miter, LineCap::Butt).len(),
+            2 * 6 + 3
+        );
 
-                if interaction.zoom_x {
-                    let new_lo = cx - (cx - view_x[0]) * factor;
-                    let new_hi = cx + (view_x[1] - cx) * factor;
-                    let clamped = clamp_range_to_bounds(
-                        (new_lo, new_hi),
-                        effective_x_bounds,
-                        interaction.boundary_padding,
-                    );
-                    new_view.x_range = Some(clamped);
-                }
+        // Round caps fan around both ends; square caps stretch the end quads
+        let round = vertices(1.0, LineJoin::Miter, LineCap::Round);
+        assert_eq!(round.len(), 2 * 6 + 2 * 8 * 3);
+        let square = vertices(1.0, LineJoin::Miter, LineCap::Square);
+        assert!(square[0].position[0] < mitered[0].position[0]);
+    }
 
-                if interaction.zoom_y {
-                    let new_lo = cy - (cy - view_y[0]) * factor;
-                    let new_hi = cy + (view_y[1] - cy) * factor;
-                    let clamped = clamp_range_to_bounds(
-                        (new_lo, new_hi),
-                        effective_y_bounds,
-                        interaction.boundary_padding,
-                    );
-                    new_view.y_range = Some(clamped);
-                }
+    #[test]
+    fn test_non_finite_points_break_the_line() {
+        let points = vec![
+            (0.0, 0.0).into(),
+            (1.0, 1.0).into(),
+            (2.0, f64::NAN).into(),
+            (3.0, f64::INFINITY).into(),
+            (4.0, 1.0).into(),
+            (5.0, 0.0).into(),
+        ];
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("dropout", PlotPoints::owned(points))],
+            &view,
+        );
+        assert_eq!(plotter.compute_data_ranges(), ([0.0, 5.0], [0.0, 1.0]));
+
+        // Two separate segments, and markers only for the finite points
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.points().len(), 4);
+        assert_eq!(primitive.line_vertices().len(), 2 * 6);
+        assert!(
+            primitive
+                .line_vertices()
+                .iter()
+                .all(|v| v.position.iter().all(|c| c.is_finite()))
+        );
+    }
 
-                // For axes with auto-fit that are not being zoomed,
-                // keep them as None (auto-fit)
-                if !interaction.zoom_x && self.view_state.x_range.is_none() {
-                    new_view.x_range = None;
-                }
-                if !interaction.zoom_y && self.view_state.y_range.is_none() {
-                    new_view.y_range = None;
-                }
+    #[test]
+    fn test_band_fills_between_edges() {
+        let fill = iced::Color::from_rgba(0.2, 0.4, 0.8, 0.3);
+        let band = BandSeries::from_mean_std(
+            vec![
+                (0.0, 1.0, 0.5),
+                (1.0, 2.0, 0.5),
+                (2.0, f64::NAN, 0.5),
+                (3.0, 2.0, 1.0),
+                (4.0, 1.0, 1.0),
+            ],
+            2.0,
+            fill,
+        );
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view).with_band(band.clone());
+        assert_eq!(plotter.compute_data_ranges(), ([0.0, 4.0], [-1.0, 4.0]));
+
+        // The NaN sample splits the band into two runs of one quad each
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.line_vertices().len(), 2 * 6);
+        assert!(primitive.line_vertices().iter().all(|v| v.color[3] == 0.3));
+
+        // A center line adds one butt-capped segment per run
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view)
+            .with_band(band.with_center_line(iced::Color::BLACK, 2.0));
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.line_vertices().len(), 4 * 6);
+    }
 
-                if let Some(ref on_change) = self.on_view_change {
-                    return Some(shader::Action::publish((on_change)(new_view)).and_capture());
-                }
-                Some(shader::Action::capture())
-            }
+    #[test]
+    fn test_z_order_layers() {
+        let solid = |color| SeriesStyle::new(ColorMode::Solid(color));
+        let points = || PlotPoints::owned(vec![(0.0, 1.0).into(), (4.0, 2.0).into()]);
+        let band = BandSeries::from_mean_std(
+            vec![(0.0, 1.0, 0.5), (4.0, 1.0, 0.5)],
+            1.0,
+            iced::Color::from_rgba(0.2, 0.4, 0.8, 0.3),
+        );
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![
+                PlotSeries::new("top", points())
+                    .with_style(solid(iced::Color::from_rgb(1.0, 0.0, 0.0)))
+                    .with_z_order(1),
+                PlotSeries::new("scatter", points())
+                    .with_style(solid(iced::Color::from_rgb(0.0, 1.0, 0.0)))
+                    .with_z_order(-1),
+            ],
+            &view,
+        )
+        .with_band(band);
+        let primitive = Harness::new(&plotter).primitive();
+
+        // Scatter, then the band alone in layer 0, then the top series
+        let layers = primitive.layers();
+        assert_eq!(layers.len(), 3);
+        let colors = |layer: &crate::shader::DrawLayer| -> Vec<[f32; 4]> {
+            primitive.line_vertices()[layer.lines.start as usize..layer.lines.end as usize]
+                .iter()
+                .map(|v| v.color)
+                .collect()
+        };
+        assert!(colors(&layers[0]).iter().all(|c| c[1] == 1.0));
+        assert!(colors(&layers[1]).iter().all(|c| c[3] == 0.3));
+        assert!(colors(&layers[2]).iter().all(|c| c[0] == 1.0));
+        assert_eq!(layers[0].markers, 0..2);
+        assert!(layers[1].markers.is_empty());
+        assert_eq!(layers[2].markers, 2..4);
+        assert_eq!(primitive.points()[2].color, [1.0, 0.0, 0.0, 1.0]);
+    }
 
-            _ => None,
-        }
+    #[test]
+    fn test_stacked_areas() {
+        let series = || {
+            vec![
+                PlotSeries::new(
+                    "a",
+                    PlotPoints::owned(vec![(0.0, 1.0).into(), (1.0, 3.0).into()]),
+                )
+                .with_stack("load"),
+                PlotSeries::new(
+                    "b",
+                    PlotPoints::owned(vec![(0.0, 3.0).into(), (1.0, 1.0).into()]),
+                )
+                .with_stack("load"),
+                PlotSeries::new(
+                    "c",
+                    PlotPoints::owned(vec![(0.0, 0.5).into(), (1.0, 0.5).into()]),
+                ),
+            ]
+        };
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> = Plotter::new(series(), &view);
+        assert!(plotter.validate().is_ok());
+
+        // Auto-fit covers the stacked totals, not the raw values
+        assert_eq!(plotter.compute_data_ranges(), ([0.0, 1.0], [0.0, 4.0]));
+        let stacks = stack_ranges(&plotter.series, &Default::default(), StackMode::Sum);
+        assert_eq!(stacks[1], vec![[1.0, 4.0], [3.0, 4.0]]);
+        assert!(stacks[2].is_empty());
+
+        // One fill quad and one line segment per stacked series, plus the
+        // unstacked line
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.line_vertices().len(), 2 * 6 + 3 * 6);
+        // The second series is drawn along the total, which is flat
+        let points = primitive.points();
+        assert_eq!(points[2].position[1], points[3].position[1]);
+        assert!(points[2].position[1] > points[1].position[1]);
+
+        // Normalized stacks always reach 100
+        let options = PlotterOptions {
+            stack_mode: StackMode::Percent,
+            ..Default::default()
+        };
+        let plotter: Plotter<'_, ()> = Plotter::new(series(), &view).with_options(options);
+        assert_eq!(plotter.compute_data_ranges(), ([0.0, 1.0], [0.0, 100.0]));
+
+        // Hiding a series takes it out of the stack
+        let mut hidden = series();
+        hidden[0].hidden = true;
+        let plotter: Plotter<'_, ()> = Plotter::new(hidden, &view);
+        assert_eq!(plotter.compute_data_ranges(), ([0.0, 1.0], [0.0, 3.0]));
     }
 
-    fn draw(&self, state: &Self::State, _cursor: Cursor, bounds: Rectangle) -> Self::Primitive {
-        // Enforce bounds when idle — but not during drag or elastic animation
-        // so that elastic over-scroll remains visible.
-        let enforce = state.interaction_mode == InteractionMode::Idle
-            && state.elastic_animation.is_none();
-        let (view_x, view_y, _, _) = self.resolve_view_ranges(enforce);
+    #[test]
+    fn test_box_plot() {
+        let mut samples: Vec<f64> = (1..=9).map(f64::from).collect();
+        samples.extend([30.0, f64::NAN]);
+        let summary = BoxSummary::from_samples("a", &samples).unwrap();
+        assert_eq!(
+            [
+                summary.min,
+                summary.q1,
+                summary.median,
+                summary.q3,
+                summary.max
+            ],
+            [1.0, 3.25, 5.5, 7.75, 9.0]
+        );
+        assert_eq!(summary.outliers, [30.0]);
+        assert!(BoxSummary::from_samples("empty", &[f64::NAN]).is_none());
+
+        let boxes = BoxPlotSeries::new(vec![
+            summary,
+            BoxSummary::new("b", [0.0, 1.0, 2.0, 3.0, 4.0]),
+        ]);
+        assert_eq!(boxes.labels(), ["a", "b"]);
+        let options = PlotterOptions {
+            x_axis: AxisConfig::default().with_categories(boxes.labels()),
+            ..Default::default()
+        };
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view)
+            .with_box_plot(boxes)
+            .with_options(options);
+        assert_eq!(plotter.compute_data_ranges(), ([-0.3, 1.3], [0.0, 30.0]));
+
+        // Per box: fill, four outline edges, median, two whiskers and two
+        // caps; the outlier is a marker
+        let primitive = Harness::new(&plotter).primitive();
+        assert_eq!(primitive.line_vertices().len(), 2 * (1 + 4 + 1 + 4) * 6);
+        assert_eq!(primitive.points().len(), 1);
+
+        // Category names label the whole-number ticks
+        let format = &plotter.options.x_axis.format;
+        let context = |value| crate::ticks::TickContext {
+            index: 0,
+            count: 1,
+            value,
+            range: [-0.3, 1.3],
+            step: 1.0,
+        };
+        assert_eq!(format(&context(1.0)), "b");
+        assert_eq!(format(&context(0.5)), "");
+        assert_eq!(format(&context(2.0)), "");
+    }
 
-        // Build selection rectangle from state if zoom-selecting
-        let selection_rect = if state.interaction_mode == InteractionMode::ZoomSelecting {
-            if let (Some(start), Some(current)) = (state.drag_start, state.zoom_select_current) {
-                Some((start, current))
-            } else {
-                None
-            }
-        } else {
-            None
+    #[test]
+    fn test_quiver_arrows_scale_with_magnitude() {
+        let quiver = QuiverSeries::new([(-0.5, 0.0, 1.0, 0.0), (0.5, 1.0, 0.0, 0.5)]);
+        let view = ViewState::default().zoom_to((-1.0, 1.0), (-1.0, 2.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view).with_quiver(quiver.clone());
+        let primitive = Harness::new(&plotter).primitive();
+        let uniforms = primitive.uniforms();
+        let [left, top] = uniforms.padding;
+        let [width, height] = uniforms.plot_size;
+        let ([x0, x1], [y0, y1]) = (uniforms.x_range, uniforms.y_range);
+        let screen = |x: f32, y: f32| {
+            let (x, y) = (x - uniforms.origin[0], y - uniforms.origin[1]);
+            (
+                left + (x - x0) / (x1 - x0) * width,
+                top + (y1 - y) / (y1 - y0) * height,
+            )
         };
 
-        // Build highlight ring from tooltip state
-        let highlight = if let Some(ref tooltip_config) = self.options.tooltip {
-            let hovered = self.tooltip_state.hovered.borrow();
-            hovered.as_ref().map(|hp| {
-                let color = [
-                    tooltip_config.highlight_color.r,
-                    tooltip_config.highlight_color.g,
-                    tooltip_config.highlight_color.b,
-                    tooltip_config.highlight_color.a,
-                ];
-                (
-                    hp.screen_pos,
-                    color,
-                    tooltip_config.highlight_radius,
-                    tooltip_config.highlight_width,
-                )
+        // The head is the last triangle; the shorter arrow points up at
+        // half the longest arrow's 30 pixels
+        let vertices = primitive.line_vertices();
+        let tip = vertices[vertices.len() - 3].position;
+        let base = screen(0.5, 1.0);
+        assert!((tip[0] - base.0).abs() < 1e-3);
+        assert!((base.1 - tip[1] - 15.0).abs() < 1e-3);
+
+        // Data-scaled arrows count towards auto-fit up to their tips
+        let fit = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &fit).with_quiver(quiver.with_scale(QuiverScale::Data(2.0)));
+        assert_eq!(plotter.compute_data_ranges(), ([-0.5, 1.5], [0.0, 2.0]));
+    }
+
+    #[test]
+    fn test_image_pans_with_the_data() {
+        let image = PlotImage::new(2, 1, vec![255; 8], (0.0, 10.0), (0.0, 5.0)).with_opacity(0.5);
+        // Without elastic bounds, which would pull the view back over the image
+        let interaction = InteractionConfig {
+            elastic: false,
+            ..Default::default()
+        };
+        let view = ViewState::default().zoom_to((5.0, 15.0), (0.0, 5.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view)
+            .with_image(image.clone())
+            .with_interaction(interaction.clone());
+        assert_eq!(plotter.validate(), Ok(()));
+
+        // The right half of the image fills the left half of the plot
+        let primitive = Harness::new(&plotter).primitive();
+        let [left, _] = primitive.uniforms().padding;
+        let [width, _] = primitive.uniforms().plot_size;
+        let vertices = primitive.image_vertices();
+        assert_eq!(vertices.len(), 6);
+        assert!((vertices[0].position[0] - left).abs() < 1e-3);
+        assert!((vertices[1].position[0] - left - width / 2.0).abs() < 1e-3);
+        assert_eq!([vertices[0].uv[0], vertices[1].uv[0]], [0.5, 1.0]);
+        assert_eq!(vertices[0].opacity, 0.5);
+
+        // Out of view, and with too few pixels, it isn't drawn
+        let away = ViewState::default().zoom_to((20.0, 30.0), (0.0, 5.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &away)
+            .with_image(image)
+            .with_interaction(interaction);
+        assert!(
+            Harness::new(&plotter)
+                .primitive()
+                .image_vertices()
+                .is_empty()
+        );
+        let short = PlotImage::new(2, 2, vec![255; 8], (0.0, 10.0), (0.0, 5.0));
+        let plotter: Plotter<'_, ()> = Plotter::new(Vec::new(), &view).with_image(short);
+        assert!(matches!(
+            plotter.validate(),
+            Err(PlotterError::ImageSize {
+                image: 0,
+                expected: 16,
+                found: 8
             })
-        } else {
-            None
+        ));
+        assert!(
+            Harness::new(&plotter)
+                .primitive()
+                .image_vertices()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_quad_follows_the_ring() {
+        let mut spectrogram = Spectrogram::new(64, 1000.0).with_columns(4);
+        spectrogram.push(&[0.0; 64 + 16 * 5]);
+        let fit = ViewState::auto_fit();
+        let fitted: Plotter<'_, ()> = Plotter::new(Vec::new(), &fit).with_spectrogram(&spectrogram);
+        assert_eq!(fitted.compute_data_ranges(), ([0.032, 0.096], [0.0, 500.0]));
+        drop(fitted);
+
+        // Six columns in a ring of four: the oldest is in slot 2, so U runs
+        // from the middle of the texture once around. The view shows the
+        // older half of the columns.
+        let view = ViewState::default().zoom_to((0.032, 0.064), (0.0, 500.0));
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &view).with_spectrogram(&spectrogram);
+        let primitive = Harness::new(&plotter).primitive();
+        let [left, top] = primitive.uniforms().padding;
+        let [width, height] = primitive.uniforms().plot_size;
+        let vertices = primitive.image_vertices();
+        assert_eq!(vertices.len(), 6);
+        let (x0, x1) = (vertices[0].position[0], vertices[1].position[0]);
+        assert!((x0 - left).abs() < 1e-3 && (x1 - left - width).abs() < 1e-3);
+        let (y0, y1) = (vertices[0].position[1], vertices[2].position[1]);
+        assert!((y0 - top).abs() < 1e-3 && (y1 - top - height).abs() < 1e-3);
+        let (u0, u1) = (vertices[0].uv[0], vertices[1].uv[0]);
+        assert!((u0 - 0.5).abs() < 1e-5 && (u1 - 1.0).abs() < 1e-5);
+
+        // New columns change the primitive
+        let before = Harness::new(&plotter).primitive().image_vertices().to_vec();
+        drop(plotter);
+        spectrogram.push(&[0.0; 16]);
+        let plotter: Plotter<'_, ()> =
+            Plotter::new(Vec::new(), &view).with_spectrogram(&spectrogram);
+        let after = Harness::new(&plotter).primitive();
+        assert!((after.image_vertices()[0].uv[0] - 0.75).abs() < 1e-5);
+        assert_ne!(before[0].position, after.image_vertices()[0].position);
+    }
+
+    #[test]
+    fn test_fixed_color_range_and_normalization() {
+        let black_white = || ColorMode::value_gradient(iced::Color::BLACK, iced::Color::WHITE);
+        let middle_color = |color: ColorMode<'static>, max: f64| {
+            let points = PlotPoints::owned(vec![
+                (0.0, 0.0).into(),
+                (1.0, 5.0).into(),
+                (2.0, max).into(),
+            ]);
+            let view = ViewState::auto_fit();
+            let plotter: Plotter<'_, ()> = Plotter::new(
+                vec![PlotSeries::new("s", points).with_style(SeriesStyle::new(color))],
+                &view,
+            );
+            Harness::new(&plotter).primitive().points()[1].color[0]
         };
 
-        let hidden = self.legend_state.hidden_series.borrow();
-        PlotterPrimitive::new(
-            &self.series,
-            bounds,
-            &self.options,
-            view_x,
-            view_y,
-            selection_rect,
-            &hidden,
-            highlight,
-        )
+        // Auto ranges recolor old points as new extremes stream in
+        assert_ne!(
+            middle_color(black_white(), 10.0),
+            middle_color(black_white(), 20.0)
+        );
+        // A fixed range doesn't
+        let fixed = || black_white().with_range(0.0, 10.0);
+        assert_eq!(middle_color(fixed(), 10.0), 0.5);
+        assert_eq!(middle_color(fixed(), 20.0), 0.5);
+
+        assert_eq!(Normalization::Log.apply(10.0, (1.0, 100.0)), Some(0.5));
+        assert_eq!(Normalization::Log.apply(0.0, (1.0, 100.0)), Some(0.0));
+        assert_eq!(
+            Normalization::Power(2.0).apply(5.0, (0.0, 10.0)),
+            Some(0.25)
+        );
+        assert_eq!(Normalization::Linear.apply(15.0, (0.0, 10.0)), Some(1.0));
+        assert_eq!(Normalization::Linear.apply(1.0, (1.0, 1.0)), None);
     }
 
-    fn mouse_interaction(
-        &self,
-        state: &Self::State,
-        bounds: Rectangle,
-        cursor: Cursor,
-    ) -> mouse::Interaction {
-        let has_any = self.interaction.pan_x
-            || self.interaction.pan_y
-            || self.interaction.zoom_x
-            || self.interaction.zoom_y
-            || self.interaction.zoom_select;
+    #[test]
+    fn test_per_point_sizes_and_colors() {
+        let red = iced::Color::from_rgb(1.0, 0.0, 0.0);
+        let blue = iced::Color::from_rgb(0.0, 0.0, 1.0);
+        let points = || {
+            PlotPoints::owned(vec![
+                (0.0, 0.0).into(),
+                (1.0, 1.0).into(),
+                (2.0, 0.0).into(),
+            ])
+        };
+        let style = SeriesStyle::default()
+            .with_marker_sizes(vec![2.0, f32::NAN, 20.0])
+            .with_point_colors(vec![red, blue, red]);
+        let view = ViewState::auto_fit();
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![
+                PlotSeries::new("bubbles", points())
+                    .with_style(style.clone())
+                    .opacity(0.5),
+            ],
+            &view,
+        );
+        assert!(plotter.validate().is_ok());
+
+        let primitive = Harness::new(&plotter).primitive();
+        let points_drawn = primitive.points();
+        // NaN falls back to the series' marker size
+        let sizes: Vec<f32> = points_drawn.iter().map(|p| p.size).collect();
+        assert_eq!(sizes, [2.0, 4.0, 20.0]);
+        // Opacity still applies on top of the per-point color
+        assert_eq!(points_drawn[1].color, [0.0, 0.0, 1.0, 0.5]);
+
+        let short = Plotter::<'_, ()>::new(
+            vec![
+                PlotSeries::new("short", points())
+                    .with_style(SeriesStyle::default().with_point_colors(vec![red])),
+            ],
+            &view,
+        );
+        assert_eq!(
+            short.validate(),
+            Err(PlotterError::PointArrayLength {
+                series: "short".into(),
+                array: "point_colors",
+                expected: 3,
+                found: 1,
+            })
+        );
+    }
 
-        if !has_any {
-            return mouse::Interaction::default();
+    #[test]
+    fn test_context_menu_reports_actions() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Message {
+            View(ViewState),
+            Action(ContextAction, (f64, f64)),
         }
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let config = ContextMenuConfig::default().with_custom("Mark", 7);
+        let plotter: Plotter<'_, Message> = Plotter::new(
+            vec![PlotSeries::new(
+                "line",
+                PlotPoints::owned(vec![(0.0, 0.0).into(), (20.0, 20.0).into()]),
+            )],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            context_menu: Some(config.clone()),
+            ..Default::default()
+        })
+        .on_view_change(Message::View)
+        .on_context_action(Message::Action);
+        let mut harness = Harness::new(&plotter);
+        let padding = plotter.plot_padding(DEFAULT_BOUNDS.size());
+        let center = Point::new(
+            padding.left + (DEFAULT_BOUNDS.width - padding.x()) / 2.0,
+            padding.top + (DEFAULT_BOUNDS.height - padding.y()) / 2.0,
+        );
+        let right_click = Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right));
+        let row = |i: usize| {
+            let row_height = config.text_size + config.padding * 2.0;
+            Point::new(center.x + 10.0, center.y + (i as f32 + 0.5) * row_height)
+        };
 
-        match state.interaction_mode {
-            InteractionMode::Panning => mouse::Interaction::Grabbing,
-            InteractionMode::ZoomSelecting => mouse::Interaction::Crosshair,
-            InteractionMode::Idle => {
-                if let Some(pos) = cursor.position_in(bounds) {
-                    // Check if cursor is over the legend area
-                    if self.options.legend.is_some() {
-                        let layout = self.legend_state.layout.borrow();
-                        // Check toggle buttons first — show pointer
-                        for toggle in layout.toggles.iter() {
-                            if toggle.rect.contains(pos) {
-                                return mouse::Interaction::Pointer;
-                            }
-                        }
-                        // Over legend background — show default cursor (no drag)
-                        if let Some(legend_bounds) = layout.bounds {
-                            if legend_bounds.contains(pos) {
-                                return mouse::Interaction::default();
-                            }
-                        }
-                    }
-                    // Show crosshair when Ctrl is held (indicating zoom select is available)
-                    if self.interaction.zoom_select && state.modifiers.control() {
-                        mouse::Interaction::Crosshair
-                    } else {
-                        mouse::Interaction::Grab
-                    }
-                } else {
-                    mouse::Interaction::default()
-                }
-            }
-        }
+        // Opening publishes nothing; the menu lists every entry
+        harness.move_to(center);
+        assert_eq!(harness.event(right_click.clone()), None);
+        let open = plotter.context_menu_state.open.borrow().clone();
+        let open = open.expect("menu is open");
+        assert_eq!(open.entries.len(), 6);
+        assert_eq!(open.data, (5.0, 5.0));
+
+        // Picking an entry reports it with where the menu was opened
+        harness.move_to(row(5));
+        assert_eq!(
+            harness.press(),
+            Some(Message::Action(ContextAction::Custom(7), (5.0, 5.0)))
+        );
+        assert!(plotter.context_menu_state.open.borrow().is_none());
+        harness.release();
+
+        // Reset view is carried out by the plot
+        harness.move_to(center);
+        harness.event(right_click.clone());
+        harness.move_to(row(0));
+        assert_eq!(
+            harness.press(),
+            Some(Message::View(view.clone().with_x_range(None)))
+        );
+        harness.release();
+
+        // Escape closes without an action
+        harness.move_to(center);
+        harness.event(right_click);
+        assert!(plotter.context_menu_state.open.borrow().is_some());
+        harness.key_press(keyboard::Key::Named(keyboard::key::Named::Escape));
+        assert!(plotter.context_menu_state.open.borrow().is_none());
     }
 }
@@ -19,21 +19,24 @@ const PATTERN_NONE: u32 = 4u;
 
 struct Uniforms {
     viewport_size: vec2<f32>,
-    x_range: vec2<f32>,
-    y_range: vec2<f32>,
-    padding: vec2<f32>,
-    marker_radius: f32,
+    x_range: vec2<f32>,    // Relative to origin
+    y_range: vec2<f32>,    // Relative to origin
+    padding: vec2<f32>,    // Plot area offset (left, top)
+    plot_size: vec2<f32>,  // Plot area size (width, height)
     line_width: f32,
+    scale_factor: f32,     // Physical pixels per logical pixel
+    origin: vec2<f32>,          // Data-space origin of the local frame (unused on the GPU)
+    compact_scale: vec2<f32>,   // Local frame units per compact marker unit
 }
 
 @group(0) @binding(0) var<uniform> uniforms: Uniforms;
 
 // Per-instance point data for markers
 struct PointInput {
-    @location(0) position: vec2<f32>,  // Data coordinates
+    @location(0) position: vec2<f32>,  // Local frame, see Uniforms::origin
     @location(1) color: vec4<f32>,
     @location(2) shape: u32,           // Marker shape
-    @location(3) _padding: u32,
+    @location(3) size: f32,            // Radius: pixels if positive, data units if negative
 }
 
 // Vertex shader output
@@ -42,6 +45,7 @@ struct VertexOutput {
     @location(0) color: vec4<f32>,
     @location(1) local_pos: vec2<f32>,  // Position within quad for shape rendering
     @location(2) shape: u32,            // Marker shape
+    @location(3) aa: f32,               // Half a physical pixel in local_pos units
 }
 
 // Quad vertices for instanced rendering (2 triangles)
@@ -56,9 +60,9 @@ const QUAD_VERTICES: array<vec2<f32>, 6> = array<vec2<f32>, 6>(
 
 // Transform data coordinates to normalized device coordinates
 fn data_to_ndc(data_pos: vec2<f32>) -> vec2<f32> {
-    // Calculate plot area (viewport minus padding)
-    let plot_width = uniforms.viewport_size.x - 2.0 * uniforms.padding.x;
-    let plot_height = uniforms.viewport_size.y - 2.0 * uniforms.padding.y;
+    // Plot area (viewport minus padding)
+    let plot_width = uniforms.plot_size.x;
+    let plot_height = uniforms.plot_size.y;
     
     // Normalize data position to 0-1 range
     let x_norm = (data_pos.x - uniforms.x_range.x) / (uniforms.x_range.y - uniforms.x_range.x);
@@ -137,10 +141,38 @@ fn evaluate_sdf(p: vec2<f32>, shape: u32) -> f32 {
     }
 }
 
+// Compact per-instance point data: f16 local-frame position, RGBA8 color
+struct CompactPointInput {
+    @location(0) position: vec2<f32>,  // Local frame divided by compact_scale
+    @location(1) color: vec4<f32>,
+    @location(2) shape_size: u32,      // Shape in the low 16 bits, f16 size in the high 16
+}
+
 @vertex
 fn vs_marker(
     @builtin(vertex_index) vertex_index: u32,
     point: PointInput,
+) -> VertexOutput {
+    return marker_vertex(vertex_index, point.position, point.color, point.shape, point.size);
+}
+
+@vertex
+fn vs_marker_compact(
+    @builtin(vertex_index) vertex_index: u32,
+    point: CompactPointInput,
+) -> VertexOutput {
+    let local_pos = point.position * uniforms.compact_scale;
+    let shape = point.shape_size & 0xffffu;
+    let size = unpack2x16float(point.shape_size).y;
+    return marker_vertex(vertex_index, local_pos, point.color, shape, size);
+}
+
+fn marker_vertex(
+    vertex_index: u32,
+    position: vec2<f32>,
+    color: vec4<f32>,
+    shape: u32,
+    size: f32,
 ) -> VertexOutput {
     var out: VertexOutput;
     
@@ -148,21 +180,34 @@ fn vs_marker(
     let local_pos = QUAD_VERTICES[vertex_index];
     
     // Transform point to NDC
-    let center_ndc = data_to_ndc(point.position);
+    let center_ndc = data_to_ndc(position);
     
+    // Marker radius in pixels; data-unit radii (negative) scale with the visible range
+    var radius_px = vec2<f32>(size, size);
+    if size < 0.0 {
+        radius_px = vec2<f32>(
+            -size * uniforms.plot_size.x / (uniforms.x_range.y - uniforms.x_range.x),
+            -size * uniforms.plot_size.y / (uniforms.y_range.y - uniforms.y_range.x)
+        );
+    }
+
     // Calculate marker size in NDC
     let marker_size_ndc = vec2<f32>(
-        (uniforms.marker_radius * 2.0) / uniforms.viewport_size.x,
-        (uniforms.marker_radius * 2.0) / uniforms.viewport_size.y
+        (radius_px.x * 2.0) / uniforms.viewport_size.x,
+        (radius_px.y * 2.0) / uniforms.viewport_size.y
     );
     
     // Offset quad vertices from center
     let final_pos = center_ndc + local_pos * marker_size_ndc;
     
     out.clip_position = vec4<f32>(final_pos, 0.0, 1.0);
-    out.color = point.color;
+    out.color = color;
     out.local_pos = local_pos;
-    out.shape = point.shape;
+    out.shape = shape;
+    // Radii are logical pixels, so the edge is softened over a fixed number
+    // of physical pixels whatever the display's scale factor
+    let radius_physical = min(radius_px.x, radius_px.y) * uniforms.scale_factor;
+    out.aa = 0.5 / max(radius_physical, 1.0);
     
     return out;
 }
@@ -178,12 +223,12 @@ fn fs_marker(in: VertexOutput) -> @location(0) vec4<f32> {
     let sdf = evaluate_sdf(in.local_pos, in.shape);
     
     // Discard pixels outside the shape
-    if sdf > 0.1 {
+    if sdf > in.aa {
         discard;
     }
     
-    // Anti-aliasing: smooth edge
-    let alpha = 1.0 - smoothstep(-0.1, 0.1, sdf);
+    // Anti-aliasing: smooth edge, one physical pixel wide
+    let alpha = 1.0 - smoothstep(-in.aa, in.aa, sdf);
     
     return vec4<f32>(in.color.rgb, in.color.a * alpha);
 }
@@ -193,12 +238,16 @@ struct LineVertexInput {
     @location(0) position: vec2<f32>,  // Already in screen coordinates
     @location(1) color: vec4<f32>,
     @location(2) edge_distance: f32,   // Signed normalised distance from line centre
+    @location(3) distance: f32,        // Distance along the line in line widths
+    @location(4) pattern: u32,         // Line pattern
 }
 
 struct LineVertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) edge_distance: f32,
+    @location(2) distance: f32,
+    @location(3) @interpolate(flat) pattern: u32,
 }
 
 @vertex
@@ -212,20 +261,94 @@ fn vs_line(vertex: LineVertexInput) -> LineVertexOutput {
     out.clip_position = vec4<f32>(ndc_x, ndc_y, 0.0, 1.0);
     out.color = vertex.color;
     out.edge_distance = vertex.edge_distance;
+    out.distance = vertex.distance;
+    out.pattern = vertex.pattern;
     
     return out;
 }
 
+// Coverage of the interval [a, b] at position t, with a soft edge of width aa
+fn dash_coverage(t: f32, a: f32, b: f32, aa: f32) -> f32 {
+    return clamp(min(t - a, b - t) / aa + 0.5, 0.0, 1.0);
+}
+
+// Pattern coverage at `distance` (in line widths) along the line
+fn pattern_coverage(distance: f32, pattern: u32, aa: f32) -> f32 {
+    switch pattern {
+        case PATTERN_DASHED: {
+            // 4 on, 3 off
+            let t = distance - floor(distance / 7.0) * 7.0;
+            return dash_coverage(t, 0.0, 4.0, aa);
+        }
+        case PATTERN_DOTTED: {
+            // 1 on, 2 off
+            let t = distance - floor(distance / 3.0) * 3.0;
+            return dash_coverage(t, 0.0, 1.0, aa);
+        }
+        case PATTERN_DASHDOT: {
+            // 4 on, 2 off, 1 on, 2 off
+            let t = distance - floor(distance / 9.0) * 9.0;
+            return max(dash_coverage(t, 0.0, 4.0, aa), dash_coverage(t, 6.0, 7.0, aa));
+        }
+        case PATTERN_NONE: { return 0.0; }
+        default: { return 1.0; }
+    }
+}
+
 @fragment
 fn fs_line(in: LineVertexOutput) -> @location(0) vec4<f32> {
+    // Derivatives must be taken in uniform control flow
+    let aa = max(fwidth(in.distance), 0.0001);
+    let edge_aa = max(fwidth(in.edge_distance), 0.0001);
+
     // Anti-aliased edges: abs(edge_distance) is 0 at centre, 1.0 at original
-    // line edge, >1.0 in the AA extension fringe.
+    // line edge, >1.0 in the AA extension fringe. Coverage ramps over one
+    // pixel centred on the edge (edge_aa is the change per pixel). Fills
+    // have edge_distance 0 everywhere and stay fully opaque.
     let d = abs(in.edge_distance);
-    let alpha = 1.0 - smoothstep(0.8, 1.0, d);
+    let coverage = clamp((1.0 - d) / edge_aa + 0.5, 0.0, 1.0);
+    let alpha = coverage * pattern_coverage(in.distance, in.pattern, aa);
     if alpha < 0.001 {
         discard;
     }
     return vec4<f32>(in.color.rgb, in.color.a * alpha);
 }
 
+// Textured quads (images and spectrograms)
+@group(1) @binding(0) var image_texture: texture_2d<f32>;
+@group(1) @binding(1) var image_sampler: sampler;
 
+struct ImageVertexInput {
+    @location(0) position: vec2<f32>,  // Already in screen coordinates
+    @location(1) uv: vec2<f32>,        // U may wrap around a ring of columns
+    @location(2) opacity: f32,
+}
+
+struct ImageVertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) opacity: f32,
+}
+
+@vertex
+fn vs_image(vertex: ImageVertexInput) -> ImageVertexOutput {
+    var out: ImageVertexOutput;
+    let ndc_x = (vertex.position.x / uniforms.viewport_size.x) * 2.0 - 1.0;
+    let ndc_y = 1.0 - (vertex.position.y / uniforms.viewport_size.y) * 2.0;
+    out.clip_position = vec4<f32>(ndc_x, ndc_y, 0.0, 1.0);
+    out.uv = vertex.uv;
+    out.opacity = vertex.opacity;
+    return out;
+}
+
+@fragment
+fn fs_image(in: ImageVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(image_texture, image_sampler, in.uv);
+    return vec4<f32>(color.rgb, color.a * in.opacity);
+}
+
+// Multisampled markers and lines, resolved; colors are premultiplied by alpha
+@fragment
+fn fs_composite(in: ImageVertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(image_texture, image_sampler, in.uv) * in.opacity;
+}
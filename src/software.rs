@@ -0,0 +1,553 @@
+//! Drawing plots with the canvas API, for renderers without custom shaders.
+//!
+//! With [`RenderBackend::Software`], or [`RenderBackend::Auto`] when iced
+//! falls back to tiny-skia, a canvas layer draws the shader's
+//! [`PlotterPrimitive`] with paths: the grid, selection and highlight
+//! triangles, then each layer's lines and markers, clipped like the GPU
+//! passes. Line patterns and line edges are cut out of the triangles along
+//! the same vertex attributes the line shader reads, and markers are the
+//! outlines of the marker shader's shapes.
+//!
+//! Images and spectrograms are only drawn by the GPU path.
+//!
+//! [`RenderBackend::Software`]: crate::plotter::RenderBackend::Software
+//! [`RenderBackend::Auto`]: crate::plotter::RenderBackend::Auto
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use iced::widget::canvas::{self, Frame, Path};
+use iced::{Color, Point, Rectangle, Renderer, Theme};
+
+use crate::gpu_types::{LineVertex, f16_bits_to_f32};
+use crate::plotter::{LinePattern, MarkerShape};
+use crate::shader::PlotterPrimitive;
+
+/// The primitive of the shader's last draw, handed to the canvas.
+pub(crate) type SoftwareFrame = Rc<RefCell<Option<PlotterPrimitive>>>;
+
+/// Dash periods drawn per triangle at most, so a degenerate distance range
+/// can't stall the frame.
+const MAX_DASHES: i64 = 4096;
+
+/// The renderer [`RenderBackend::Auto`] layers were last drawn with: 0
+/// before any was drawn, then [`WGPU`] or [`TINY_SKIA`]. iced picks one for
+/// the whole application, so plots share it.
+///
+/// [`RenderBackend::Auto`]: crate::plotter::RenderBackend::Auto
+static RENDERER: AtomicU8 = AtomicU8::new(0);
+const WGPU: u8 = 1;
+const TINY_SKIA: u8 = 2;
+
+/// Whether iced draws with tiny-skia, or `None` until a [`SoftwareLayer`]
+/// has found out.
+pub(crate) fn software_renderer() -> Option<bool> {
+    match RENDERER.load(Ordering::Relaxed) {
+        WGPU => Some(false),
+        TINY_SKIA => Some(true),
+        _ => None,
+    }
+}
+
+/// Canvas layer that draws the shader's primitive when the shader can't.
+pub(crate) struct SoftwareLayer {
+    pub frame: SoftwareFrame,
+    /// Draw on wgpu too, see [`RenderBackend::Software`](crate::plotter::RenderBackend::Software).
+    pub forced: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct SoftwareState {
+    cache: canvas::Cache,
+    /// Key of the primitive the cache was drawn from
+    key: Cell<Option<u64>>,
+}
+
+impl<Message> canvas::Program<Message> for SoftwareLayer {
+    type State = SoftwareState;
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: iced::mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        // Forced layers may be drawn by a renderer picked for an export
+        if !self.forced {
+            let tiny_skia = matches!(renderer, Renderer::Secondary(_));
+            RENDERER.store(if tiny_skia { TINY_SKIA } else { WGPU }, Ordering::Relaxed);
+            if !tiny_skia {
+                return Vec::new();
+            }
+        }
+        let frame = self.frame.borrow();
+        let Some(primitive) = frame.as_ref() else {
+            return Vec::new();
+        };
+        if state.key.replace(Some(primitive.key())) != Some(primitive.key()) {
+            state.cache.clear();
+        }
+        vec![state.cache.draw(renderer, bounds.size(), |frame| {
+            draw_primitive(frame, primitive)
+        })]
+    }
+}
+
+/// Draw everything the GPU passes would, in the same order.
+pub(crate) fn draw_primitive(frame: &mut Frame, primitive: &PlotterPrimitive) {
+    let mut background = Vec::new();
+    for triangles in [
+        &primitive.grid_vertices()[..],
+        primitive.selection_vertices(),
+        primitive.highlight_vertices(),
+    ] {
+        line_polygons(triangles, &mut background);
+    }
+    fill_polygons(frame, &background);
+
+    // Lines are clipped to the plot area, markers to it grown by the bleed
+    let uniforms = primitive.uniforms();
+    let config = primitive.config();
+    let plot = Rectangle::new(uniforms.padding.into(), uniforms.plot_size.into());
+    let marker_area = plot
+        .expand(config.marker_bleed.max(0.0))
+        .intersection(&Rectangle::with_size(frame.size()))
+        .unwrap_or(plot);
+
+    for layer in primitive.layers() {
+        let lines =
+            &primitive.line_vertices()[layer.lines.start as usize..layer.lines.end as usize];
+        if !lines.is_empty() {
+            let mut polygons = Vec::new();
+            line_polygons(lines, &mut polygons);
+            frame.with_clip(plot, |frame| fill_polygons(frame, &polygons));
+        }
+        if config.show_markers && !layer.markers.is_empty() {
+            let polygons: Vec<_> = layer
+                .markers
+                .clone()
+                .filter_map(|i| marker_polygon(primitive, i as usize))
+                .collect();
+            frame.with_clip(marker_area, |frame| fill_polygons(frame, &polygons));
+        }
+    }
+}
+
+/// Fill polygons, one path per run of the same color. Windings are made
+/// the same so overlaps within a path don't leave holes, and shared edges
+/// leave no anti-aliasing seams.
+fn fill_polygons(frame: &mut Frame, polygons: &[(Color, Vec<Point>)]) {
+    for run in polygons.chunk_by(|a, b| a.0 == b.0) {
+        let path = Path::new(|builder| {
+            for (_, points) in run {
+                let area: f32 = points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|(a, b)| a.x * b.y - b.x * a.y)
+                    .sum();
+                if area.abs() < 1e-6 {
+                    continue;
+                }
+                let mut ordered: Box<dyn Iterator<Item = &Point>> = if area > 0.0 {
+                    Box::new(points.iter())
+                } else {
+                    Box::new(points.iter().rev())
+                };
+                if let Some(first) = ordered.next() {
+                    builder.move_to(*first);
+                    ordered.for_each(|p| builder.line_to(*p));
+                    builder.close();
+                }
+            }
+        });
+        frame.fill(&path, run[0].0);
+    }
+}
+
+/// A line vertex reduced to what the line fragment shader reads.
+#[derive(Clone, Copy, Debug)]
+struct Corner {
+    position: Point,
+    distance: f32,
+    edge_distance: f32,
+}
+
+impl Corner {
+    fn lerp(&self, other: &Corner, t: f32) -> Corner {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Corner {
+            position: Point::new(
+                mix(self.position.x, other.position.x),
+                mix(self.position.y, other.position.y),
+            ),
+            distance: mix(self.distance, other.distance),
+            edge_distance: mix(self.edge_distance, other.edge_distance),
+        }
+    }
+}
+
+/// Clip a convex polygon to where `value` is at most zero.
+fn clip(polygon: &[Corner], value: impl Fn(&Corner) -> f32) -> Vec<Corner> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 2);
+    for (i, a) in polygon.iter().enumerate() {
+        let b = &polygon[(i + 1) % polygon.len()];
+        let (va, vb) = (value(a), value(b));
+        if va <= 0.0 {
+            clipped.push(*a);
+        }
+        if (va <= 0.0) != (vb <= 0.0) {
+            clipped.push(a.lerp(b, va / (va - vb)));
+        }
+    }
+    clipped
+}
+
+/// Period and drawn intervals of a line pattern, in line widths, as in the
+/// line shader; `None` for solid lines.
+fn dashes(pattern: u32) -> Option<(f32, &'static [[f32; 2]])> {
+    match pattern {
+        p if p == LinePattern::Dashed.as_u32() => Some((7.0, &[[0.0, 4.0]])),
+        p if p == LinePattern::Dotted.as_u32() => Some((3.0, &[[0.0, 1.0]])),
+        p if p == LinePattern::DashDot.as_u32() => Some((9.0, &[[0.0, 4.0], [6.0, 7.0]])),
+        _ => None,
+    }
+}
+
+/// The covered parts of line triangles: within the line's edges (fills
+/// have an edge distance of 0 throughout) and inside its pattern's dashes.
+fn line_polygons(triangles: &[LineVertex], polygons: &mut Vec<(Color, Vec<Point>)>) {
+    for triangle in triangles.chunks_exact(3) {
+        let pattern = triangle[0].pattern;
+        if pattern == LinePattern::None.as_u32() {
+            continue;
+        }
+        let color = triangle.iter().fold([0.0; 4], |sum, v| {
+            std::array::from_fn(|i| sum[i] + v.color[i] / 3.0)
+        });
+        let color = Color::from_rgba(color[0], color[1], color[2], color[3]);
+        let corners: Vec<_> = triangle
+            .iter()
+            .map(|v| Corner {
+                position: v.position.into(),
+                distance: v.distance,
+                edge_distance: v.edge_distance,
+            })
+            .collect();
+        let inside = clip(&clip(&corners, |c| c.edge_distance - 1.0), |c| {
+            -c.edge_distance - 1.0
+        });
+        let mut push = |polygon: Vec<Corner>| {
+            if polygon.len() >= 3 {
+                polygons.push((color, polygon.iter().map(|c| c.position).collect()));
+            }
+        };
+        let Some((period, intervals)) = dashes(pattern) else {
+            push(inside);
+            continue;
+        };
+        let (lo, hi) = inside
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), c| {
+                (lo.min(c.distance), hi.max(c.distance))
+            });
+        if !(lo.is_finite() && hi.is_finite()) {
+            continue;
+        }
+        let first = (lo / period).floor() as i64;
+        let last = ((hi / period).floor() as i64).min(first + MAX_DASHES);
+        for k in first..=last {
+            let start = k as f32 * period;
+            for [a, b] in intervals {
+                let dash = clip(&inside, |c| start + a - c.distance);
+                push(clip(&dash, |c| c.distance - (start + b)));
+            }
+        }
+    }
+}
+
+/// Outline of marker `index`, in whichever format the primitive holds, in
+/// screen coordinates. `None` for [`MarkerShape::None`] and invalid radii.
+fn marker_polygon(primitive: &PlotterPrimitive, index: usize) -> Option<(Color, Vec<Point>)> {
+    let uniforms = primitive.uniforms();
+    let (position, color, shape, size) = match primitive.compact_points().get(index) {
+        Some(point) => (
+            std::array::from_fn::<_, 2, _>(|i| {
+                f16_bits_to_f32(point.position[i]) * uniforms.compact_scale[i]
+            }),
+            Color::from_rgba8(
+                point.color[0],
+                point.color[1],
+                point.color[2],
+                f32::from(point.color[3]) / 255.0,
+            ),
+            u32::from(point.shape),
+            f16_bits_to_f32(point.size),
+        ),
+        None => {
+            let point = primitive.points().get(index)?;
+            let [r, g, b, a] = point.color;
+            (
+                point.position,
+                Color::from_rgba(r, g, b, a),
+                point.shape,
+                point.size,
+            )
+        }
+    };
+    if shape == MarkerShape::None.as_u32() {
+        return None;
+    }
+
+    // Data-unit radii (negative) scale with the view, as in the marker shader
+    let span = [
+        uniforms.x_range[1] - uniforms.x_range[0],
+        uniforms.y_range[1] - uniforms.y_range[0],
+    ];
+    let radius = if size < 0.0 {
+        [0, 1].map(|i| -size * uniforms.plot_size[i] / span[i])
+    } else {
+        [size; 2]
+    };
+    if !radius.iter().all(|r| r.is_finite() && *r > 0.0) {
+        return None;
+    }
+    let center = Point::new(
+        uniforms.padding[0] + (position[0] - uniforms.x_range[0]) / span[0] * uniforms.plot_size[0],
+        uniforms.padding[1]
+            + (1.0 - (position[1] - uniforms.y_range[0]) / span[1]) * uniforms.plot_size[1],
+    );
+
+    // Shapes where the marker shader's distance fields are at most zero, in
+    // its quad space with Y up
+    let outline: Vec<[f32; 2]> = match shape {
+        s if s == MarkerShape::Square.as_u32() => {
+            vec![[-0.7, -0.7], [0.7, -0.7], [0.7, 0.7], [-0.7, 0.7]]
+        }
+        s if s == MarkerShape::Diamond.as_u32() => {
+            vec![[0.0, -1.0], [1.0, 0.0], [0.0, 1.0], [-1.0, 0.0]]
+        }
+        s if s == MarkerShape::TriangleUp.as_u32() => {
+            let x = 0.5 / 0.866;
+            vec![[-x, -0.5], [0.0, -1.0], [x, -0.5]]
+        }
+        s if s == MarkerShape::TriangleDown.as_u32() => {
+            let y = 1.0 - 0.866 * 0.7;
+            vec![[-0.7, -0.5], [0.7, -0.5], [0.7, y], [0.0, 1.0], [-0.7, y]]
+        }
+        s if s == MarkerShape::Cross.as_u32() => {
+            // Both diagonals as one outline around the center
+            let t = 0.2;
+            vec![
+                [-1.0, -1.0],
+                [-1.0 + t, -1.0],
+                [0.0, -t],
+                [1.0 - t, -1.0],
+                [1.0, -1.0],
+                [1.0, -1.0 + t],
+                [t, 0.0],
+                [1.0, 1.0 - t],
+                [1.0, 1.0],
+                [1.0 - t, 1.0],
+                [0.0, t],
+                [-1.0 + t, 1.0],
+                [-1.0, 1.0],
+                [-1.0, 1.0 - t],
+                [-t, 0.0],
+                [-1.0, -1.0 + t],
+            ]
+        }
+        s if s == MarkerShape::Plus.as_u32() => {
+            let t = 0.2;
+            vec![
+                [-t, -1.0],
+                [t, -1.0],
+                [t, -t],
+                [1.0, -t],
+                [1.0, t],
+                [t, t],
+                [t, 1.0],
+                [-t, 1.0],
+                [-t, t],
+                [-1.0, t],
+                [-1.0, -t],
+                [-t, -t],
+            ]
+        }
+        _ => {
+            let segments = (radius[0].max(radius[1]) * 2.0).clamp(12.0, 64.0) as usize;
+            (0..segments)
+                .map(|i| {
+                    let angle = std::f32::consts::TAU * i as f32 / segments as f32;
+                    [angle.cos(), angle.sin()]
+                })
+                .collect()
+        }
+    };
+    let points = outline
+        .into_iter()
+        .map(|[x, y]| Point::new(center.x + x * radius[0], center.y - y * radius[1]))
+        .collect();
+    Some((color, points))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotter::{
+        ColorMode, PlotPoints, PlotSeries, Plotter, PlotterOptions, RenderBackend, SeriesStyle,
+        ViewState,
+    };
+    use crate::testing::Harness;
+
+    fn vertex(x: f32, y: f32, distance: f32, edge_distance: f32, pattern: u32) -> LineVertex {
+        LineVertex::new(x, y, [1.0; 4], distance, pattern).with_edge_distance(edge_distance)
+    }
+
+    fn area(points: &[Point]) -> f32 {
+        let twice: f32 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum();
+        twice.abs() / 2.0
+    }
+
+    #[test]
+    fn test_line_polygons_cut_edges_and_dashes() {
+        // A 14 x 4 quad along X: 7 line widths of 2 px, edge distances
+        // running to 2 at the top and bottom like an anti-aliasing fringe
+        let quad = |pattern| {
+            [
+                vertex(0.0, -2.0, 0.0, -2.0, pattern),
+                vertex(14.0, -2.0, 7.0, -2.0, pattern),
+                vertex(14.0, 2.0, 7.0, 2.0, pattern),
+                vertex(0.0, -2.0, 0.0, -2.0, pattern),
+                vertex(14.0, 2.0, 7.0, 2.0, pattern),
+                vertex(0.0, 2.0, 0.0, 2.0, pattern),
+            ]
+        };
+        let covered = |pattern: LinePattern| {
+            let mut polygons = Vec::new();
+            line_polygons(&quad(pattern.as_u32()), &mut polygons);
+            polygons.iter().map(|(_, p)| area(p)).sum::<f32>()
+        };
+
+        // Only the inner half of the height is within the edges
+        assert!((covered(LinePattern::Solid) - 28.0).abs() < 1e-3);
+        // Dashes are 4 of every 7 line widths
+        assert!((covered(LinePattern::Dashed) - 16.0).abs() < 1e-3);
+        assert_eq!(covered(LinePattern::None), 0.0);
+    }
+
+    #[test]
+    fn test_software_primitive_has_line_and_marker_geometry() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+        let style = SeriesStyle::new(ColorMode::solid(red))
+            .with_line_width(6.0)
+            .with_marker_size(8.0);
+        let points = vec![(0.0, 5.0).into(), (10.0, 5.0).into()];
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points)).with_style(style)],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            padding: 0.0,
+            backend: RenderBackend::Software,
+            ..PlotterOptions::default()
+        });
+        let mut harness = Harness::new(&plotter);
+        harness.bounds = Rectangle::with_size(iced::Size::new(210.0, 100.0));
+        let primitive = harness.primitive();
+        assert!(primitive.config().software);
+
+        // What `draw_primitive` fills for the series
+        let [layer] = primitive.layers() else {
+            panic!("one series, one layer");
+        };
+        let mut lines = Vec::new();
+        line_polygons(
+            &primitive.line_vertices()[layer.lines.start as usize..layer.lines.end as usize],
+            &mut lines,
+        );
+        let markers: Vec<_> = layer
+            .markers
+            .clone()
+            .filter_map(|i| marker_polygon(&primitive, i as usize))
+            .collect();
+
+        // A 6 px line across the plot at half height
+        let points = lines.iter().flat_map(|(_, polygon)| polygon);
+        let (min, max) = points.fold(
+            (
+                Point::new(f32::MAX, f32::MAX),
+                Point::new(f32::MIN, f32::MIN),
+            ),
+            |(min, max), p| {
+                (
+                    Point::new(min.x.min(p.x), min.y.min(p.y)),
+                    Point::new(max.x.max(p.x), max.y.max(p.y)),
+                )
+            },
+        );
+        assert!(min.x <= 0.0 && max.x >= 210.0);
+        assert!((46.0..=47.5).contains(&min.y) && (52.5..=54.0).contains(&max.y));
+        assert!(lines.iter().all(|(color, _)| *color == red));
+
+        // A marker of 8 px radius at each end
+        assert_eq!(markers.len(), 2);
+        for ((color, outline), x) in markers.iter().zip([0.0, 210.0]) {
+            assert_eq!(*color, red);
+            let center = outline
+                .iter()
+                .fold(Point::ORIGIN, |c, p| Point::new(c.x + p.x, c.y + p.y));
+            let center = Point::new(
+                center.x / outline.len() as f32,
+                center.y / outline.len() as f32,
+            );
+            assert!(center.distance(Point::new(x, 50.0)) < 0.5);
+            assert!((190.0..=64.0 * std::f32::consts::PI).contains(&area(outline)));
+        }
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn test_software_backend_renders_without_gpu() {
+        let view = ViewState::with_ranges((0.0, 10.0), (0.0, 10.0));
+        let red = iced::Color::from_rgb(1.0, 0.0, 0.0);
+        let dashed = SeriesStyle::new(ColorMode::solid(red))
+            .with_line_width(6.0)
+            .with_line_pattern(LinePattern::Dashed)
+            .with_marker_size(8.0);
+        let points = vec![(0.0, 5.0).into(), (10.0, 5.0).into()];
+        let plotter: Plotter<'_, ()> = Plotter::new(
+            vec![PlotSeries::new("dashed", PlotPoints::owned(points)).with_style(dashed)],
+            &view,
+        )
+        .with_options(PlotterOptions {
+            padding: 0.0,
+            backend: RenderBackend::Software,
+            ..PlotterOptions::default()
+        });
+
+        // Drawn by tiny-skia on the CPU: a dash starts at the left end, and
+        // the gap after it shows the background
+        let pixels = plotter
+            .render_to_image(210, 100)
+            .expect("tiny-skia renders");
+        let red_at = |x: usize, y: usize| {
+            let [r, g, b, _] = pixels[(y * 210 + x) * 4..][..4] else {
+                unreachable!()
+            };
+            r > 200 && g < 80 && b < 80
+        };
+        assert!(red_at(12, 50));
+        assert!(!red_at(30, 50));
+        assert!(!red_at(12, 40));
+        // Markers at both ends, beyond the line's edges
+        assert!(red_at(2, 45));
+    }
+}
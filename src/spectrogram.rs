@@ -0,0 +1,362 @@
+//! Scrolling spectrograms of sampled signals.
+//!
+//! A [`Spectrogram`] turns a stream of samples into a short-time Fourier
+//! transform: every `hop` samples it transforms the latest `fft_size` of
+//! them into one column of colored frequency bins. The newest `columns`
+//! columns are kept in a ring, uploaded to the GPU as a texture where only
+//! the new columns are written, and drawn beneath the series so the view
+//! scrolls as samples arrive:
+//!
+//! ```
+//! use iced_plotter::spectrogram::Spectrogram;
+//!
+//! let sample_rate = 8000.0;
+//! let mut spectrogram = Spectrogram::new(256, sample_rate).with_columns(400);
+//! let tone: Vec<f32> = (0..8000)
+//!     .map(|i| (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 8000.0).sin())
+//!     .collect();
+//! spectrogram.push(&tone);
+//! assert_eq!(spectrogram.frequency_range(), [0.0, 4000.0]);
+//! assert!((spectrogram.peak_frequency().unwrap() - 1000.0).abs() < 40.0);
+//! ```
+//!
+//! Plot it with
+//! [`Plotter::with_spectrogram`](crate::plotter::Plotter::with_spectrogram);
+//! X is time in seconds and Y frequency in Hz.
+
+use crate::pipeline::next_image_id;
+use crate::plotter::ColorMode;
+use std::sync::{Arc, Mutex};
+
+/// Largest supported FFT size, giving 2048 frequency bins.
+const MAX_FFT_SIZE: usize = 4096;
+
+/// Largest number of columns kept, the texture width.
+const MAX_COLUMNS: usize = 2048;
+
+/// Colors in the palette each column is looked up in.
+const PALETTE_SIZE: usize = 256;
+
+/// Short-time Fourier transform of a sample stream, kept as a ring of
+/// colored columns.
+///
+/// Each column is the Hann-windowed magnitude spectrum in dB, scaled so a
+/// full-scale sine reads 0 dB, and colored by where it falls in
+/// [`Spectrogram::with_db_range`]. Columns are colored once, when they are
+/// computed: changing the colors or range only affects later columns.
+#[derive(Debug)]
+pub struct Spectrogram {
+    fft_size: usize,
+    hop: usize,
+    sample_rate: f64,
+    columns: usize,
+    start_time: f64,
+    db_range: (f32, f32),
+    palette: Vec<[u8; 4]>,
+    window: Vec<f32>,
+    /// Samples not yet consumed by a column, at most `fft_size - 1` plus
+    /// the last push.
+    pending: Vec<f32>,
+    /// Levels of the newest column in dB, lowest frequency first.
+    latest: Vec<f32>,
+    /// RGBA texels, `columns` wide and one row per bin with the highest
+    /// frequency on top. Column `k` is in slot `k % columns`. Shared with
+    /// the primitives drawing it, which upload new columns.
+    texels: Arc<Mutex<Vec<u8>>>,
+    pushed: u64,
+    id: u64,
+}
+
+impl Clone for Spectrogram {
+    /// A copy with its own texels, and an [`Spectrogram::id`] of its own.
+    fn clone(&self) -> Self {
+        let texels = self.texels.lock().expect("texels lock").clone();
+        Self {
+            window: self.window.clone(),
+            palette: self.palette.clone(),
+            pending: self.pending.clone(),
+            latest: self.latest.clone(),
+            texels: Arc::new(Mutex::new(texels)),
+            id: next_image_id(),
+            ..*self
+        }
+    }
+}
+
+impl Spectrogram {
+    /// A spectrogram of a signal sampled at `sample_rate` Hz, transforming
+    /// `fft_size` samples per column (rounded up to a power of two, at most
+    /// 4096) every quarter of that, and keeping 512 columns.
+    pub fn new(fft_size: usize, sample_rate: f64) -> Self {
+        let fft_size = fft_size.clamp(2, MAX_FFT_SIZE).next_power_of_two();
+        let mut spectrogram = Self {
+            fft_size,
+            hop: (fft_size / 4).max(1),
+            sample_rate,
+            columns: 512,
+            start_time: 0.0,
+            db_range: (-100.0, 0.0),
+            palette: Vec::new(),
+            window: Vec::new(),
+            pending: Vec::new(),
+            latest: Vec::new(),
+            texels: Arc::new(Mutex::new(Vec::new())),
+            pushed: 0,
+            id: 0,
+        };
+        spectrogram.palette = palette(&ColorMode::value_gradient(
+            iced::Color::from_rgb(0.0, 0.0, 0.1),
+            iced::Color::from_rgb(1.0, 0.9, 0.3),
+        ))
+        .unwrap_or_default();
+        spectrogram.clear();
+        spectrogram
+    }
+
+    /// Set the samples between columns, at most the FFT size. Clears the
+    /// spectrogram.
+    pub fn with_hop(mut self, hop: usize) -> Self {
+        self.hop = hop.clamp(1, self.fft_size);
+        self.clear();
+        self
+    }
+
+    /// Set how many columns are kept, at most 2048. Clears the spectrogram.
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = columns.clamp(1, MAX_COLUMNS);
+        self.clear();
+        self
+    }
+
+    /// Set the time in seconds of the first sample.
+    pub fn with_start_time(mut self, start_time: f64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Set the levels in dB mapped to the low and high end of the colors
+    /// (-100 to 0 by default).
+    pub fn with_db_range(mut self, low: f32, high: f32) -> Self {
+        self.db_range = (low, high);
+        self
+    }
+
+    /// Color levels like `mode` colors values, e.g. a
+    /// [`ColorMode::Colormap`]. Modes that don't encode a value, like
+    /// [`ColorMode::Solid`], leave the colors unchanged.
+    pub fn with_colors(mut self, mode: &ColorMode<'_>) -> Self {
+        if let Some(palette) = palette(mode) {
+            self.palette = palette;
+        }
+        self
+    }
+
+    /// Append samples, computing a column for every `hop` of them once the
+    /// first `fft_size` have arrived.
+    pub fn push(&mut self, samples: &[f32]) {
+        self.pending.extend_from_slice(samples);
+        let mut start = 0;
+        let mut texels = self.texels.lock().expect("texels lock");
+        while self.pending.len() - start >= self.fft_size {
+            let frame = &self.pending[start..start + self.fft_size];
+            self.latest = spectrum_db(frame, &self.window);
+            let slot = (self.pushed % self.columns as u64) as usize;
+            let bins = self.latest.len();
+            for (bin, db) in self.latest.iter().enumerate() {
+                let t = (db - self.db_range.0) / (self.db_range.1 - self.db_range.0);
+                let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+                let color = self.palette[(t * (PALETTE_SIZE - 1) as f32).round() as usize];
+                let row = bins - 1 - bin;
+                let offset = (row * self.columns + slot) * 4;
+                texels[offset..offset + 4].copy_from_slice(&color);
+            }
+            self.pushed += 1;
+            start += self.hop;
+        }
+        self.pending.drain(..start);
+    }
+
+    /// Drop all samples and columns.
+    pub fn clear(&mut self) {
+        self.window = hann_window(self.fft_size);
+        self.pending.clear();
+        self.latest.clear();
+        self.pushed = 0;
+        // Drawn texels are keyed by id, so the GPU copy starts over
+        self.id = next_image_id();
+        *self.texels.lock().expect("texels lock") = vec![0; self.columns * self.bins() * 4];
+    }
+
+    /// Columns computed in total, including dropped ones.
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    /// Number of columns kept.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of frequency bins per column, half the FFT size.
+    pub fn bins(&self) -> usize {
+        self.fft_size / 2
+    }
+
+    /// Levels of the newest column in dB, lowest frequency first, e.g. for
+    /// a spectrum plot next to the spectrogram.
+    pub fn latest_column(&self) -> &[f32] {
+        &self.latest
+    }
+
+    /// Frequency in Hz of the loudest bin of the newest column.
+    pub fn peak_frequency(&self) -> Option<f64> {
+        let (bin, _) = self
+            .latest
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))?;
+        Some(self.bin_frequency(bin))
+    }
+
+    /// Center frequency in Hz of bin `bin`.
+    pub fn bin_frequency(&self, bin: usize) -> f64 {
+        bin as f64 * self.sample_rate / self.fft_size as f64
+    }
+
+    /// Time span in seconds of the kept columns, `None` before the first.
+    /// Column `k` covers the `hop` samples starting at sample `k * hop`.
+    pub fn time_range(&self) -> Option<[f64; 2]> {
+        if self.pushed == 0 {
+            return None;
+        }
+        let first = self.pushed.saturating_sub(self.columns as u64);
+        let seconds =
+            |column: u64| self.start_time + (column * self.hop as u64) as f64 / self.sample_rate;
+        Some([seconds(first), seconds(self.pushed)])
+    }
+
+    /// Frequencies in Hz shown, from zero to the Nyquist frequency.
+    pub fn frequency_range(&self) -> [f64; 2] {
+        [0.0, self.sample_rate / 2.0]
+    }
+
+    /// Identifies this spectrogram's texels; clones get a new one.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The texels, shared with the primitives drawing them.
+    pub(crate) fn texels(&self) -> Arc<Mutex<Vec<u8>>> {
+        Arc::clone(&self.texels)
+    }
+}
+
+/// Lookup table of `mode`'s colors from the low to the high end.
+fn palette(mode: &ColorMode<'_>) -> Option<Vec<[u8; 4]>> {
+    (0..PALETTE_SIZE)
+        .map(|i| {
+            let color = crate::plotter::colorbar_color(mode, i as f32 / (PALETTE_SIZE - 1) as f32)?;
+            Some(color.into_rgba8())
+        })
+        .collect()
+}
+
+/// Periodic Hann window of `size` samples.
+fn hann_window(size: usize) -> Vec<f32> {
+    (0..size)
+        .map(|i| {
+            let phase = 2.0 * std::f64::consts::PI * i as f64 / size as f64;
+            (0.5 - 0.5 * phase.cos()) as f32
+        })
+        .collect()
+}
+
+/// Windowed magnitude spectrum of `frame` in dB, one value per bin below
+/// the Nyquist frequency. `frame.len()` must be a power of two.
+fn spectrum_db(frame: &[f32], window: &[f32]) -> Vec<f32> {
+    let n = frame.len();
+    let mut re: Vec<f32> = frame.iter().zip(window).map(|(s, w)| s * w).collect();
+    let mut im = vec![0.0; n];
+    fft(&mut re, &mut im);
+    // A full-scale sine peaks at the window's coherent gain times n / 2
+    let gain = window.iter().sum::<f32>() / 2.0;
+    (0..n / 2)
+        .map(|k| {
+            let magnitude = (re[k] * re[k] + im[k] * im[k]).sqrt() / gain;
+            20.0 * magnitude.max(1e-12).log10()
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; the length must be a power of two.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if j > i {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * std::f64::consts::PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (wr, wi) = (cos as f32, sin as f32);
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * wr - im[b] * wi;
+                let ti = re[b] * wi + im[b] * wr;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_scroll_through_the_ring() {
+        let mut spectrogram = Spectrogram::new(64, 1000.0).with_columns(4);
+        assert_eq!(spectrogram.bins(), 32);
+        assert_eq!(spectrogram.time_range(), None);
+
+        // 64 samples make the first column, then one more every 16
+        spectrogram.push(&[0.0; 63]);
+        assert_eq!(spectrogram.pushed(), 0);
+        spectrogram.push(&[0.0; 1 + 16 * 5]);
+        assert_eq!(spectrogram.pushed(), 6);
+        // The 4 kept columns start at column 2, 32 samples in
+        assert_eq!(spectrogram.time_range(), Some([0.032, 0.096]));
+
+        // A 125 Hz sine peaks in bin 8, which is row 23 of 32
+        let sine: Vec<f32> = (0..64)
+            .map(|i| (2.0 * std::f32::consts::PI * 125.0 * i as f32 / 1000.0).sin())
+            .collect();
+        spectrogram.clear();
+        spectrogram.push(&sine);
+        assert_eq!(spectrogram.peak_frequency(), Some(125.0));
+        let peak = spectrogram.latest_column()[8];
+        assert!(peak.abs() < 0.5, "full-scale sine at {peak} dB");
+
+        let texels = spectrogram.texels();
+        let texels = texels.lock().unwrap();
+        let brightest = (0..32)
+            .max_by_key(|row| {
+                texels[row * 4 * 4..row * 4 * 4 + 3]
+                    .iter()
+                    .map(|&c| c as u32)
+                    .sum::<u32>()
+            })
+            .unwrap();
+        assert_eq!(brightest, 23);
+    }
+}
@@ -0,0 +1,153 @@
+//! Fixed-capacity buffers for live data.
+//!
+//! [`StreamingSeries`] keeps the latest `capacity` points of a stream with
+//! O(1) pushes, and always exposes them as one contiguous slice (oldest
+//! first), so a stream plots like any other borrowed series:
+//!
+//! ```
+//! use iced_plotter::plotter::PlotSeries;
+//! use iced_plotter::streaming::StreamingSeries;
+//!
+//! let mut stream = StreamingSeries::new(1000);
+//! for i in 0..5000 {
+//!     stream.push((f64::from(i), 0.0));
+//! }
+//! assert_eq!(stream.len(), 1000);
+//! assert_eq!(stream.as_slice()[0].x, 4000.0);
+//! let _series = PlotSeries::new("live", stream.points());
+//! ```
+
+use crate::plotter::{PlotPoint, PlotPoints};
+
+/// A ring buffer of the most recent points of a stream.
+///
+/// Once full, each push drops the oldest point. Every point is stored twice,
+/// `capacity` apart, so the window of live points is always contiguous no
+/// matter where the ring currently starts: pushing writes two slots instead
+/// of shifting the whole buffer like `Vec::remove(0)` would.
+#[derive(Clone, Debug)]
+pub struct StreamingSeries {
+    /// While filling, the points in order. Once full, `2 * capacity` slots
+    /// with slot `i` and `i + capacity` holding the same point.
+    buffer: Vec<PlotPoint>,
+    capacity: usize,
+    /// Slot of the oldest point once full.
+    start: usize,
+    /// Points pushed since creation or the last [`Self::clear`].
+    pushed: u64,
+}
+
+impl StreamingSeries {
+    /// An empty stream keeping the latest `capacity` points (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            buffer: Vec::with_capacity(2 * capacity),
+            capacity,
+            start: 0,
+            pushed: 0,
+        }
+    }
+
+    /// Append a point, dropping the oldest one when full.
+    pub fn push(&mut self, point: impl Into<PlotPoint>) {
+        let point = point.into();
+        self.pushed += 1;
+        if self.buffer.len() < self.capacity {
+            self.buffer.push(point);
+            if self.buffer.len() == self.capacity {
+                // Switch to the mirrored layout
+                self.buffer.extend_from_within(..);
+            }
+            return;
+        }
+        self.buffer[self.start + self.capacity] = point.clone();
+        self.buffer[self.start] = point;
+        self.start = (self.start + 1) % self.capacity;
+    }
+
+    /// The live points, oldest first.
+    pub fn as_slice(&self) -> &[PlotPoint] {
+        if self.buffer.len() < self.capacity {
+            &self.buffer
+        } else {
+            &self.buffer[self.start..self.start + self.capacity]
+        }
+    }
+
+    /// Borrow the live points for a [`crate::plotter::PlotSeries`].
+    pub fn points(&self) -> PlotPoints<'_> {
+        PlotPoints::borrowed(self.as_slice())
+    }
+
+    /// The newest point.
+    pub fn last(&self) -> Option<&PlotPoint> {
+        self.as_slice().last()
+    }
+
+    /// Number of live points.
+    pub fn len(&self) -> usize {
+        self.buffer.len().min(self.capacity)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Number of points kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Points pushed in total, including dropped ones. Changes on every
+    /// push, so it also works as a [`crate::plotter::PlotSeries::generation`].
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    /// Drop all points.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.start = 0;
+        self.pushed = 0;
+    }
+}
+
+impl<P: Into<PlotPoint>> Extend<P> for StreamingSeries {
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        for point in iter {
+            self.push(point);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xs(stream: &StreamingSeries) -> Vec<f64> {
+        stream.as_slice().iter().map(|p| p.x).collect()
+    }
+
+    #[test]
+    fn test_stream_keeps_latest_in_order() {
+        let mut stream = StreamingSeries::new(3);
+        stream.extend([(0.0, 0.0), (1.0, 0.0)]);
+        assert_eq!(xs(&stream), [0.0, 1.0]);
+
+        // Filling up, then wrapping around the ring more than once
+        for i in 2..9 {
+            stream.push((f64::from(i), 0.0));
+            let first = f64::from((i - 2).max(0));
+            assert_eq!(xs(&stream), [first, first + 1.0, first + 2.0]);
+        }
+        assert_eq!(stream.len(), 3);
+        assert_eq!(stream.pushed(), 9);
+        assert_eq!(stream.last().map(|p| p.x), Some(8.0));
+
+        stream.clear();
+        assert!(stream.is_empty());
+        stream.push((9.0, 0.0));
+        assert_eq!(xs(&stream), [9.0]);
+    }
+}
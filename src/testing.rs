@@ -0,0 +1,219 @@
+//! Headless helpers for testing plots.
+//!
+//! [`Harness`] drives a [`Plotter`] the way the iced runtime does, by feeding
+//! events to its `update` and building primitives with `draw`, at fixed bounds
+//! and with a [`ManualClock`]. Tests can then assert on generated vertices,
+//! ticks and interaction state without a window or GPU.
+
+use crate::plotter::Plotter;
+use crate::shader::{PlotterPrimitive, PlotterState};
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use iced::mouse::{self, Cursor};
+use iced::widget::shader::Program;
+use iced::{Event, Point, Rectangle, keyboard};
+
+/// Widget bounds used by [`Harness::new`].
+pub const DEFAULT_BOUNDS: Rectangle = Rectangle {
+    x: 0.0,
+    y: 0.0,
+    width: 800.0,
+    height: 600.0,
+};
+
+/// A clock that only moves when told to.
+///
+/// Pass [`ManualClock::source`] to [`Plotter::with_clock`] to make
+/// double-click detection and animations deterministic.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    /// Start the clock at the current instant.
+    pub fn new() -> Self {
+        Self {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+
+    /// Current time.
+    pub fn now(&self) -> Instant {
+        self.now.get()
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+
+    /// A time source reading this clock, for [`Plotter::with_clock`].
+    pub fn source(&self) -> impl Fn() -> Instant + 'static {
+        let now = self.now.clone();
+        move || now.get()
+    }
+}
+
+/// Drives a [`Plotter`] without a window.
+///
+/// Positions are widget-local, like those the plot reports in its own types.
+pub struct Harness<'p, 'a, Message> {
+    plotter: &'p Plotter<'a, Message>,
+    /// Widget state carried between events, as iced's widget tree would.
+    pub state: PlotterState,
+    /// Widget bounds passed to `update` and `draw`.
+    pub bounds: Rectangle,
+    cursor: Cursor,
+}
+
+impl<'p, 'a, Message: Clone> Harness<'p, 'a, Message> {
+    /// Wrap `plotter` with [`DEFAULT_BOUNDS`] and the cursor outside the widget.
+    pub fn new(plotter: &'p Plotter<'a, Message>) -> Self {
+        Self {
+            plotter,
+            state: PlotterState::default(),
+            bounds: DEFAULT_BOUNDS,
+            cursor: Cursor::Unavailable,
+        }
+    }
+
+    /// Use different widget bounds.
+    pub fn with_bounds(mut self, bounds: Rectangle) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Build the primitive the plot would render now.
+    pub fn primitive(&self) -> PlotterPrimitive {
+        self.plotter.draw(&self.state, self.cursor, self.bounds)
+    }
+
+    /// Feed an arbitrary event and return the message it published, if any.
+    pub fn event(&mut self, event: Event) -> Option<Message> {
+        let action = self
+            .plotter
+            .update(&mut self.state, &event, self.bounds, self.cursor)?;
+        action.into_inner().0
+    }
+
+    /// Move the cursor to a widget-local position.
+    pub fn move_to(&mut self, position: Point) -> Option<Message> {
+        let position = Point::new(self.bounds.x + position.x, self.bounds.y + position.y);
+        self.cursor = Cursor::Available(position);
+        self.event(Event::Mouse(mouse::Event::CursorMoved { position }))
+    }
+
+    /// Press the left mouse button at the cursor.
+    pub fn press(&mut self) -> Option<Message> {
+        self.event(Event::Mouse(mouse::Event::ButtonPressed(
+            mouse::Button::Left,
+        )))
+    }
+
+    /// Release the left mouse button at the cursor.
+    pub fn release(&mut self) -> Option<Message> {
+        self.event(Event::Mouse(mouse::Event::ButtonReleased(
+            mouse::Button::Left,
+        )))
+    }
+
+    /// Left-drag from `from` to `to`, returning every published message.
+    pub fn drag(&mut self, from: Point, to: Point) -> Vec<Message> {
+        [
+            self.move_to(from),
+            self.press(),
+            self.move_to(to),
+            self.release(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Scroll the wheel by `lines` at the cursor; positive zooms in.
+    pub fn scroll(&mut self, lines: f32) -> Option<Message> {
+        self.event(Event::Mouse(mouse::Event::WheelScrolled {
+            delta: mouse::ScrollDelta::Lines { x: 0.0, y: lines },
+        }))
+    }
+
+    /// Press and release `key` with no modifiers held.
+    pub fn key_press(&mut self, key: keyboard::Key) -> Option<Message> {
+        let physical_key =
+            keyboard::key::Physical::Unidentified(keyboard::key::NativeCode::Unidentified);
+        let published = self.event(Event::Keyboard(keyboard::Event::KeyPressed {
+            key: key.clone(),
+            modified_key: key.clone(),
+            physical_key,
+            location: keyboard::Location::Standard,
+            modifiers: keyboard::Modifiers::empty(),
+            text: None,
+            repeat: false,
+        }));
+        self.event(Event::Keyboard(keyboard::Event::KeyReleased {
+            key: key.clone(),
+            modified_key: key,
+            physical_key,
+            location: keyboard::Location::Standard,
+            modifiers: keyboard::Modifiers::empty(),
+        }));
+        published
+    }
+
+    /// Change the held keyboard modifiers.
+    pub fn set_modifiers(&mut self, modifiers: keyboard::Modifiers) -> Option<Message> {
+        self.event(Event::Keyboard(keyboard::Event::ModifiersChanged(
+            modifiers,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotter::{
+        InteractionConfig, PlotPoint, PlotPoints, PlotSeries, Plotter, ViewState,
+    };
+
+    #[test]
+    fn test_double_click_depends_on_clock() {
+        let view = ViewState::with_ranges((2.0, 4.0), (2.0, 4.0));
+        let clock = ManualClock::new();
+        let points: Vec<PlotPoint> = (0..10)
+            .map(|i| (f64::from(i), f64::from(i)).into())
+            .collect();
+        let plotter = Plotter::new(
+            vec![PlotSeries::new("line", PlotPoints::owned(points))],
+            &view,
+        )
+        .with_interaction(InteractionConfig::full())
+        .with_clock(clock.source())
+        .on_view_change(|view| view);
+        let mut harness = Harness::new(&plotter);
+        let center = Point::new(400.0, 300.0);
+
+        // Two clicks far apart in time are not a double click
+        harness.move_to(center);
+        harness.press();
+        harness.release();
+        clock.advance(Duration::from_secs(1));
+        let slow = harness.press();
+        assert!(slow.is_none_or(|view| view.x_range.is_some()));
+        harness.release();
+
+        clock.advance(Duration::from_millis(100));
+        let fast = harness.press().expect("double click resets the view");
+        assert_eq!(fast.x_range, None);
+        assert_eq!(fast.y_range, None);
+    }
+}
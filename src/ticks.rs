@@ -1,20 +1,151 @@
+/// Where an axis places its ticks.
 #[derive(Clone, Debug)]
-pub struct TickConfig {
-    pub min_ticks: usize,
-    pub max_ticks: usize,
+pub enum TickConfig {
+    /// Round values picked automatically, aiming for between `min_ticks`
+    /// and `max_ticks` ticks across the visible range.
+    Auto { min_ticks: usize, max_ticks: usize },
+    /// Ticks at these positions only, labeled by the axis formatter. Use it
+    /// for domain-meaningful values such as multiples of π.
+    Fixed(Vec<f64>),
+    /// Ticks at these positions with these labels, e.g. shift boundaries.
+    /// The axis formatter is not consulted.
+    Labeled(Vec<(f64, String)>),
 }
 
 impl Default for TickConfig {
     fn default() -> Self {
-        Self {
-            min_ticks: 4,
-            max_ticks: 10,
+        Self::auto(4, 10)
+    }
+}
+
+impl TickConfig {
+    /// Automatic ticks, between `min_ticks` and `max_ticks` of them.
+    pub fn auto(min_ticks: usize, max_ticks: usize) -> Self {
+        Self::Auto {
+            min_ticks,
+            max_ticks,
+        }
+    }
+
+    /// Whether tick positions are picked automatically.
+    pub fn is_auto(&self) -> bool {
+        matches!(self, Self::Auto { .. })
+    }
+
+    /// The label a [`TickConfig::Labeled`] config gives the tick at `value`.
+    pub fn label(&self, value: f64) -> Option<&str> {
+        match self {
+            Self::Labeled(ticks) => ticks
+                .iter()
+                .find(|(position, _)| *position == value)
+                .map(|(_, label)| label.as_str()),
+            Self::Auto { .. } | Self::Fixed(_) => None,
+        }
+    }
+
+    /// Explicit tick positions inside `[lo, hi]`, sorted. `None` for
+    /// automatic ticks.
+    fn explicit(&self, lo: f64, hi: f64) -> Option<Vec<f64>> {
+        let mut ticks: Vec<f64> = match self {
+            Self::Auto { .. } => return None,
+            Self::Fixed(ticks) => ticks.clone(),
+            Self::Labeled(ticks) => ticks.iter().map(|(position, _)| *position).collect(),
+        };
+        ticks.retain(|t| t.is_finite() && *t >= lo && *t <= hi);
+        ticks.sort_by(f64::total_cmp);
+        ticks.dedup();
+        Some(ticks)
+    }
+
+    /// Number of ticks the automatic algorithm aims for.
+    fn target(&self) -> f64 {
+        match *self {
+            Self::Auto {
+                min_ticks,
+                max_ticks,
+            } => ((min_ticks + max_ticks) / 2).max(2) as f64,
+            Self::Fixed(_) | Self::Labeled(_) => Self::default().target(),
+        }
+    }
+}
+
+/// How an axis interprets its values when placing and labeling ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AxisKind {
+    /// Plain numbers, ticked at round multiples of 1, 2 or 5.
+    #[default]
+    Linear,
+    /// Unix timestamps in UTC, ticked on minute, hour, day, month or year
+    /// boundaries. The axis reference is ignored, so labels always show
+    /// absolute times.
+    Time { epoch: TimeEpoch },
+    /// Category indices, ticked at whole numbers only. The axis reference is
+    /// ignored; see
+    /// [`AxisConfig::with_categories`](crate::plotter::AxisConfig::with_categories).
+    Category,
+}
+
+/// Unit of the timestamps on an [`AxisKind::Time`] axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TimeEpoch {
+    /// Seconds since 1970-01-01 UTC.
+    #[default]
+    Seconds,
+    /// Milliseconds since 1970-01-01 UTC.
+    Millis,
+}
+
+impl TimeEpoch {
+    /// Seconds per data unit.
+    pub fn seconds_per_unit(self) -> f64 {
+        match self {
+            TimeEpoch::Seconds => 1.0,
+            TimeEpoch::Millis => 0.001,
         }
     }
 }
 
-pub fn compute_ticks(range_min: f32, range_max: f32, config: &TickConfig) -> Vec<f32> {
-    if (range_max - range_min).abs() < f32::EPSILON {
+/// What an axis formatter knows about the tick it labels.
+///
+/// Values are relative to the axis reference (see
+/// [`AxisConfig::reference`](crate::plotter::AxisConfig::reference)), so
+/// they match what the label should show.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickContext {
+    /// Position among the labeled ticks, counting up from the low end.
+    pub index: usize,
+    /// Number of labeled ticks.
+    pub count: usize,
+    /// The tick value.
+    pub value: f64,
+    /// Visible range of the axis.
+    pub range: [f64; 2],
+    /// Distance between adjacent ticks.
+    pub step: f64,
+}
+
+impl TickContext {
+    /// Whether this is the lowest labeled tick.
+    pub fn is_first(&self) -> bool {
+        self.index == 0
+    }
+
+    /// Whether this is the highest labeled tick.
+    pub fn is_last(&self) -> bool {
+        self.index + 1 == self.count
+    }
+
+    /// Decimal places needed to tell adjacent ticks apart.
+    pub fn decimals(&self) -> usize {
+        (-self.step.abs().log10()).ceil().max(0.0) as usize
+    }
+}
+
+pub fn compute_ticks(range_min: f64, range_max: f64, config: &TickConfig) -> Vec<f64> {
+    if let Some(ticks) = config.explicit(range_min.min(range_max), range_min.max(range_max)) {
+        return ticks;
+    }
+    if (range_max - range_min).abs() < f64::EPSILON {
         return vec![range_min];
     }
 
@@ -24,10 +155,10 @@ pub fn compute_ticks(range_min: f32, range_max: f32, config: &TickConfig) -> Vec
         (range_max, range_min)
     };
 
-    let target = ((config.min_ticks + config.max_ticks) / 2).max(2) as f32;
+    let target = config.target();
     let rough_step = (hi - lo) / target;
 
-    let magnitude = 10.0_f32.powf(rough_step.log10().floor());
+    let magnitude = 10.0_f64.powf(rough_step.log10().floor());
     let normalized = rough_step / magnitude;
 
     let nice_factor = if normalized <= 1.0 {
@@ -53,3 +184,516 @@ pub fn compute_ticks(range_min: f32, range_max: f32, config: &TickConfig) -> Vec
 
     ticks
 }
+
+/// Like [`compute_ticks`], but places ticks at round values relative to
+/// `offset`, or on calendar boundaries for a time axis (which ignores
+/// `offset`). Returned ticks are absolute. Explicit ticks are returned as
+/// given, whatever the kind.
+pub fn compute_ticks_from(
+    kind: AxisKind,
+    range_min: f64,
+    range_max: f64,
+    offset: f64,
+    config: &TickConfig,
+) -> Vec<f64> {
+    if !config.is_auto() {
+        return compute_ticks(range_min, range_max, config);
+    }
+    match kind {
+        AxisKind::Time { epoch } => {
+            return compute_time_ticks(range_min, range_max, epoch, config);
+        }
+        AxisKind::Category => return compute_category_ticks(range_min, range_max, config),
+        AxisKind::Linear => {}
+    }
+    if offset == 0.0 {
+        return compute_ticks(range_min, range_max, config);
+    }
+    compute_ticks(range_min - offset, range_max - offset, config)
+        .into_iter()
+        .map(|t| t + offset)
+        .collect()
+}
+
+/// Ticks inside `[range_min, range_max]` placed like [`compute_ticks_from`],
+/// each with the [`TickContext`] to label it with.
+pub fn visible_ticks(
+    kind: AxisKind,
+    range_min: f64,
+    range_max: f64,
+    offset: f64,
+    config: &TickConfig,
+) -> Vec<(f64, TickContext)> {
+    let offset = match kind {
+        AxisKind::Linear => offset,
+        AxisKind::Time { .. } | AxisKind::Category => 0.0,
+    };
+    let ticks = compute_ticks_from(kind, range_min, range_max, offset, config);
+    let step = match ticks.as_slice() {
+        [a, b, ..] => b - a,
+        _ => range_max - range_min,
+    };
+    let visible: Vec<f64> = ticks
+        .into_iter()
+        .filter(|t| *t >= range_min && *t <= range_max)
+        .collect();
+    let count = visible.len();
+    visible
+        .into_iter()
+        .enumerate()
+        .map(|(index, tick)| {
+            let context = TickContext {
+                index,
+                count,
+                value: tick - offset,
+                range: [range_min - offset, range_max - offset],
+                step,
+            };
+            (tick, context)
+        })
+        .collect()
+}
+
+/// Ticks for an [`AxisKind::Category`] axis: like [`compute_ticks`], but
+/// never closer than one unit apart.
+fn compute_category_ticks(range_min: f64, range_max: f64, config: &TickConfig) -> Vec<f64> {
+    let ticks = compute_ticks(range_min, range_max, config);
+    if let [a, b, ..] = ticks.as_slice()
+        && b - a >= 1.0
+    {
+        return ticks;
+    }
+    let (lo, hi) = (range_min.min(range_max), range_min.max(range_max));
+    (lo.floor() as i64..=hi.ceil() as i64)
+        .map(|i| i as f64)
+        .collect()
+}
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+
+/// Tick intervals in seconds that divide evenly into days. Longer spans
+/// tick on month or year boundaries.
+const TIME_STEPS: [f64; 20] = [
+    1.0,
+    2.0,
+    5.0,
+    10.0,
+    15.0,
+    30.0,
+    MINUTE,
+    2.0 * MINUTE,
+    5.0 * MINUTE,
+    10.0 * MINUTE,
+    15.0 * MINUTE,
+    30.0 * MINUTE,
+    HOUR,
+    2.0 * HOUR,
+    3.0 * HOUR,
+    6.0 * HOUR,
+    12.0 * HOUR,
+    DAY,
+    2.0 * DAY,
+    7.0 * DAY,
+];
+
+/// Month intervals, each dividing a year.
+const MONTH_STEPS: [i64; 4] = [1, 2, 3, 6];
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Ticks for an [`AxisKind::Time`] axis, on the calendar boundary interval
+/// closest to the configured tick count. Spans below a second fall back to
+/// [`compute_ticks`].
+fn compute_time_ticks(
+    range_min: f64,
+    range_max: f64,
+    epoch: TimeEpoch,
+    config: &TickConfig,
+) -> Vec<f64> {
+    let scale = epoch.seconds_per_unit();
+    let (lo, hi) = if range_min < range_max {
+        (range_min * scale, range_max * scale)
+    } else {
+        (range_max * scale, range_min * scale)
+    };
+    let target = config.target();
+    let rough_step = (hi - lo) / target;
+    if rough_step.is_nan() || rough_step < 1.0 {
+        return compute_ticks(range_min, range_max, config);
+    }
+
+    let seconds: Vec<f64> = if let Some(&step) = TIME_STEPS.iter().find(|&&s| s >= rough_step) {
+        let start = (lo / step).floor() * step;
+        (0..)
+            .map(|i| start + i as f64 * step)
+            .take_while(|&t| t <= hi + step * 0.001)
+            .collect()
+    } else {
+        // Count in months from year 0, so steps line up with January
+        let (year, month, _) = civil_from_days((lo / DAY).floor() as i64);
+        let first = year * 12 + i64::from(month) - 1;
+        let month_start = |index: i64| {
+            let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+            days_from_civil(year, month, 1) as f64 * DAY
+        };
+        let months = match MONTH_STEPS
+            .into_iter()
+            .find(|&m| m as f64 * 30.44 * DAY >= rough_step)
+        {
+            Some(months) => months,
+            None => {
+                // Whole years, at round multiples like linear ticks
+                let years = rough_step / (365.2425 * DAY);
+                let magnitude = 10.0_f64.powf(years.log10().floor());
+                let nice = [1.0, 2.0, 5.0, 10.0]
+                    .into_iter()
+                    .find(|f| f * magnitude >= years)
+                    .unwrap_or(10.0);
+                12 * (nice * magnitude).round().max(1.0) as i64
+            }
+        };
+        let start = first - first.rem_euclid(months);
+        (0..)
+            .map(|i| month_start(start + i * months))
+            .take_while(|&t| t <= hi)
+            .collect()
+    };
+    seconds.into_iter().map(|t| t / scale).collect()
+}
+
+/// Most decimals a built-in label shows.
+const MAX_DECIMALS: usize = 12;
+
+/// Decimal places needed to write multiples of `step` exactly, so a step
+/// of 0.25 shows two.
+fn decimals_for(step: f64) -> usize {
+    let step = step.abs();
+    if !step.is_normal() {
+        return 0;
+    }
+    let mut decimals = ((-step.log10() - 1e-9).ceil().max(0.0) as usize).min(MAX_DECIMALS);
+    while decimals < MAX_DECIMALS {
+        let scaled = step * 10f64.powi(decimals as i32);
+        if (scaled - scaled.round()).abs() < 1e-6 * scaled {
+            break;
+        }
+        decimals += 1;
+    }
+    decimals
+}
+
+/// The tick value, with rounding noise around zero removed so it doesn't
+/// show as "-0".
+fn clean_value(context: &TickContext) -> f64 {
+    if context.value.abs() < context.step.abs() * 1e-9 {
+        0.0
+    } else {
+        context.value
+    }
+}
+
+/// `value` with `decimals` places, trailing zeros dropped.
+fn trimmed(value: f64, decimals: usize) -> String {
+    let text = format!("{value:.decimals$}");
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+/// Power of ten of `value`'s leading digit, rounded down to a multiple of
+/// `multiple`. Zero for zero.
+fn exponent(value: f64, multiple: i32) -> i32 {
+    if value == 0.0 || !value.is_finite() {
+        return 0;
+    }
+    let exponent = value.abs().log10().floor() as i32;
+    exponent.div_euclid(multiple) * multiple
+}
+
+/// Default tick label: the value with as many decimals as the tick step
+/// needs ("0.25", "1500").
+pub fn number_label(context: &TickContext) -> String {
+    let decimals = decimals_for(context.step);
+    format!("{:.decimals$}", clean_value(context))
+}
+
+/// Tick label with an SI prefix: "1.2k", "3.4M", "250µ".
+pub fn si_label(context: &TickContext) -> String {
+    const PREFIXES: [&str; 17] = [
+        "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+    ];
+    let value = clean_value(context);
+    let exponent = exponent(value, 3).clamp(-24, 24);
+    let scale = 10f64.powi(exponent);
+    let prefix = PREFIXES[(exponent / 3 + 8) as usize];
+    let decimals = decimals_for(context.step / scale);
+    format!("{}{prefix}", trimmed(value / scale, decimals))
+}
+
+/// Tick label in engineering notation, with an exponent that is a
+/// multiple of three: "1.5e3", "250e-6".
+pub fn engineering_label(context: &TickContext) -> String {
+    exponent_label(context, 3)
+}
+
+/// Tick label in scientific notation: "1.5e3", "2.5e-4".
+pub fn scientific_label(context: &TickContext) -> String {
+    exponent_label(context, 1)
+}
+
+/// Mantissa and exponent label, the exponent a multiple of `multiple`.
+fn exponent_label(context: &TickContext, multiple: i32) -> String {
+    let value = clean_value(context);
+    let exponent = exponent(value, multiple);
+    let scale = 10f64.powi(exponent);
+    let mantissa = trimmed(value / scale, decimals_for(context.step / scale));
+    if exponent == 0 {
+        mantissa
+    } else {
+        format!("{mantissa}e{exponent}")
+    }
+}
+
+/// Tick label for a fraction shown as a percentage: 0.25 is "25%".
+pub fn percent_label(context: &TickContext) -> String {
+    let decimals = decimals_for(context.step * 100.0);
+    format!("{:.decimals$}%", clean_value(context) * 100.0)
+}
+
+/// Tick label for a duration in seconds, down to the unit the tick step
+/// needs: "2h", "1h 30m", "45s", "1.5s".
+pub fn duration_label(context: &TickContext) -> String {
+    let value = clean_value(context);
+    let step = context.step.abs();
+    let sign = if value < 0.0 { "-" } else { "" };
+    let mut rest = value.abs();
+    let mut parts = Vec::new();
+    for (unit, seconds) in [("d", DAY), ("h", HOUR), ("m", MINUTE)] {
+        let count = if step >= seconds {
+            (rest / seconds).round()
+        } else {
+            // Small rounding errors must not drop a whole unit
+            (rest / seconds + 1e-9).floor()
+        };
+        if count >= 1.0 {
+            parts.push(format!("{count}{unit}"));
+        }
+        rest = (rest - count * seconds).max(0.0);
+        if step >= seconds {
+            rest = 0.0;
+            break;
+        }
+    }
+    if rest > 0.0 || parts.is_empty() {
+        let seconds = trimmed(rest, decimals_for(step));
+        if seconds != "0" || parts.is_empty() {
+            parts.push(format!("{seconds}s"));
+        }
+    }
+    format!("{sign}{}", parts.join(" "))
+}
+
+/// Label for a tick on an [`AxisKind::Time`] axis, in UTC.
+///
+/// The precision follows the tick step: years ("2024"), months ("Mar"),
+/// days ("Mar 4"), minutes ("12:30") or seconds ("12:30:15"). Ticks on a
+/// coarser boundary name it instead, so midnight shows the date and
+/// January 1st the year.
+pub fn time_label(context: &TickContext, epoch: TimeEpoch) -> String {
+    let scale = epoch.seconds_per_unit();
+    let step = context.step.abs() * scale;
+    let millis = (context.value * scale * 1000.0).round() as i64;
+    let day_millis = (DAY * 1000.0) as i64;
+    let (year, month, day) = civil_from_days(millis.div_euclid(day_millis));
+    let time = millis.rem_euclid(day_millis);
+    let (hours, minutes, seconds) = (time / 3_600_000, time / 60_000 % 60, time / 1000 % 60);
+    let month_name = MONTH_NAMES[month as usize - 1];
+
+    if step >= 360.0 * DAY || (step >= 28.0 * DAY && month == 1) {
+        format!("{year}")
+    } else if step >= 28.0 * DAY {
+        month_name.to_string()
+    } else if step >= DAY || time == 0 {
+        if month == 1 && day == 1 && time == 0 {
+            format!("{year}")
+        } else {
+            format!("{month_name} {day}")
+        }
+    } else if step >= MINUTE {
+        format!("{hours:02}:{minutes:02}")
+    } else if step >= 1.0 {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        let decimals = ((-step.log10()).ceil().max(1.0) as u32).min(3);
+        let fraction = time % 1000 / 10_i64.pow(3 - decimals);
+        let width = decimals as usize;
+        format!("{hours:02}:{minutes:02}:{seconds:02}.{fraction:0width$}")
+    }
+}
+
+/// Year, month and day of the day `days` after 1970-01-01 (proleptic
+/// Gregorian calendar).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_ticks_context() {
+        let ticks = visible_ticks(
+            AxisKind::Linear,
+            1003.0,
+            1047.0,
+            1000.0,
+            &TickConfig::default(),
+        );
+        let values: Vec<f64> = ticks.iter().map(|(_, c)| c.value).collect();
+        assert_eq!(values, [10.0, 20.0, 30.0, 40.0]);
+        let (tick, last) = ticks[ticks.len() - 1];
+        assert_eq!(tick, 1040.0);
+        assert!(last.is_last() && !last.is_first());
+        assert_eq!(last.range, [3.0, 47.0]);
+        assert_eq!(last.step, 10.0);
+        assert_eq!(last.decimals(), 0);
+    }
+
+    #[test]
+    fn test_builtin_labels() {
+        let context = |value: f64, step: f64| TickContext {
+            index: 0,
+            count: 1,
+            value,
+            range: [value, value + step],
+            step,
+        };
+        assert_eq!(number_label(&context(1500.0, 500.0)), "1500");
+        assert_eq!(number_label(&context(-1e-17, 0.25)), "0.00");
+        assert_eq!(si_label(&context(1500.0, 500.0)), "1.5k");
+        assert_eq!(si_label(&context(3_400_000.0, 200_000.0)), "3.4M");
+        assert_eq!(si_label(&context(0.00025, 0.00005)), "250µ");
+        assert_eq!(engineering_label(&context(0.00025, 0.00005)), "250e-6");
+        assert_eq!(scientific_label(&context(1500.0, 500.0)), "1.5e3");
+        assert_eq!(scientific_label(&context(0.0, 500.0)), "0");
+        assert_eq!(percent_label(&context(0.25, 0.05)), "25%");
+        assert_eq!(percent_label(&context(0.125, 0.005)), "12.5%");
+        assert_eq!(duration_label(&context(5400.0, 1800.0)), "1h 30m");
+        assert_eq!(duration_label(&context(7200.0, 3600.0)), "2h");
+        assert_eq!(duration_label(&context(45.0, 15.0)), "45s");
+        assert_eq!(duration_label(&context(-1.5, 0.5)), "-1.5s");
+        assert_eq!(duration_label(&context(0.0, 60.0)), "0s");
+    }
+
+    #[test]
+    fn test_explicit_ticks() {
+        let pi = std::f64::consts::PI;
+        let fixed = TickConfig::Fixed(vec![2.0 * pi, 0.0, pi, -pi, 0.0]);
+        let ticks: Vec<f64> = visible_ticks(AxisKind::Linear, -1.0, 7.0, 0.0, &fixed)
+            .iter()
+            .map(|(t, _)| *t)
+            .collect();
+        assert_eq!(ticks, [0.0, pi, 2.0 * pi]);
+
+        let labeled = TickConfig::Labeled(vec![(6.0, "Early".into()), (14.0, "Late".into())]);
+        let time = AxisKind::Time {
+            epoch: TimeEpoch::Seconds,
+        };
+        let ticks = visible_ticks(time, 0.0, 24.0, 0.0, &labeled);
+        let labels: Vec<_> = ticks
+            .iter()
+            .filter_map(|(t, _)| labeled.label(*t))
+            .collect();
+        assert_eq!(labels, ["Early", "Late"]);
+        assert_eq!(ticks[0].1.step, 8.0);
+        assert_eq!(fixed.label(0.0), None);
+    }
+
+    #[test]
+    fn test_time_ticks_on_calendar_boundaries() {
+        let time = AxisKind::Time {
+            epoch: TimeEpoch::Seconds,
+        };
+        let labels = |lo: f64, hi: f64| -> Vec<String> {
+            visible_ticks(time, lo, hi, 0.0, &TickConfig::default())
+                .iter()
+                .map(|(_, c)| time_label(c, TimeEpoch::Seconds))
+                .collect()
+        };
+        // 2024-03-04 00:00:00 UTC
+        let day = 1_709_510_400.0;
+        assert_eq!(
+            labels(day + 20.5 * HOUR, day + 27.5 * HOUR),
+            [
+                "21:00", "22:00", "23:00", "Mar 5", "01:00", "02:00", "03:00"
+            ]
+        );
+        assert_eq!(
+            labels(day + 12.0 * HOUR + 7.0, day + 12.0 * HOUR + 38.0),
+            [
+                "12:00:10", "12:00:15", "12:00:20", "12:00:25", "12:00:30", "12:00:35"
+            ]
+        );
+        assert_eq!(
+            labels(day - 70.0 * DAY, day + 330.0 * DAY),
+            ["2024", "Mar", "May", "Jul", "Sep", "Nov", "2025"]
+        );
+
+        // Milliseconds tick on the same boundaries
+        let millis = visible_ticks(
+            AxisKind::Time {
+                epoch: TimeEpoch::Millis,
+            },
+            (day + 20.5 * HOUR) * 1000.0,
+            (day + 27.5 * HOUR) * 1000.0,
+            0.0,
+            &TickConfig::default(),
+        );
+        assert_eq!(millis[3].0, (day + DAY) * 1000.0);
+        assert_eq!(days_from_civil(2024, 3, 4) as f64 * DAY, day);
+    }
+
+    #[test]
+    fn test_category_ticks_are_whole_numbers() {
+        let values = |lo, hi| {
+            visible_ticks(AxisKind::Category, lo, hi, 0.5, &TickConfig::default())
+                .into_iter()
+                .map(|(tick, context)| {
+                    assert_eq!(tick, context.value);
+                    tick
+                })
+                .collect::<Vec<_>>()
+        };
+        // Zoomed in past one category per tick
+        assert_eq!(values(-0.5, 2.5), [0.0, 1.0, 2.0]);
+        assert_eq!(values(0.2, 0.8), Vec::<f64>::new());
+        // Many categories keep the usual round steps
+        assert_eq!(values(-0.5, 40.5), [0.0, 10.0, 20.0, 30.0, 40.0]);
+    }
+}
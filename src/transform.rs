@@ -0,0 +1,296 @@
+//! Series computed from other series.
+//!
+//! A [`Transform`] turns a series' points into a smoothed, differentiated
+//! or accumulated copy, e.g. to overlay a moving average on noisy data:
+//!
+//! ```
+//! use iced_plotter::plotter::{PlotPoints, PlotSeries};
+//! use iced_plotter::transform::Transform;
+//!
+//! let noisy: Vec<_> = (0..100)
+//!     .map(|i| (f64::from(i), f64::from(i % 7)).into())
+//!     .collect();
+//! let raw = PlotSeries::new("raw", PlotPoints::owned(noisy));
+//! let smooth = PlotSeries::derived(&raw, Transform::MovingAverage(7));
+//! assert_eq!(smooth.label, "raw (SMA 7)");
+//! ```
+//!
+//! [`PlotSeries::derived`](crate::plotter::PlotSeries::derived) computes the
+//! points on every call. For live data, keep a [`DerivedSeries`] in the app
+//! state instead: it only recomputes when the source changes.
+//!
+//! Non-finite points are gaps. They are passed through, and windowed
+//! transforms start over after them.
+
+use crate::plotter::{PlotPoint, PlotPoints};
+
+/// A computation deriving one series from another.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transform {
+    /// Mean of the last `n` points (fewer at the start).
+    MovingAverage(usize),
+    /// Exponential moving average with the smoothing of an `n`-point window,
+    /// `alpha = 2 / (n + 1)`.
+    ExponentialAverage(usize),
+    /// Slope dy/dx, from the neighbors on both sides (one side at the ends).
+    Derivative,
+    /// Running sum of the Y values.
+    CumulativeSum,
+    /// Running integral over X, by the trapezoid rule.
+    Integral,
+    /// Y minus the least-squares line through all points.
+    Detrend,
+}
+
+impl std::fmt::Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transform::MovingAverage(n) => write!(f, "SMA {n}"),
+            Transform::ExponentialAverage(n) => write!(f, "EMA {n}"),
+            Transform::Derivative => f.write_str("d/dx"),
+            Transform::CumulativeSum => f.write_str("cumulative"),
+            Transform::Integral => f.write_str("integral"),
+            Transform::Detrend => f.write_str("detrended"),
+        }
+    }
+}
+
+impl Transform {
+    /// The derived points, one per source point, at the same X.
+    pub fn apply(self, points: &[PlotPoint]) -> Vec<PlotPoint> {
+        let finite = |p: &PlotPoint| p.x.is_finite() && p.y.is_finite();
+        let with_y = |p: &PlotPoint, y: f64| PlotPoint { x: p.x, y };
+        match self {
+            Transform::MovingAverage(n) => {
+                let n = n.max(1);
+                let mut sum = 0.0;
+                let mut start = 0;
+                points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| {
+                        if !finite(p) {
+                            (sum, start) = (0.0, i + 1);
+                            return p.clone();
+                        }
+                        sum += p.y;
+                        if i - start >= n {
+                            sum -= points[i - n].y;
+                            start = i + 1 - n;
+                        }
+                        with_y(p, sum / (i + 1 - start) as f64)
+                    })
+                    .collect()
+            }
+            Transform::ExponentialAverage(n) => {
+                let alpha = 2.0 / (n.max(1) as f64 + 1.0);
+                let mut average = None;
+                points
+                    .iter()
+                    .map(|p| {
+                        if !finite(p) {
+                            average = None;
+                            return p.clone();
+                        }
+                        let y = match average {
+                            Some(a) => a + alpha * (p.y - a),
+                            None => p.y,
+                        };
+                        average = Some(y);
+                        with_y(p, y)
+                    })
+                    .collect()
+            }
+            Transform::Derivative => (0..points.len())
+                .map(|i| {
+                    let p = &points[i];
+                    let neighbor =
+                        |j: Option<usize>| j.and_then(|j| points.get(j)).filter(|q| finite(q));
+                    let (a, b) = match (neighbor(i.checked_sub(1)), neighbor(Some(i + 1))) {
+                        (Some(a), Some(b)) => (a, b),
+                        (Some(a), None) => (a, p),
+                        (None, Some(b)) => (p, b),
+                        (None, None) => return with_y(p, f64::NAN),
+                    };
+                    if !finite(p) || a.x == b.x {
+                        return with_y(p, f64::NAN);
+                    }
+                    with_y(p, (b.y - a.y) / (b.x - a.x))
+                })
+                .collect(),
+            Transform::CumulativeSum => {
+                let mut sum = 0.0;
+                points
+                    .iter()
+                    .map(|p| {
+                        if !finite(p) {
+                            return p.clone();
+                        }
+                        sum += p.y;
+                        with_y(p, sum)
+                    })
+                    .collect()
+            }
+            Transform::Integral => {
+                let mut sum = 0.0;
+                let mut prev: Option<&PlotPoint> = None;
+                points
+                    .iter()
+                    .map(|p| {
+                        if !finite(p) {
+                            return p.clone();
+                        }
+                        if let Some(q) = prev {
+                            sum += (p.x - q.x) * (p.y + q.y) / 2.0;
+                        }
+                        prev = Some(p);
+                        with_y(p, sum)
+                    })
+                    .collect()
+            }
+            Transform::Detrend => {
+                let (mut n, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+                for p in points.iter().filter(|p| finite(p)) {
+                    n += 1.0;
+                    sx += p.x;
+                    sy += p.y;
+                    sxx += p.x * p.x;
+                    sxy += p.x * p.y;
+                }
+                let denominator = n * sxx - sx * sx;
+                let slope = if denominator.abs() > f64::EPSILON {
+                    (n * sxy - sx * sy) / denominator
+                } else {
+                    0.0
+                };
+                let intercept = if n > 0.0 { (sy - slope * sx) / n } else { 0.0 };
+                points
+                    .iter()
+                    .map(|p| with_y(p, p.y - (intercept + slope * p.x)))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Derived points of a live series, recomputed only when the source changes.
+///
+/// Call [`DerivedSeries::update`] whenever the source may have changed
+/// (e.g. after pushing to a
+/// [`StreamingSeries`](crate::streaming::StreamingSeries)), and plot
+/// [`DerivedSeries::points`]. The source counts as changed when its
+/// generation, length or first or last point differ from the last update.
+#[derive(Clone, Debug)]
+pub struct DerivedSeries {
+    transform: Transform,
+    /// Source generation, length and endpoints the points were derived from.
+    key: Option<(u64, usize, [u64; 4])>,
+    points: Vec<PlotPoint>,
+}
+
+impl DerivedSeries {
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            transform,
+            key: None,
+            points: Vec::new(),
+        }
+    }
+
+    /// Derive the points from `source` unless they are up to date. Returns
+    /// whether they were recomputed.
+    pub fn update(&mut self, source: &[PlotPoint], generation: u64) -> bool {
+        let ends = match (source.first(), source.last()) {
+            (Some(a), Some(b)) => [a.x, a.y, b.x, b.y].map(f64::to_bits),
+            _ => [0; 4],
+        };
+        let key = Some((generation, source.len(), ends));
+        if self.key == key {
+            return false;
+        }
+        self.key = key;
+        self.points = self.transform.apply(source);
+        true
+    }
+
+    /// Change the transform; the next update recomputes.
+    pub fn set_transform(&mut self, transform: Transform) {
+        if self.transform != transform {
+            self.transform = transform;
+            self.key = None;
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// The derived points as of the last update.
+    pub fn as_slice(&self) -> &[PlotPoint] {
+        &self.points
+    }
+
+    /// Borrow the derived points for a [`crate::plotter::PlotSeries`].
+    pub fn points(&self) -> PlotPoints<'_> {
+        PlotPoints::borrowed(&self.points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ys(points: &[PlotPoint]) -> Vec<f64> {
+        points.iter().map(|p| p.y).collect()
+    }
+
+    #[test]
+    fn test_transforms() {
+        let points: Vec<PlotPoint> = [1.0, 3.0, 5.0, 7.0]
+            .iter()
+            .enumerate()
+            .map(|(i, y)| (i as f64, *y).into())
+            .collect();
+        assert_eq!(
+            ys(&Transform::MovingAverage(2).apply(&points)),
+            [1.0, 2.0, 4.0, 6.0]
+        );
+        assert_eq!(
+            ys(&Transform::ExponentialAverage(3).apply(&points)),
+            [1.0, 2.0, 3.5, 5.25]
+        );
+        assert_eq!(ys(&Transform::Derivative.apply(&points)), [2.0; 4]);
+        assert_eq!(
+            ys(&Transform::CumulativeSum.apply(&points)),
+            [1.0, 4.0, 9.0, 16.0]
+        );
+        assert_eq!(
+            ys(&Transform::Integral.apply(&points)),
+            [0.0, 2.0, 6.0, 12.0]
+        );
+        assert_eq!(ys(&Transform::Detrend.apply(&points)), [0.0; 4]);
+
+        // A gap restarts the window
+        let gapped = [(0.0, 2.0), (1.0, f64::NAN), (2.0, 4.0), (3.0, 6.0)].map(PlotPoint::from);
+        let averaged = ys(&Transform::MovingAverage(3).apply(&gapped));
+        assert_eq!(averaged[0], 2.0);
+        assert!(averaged[1].is_nan());
+        assert_eq!(averaged[2..], [4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_derived_series_recomputes_on_change() {
+        let mut derived = DerivedSeries::new(Transform::CumulativeSum);
+        let mut source = vec![PlotPoint::from((0.0, 1.0))];
+        assert!(derived.update(&source, 0));
+        assert!(!derived.update(&source, 0));
+
+        source.push((1.0, 2.0).into());
+        assert!(derived.update(&source, 0));
+        assert_eq!(ys(derived.as_slice()), [1.0, 3.0]);
+
+        derived.set_transform(Transform::MovingAverage(2));
+        assert!(derived.update(&source, 0));
+        assert_eq!(ys(derived.as_slice()), [1.0, 1.5]);
+    }
+}
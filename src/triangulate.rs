@@ -0,0 +1,96 @@
+//! Polygon triangulation for filled shapes.
+
+/// Triangulate a simple (non self-intersecting) polygon by ear clipping.
+///
+/// Works for convex and concave outlines in either winding order. The loop is
+/// implicitly closed; a repeated first vertex at the end is ignored. Returns
+/// index triples into `points`.
+pub fn triangulate(points: &[(f32, f32)]) -> Vec<[usize; 3]> {
+    let mut n = points.len();
+    if n >= 2 && points[0] == points[n - 1] {
+        n -= 1;
+    }
+    if n < 3 {
+        return Vec::new();
+    }
+
+    // Signed area decides which turn direction counts as convex
+    let area: f32 = (0..n)
+        .map(|i| {
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[(i + 1) % n];
+            x0 * y1 - x1 * y0
+        })
+        .sum();
+    let orientation = if area >= 0.0 { 1.0 } else { -1.0 };
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut triangles = Vec::with_capacity(n - 2);
+
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let ear = (0..len).find(|&i| {
+            let a = remaining[(i + len - 1) % len];
+            let b = remaining[i];
+            let c = remaining[(i + 1) % len];
+            if cross(points[a], points[b], points[c]) * orientation <= 0.0 {
+                return false;
+            }
+            remaining
+                .iter()
+                .filter(|&&p| p != a && p != b && p != c)
+                .all(|&p| !in_triangle(points[p], points[a], points[b], points[c]))
+        });
+
+        // Degenerate or self-intersecting input: clip the next vertex anyway
+        // so we always terminate.
+        let i = ear.unwrap_or(0);
+        let a = remaining[(i + len - 1) % len];
+        let b = remaining[i];
+        let c = remaining[(i + 1) % len];
+        triangles.push([a, b, c]);
+        remaining.remove(i);
+    }
+
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+fn cross(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+fn in_triangle(p: (f32, f32), a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> bool {
+    let d1 = cross(a, b, p);
+    let d2 = cross(b, c, p);
+    let d3 = cross(c, a, p);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concave_polygon_area() {
+        // L-shape with area 3, wound clockwise
+        let points = [
+            (0.0, 0.0),
+            (0.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 0.0),
+        ];
+        let triangles = triangulate(&points);
+        assert_eq!(triangles.len(), 4);
+
+        let area: f32 = triangles
+            .iter()
+            .map(|&[a, b, c]| cross(points[a], points[b], points[c]).abs() / 2.0)
+            .sum();
+        assert!((area - 3.0).abs() < 1e-5);
+    }
+}